@@ -45,6 +45,12 @@ enum Command {
         /// Disable the given feature.
         #[arg(long, group = "toggle")]
         disable: bool,
+        /// Only toggle the feature for the market of the given market token.
+        #[arg(long, group = "scope")]
+        market: Option<Pubkey>,
+        /// Only toggle the feature for the GLV of the given GLV token.
+        #[arg(long, group = "scope")]
+        glv: Option<Pubkey>,
     },
 }
 
@@ -76,11 +82,21 @@ impl super::Command for Configuration {
                 action,
                 enable,
                 disable,
+                market,
+                glv,
             } => {
                 if enable == disable {
                     return Err(eyre::eyre!("invalid toggle flags"));
                 }
-                let builder = client.toggle_feature(store, *domain, *action, *enable);
+                let builder = match (market, glv) {
+                    (Some(market_token), _) => {
+                        client.toggle_market_feature(store, market_token, *domain, *action, *enable)
+                    }
+                    (None, Some(glv_token)) => {
+                        client.toggle_glv_feature(store, glv_token, *domain, *action, *enable)
+                    }
+                    (None, None) => client.toggle_feature(store, *domain, *action, *enable),
+                };
                 builder.into_bundle_with_options(options)?
             }
         };
@@ -260,6 +260,10 @@ enum Command {
         prepare_position_only: bool,
         #[command(flatten)]
         should_keep_position: ShouldKeepPosition,
+        #[command(flatten)]
+        linked_orders: LinkedOrders,
+        #[command(flatten)]
+        twap: Twap,
     },
     /// Create a limit increase order.
     LimitIncrease {
@@ -286,6 +290,10 @@ enum Command {
         /// Trigger price.
         #[arg(long)]
         price: Value,
+        /// Create a stop increase order instead, which is triggered when
+        /// the price breaks through the trigger price.
+        #[arg(long)]
+        stop: bool,
         /// Acceptable price.
         #[arg(long)]
         acceptable_price: Option<Value>,
@@ -340,6 +348,8 @@ enum Command {
         competition: Option<Pubkey>,
         #[command(flatten)]
         should_keep_position: ShouldKeepPosition,
+        #[command(flatten)]
+        twap: Twap,
     },
     /// Create a limit decrese order.
     LimitDecrease {
@@ -381,6 +391,9 @@ enum Command {
         /// Valid from this timestamp.
         #[arg(long)]
         valid_from_ts: Option<humantime::Timestamp>,
+        /// Valid until this timestamp.
+        #[arg(long)]
+        valid_until_ts: Option<humantime::Timestamp>,
         #[command(flatten)]
         should_keep_position: ShouldKeepPosition,
     },
@@ -423,6 +436,9 @@ enum Command {
         /// Valid from this timestamp.
         #[arg(long)]
         valid_from_ts: Option<humantime::Timestamp>,
+        /// Valid until this timestamp.
+        #[arg(long)]
+        valid_until_ts: Option<humantime::Timestamp>,
         #[command(flatten)]
         should_keep_position: ShouldKeepPosition,
     },
@@ -517,6 +533,9 @@ enum Command {
         #[arg(long)]
         keep: bool,
     },
+    /// Cancel and close an expired order.
+    /// Requires appropriate permissions.
+    CancelExpiredOrder { order: Pubkey },
     /// GLV operations.
     Glv {
         #[command(flatten)]
@@ -555,6 +574,14 @@ enum Command {
         args: executor::ExecutorArgs,
         market_token: Pubkey,
     },
+    /// Start the settlement of the given market, freezing the settlement price.
+    /// Requires appropriate permissions.
+    #[cfg(feature = "execute")]
+    StartMarketSettlement {
+        #[command(flatten)]
+        args: executor::ExecutorArgs,
+        market_token: Pubkey,
+    },
     /// Close a profitable position when ADL is enabled.
     #[cfg(feature = "execute")]
     Adl {
@@ -575,11 +602,62 @@ enum Command {
         args: executor::ExecutorArgs,
         position: Pubkey,
     },
+    /// Force-close a position of a settled market.
+    #[cfg(feature = "execute")]
+    SettlePosition {
+        #[command(flatten)]
+        args: executor::ExecutorArgs,
+        position: Pubkey,
+    },
     /// Close an empty position account.
     CloseEmptyPositions {
         #[clap(flatten)]
         args: CloseEmptyPositionsArgs,
     },
+    /// Initiate a transfer of a position to another owner.
+    TransferPosition {
+        /// The position to transfer.
+        position: Pubkey,
+        /// The next owner.
+        #[arg(long)]
+        to: Pubkey,
+    },
+    /// Cancel a position transfer.
+    CancelPositionTransfer {
+        /// The position being transferred.
+        position: Pubkey,
+    },
+    /// Accept a position transfer.
+    AcceptPosition {
+        /// The position being transferred.
+        position: Pubkey,
+    },
+    /// Count the orders of a position created before the order count was introduced.
+    /// Requires appropriate permissions.
+    SyncPositionOrderCount {
+        /// The position to sync the order count of.
+        position: Pubkey,
+    },
+    /// Request to switch the collateral token of a position to the opposite token.
+    RequestCollateralSwitch {
+        /// The position to switch the collateral token of.
+        position: Pubkey,
+        /// The minimum collateral amount after the switch.
+        #[arg(long, default_value_t = 0)]
+        min_collateral_amount: u64,
+    },
+    /// Cancel a collateral switch request.
+    CancelCollateralSwitch {
+        /// The position with the request.
+        position: Pubkey,
+    },
+    /// Switch the collateral token of a position.
+    #[cfg(feature = "execute")]
+    SwitchCollateral {
+        #[command(flatten)]
+        args: executor::ExecutorArgs,
+        position: Pubkey,
+    },
     /// Update fees state.
     #[cfg(all(feature = "execute", feature = "nightly-cli-update-fees-state"))]
     UpdateFeesState {
@@ -748,6 +826,38 @@ impl ShouldKeepPosition {
     }
 }
 
+#[derive(Debug, clap::Args, Default)]
+pub(crate) struct LinkedOrders {
+    /// Trigger price of the take-profit (limit decrease) order to link to the increase order.
+    #[arg(long, requires = "stop_loss", conflicts_with_all = ["wait", "prepare_position_only"])]
+    take_profit: Option<Value>,
+    /// Trigger price of the stop-loss order to link to the increase order.
+    #[arg(long, requires = "take_profit")]
+    stop_loss: Option<Value>,
+}
+
+impl LinkedOrders {
+    pub(crate) fn prices(&self) -> Option<(&Value, &Value)> {
+        Some((self.take_profit.as_ref()?, self.stop_loss.as_ref()?))
+    }
+}
+
+#[derive(Debug, clap::Args, Default)]
+pub(crate) struct Twap {
+    /// Split the order into the given number of slices, executed one per interval.
+    #[arg(long, requires = "twap_interval", conflicts_with = "wait")]
+    twap_slices: Option<u16>,
+    /// The interval between two TWAP slices in seconds.
+    #[arg(long, requires = "twap_slices")]
+    twap_interval: Option<u32>,
+}
+
+impl Twap {
+    pub(crate) fn params(&self) -> Option<(u16, u32)> {
+        Some((self.twap_slices?, self.twap_interval?))
+    }
+}
+
 impl super::Command for Exchange {
     fn is_client_required(&self) -> bool {
         true
@@ -1247,6 +1357,8 @@ impl super::Command for Exchange {
                 acceptable_price,
                 prepare_position_only,
                 should_keep_position,
+                linked_orders,
+                twap,
             } => {
                 let market_address = client.find_market_address(store, market_token);
                 let market = client.market(&market_address).await?;
@@ -1293,6 +1405,9 @@ impl super::Command for Exchange {
                 if let Some(price) = acceptable_price {
                     builder.acceptable_price(unit_price(price, token_map, &market)?);
                 }
+                if let Some((slices, interval)) = twap.params() {
+                    builder.twap(slices, interval);
+                }
 
                 builder.swap_path(swap.clone());
 
@@ -1340,6 +1455,52 @@ impl super::Command for Exchange {
                         bundle.push(txn)?;
                     }
 
+                    if let Some((take_profit, stop_loss)) = linked_orders.prices() {
+                        let size = size.to_u128()?;
+                        let take_profit = unit_price(take_profit, token_map, &market)?;
+                        let stop_loss = unit_price(stop_loss, token_map, &market)?;
+                        let mut take_profit_builder = client.limit_decrease(
+                            store,
+                            market_token,
+                            side.is_long(),
+                            size,
+                            take_profit,
+                            is_collateral_token_long,
+                            0,
+                        );
+                        let mut stop_loss_builder = client.stop_loss(
+                            store,
+                            market_token,
+                            side.is_long(),
+                            size,
+                            stop_loss,
+                            is_collateral_token_long,
+                            0,
+                        );
+                        let (take_profit_rpc, take_profit_order) = take_profit_builder
+                            .decrease_position_swap_type(Some(
+                                DecreasePositionSwapType::PnlTokenToCollateralToken,
+                            ))
+                            .build_with_address()
+                            .await?;
+                        let (stop_loss_rpc, stop_loss_order) = stop_loss_builder
+                            .decrease_position_swap_type(Some(
+                                DecreasePositionSwapType::PnlTokenToCollateralToken,
+                            ))
+                            .build_with_address()
+                            .await?;
+                        println!("Take-profit order: {take_profit_order}");
+                        println!("Stop-loss order: {stop_loss_order}");
+                        bundle.push(take_profit_rpc)?;
+                        bundle.push(stop_loss_rpc)?;
+                        bundle.push(client.link_orders(
+                            store,
+                            &order,
+                            &take_profit_order,
+                            &stop_loss_order,
+                        ))?;
+                    }
+
                     bundle
                 }
             }
@@ -1351,6 +1512,7 @@ impl super::Command for Exchange {
                 initial_collateral_token_amount,
                 side,
                 price,
+                stop,
                 size,
                 swap,
                 wait,
@@ -1381,15 +1543,27 @@ impl super::Command for Exchange {
                         is_collateral_token_long,
                     )?;
                 }
-                let mut builder = client.limit_increase(
-                    store,
-                    market_token,
-                    side.is_long(),
-                    size.to_u128()?,
-                    price,
-                    is_collateral_token_long,
-                    initial_collateral_token_amount,
-                );
+                let mut builder = if *stop {
+                    client.stop_increase(
+                        store,
+                        market_token,
+                        side.is_long(),
+                        size.to_u128()?,
+                        price,
+                        is_collateral_token_long,
+                        initial_collateral_token_amount,
+                    )
+                } else {
+                    client.limit_increase(
+                        store,
+                        market_token,
+                        side.is_long(),
+                        size.to_u128()?,
+                        price,
+                        is_collateral_token_long,
+                        initial_collateral_token_amount,
+                    )
+                };
                 if let Some(nonce) = nonce {
                     builder.nonce(nonce);
                 }
@@ -1459,6 +1633,7 @@ impl super::Command for Exchange {
                 min_output,
                 acceptable_price,
                 should_keep_position,
+                twap,
             } => {
                 let is_collateral_token_long = collateral_side.is_long();
                 let market = if token_map.is_some() {
@@ -1509,6 +1684,9 @@ impl super::Command for Exchange {
                         market.as_ref().expect("must exist"),
                     )?);
                 }
+                if let Some((slices, interval)) = twap.params() {
+                    builder.twap(slices, interval);
+                }
 
                 let (rpc, order) = builder
                     .decrease_position_swap_type(Some(
@@ -1554,6 +1732,7 @@ impl super::Command for Exchange {
                 min_output,
                 acceptable_price,
                 valid_from_ts,
+                valid_until_ts,
                 should_keep_position,
             }
             | Command::StopLoss {
@@ -1570,6 +1749,7 @@ impl super::Command for Exchange {
                 min_output,
                 acceptable_price,
                 valid_from_ts,
+                valid_until_ts,
                 should_keep_position,
             } => {
                 let market_address = client.find_market_address(store, market_token);
@@ -1623,6 +1803,9 @@ impl super::Command for Exchange {
                 if let Some(ts) = valid_from_ts {
                     builder.valid_from_ts(to_unix_timestamp(ts)?);
                 }
+                if let Some(ts) = valid_until_ts {
+                    builder.valid_until_ts(to_unix_timestamp(ts)?);
+                }
 
                 let (rpc, order) = builder
                     .swap_path(swap.clone())
@@ -1672,11 +1855,13 @@ impl super::Command for Exchange {
                     None
                 };
                 let min_output = match kind {
-                    OrderKind::LimitDecrease | OrderKind::StopLossDecrease => min_output
+                    OrderKind::LimitDecrease
+                    | OrderKind::StopLossDecrease
+                    | OrderKind::TrailingStopDecrease => min_output
                         .as_ref()
                         .map(|value| value.to_u128(MARKET_DECIMALS))
                         .transpose()?,
-                    OrderKind::LimitIncrease => {
+                    OrderKind::LimitIncrease | OrderKind::StopIncrease => {
                         if let Some(amount) = min_output {
                             if token_map.is_none() {
                                 token_map = Some(client.authorized_token_map(store).await?);
@@ -1949,6 +2134,11 @@ impl super::Command for Exchange {
 
                 rpc.into_bundle_with_options(options)?
             }
+            Command::CancelExpiredOrder { order } => client
+                .cancel_expired_order(order)?
+                .build()
+                .await?
+                .into_bundle_with_options(options)?,
             Command::Glv { glv_token, command } => {
                 let glv_token = glv_token.address(client, store);
 
@@ -2307,6 +2497,17 @@ impl super::Command for Exchange {
                 return Ok(());
             }
             #[cfg(feature = "execute")]
+            Command::StartMarketSettlement { args, market_token } => {
+                ctx.require_not_serialize_only_mode()?;
+                ctx.require_not_ix_buffer_mode()?;
+
+                let executor = args.build(client).await?;
+                let oracle = ctx.config().oracle()?;
+                let builder = client.start_market_settlement(store, oracle, market_token);
+                executor.execute(builder, options).await?;
+                return Ok(());
+            }
+            #[cfg(feature = "execute")]
             Command::Adl {
                 args,
                 position,
@@ -2349,6 +2550,21 @@ impl super::Command for Exchange {
                 executor.execute(builder, options).await?;
                 return Ok(());
             }
+            #[cfg(feature = "execute")]
+            Command::SettlePosition { args, position } => {
+                ctx.require_not_serialize_only_mode()?;
+                ctx.require_not_ix_buffer_mode()?;
+
+                let executor = args.build(client).await?;
+                let oracle = ctx.config().oracle()?;
+                let mut builder = client.settle_position(oracle, position)?;
+                for alt in ctx.config().alts() {
+                    let alt = client.alt(alt).await?.ok_or(gmsol_sdk::Error::NotFound)?;
+                    builder.add_alt(alt);
+                }
+                executor.execute(builder, options).await?;
+                return Ok(());
+            }
             Command::CloseEmptyPositions {
                 args:
                     CloseEmptyPositionsArgs {
@@ -2383,6 +2599,53 @@ impl super::Command for Exchange {
 
                 bundle
             }
+            Command::TransferPosition { position, to } => client
+                .transfer_position(store, position, to)
+                .into_bundle_with_options(options)?,
+            Command::CancelPositionTransfer { position } => client
+                .cancel_position_transfer(store, position)
+                .into_bundle_with_options(options)?,
+            Command::AcceptPosition { position } => {
+                let (rpc, target) = client
+                    .accept_position(store, position)
+                    .await?
+                    .swap_output(());
+                println!("Position: {target}");
+                rpc.into_bundle_with_options(options)?
+            }
+            Command::SyncPositionOrderCount { position } => client
+                .sync_position_order_count(store, position)
+                .await?
+                .into_bundle_with_options(options)?,
+            Command::RequestCollateralSwitch {
+                position,
+                min_collateral_amount,
+            } => {
+                let (rpc, target) = client
+                    .request_position_collateral_switch(store, position, *min_collateral_amount)
+                    .await?
+                    .swap_output(());
+                println!("Target position: {target}");
+                rpc.into_bundle_with_options(options)?
+            }
+            Command::CancelCollateralSwitch { position } => client
+                .cancel_position_collateral_switch(store, position)
+                .into_bundle_with_options(options)?,
+            #[cfg(feature = "execute")]
+            Command::SwitchCollateral { args, position } => {
+                ctx.require_not_serialize_only_mode()?;
+                ctx.require_not_ix_buffer_mode()?;
+
+                let executor = args.build(client).await?;
+                let oracle = ctx.config().oracle()?;
+                let mut builder = client.switch_position_collateral(oracle, position);
+                for alt in ctx.config().alts() {
+                    let alt = client.alt(alt).await?.ok_or(gmsol_sdk::Error::NotFound)?;
+                    builder.add_alt(alt);
+                }
+                executor.execute(builder, options).await?;
+                return Ok(());
+            }
             #[cfg(all(feature = "execute", feature = "nightly-cli-update-fees-state"))]
             Command::UpdateFeesState {
                 args,
@@ -24,7 +24,10 @@ use gmsol_sdk::{
     programs::{
         anchor_lang::prelude::Pubkey,
         bytemuck,
-        gmsol_store::accounts::{MarketConfigBuffer, VirtualInventory},
+        gmsol_store::{
+            accounts::{MarketConfigBuffer, VirtualInventory},
+            types::MarketScheduleParams,
+        },
     },
     serde::{
         serde_market::{SerdeMarket, SerdeMarketConfig, SerdeMarketConfigBuffer},
@@ -157,6 +160,33 @@ enum Command {
         token: Pubkey,
         provider: PriceProviderKind,
     },
+    /// Set the providers whose prices are aggregated with the expected provider of token.
+    SetPriceAggregation {
+        #[arg(long)]
+        token_map: Option<Pubkey>,
+        token: Pubkey,
+        /// The providers to aggregate. Price aggregation is disabled if not provided.
+        #[arg(long, short)]
+        provider: Vec<PriceProviderKind>,
+        /// The max allowed spread between the mid prices of the providers,
+        /// as a factor of their median.
+        #[arg(long, default_value = "0.01")]
+        max_spread_factor: Value,
+    },
+    /// Set the min spread and the spread multiplier of the prices of token.
+    SetPriceSpread {
+        #[arg(long)]
+        token_map: Option<Pubkey>,
+        token: Pubkey,
+        /// The min spread between the max and min prices, as a factor of the mid price.
+        /// `0` means no min spread.
+        #[arg(long, default_value = "0")]
+        min_spread_factor: Value,
+        /// The factor by which the spread reported by the providers is multiplied.
+        /// `0` means the spread is not multiplied.
+        #[arg(long, default_value = "0")]
+        spread_multiplier: Value,
+    },
     /// Create a `MarketConfigBuffer` account.
     CreateBuffer {
         #[command(flatten)]
@@ -288,6 +318,33 @@ enum Command {
         #[command(flatten)]
         toggle: ToggleValue,
     },
+    /// Reset the circuit breaker of the given markets.
+    ResetCircuitBreaker {
+        #[arg(required = true, num_args = 1..)]
+        market_tokens: Vec<Pubkey>,
+    },
+    /// Set the trading-hours schedule of the given market.
+    SetSchedule {
+        market_token: Pubkey,
+        /// The trading days, where `0` is Monday and `6` is Sunday.
+        #[arg(long, short = 'd', value_parser = clap::value_parser!(u8).range(0..7))]
+        trading_day: Vec<u8>,
+        /// The time at which a trading session opens, in seconds since 00:00 UTC.
+        #[arg(long, default_value_t = 0)]
+        open_time: u32,
+        /// The duration of a trading session in seconds.
+        /// The schedule is disabled if zero.
+        #[arg(long, default_value_t = 0)]
+        duration: u32,
+        /// The days on which no trading session opens, in days since the Unix epoch.
+        #[arg(long)]
+        holiday: Vec<u16>,
+    },
+    /// Complete the settlement of the given markets.
+    CompleteSettlement {
+        #[arg(required = true, num_args = 1..)]
+        market_tokens: Vec<Pubkey>,
+    },
     /// Initialize GT.
     InitGt {
         #[arg(long, short, default_value_t = 7)]
@@ -313,6 +370,17 @@ enum Command {
     },
     /// Set referred discount.
     SetReferredDiscountFactor { factor: Value },
+    /// Set order fee discount tiers by trading volume.
+    ///
+    /// Passing no tiers disables the volume discount.
+    SetVolumeTiers {
+        /// The min 30-day trading volume (in USD) of each tier.
+        #[arg(long, value_delimiter = ',')]
+        thresholds: Vec<Value>,
+        /// The order fee discount factor of each tier.
+        #[arg(long, value_delimiter = ',')]
+        factors: Vec<Value>,
+    },
     /// Create or update token metadata from file.
     UpdateTokenMetadatas { path: PathBuf },
     /// Display virtual inventories.
@@ -661,6 +729,40 @@ impl super::Command for Market {
                     .set_expected_provider(store, &token_map_address, token, *provider)
                     .into_bundle_with_options(options)?
             }
+            Command::SetPriceAggregation {
+                token_map,
+                token,
+                provider,
+                max_spread_factor,
+            } => {
+                let token_map_address = token_map_address(client, token_map.as_ref()).await?;
+                client
+                    .set_price_aggregation(
+                        store,
+                        &token_map_address,
+                        token,
+                        provider,
+                        max_spread_factor.to_u128()?,
+                    )
+                    .into_bundle_with_options(options)?
+            }
+            Command::SetPriceSpread {
+                token_map,
+                token,
+                min_spread_factor,
+                spread_multiplier,
+            } => {
+                let token_map_address = token_map_address(client, token_map.as_ref()).await?;
+                client
+                    .set_price_spread(
+                        store,
+                        &token_map_address,
+                        token,
+                        min_spread_factor.to_u128()?,
+                        spread_multiplier.to_u128()?,
+                    )
+                    .into_bundle_with_options(options)?
+            }
             Command::CreateBuffer {
                 keypair,
                 expire_after,
@@ -929,6 +1031,43 @@ impl super::Command for Market {
                 }
                 bundle
             }
+            Command::ResetCircuitBreaker { market_tokens } => {
+                let mut bundle = client.bundle_with_options(options);
+                for market_token in market_tokens {
+                    let rpc = client.reset_market_circuit_breaker(store, market_token);
+                    bundle.push(rpc)?;
+                }
+                bundle
+            }
+            Command::SetSchedule {
+                market_token,
+                trading_day,
+                open_time,
+                duration,
+                holiday,
+            } => {
+                let trading_days = trading_day.iter().fold(0u8, |days, day| days | (1 << day));
+                client
+                    .set_market_schedule(
+                        store,
+                        market_token,
+                        MarketScheduleParams {
+                            trading_days,
+                            open_time: *open_time,
+                            duration: *duration,
+                            holidays: holiday.clone(),
+                        },
+                    )
+                    .into_bundle_with_options(options)?
+            }
+            Command::CompleteSettlement { market_tokens } => {
+                let mut bundle = client.bundle_with_options(options);
+                for market_token in market_tokens {
+                    let rpc = client.complete_market_settlement(store, market_token);
+                    bundle.push(rpc)?;
+                }
+                bundle
+            }
             Command::SetConfigUpdatable {
                 flags,
                 factors,
@@ -1006,6 +1145,25 @@ impl super::Command for Market {
                     &factor.to_u128()?,
                 )
                 .into_bundle_with_options(options)?,
+            Command::SetVolumeTiers {
+                thresholds,
+                factors,
+            } => {
+                if thresholds.len() != factors.len() {
+                    eyre::bail!("the number of thresholds and factors must match");
+                }
+                let thresholds = thresholds
+                    .iter()
+                    .map(|v| Ok(v.to_u128()?))
+                    .collect::<eyre::Result<Vec<_>>>()?;
+                let factors = factors
+                    .iter()
+                    .map(|v| Ok(v.to_u128()?))
+                    .collect::<eyre::Result<Vec<_>>>()?;
+                client
+                    .set_volume_tiers(store, thresholds, factors)
+                    .into_bundle_with_options(options)?
+            }
             Command::UpdateTokenMetadatas { path } => {
                 let config: TokenMetadatas = toml_from_file(path)?;
                 let mut bundle = client.bundle_with_options(options);
@@ -1,6 +1,8 @@
 use gmsol_sdk::{
-    ops::user::UserOps, programs::anchor_lang::prelude::Pubkey,
-    programs::gmsol_store::accounts::ReferralCodeV2,
+    ops::{delegation::DelegationOps, order_intent::OrderIntentOps, user::UserOps},
+    programs::anchor_lang::prelude::Pubkey,
+    programs::gmsol_store::{accounts::ReferralCodeV2, types::DelegationParams},
+    utils::Value,
 };
 
 /// User account commands.
@@ -14,6 +16,8 @@ pub struct User {
 enum Command {
     /// Prepare User Account.
     Prepare,
+    /// Prepare User Stats Account.
+    PrepareStats,
     /// Initialize Referral Code.
     InitReferralCode { code: String },
     /// Transfer Referral Code.
@@ -24,6 +28,33 @@ enum Command {
     AcceptReferralCode { code: String },
     /// Set Referrer.
     SetReferrer { code: String },
+    /// Authorize a delegate to trade on behalf of the payer.
+    CreateDelegation {
+        /// The delegate.
+        delegate: Pubkey,
+        /// Allowed market tokens.
+        #[arg(long, short, action = clap::ArgAction::Append, required = true)]
+        market: Vec<Pubkey>,
+        /// Max size delta in usd per order.
+        #[arg(long)]
+        max_size: Value,
+        /// Max cumulative collateral amount (in raw units) the delegate can spend.
+        #[arg(long, default_value_t = 0)]
+        max_collateral_spend: u64,
+        /// The delegation cannot be used after this timestamp.
+        #[arg(long)]
+        expires_at: i64,
+    },
+    /// Revoke the delegation granted to a delegate.
+    RevokeDelegation { delegate: Pubkey },
+    /// Approve the intent authority to spend from the token account of the payer,
+    /// allowing relayers to create orders from intents signed by the payer.
+    ApproveIntentAuthority {
+        /// Token mint.
+        token: Pubkey,
+        /// Amount (in raw units) to approve.
+        amount: u64,
+    },
 }
 
 impl super::Command for User {
@@ -38,6 +69,7 @@ impl super::Command for User {
 
         let txn = match &self.command {
             Command::Prepare => client.prepare_user(store)?,
+            Command::PrepareStats => client.prepare_user_stats(store)?,
             Command::InitReferralCode { code } => {
                 client.initialize_referral_code(store, ReferralCodeV2::decode(code)?)?
             }
@@ -57,6 +89,32 @@ impl super::Command for User {
                     .set_referrer(store, ReferralCodeV2::decode(code)?, None)
                     .await?
             }
+            Command::CreateDelegation {
+                delegate,
+                market,
+                max_size,
+                max_collateral_spend,
+                expires_at,
+            } => {
+                let (txn, delegation) = client
+                    .create_delegation(
+                        store,
+                        delegate,
+                        DelegationParams {
+                            markets: market.clone(),
+                            max_size_delta_value: max_size.to_u128()?,
+                            max_collateral_spend: *max_collateral_spend,
+                            expires_at: *expires_at,
+                        },
+                    )
+                    .swap_output(());
+                println!("{delegation}");
+                txn
+            }
+            Command::RevokeDelegation { delegate } => client.revoke_delegation(store, delegate),
+            Command::ApproveIntentAuthority { token, amount } => {
+                client.approve_intent_authority(store, token, *amount)?
+            }
         };
 
         let bundle = txn.into_bundle_with_options(options)?;
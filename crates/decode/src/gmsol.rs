@@ -6,14 +6,16 @@ pub mod programs {
     use gmsol_programs::gmsol_store::{
         accounts::{
             Deposit, Glv, GlvDeposit, GlvShift, GlvWithdrawal, Market, Order, Position, Shift,
-            Store, UserHeader, VirtualInventory, Withdrawal,
+            Store, UserHeader, UserStats, VirtualInventory, Withdrawal,
         },
         events::{
             BorrowingFeesUpdated, BuilderFeeFactorSet, DepositExecuted, DepositRemoved,
             GlvDepositRemoved, GlvPricing, GlvTokenValue, GlvWithdrawalRemoved, GtBuyback,
-            GtUpdated, InsufficientFundingFeePayment, MarketFeesUpdated, MarketStateUpdated,
-            MarketTokenValue, OrderRemoved, OrderUpdated, PositionDecreased, PositionIncreased,
-            ShiftRemoved, SwapExecuted, TradeEvent, WithdrawalExecuted, WithdrawalRemoved,
+            GtUpdated, InsufficientFundingFeePayment, LinkedOrderCancelled,
+            MarketCircuitBreakerTripped, MarketFeesUpdated, MarketStateUpdated, MarketTokenValue,
+            OrderRemoved, OrderUpdated, PositionCollateralSwitched, PositionDecreased,
+            PositionIncreased, ShiftRemoved, SwapExecuted, TradeEvent, WithdrawalExecuted,
+            WithdrawalRemoved,
         },
     };
 
@@ -30,6 +32,7 @@ pub mod programs {
     impl_decode_for_zero_copy!(GlvShift);
     impl_decode_for_zero_copy!(Glv);
     impl_decode_for_zero_copy!(UserHeader);
+    impl_decode_for_zero_copy!(UserStats);
 
     impl_decode_for_cpi_event!(DepositRemoved);
     impl_decode_for_cpi_event!(DepositExecuted);
@@ -54,6 +57,9 @@ pub mod programs {
     impl_decode_for_cpi_event!(MarketTokenValue);
     impl_decode_for_cpi_event!(GlvTokenValue);
     impl_decode_for_cpi_event!(BuilderFeeFactorSet);
+    impl_decode_for_cpi_event!(PositionCollateralSwitched);
+    impl_decode_for_cpi_event!(MarketCircuitBreakerTripped);
+    impl_decode_for_cpi_event!(LinkedOrderCancelled);
 
     untagged!(
         GMSOLAccountData,
@@ -71,6 +77,7 @@ pub mod programs {
             VirtualInventory,
             Position,
             UserHeader,
+            UserStats,
             UnknownOwnedData
         ]
     );
@@ -103,6 +110,9 @@ pub mod programs {
             MarketTokenValue,
             GlvTokenValue,
             BuilderFeeFactorSet,
+            PositionCollateralSwitched,
+            MarketCircuitBreakerTripped,
+            LinkedOrderCancelled,
             UnknownOwnedData
         ]
     );
@@ -818,4 +818,198 @@ mod tests {
         println!("{market:#?}");
         Ok(())
     }
+
+    #[test]
+    fn partial_liquidation() -> crate::Result<()> {
+        use crate::{
+            params::position::PositionParams, position::PositionState, test::TestMarketConfig,
+        };
+
+        let config = TestMarketConfig {
+            position_params: PositionParams::builder()
+                .min_position_size_usd(1_000_000_000)
+                .min_collateral_value(1_000_000_000)
+                .min_collateral_factor(10_000_000)
+                .partial_liquidation_target_collateral_factor(Some(20_000_000))
+                .max_positive_position_impact_factor(5_000_000)
+                .max_negative_position_impact_factor(5_000_000)
+                .max_position_impact_factor_for_liquidations(2_500_000)
+                .build(),
+            ..Default::default()
+        };
+        let mut market = TestMarket::<u64, 9>::with_config(config);
+        let prices = Prices::new_for_test(12_000, 12_000, 1);
+        market.deposit(10_000_000_000, 0, prices)?.execute()?;
+        market.deposit(0, 1_000_000_000_000, prices)?.execute()?;
+
+        let mut position = TestPosition::long(false);
+        let report = position
+            .ops(&mut market)
+            .increase(prices, 20_000_000_000, 1_000_000_000_000, None)?
+            .execute()?;
+        println!("{report:#?}");
+
+        // Not liquidatable.
+        assert!(position
+            .ops(&mut market)
+            .partial_liquidation_size_delta_usd(&prices)?
+            .is_none());
+
+        let prices = Prices::new_for_test(11_880, 11_880, 1);
+        assert!(position
+            .ops(&mut market)
+            .check_liquidatable(&prices, true, true)?
+            .is_some());
+        let size_in_usd = *position.ops(&mut market).size_in_usd();
+        let size_delta_usd = position
+            .ops(&mut market)
+            .partial_liquidation_size_delta_usd(&prices)?
+            .expect("should be partially liquidated");
+        assert!(size_delta_usd < size_in_usd);
+
+        let report = position
+            .ops(&mut market)
+            .decrease(
+                prices,
+                size_delta_usd,
+                None,
+                0,
+                DecreasePositionFlags {
+                    is_liquidation_order: true,
+                    ..Default::default()
+                },
+            )?
+            .execute()?;
+        assert!(!report.should_remove());
+        assert!(report.fees().liquidation_fees().is_some());
+        assert_eq!(
+            *position.ops(&mut market).size_in_usd(),
+            size_in_usd - size_delta_usd
+        );
+        assert!(position
+            .ops(&mut market)
+            .check_liquidatable(&prices, true, true)?
+            .is_none());
+
+        // Insolvent positions must be fully liquidated.
+        let prices = Prices::new_for_test(11_000, 11_000, 1);
+        assert!(position
+            .ops(&mut market)
+            .partial_liquidation_size_delta_usd(&prices)?
+            .is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn size_tier_is_scaled_for_liquidation() -> crate::Result<()> {
+        use crate::{params::position::PositionParams, test::TestMarketConfig};
+
+        let config = TestMarketConfig {
+            position_params: PositionParams::builder()
+                .min_position_size_usd(1_000_000_000)
+                .min_collateral_value(1_000_000_000)
+                .min_collateral_factor(10_000_000)
+                .min_collateral_factor_for_liquidation(Some(5_000_000))
+                .max_positive_position_impact_factor(5_000_000)
+                .max_negative_position_impact_factor(5_000_000)
+                .max_position_impact_factor_for_liquidations(2_500_000)
+                .build(),
+            // Max leverage of 20x for positions of at least $50,
+            // i.e., 40x for liquidation.
+            position_size_tiers: vec![(50_000_000_000, 50_000_000)],
+            ..Default::default()
+        };
+        let mut market = TestMarket::<u64, 9>::with_config(config);
+        let prices = Prices::new_for_test(120, 120, 1);
+        market.deposit(10_000_000_000, 0, prices)?.execute()?;
+        market.deposit(0, 1_000_000_000_000, prices)?.execute()?;
+
+        let mut position = TestPosition::long(false);
+        let prices = Prices::new_for_test(123, 123, 1);
+        let report = position
+            .ops(&mut market)
+            .increase(prices, 6_000_000_000, 100_000_000_000, None)?
+            .execute()?;
+        println!("{report:#?}");
+        assert!(position
+            .ops(&mut market)
+            .check_liquidatable(&prices, true, true)?
+            .is_none());
+
+        // The position no longer meets the min collateral factor of the tier,
+        // but it is not liquidatable.
+        let prices = Prices::new_for_test(121, 121, 1);
+        assert!(position
+            .ops(&mut market)
+            .check_liquidatable(&prices, true, false)?
+            .is_some());
+        assert!(position
+            .ops(&mut market)
+            .check_liquidatable(&prices, true, true)?
+            .is_none());
+
+        // It is liquidatable once it falls below the scaled factor.
+        let prices = Prices::new_for_test(118, 118, 1);
+        assert!(position
+            .ops(&mut market)
+            .check_liquidatable(&prices, true, true)?
+            .is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn capped_negative_price_impact_for_decrease() -> crate::Result<()> {
+        use crate::{params::position::PositionParams, test::TestMarketConfig};
+
+        for (max_impact_factor, is_capped) in [(5_000_000, false), (1_000, true)] {
+            let config = TestMarketConfig {
+                position_params: PositionParams::builder()
+                    .min_position_size_usd(1_000_000_000)
+                    .min_collateral_value(1_000_000_000)
+                    .min_collateral_factor(10_000_000)
+                    .max_positive_position_impact_factor(5_000_000)
+                    .max_negative_position_impact_factor(max_impact_factor)
+                    .max_position_impact_factor_for_liquidations(2_500_000)
+                    .build(),
+                ..Default::default()
+            };
+            let mut market = TestMarket::<u64, 9>::with_config(config);
+            let prices = Prices::new_for_test(120, 120, 1);
+            market.deposit(100_000_000_000, 0, prices)?.execute()?;
+            market.deposit(0, 10_000_000_000_000, prices)?.execute()?;
+
+            let mut short = TestPosition::short(false);
+            let report = short
+                .ops(&mut market)
+                .increase(prices, 200_000_000_000, 2_000_000_000_000, None)?
+                .execute()?;
+            println!("{report:#?}");
+            let mut long = TestPosition::long(false);
+            let report = long
+                .ops(&mut market)
+                .increase(prices, 100_000_000_000, 1_000_000_000_000, None)?
+                .execute()?;
+            println!("{report:#?}");
+
+            // Closing the long position increases the imbalance.
+            let report = long
+                .ops(&mut market)
+                .decrease(prices, 1_000_000_000_000, None, 0, Default::default())?
+                .execute()?;
+            println!("{report:#?}");
+            assert!(report.price_impact_value().is_negative());
+            let claimable = report.claimable_collateral_for_user();
+            let claimable_amount =
+                claimable.output_token_amount() + claimable.secondary_output_token_amount();
+            if is_capped {
+                // The negative impact beyond the cap is recorded as claimable collateral.
+                assert_ne!(*report.price_impact_diff(), 0);
+                assert_eq!(*report.price_impact_diff(), claimable_amount);
+            } else {
+                assert_eq!(*report.price_impact_diff(), 0);
+                assert_eq!(claimable_amount, 0);
+            }
+        }
+        Ok(())
+    }
 }
@@ -567,7 +567,7 @@ struct ExecutionParamsWithPriceImpact<T: Unsigned> {
 mod tests {
     use crate::{
         market::LiquidityMarketMutExt,
-        test::{TestMarket, TestPosition},
+        test::{TestMarket, TestMarketConfig, TestPosition},
         MarketAction,
     };
 
@@ -594,4 +594,42 @@ mod tests {
         println!("{position:#?}");
         Ok(())
     }
+
+    #[test]
+    fn size_tiers() -> crate::Result<()> {
+        let mut market = TestMarket::<u64, 9>::with_config(TestMarketConfig {
+            // Max leverage of 20x for positions of at least $50.
+            position_size_tiers: vec![(50_000_000_000, 50_000_000)],
+            ..Default::default()
+        });
+        let prices = Prices::new_for_test(120, 120, 1);
+        market.deposit(1_000_000_000, 0, prices)?.execute()?;
+        market.deposit(0, 1_000_000_000, prices)?.execute()?;
+
+        let mut position = TestPosition::long(true);
+        let prices = Prices::new_for_test(123, 123, 1);
+        // A $49 position with 30x leverage does not reach the tier.
+        let report = position
+            .ops(&mut market)
+            .increase(prices, 13_279_000, 49_000_000_000, None)?
+            .execute()?;
+        println!("{report:#?}");
+        // It is rejected once the position reaches the tier.
+        let err = position
+            .ops(&mut market)
+            .increase(prices, 1_000_000, 2_000_000_000, None)?
+            .execute()
+            .expect_err("should fail with insufficient collateral");
+        assert!(matches!(
+            err,
+            crate::Error::InvalidArgument("insufficient collateral usd")
+        ));
+        // It is allowed with enough collateral.
+        let report = position
+            .ops(&mut market)
+            .increase(prices, 200_000_000, 2_000_000_000, None)?
+            .execute()?;
+        println!("{report:#?}");
+        Ok(())
+    }
 }
@@ -1,6 +1,6 @@
 use std::ops::DerefMut;
 
-use num_traits::{CheckedAdd, Signed};
+use num_traits::{CheckedAdd, Signed, Zero};
 
 use crate::{
     action::update_funding_state::UpdateFundingState,
@@ -47,6 +47,18 @@ pub trait PerpMarket<const DECIMALS: u8>:
         is_long: bool,
     ) -> crate::Result<Self::Num>;
 
+    /// Get min collateral factor for the given position size.
+    ///
+    /// Returns zero if the size does not reach any of the size tiers.
+    /// The default implementation has no size tiers and always returns zero.
+    fn min_collateral_factor_for_position_size(
+        &self,
+        size_in_usd: &Self::Num,
+    ) -> crate::Result<Self::Num> {
+        let _ = size_in_usd;
+        Ok(Zero::zero())
+    }
+
     /// Get liquidation fee params.
     fn liquidation_fee_params(&self) -> crate::Result<LiquidationFeeParams<Self::Num>>;
 }
@@ -158,6 +170,13 @@ impl<M: PerpMarket<DECIMALS>, const DECIMALS: u8> PerpMarket<DECIMALS> for &mut
         (**self).min_collateral_factor_for_open_interest_multiplier(is_long)
     }
 
+    fn min_collateral_factor_for_position_size(
+        &self,
+        size_in_usd: &Self::Num,
+    ) -> crate::Result<Self::Num> {
+        (**self).min_collateral_factor_for_position_size(size_in_usd)
+    }
+
     fn liquidation_fee_params(&self) -> crate::Result<LiquidationFeeParams<Self::Num>> {
         (**self).liquidation_fee_params()
     }
@@ -345,36 +364,17 @@ pub trait PerpMarketExt<const DECIMALS: u8>: PerpMarket<DECIMALS> {
         for_liquidations: bool,
         impact: &mut Self::Signed,
     ) -> crate::Result<Self::Num> {
-        use crate::{num::UnsignedAbs, utils};
-        use num_traits::{CheckedSub, Signed, Zero};
+        use crate::PositionImpactMarketExt;
 
-        let mut impact_diff = Zero::zero();
-        if impact.is_negative() {
-            let params = self.position_params()?;
-            let max_impact_factor = if for_liquidations {
-                params.max_position_impact_factor_for_liquidations()
-            } else {
-                params.max_negative_position_impact_factor()
-            };
-            // Although `size_delta_usd` is still used here to calculate the max impact even in the case of liquidation,
-            // partial liquidation is not allowed. Therefore, `size_delta_usd == size_in_usd` always holds,
-            // ensuring consistency with the Solidity version.
-            let min_impact = utils::apply_factor(&size_delta_usd.unsigned_abs(), max_impact_factor)
-                .ok_or(crate::Error::Computation(
-                    "calculating max negative position impact based on max factor",
-                ))?
-                .to_opposite_signed()?;
-            if *impact < min_impact {
-                impact_diff = min_impact
-                    .checked_sub(impact)
-                    .ok_or(crate::Error::Computation(
-                        "overflow calculating impact diff",
-                    ))?
-                    .unsigned_abs();
-                *impact = min_impact;
-            }
-        }
-        Ok(impact_diff)
+        let params = self.position_params()?;
+        let max_impact_factor = if for_liquidations {
+            params.max_position_impact_factor_for_liquidations()
+        } else {
+            params.max_negative_position_impact_factor()
+        };
+        // Note that `size_delta_usd` is used to calculate the max impact even in the case
+        // of liquidation, so the cap for a partial liquidation is proportional to the closed size.
+        self.cap_negative_position_price_impact_by_factor(size_delta_usd, max_impact_factor, impact)
     }
 }
 
@@ -114,6 +114,45 @@ pub trait PositionImpactMarketExt<const DECIMALS: u8>: PositionImpactMarket<DECI
                 ))?;
         Ok((distribution_amount, next_amount))
     }
+
+    /// Caps negative position price impact in-place by the given max impact factor.
+    /// If `impact` is not negative, the function does nothing.
+    ///
+    /// # Returns
+    ///
+    /// - The capped amount of the negative `impact`.
+    fn cap_negative_position_price_impact_by_factor(
+        &self,
+        size_delta_usd: &Self::Signed,
+        max_impact_factor: &Self::Num,
+        impact: &mut Self::Signed,
+    ) -> crate::Result<Self::Num> {
+        use crate::{
+            num::{Unsigned, UnsignedAbs},
+            utils,
+        };
+        use num_traits::Signed;
+
+        if !impact.is_negative() {
+            return Ok(Zero::zero());
+        }
+        let min_impact = utils::apply_factor(&size_delta_usd.unsigned_abs(), max_impact_factor)
+            .ok_or(crate::Error::Computation(
+                "calculating max negative position impact based on max factor",
+            ))?
+            .to_opposite_signed()?;
+        if *impact >= min_impact {
+            return Ok(Zero::zero());
+        }
+        let impact_diff = min_impact
+            .checked_sub(impact)
+            .ok_or(crate::Error::Computation(
+                "overflow calculating impact diff",
+            ))?
+            .unsigned_abs();
+        *impact = min_impact;
+        Ok(impact_diff)
+    }
 }
 
 impl<M: PositionImpactMarket<DECIMALS> + ?Sized, const DECIMALS: u8>
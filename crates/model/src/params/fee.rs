@@ -404,6 +404,11 @@ pub struct LiquidationFeeParams<T> {
 }
 
 impl<T> LiquidationFeeParams<T> {
+    /// Get liquidation fee factor.
+    pub fn factor(&self) -> &T {
+        &self.factor
+    }
+
     pub(crate) fn fee<const DECIMALS: u8>(
         &self,
         size_delta_usd: &T,
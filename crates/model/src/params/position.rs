@@ -8,6 +8,8 @@ pub struct PositionParams<T> {
     min_collateral_factor: T,
     #[builder(default)]
     min_collateral_factor_for_liquidation: Option<T>,
+    #[builder(default)]
+    partial_liquidation_target_collateral_factor: Option<T>,
     max_positive_position_impact_factor: T,
     max_negative_position_impact_factor: T,
     max_position_impact_factor_for_liquidations: T,
@@ -28,6 +30,7 @@ impl<T> PositionParams<T> {
             min_collateral_value,
             min_collateral_factor,
             min_collateral_factor_for_liquidation: None,
+            partial_liquidation_target_collateral_factor: None,
             max_positive_position_impact_factor,
             max_negative_position_impact_factor,
             max_position_impact_factor_for_liquidations,
@@ -56,6 +59,13 @@ impl<T> PositionParams<T> {
             .unwrap_or_else(|| self.min_collateral_factor())
     }
 
+    /// Get the target collateral factor for partial liquidation.
+    ///
+    /// Returns `None` if partial liquidation is disabled.
+    pub fn partial_liquidation_target_collateral_factor(&self) -> Option<&T> {
+        self.partial_liquidation_target_collateral_factor.as_ref()
+    }
+
     /// Get max positive position impact factor.
     pub fn max_positive_position_impact_factor(&self) -> &T {
         &self.max_positive_position_impact_factor
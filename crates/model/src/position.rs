@@ -287,6 +287,10 @@ pub trait PositionExt<const DECIMALS: u8>: Position<DECIMALS> {
                     .position_params()?
                     .min_collateral_factor()
                     .clone(),
+            )
+            .max(
+                self.market()
+                    .min_collateral_factor_for_position_size(&delta.next_size_in_usd)?,
             );
 
         match check_collateral(
@@ -468,69 +472,25 @@ pub trait PositionExt<const DECIMALS: u8>: Position<DECIMALS> {
         should_validate_min_collateral_usd: bool,
         for_liquidation: bool,
     ) -> crate::Result<Option<LiquidatableReason>> {
-        use num_traits::{CheckedAdd, CheckedMul, CheckedSub};
-
         let size_in_usd = self.size_in_usd();
-
-        let (pnl, _, _) = self.pnl_value(prices, size_in_usd)?;
-
-        let collateral_value = self.collateral_value(prices)?;
-        let collateral_price = self.collateral_price(prices);
-
-        let size_delta_usd = size_in_usd.to_opposite_signed()?;
-
-        let PriceImpact {
-            value: mut price_impact_value,
-            balance_change,
-        } = self.position_price_impact(&size_delta_usd, true)?;
-
-        if price_impact_value.is_negative() {
-            self.market().cap_negative_position_price_impact(
-                &size_delta_usd,
-                true,
-                &mut price_impact_value,
-            )?;
-        } else {
-            price_impact_value = Zero::zero();
-        }
-
-        let fees = self.position_fees(
-            collateral_price,
-            size_in_usd,
-            balance_change,
-            // Should not account for liquidation fees to determine if position should be liquidated.
-            false,
-        )?;
-
-        let collateral_cost_value = fees
-            .total_cost_amount()?
-            .checked_mul(collateral_price.pick_price(false))
-            .ok_or(crate::Error::Computation(
-                "overflow calculating collateral cost value",
-            ))?;
-
-        let remaining_collateral_value = collateral_value
-            .to_signed()?
-            .checked_add(&pnl)
-            .and_then(|v| {
-                v.checked_add(&price_impact_value)?
-                    .checked_sub(&collateral_cost_value.to_signed().ok()?)
-            })
-            .ok_or(crate::Error::Computation(
-                "calculating remaining collateral value",
-            ))?;
+        let remaining_collateral_value = remaining_collateral_value_for_liquidation(self, prices)?;
 
         let params = self.market().position_params()?;
 
         let collateral_factor = if for_liquidation {
-            params.min_collateral_factor_for_liquidation()
+            params.min_collateral_factor_for_liquidation().clone().max(
+                min_collateral_factor_for_position_size_for_liquidation(self)?,
+            )
         } else {
-            params.min_collateral_factor()
+            params.min_collateral_factor().clone().max(
+                self.market()
+                    .min_collateral_factor_for_position_size(size_in_usd)?,
+            )
         };
 
         match check_collateral(
             size_in_usd,
-            collateral_factor,
+            &collateral_factor,
             should_validate_min_collateral_usd.then(|| params.min_collateral_value()),
             false,
             &remaining_collateral_value,
@@ -546,6 +506,94 @@ pub trait PositionExt<const DECIMALS: u8>: Position<DECIMALS> {
         }
     }
 
+    /// Get the size (in USD) to close for partially liquidating the position.
+    ///
+    /// The size is chosen so that the position is restored to the partial liquidation
+    /// target collateral factor (but no less than the min collateral factors) after paying
+    /// the liquidation fee. Returns `None` if the position should be fully liquidated
+    /// instead, i.e. partial liquidation is disabled, the position is not liquidatable
+    /// only because of its leverage, or the whole position would have to be closed.
+    fn partial_liquidation_size_delta_usd(
+        &self,
+        prices: &Prices<Self::Num>,
+    ) -> crate::Result<Option<Self::Num>> {
+        use num_traits::CheckedSub;
+
+        let params = self.market().position_params()?;
+        let Some(target_factor) = params.partial_liquidation_target_collateral_factor() else {
+            return Ok(None);
+        };
+
+        let size_in_usd = self.size_in_usd();
+        let remaining_collateral_value = remaining_collateral_value_for_liquidation(self, prices)?;
+        let min_collateral_factor_for_liquidation =
+            params.min_collateral_factor_for_liquidation().clone().max(
+                min_collateral_factor_for_position_size_for_liquidation(self)?,
+            );
+
+        if !matches!(
+            check_collateral(
+                size_in_usd,
+                &min_collateral_factor_for_liquidation,
+                Some(params.min_collateral_value()),
+                false,
+                &remaining_collateral_value,
+            )?,
+            CheckCollateralResult::MinCollateralForLeverage
+        ) {
+            return Ok(None);
+        }
+
+        let target_factor = target_factor
+            .max(params.min_collateral_factor())
+            .max(&min_collateral_factor_for_liquidation);
+        let liquidation_fee_factor = self.market().liquidation_fee_params()?.factor().clone();
+
+        // Closing `size_delta_usd` keeps the remaining collateral value except for the
+        // liquidation fee, so it must satisfy:
+        // `remaining_collateral_value - size_delta_usd * liquidation_fee_factor
+        //      >= (size_in_usd - size_delta_usd) * target_factor`.
+        let Some(denominator) = target_factor.checked_sub(&liquidation_fee_factor) else {
+            return Ok(None);
+        };
+        if denominator.is_zero() {
+            return Ok(None);
+        }
+        let target_collateral_value = crate::utils::apply_factor(size_in_usd, target_factor)
+            .ok_or(crate::Error::Computation(
+                "partial liquidation: calculating target collateral value",
+            ))?;
+        let Some(numerator) =
+            target_collateral_value.checked_sub(&remaining_collateral_value.unsigned_abs())
+        else {
+            return Ok(None);
+        };
+        let size_delta_usd = numerator
+            .checked_mul_div_ceil(&Self::Num::UNIT, &denominator)
+            .ok_or(crate::Error::Computation(
+                "partial liquidation: calculating size delta usd",
+            ))?;
+
+        if size_delta_usd.is_zero() || size_delta_usd >= *size_in_usd {
+            return Ok(None);
+        }
+
+        // The remaining collateral value must not fall below the min collateral value.
+        let liquidation_fee_value =
+            crate::utils::apply_factor(&size_delta_usd, &liquidation_fee_factor).ok_or(
+                crate::Error::Computation("partial liquidation: calculating liquidation fee value"),
+            )?;
+        let is_remaining_collateral_sufficient = remaining_collateral_value
+            .unsigned_abs()
+            .checked_sub(&liquidation_fee_value)
+            .is_some_and(|value| value >= *params.min_collateral_value());
+        if !is_remaining_collateral_sufficient {
+            return Ok(None);
+        }
+
+        Ok(Some(size_delta_usd))
+    }
+
     /// Get position price impact.
     fn position_price_impact(
         &self,
@@ -761,8 +809,8 @@ pub trait PositionExt<const DECIMALS: u8>: Position<DECIMALS> {
 
         let liquidation_fees = is_liquidation
             .then(|| {
-                // Although `size_delta_usd` is used here to calculate liquidation fee, partial liquidation is not allowed.
-                // Therefore, `size_delta_usd == size_in_usd` always holds, ensuring consistency with the Solidity version.
+                // The liquidation fee is proportional to `size_delta_usd`, so a partial liquidation
+                // is only charged for the closed size.
                 self.market()
                     .liquidation_fee_params()?
                     .fee(size_delta_usd, collateral_token_price)
@@ -786,6 +834,99 @@ pub trait PositionExt<const DECIMALS: u8>: Position<DECIMALS> {
 
 impl<const DECIMALS: u8, P: Position<DECIMALS>> PositionExt<DECIMALS> for P {}
 
+/// Get the min collateral factor for liquidation of the size tier reached by the position.
+///
+/// The min collateral factor of a size tier applies to increases. For liquidation, it is
+/// scaled by the ratio of the min collateral factor for liquidation to the min collateral
+/// factor, so that the tiers keep the same buffer between the two as the base factors.
+fn min_collateral_factor_for_position_size_for_liquidation<const DECIMALS: u8, P>(
+    position: &P,
+) -> crate::Result<P::Num>
+where
+    P: PositionExt<DECIMALS> + ?Sized,
+{
+    let factor = position
+        .market()
+        .min_collateral_factor_for_position_size(position.size_in_usd())?;
+    let params = position.market().position_params()?;
+    let min_collateral_factor = params.min_collateral_factor();
+    if factor.is_zero() || min_collateral_factor.is_zero() {
+        return Ok(factor);
+    }
+    factor
+        .checked_mul_div(
+            params.min_collateral_factor_for_liquidation(),
+            min_collateral_factor,
+        )
+        .ok_or(crate::Error::Computation(
+            "calculating min collateral factor of size tier for liquidation",
+        ))
+}
+
+/// Get the remaining collateral value of the position after paying for the costs
+/// of closing it, which is used to determine whether the position is liquidatable.
+fn remaining_collateral_value_for_liquidation<const DECIMALS: u8, P>(
+    position: &P,
+    prices: &Prices<P::Num>,
+) -> crate::Result<P::Signed>
+where
+    P: PositionExt<DECIMALS> + ?Sized,
+{
+    use num_traits::{CheckedAdd, CheckedMul, CheckedSub};
+
+    let size_in_usd = position.size_in_usd();
+
+    let (pnl, _, _) = position.pnl_value(prices, size_in_usd)?;
+
+    let collateral_value = position.collateral_value(prices)?;
+    let collateral_price = position.collateral_price(prices);
+
+    let size_delta_usd = size_in_usd.to_opposite_signed()?;
+
+    let PriceImpact {
+        value: mut price_impact_value,
+        balance_change,
+    } = position.position_price_impact(&size_delta_usd, true)?;
+
+    if price_impact_value.is_negative() {
+        position.market().cap_negative_position_price_impact(
+            &size_delta_usd,
+            true,
+            &mut price_impact_value,
+        )?;
+    } else {
+        price_impact_value = Zero::zero();
+    }
+
+    let fees = position.position_fees(
+        collateral_price,
+        size_in_usd,
+        balance_change,
+        // Should not account for liquidation fees to determine if position should be liquidated.
+        false,
+    )?;
+
+    let collateral_cost_value = fees
+        .total_cost_amount()?
+        .checked_mul(collateral_price.pick_price(false))
+        .ok_or(crate::Error::Computation(
+            "overflow calculating collateral cost value",
+        ))?;
+
+    let remaining_collateral_value = collateral_value
+        .to_signed()?
+        .checked_add(&pnl)
+        .and_then(|v| {
+            v.checked_add(&price_impact_value)?
+                .checked_sub(&collateral_cost_value.to_signed().ok()?)
+        })
+        .ok_or(crate::Error::Computation(
+            "calculating remaining collateral value",
+        ))?;
+
+    Ok(remaining_collateral_value)
+}
+
 /// Extension trait for [`PositionMut`] with utils.
 pub trait PositionMutExt<const DECIMALS: u8>: PositionMut<DECIMALS>
 where
@@ -29,7 +29,7 @@ use crate::{
     BaseMarketMut, BorrowingFeeMarket, PerpMarketMut, PositionImpactMarketMut, PositionMut,
     PositionState, PositionStateMut, SwapMarketMut,
 };
-use num_traits::{CheckedSub, Signed};
+use num_traits::{CheckedSub, Signed, Zero};
 
 /// Test Pool.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
@@ -258,6 +258,8 @@ pub struct TestMarketConfig<T, const DECIMALS: u8> {
     pub max_open_interest: T,
     /// Min collateral factor for OI.
     pub min_collateral_factor_for_oi: T,
+    /// Position size tiers, as `(min_size_in_usd, min_collateral_factor)` pairs.
+    pub position_size_tiers: Vec<(T, T)>,
     /// Ignore open interest for usage factor.
     pub ignore_open_interest_for_usage_factor: bool,
     /// Liquidation fee params.
@@ -335,6 +337,7 @@ impl Default for TestMarketConfig<u64, 9> {
             max_open_interest: u64::MAX,
             // min collateral factor of 0.005 when open interest is $83,000,000
             min_collateral_factor_for_oi: 5 * 10u64.pow(6) / 83_000_000,
+            position_size_tiers: vec![],
             ignore_open_interest_for_usage_factor: false,
             liquidation_fee_params: LiquidationFeeParams::builder()
                 .factor(2_000_000)
@@ -418,6 +421,7 @@ impl Default for TestMarketConfig<u128, 20> {
             max_open_interest: 1_000_000_000 * 10u128.pow(20),
             // min collateral factor of 0.005 when open interest is $83,000,000
             min_collateral_factor_for_oi: 5 * 10u128.pow(17) / 83_000_000,
+            position_size_tiers: vec![],
             ignore_open_interest_for_usage_factor: false,
             liquidation_fee_params: LiquidationFeeParams::builder()
                 .factor(200_000_000_000_000_000)
@@ -759,6 +763,20 @@ where
         Ok(self.config.min_collateral_factor_for_oi.clone())
     }
 
+    fn min_collateral_factor_for_position_size(
+        &self,
+        size_in_usd: &Self::Num,
+    ) -> crate::Result<Self::Num> {
+        Ok(self
+            .config
+            .position_size_tiers
+            .iter()
+            .filter(|(min_size_in_usd, _)| size_in_usd >= min_size_in_usd)
+            .map(|(_, factor)| factor.clone())
+            .max()
+            .unwrap_or_else(Zero::zero))
+    }
+
     fn liquidation_fee_params(&self) -> crate::Result<LiquidationFeeParams<Self::Num>> {
         Ok(self.config.liquidation_fee_params.clone())
     }
@@ -132,6 +132,15 @@ impl MarketConfig {
         }
     }
 
+    fn partial_liquidation_target_collateral_factor(&self) -> Option<u128> {
+        let factor = self.partial_liquidation_target_collateral_factor;
+        if factor == 0 {
+            None
+        } else {
+            Some(factor)
+        }
+    }
+
     fn skip_borrowing_fee_for_smaller_side(&self, is_market_closed: bool) -> bool {
         if self.use_market_closed_params(is_market_closed) {
             self.flag(MarketConfigFlag::MarketClosedSkipBorrowingFeeForSmallerSide)
@@ -650,6 +659,13 @@ impl MarketModel {
             market_token: *market_token,
             collateral_token,
             state: Zeroable::zeroed(),
+            next_owner: Pubkey::default(),
+            num_orders: 0,
+            order_count_synced: 1,
+            padding_1: Zeroable::zeroed(),
+            collateral_switch_requested_at: 0,
+            collateral_switch_requested_at_slot: 0,
+            collateral_switch_min_amount: 0,
             reserved: Zeroable::zeroed(),
         };
         PositionModel::new(self, Arc::new(position))
@@ -989,6 +1005,9 @@ impl gmsol_model::PerpMarket<{ constants::MARKET_DECIMALS }> for MarketModel {
                 self.config
                     .min_collateral_factor_for_liquidation(self.is_closed()),
             )
+            .partial_liquidation_target_collateral_factor(
+                self.config.partial_liquidation_target_collateral_factor(),
+            )
             .build())
     }
 
@@ -1016,6 +1035,35 @@ impl gmsol_model::PerpMarket<{ constants::MARKET_DECIMALS }> for MarketModel {
         }
     }
 
+    fn min_collateral_factor_for_position_size(
+        &self,
+        size_in_usd: &Self::Num,
+    ) -> gmsol_model::Result<Self::Num> {
+        Ok([
+            (
+                self.config.size_tier1_min_position_size_usd,
+                self.config.size_tier1_min_collateral_factor,
+            ),
+            (
+                self.config.size_tier2_min_position_size_usd,
+                self.config.size_tier2_min_collateral_factor,
+            ),
+            (
+                self.config.size_tier3_min_position_size_usd,
+                self.config.size_tier3_min_collateral_factor,
+            ),
+            (
+                self.config.size_tier4_min_position_size_usd,
+                self.config.size_tier4_min_collateral_factor,
+            ),
+        ]
+        .into_iter()
+        .filter(|(min_size_in_usd, _)| *min_size_in_usd != 0 && size_in_usd >= min_size_in_usd)
+        .map(|(_, factor)| factor)
+        .max()
+        .unwrap_or(0))
+    }
+
     fn liquidation_fee_params(&self) -> gmsol_model::Result<LiquidationFeeParams<Self::Num>> {
         Ok(LiquidationFeeParams::builder()
             .factor(self.config.liquidation_fee_factor)
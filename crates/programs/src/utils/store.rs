@@ -118,6 +118,45 @@ impl Store {
             Ok(discount_factor_for_rank)
         }
     }
+
+    /// Get order fee discount factor for the given trading volume (in USD).
+    pub fn volume_discount_factor(&self, volume: u128) -> u128 {
+        let tiers = &self.volume_tiers;
+        let len = usize::from(tiers.num_tiers);
+        tiers.thresholds[..len]
+            .iter()
+            .zip(&tiers.factors[..len])
+            .take_while(|(threshold, _)| **threshold <= volume)
+            .last()
+            .map(|(_, factor)| *factor)
+            .unwrap_or(0)
+    }
+
+    /// Get order fee discount factor, including the discount for the given
+    /// rolling 30-day trading volume (in USD).
+    pub fn order_fee_discount_factor_with_volume(
+        &self,
+        rank: u8,
+        is_referred: bool,
+        volume: u128,
+    ) -> crate::Result<u128> {
+        use crate::constants::{MARKET_DECIMALS, MARKET_USD_UNIT};
+        use gmsol_model::utils::apply_factor;
+
+        let discount_factor = self.order_fee_discount_factor(rank, is_referred)?;
+        let discount_factor_for_volume = self.volume_discount_factor(volume);
+
+        let complement_discount_factor_for_volume = MARKET_USD_UNIT
+            .checked_sub(discount_factor_for_volume)
+            .ok_or_else(|| crate::Error::custom("complement calculation overflow"))?;
+
+        apply_factor::<_, { MARKET_DECIMALS }>(
+            &discount_factor,
+            &complement_discount_factor_for_volume,
+        )
+        .and_then(|factor| discount_factor_for_volume.checked_add(factor))
+        .ok_or_else(|| crate::Error::custom("discount factor calculation overflow"))
+    }
 }
 
 impl ReferralCodeV2 {
@@ -237,10 +276,11 @@ mod utils {
         pubkey::{self, optional_address},
         swap::{self, HasSwapParams},
         token_config::{self, TokensCollector},
+        user::{UserFlag, MAX_USER_FLAGS},
     };
 
     use crate::gmsol_store::{
-        accounts::{Glv, Market, Position},
+        accounts::{Glv, Market, Order, Position, UserHeader},
         events::TradeEvent,
         types::{
             ActionFlagContainer, ActionHeader, GlvMarketConfig, GlvMarketFlagContainer, GlvMarkets,
@@ -248,7 +288,7 @@ mod utils {
             MarketFlagContainer, MarketMeta, Members, MembersEntry, OrderActionParams,
             OrderFlagContainer, OrderKind, RoleMap, RoleMapEntry, RoleMetadata, RoleStore,
             SwapActionParams, TokenAndAccount, Tokens, TokensEntry, UpdateTokenConfigParams,
-            VirtualInventoryFlagContainer,
+            UserFlagContainer, VirtualInventoryFlagContainer,
         },
     };
 
@@ -277,6 +317,7 @@ mod utils {
     impl_flags!(MarketConfigFlag, MAX_MARKET_CONFIG_FLAGS, u128);
     impl_flags!(MarketConfigFactor, MAX_MARKET_CONFIG_FACTORS, u128);
     impl_flags!(OrderFlag, MAX_ORDER_FLAGS, u8);
+    impl_flags!(UserFlag, MAX_USER_FLAGS, u8);
 
     impl From<SwapActionParams> for swap::SwapActionParams {
         fn from(params: SwapActionParams) -> Self {
@@ -481,6 +522,43 @@ mod utils {
                 MarketConfigKey::MarketClosedBorrowingFeeAboveOptimalUsageFactor => {
                     &self.market_closed_borrowing_fee_above_optimal_usage_factor
                 }
+                MarketConfigKey::PartialLiquidationTargetCollateralFactor => {
+                    &self.partial_liquidation_target_collateral_factor
+                }
+                MarketConfigKey::CircuitBreakerMaxPriceChangeFactor => {
+                    &self.circuit_breaker_max_price_change_factor
+                }
+                MarketConfigKey::CircuitBreakerWindow => &self.circuit_breaker_window,
+                MarketConfigKey::MaxPositionSizeUsdForLong => &self.max_position_size_usd_for_long,
+                MarketConfigKey::MaxPositionSizeUsdForShort => {
+                    &self.max_position_size_usd_for_short
+                }
+                MarketConfigKey::SizeTier1MinPositionSizeUsd => {
+                    &self.size_tier1_min_position_size_usd
+                }
+                MarketConfigKey::SizeTier1MinCollateralFactor => {
+                    &self.size_tier1_min_collateral_factor
+                }
+                MarketConfigKey::SizeTier2MinPositionSizeUsd => {
+                    &self.size_tier2_min_position_size_usd
+                }
+                MarketConfigKey::SizeTier2MinCollateralFactor => {
+                    &self.size_tier2_min_collateral_factor
+                }
+                MarketConfigKey::SizeTier3MinPositionSizeUsd => {
+                    &self.size_tier3_min_position_size_usd
+                }
+                MarketConfigKey::SizeTier3MinCollateralFactor => {
+                    &self.size_tier3_min_collateral_factor
+                }
+                MarketConfigKey::SizeTier4MinPositionSizeUsd => {
+                    &self.size_tier4_min_position_size_usd
+                }
+                MarketConfigKey::SizeTier4MinCollateralFactor => {
+                    &self.size_tier4_min_collateral_factor
+                }
+                MarketConfigKey::PreCloseWindow => &self.pre_close_window,
+                MarketConfigKey::GapToleranceFactor => &self.gap_tolerance_factor,
                 _ => return None,
             };
             Some(value)
@@ -518,6 +596,8 @@ mod utils {
                 OrderKind::LimitIncrease => Self::LimitIncrease,
                 OrderKind::LimitDecrease => Self::LimitDecrease,
                 OrderKind::StopLossDecrease => Self::StopLossDecrease,
+                OrderKind::TrailingStopDecrease => Self::TrailingStopDecrease,
+                OrderKind::StopIncrease => Self::StopIncrease,
             }
         }
     }
@@ -536,6 +616,8 @@ mod utils {
                 order::OrderKind::LimitIncrease => Ok(Self::LimitIncrease),
                 order::OrderKind::LimitDecrease => Ok(Self::LimitDecrease),
                 order::OrderKind::StopLossDecrease => Ok(Self::StopLossDecrease),
+                order::OrderKind::TrailingStopDecrease => Ok(Self::TrailingStopDecrease),
+                order::OrderKind::StopIncrease => Ok(Self::StopIncrease),
                 kind => Err(crate::Error::custom(format!(
                     "unsupported order kind: {kind}"
                 ))),
@@ -571,6 +653,51 @@ mod utils {
                 .map_err(|_| crate::Error::custom("unknown decrease position swap type"))?;
             Ok(ty)
         }
+
+        /// Get the number of remaining TWAP slices.
+        ///
+        /// Always returns `1` for non-TWAP orders.
+        pub fn twap_remaining_slices(&self) -> u16 {
+            self.twap_slices
+                .saturating_sub(self.twap_executed_slices)
+                .max(1)
+        }
+    }
+
+    impl Order {
+        /// Returns whether the order is the parent of linked orders that have not been resolved.
+        pub fn has_pending_linked_orders(&self) -> bool {
+            self.params
+                .flags
+                .get_flag(OrderFlag::HasPendingLinkedOrders)
+        }
+
+        /// Returns whether the order is a linked order waiting for its parent to be executed.
+        pub fn is_pending_activation(&self) -> bool {
+            self.params.flags.get_flag(OrderFlag::IsPendingActivation)
+        }
+
+        /// Returns whether the order is counted in the number of orders of its position.
+        pub fn is_counted_by_position(&self) -> bool {
+            self.params.flags.get_flag(OrderFlag::IsCountedByPosition)
+        }
+
+        /// Get the sibling order of a linked (decrease) order.
+        pub fn linked_sibling(&self) -> crate::Result<Option<&Pubkey>> {
+            if self.params.kind()?.is_decrease_position() {
+                Ok(optional_address(&self.linked_orders[1]))
+            } else {
+                Ok(None)
+            }
+        }
+    }
+
+    impl UserHeader {
+        /// Returns whether the owner has a user stats account, which must be
+        /// provided to every execution of the owner's trades.
+        pub fn has_stats(&self) -> bool {
+            self.flags.get_flag(UserFlag::HasStats)
+        }
     }
 
     impl Position {
@@ -912,4 +1039,30 @@ mod tests {
         let expected = MARKET_USD_UNIT / 100 * 70;
         assert_eq!(factor, expected, "combined high discounts should be 70%");
     }
+
+    #[test]
+    fn test_order_fee_discount_factor_with_volume() {
+        let mut discount_factors = [0u128; 16];
+        discount_factors[1] = MARKET_USD_UNIT / 100 * 10; // 10%
+        let mut store = create_test_store(9, discount_factors, MARKET_USD_UNIT / 100 * 10);
+        store.volume_tiers.num_tiers = 2;
+        store.volume_tiers.thresholds[..2]
+            .copy_from_slice(&[1_000_000 * MARKET_USD_UNIT, 10_000_000 * MARKET_USD_UNIT]);
+        store.volume_tiers.factors[..2]
+            .copy_from_slice(&[MARKET_USD_UNIT / 100 * 20, MARKET_USD_UNIT / 100 * 50]);
+
+        assert_eq!(store.volume_discount_factor(999_999 * MARKET_USD_UNIT), 0);
+        assert_eq!(
+            store
+                .order_fee_discount_factor_with_volume(1, false, 0)
+                .unwrap(),
+            MARKET_USD_UNIT / 100 * 10
+        );
+
+        // Combined: 1 - (1 - 0.1) * (1 - 0.1) * (1 - 0.5) = 1 - 0.405 = 59.5%
+        let factor = store
+            .order_fee_discount_factor_with_volume(1, true, 20_000_000 * MARKET_USD_UNIT)
+            .unwrap();
+        assert_eq!(factor, MARKET_USD_UNIT / 1_000 * 595);
+    }
 }
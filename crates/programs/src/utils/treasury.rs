@@ -40,9 +40,7 @@ mod utils {
         gt::{GtBankFlags, MAX_GT_BANK_FLAGS},
         impl_fixed_map, impl_flags,
         pubkey::to_bytes,
-        token_config::{
-            TokenFlag, TokenMapAccess, TokenRecord, TokensWithFeed, MAX_TREASURY_TOKEN_FLAGS,
-        },
+        token_config::{TokenFlag, TokenMapAccess, TokensWithFeed, MAX_TREASURY_TOKEN_FLAGS},
     };
 
     const MAX_TOKENS: usize = 16;
@@ -78,17 +76,7 @@ mod utils {
                 .tokens()
                 .chain(treasury_vault_config.tokens())
                 .collect::<BTreeSet<_>>();
-            let records = tokens
-                .iter()
-                .map(|token| {
-                    let config = map
-                        .get(token)
-                        .ok_or_else(|| crate::Error::custom("unknown token"))?;
-                    TokenRecord::from_config(*token, config).map_err(crate::Error::custom)
-                })
-                .collect::<Result<Vec<_>, _>>()?;
-
-            TokensWithFeed::try_from_records(records).map_err(crate::Error::custom)
+            TokensWithFeed::try_from_token_map(map, &tokens).map_err(crate::Error::custom)
         }
     }
 
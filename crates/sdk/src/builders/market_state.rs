@@ -155,6 +155,93 @@ impl FromRpcClientWith<UpdateClosedState> for UpdateClosedStateHint {
     }
 }
 
+/// Builder for `start_market_settlement` instruction.
+#[cfg_attr(js, derive(tsify_next::Tsify))]
+#[cfg_attr(js, tsify(from_wasm_abi))]
+#[cfg_attr(serde, derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, TypedBuilder)]
+pub struct StartMarketSettlement {
+    /// Payer (a.k.a. authority).
+    #[builder(setter(into))]
+    pub payer: StringPubkey,
+    /// Store program.
+    #[cfg_attr(serde, serde(default))]
+    #[builder(default)]
+    pub store_program: StoreProgram,
+    /// Oracle buffer account.
+    #[builder(setter(into))]
+    pub oracle: StringPubkey,
+    /// Market token mint address.
+    #[builder(setter(into))]
+    pub market_token: StringPubkey,
+    /// Feeds Parser.
+    #[cfg_attr(serde, serde(skip))]
+    #[builder(default)]
+    pub feeds_parser: FeedsParser,
+}
+
+impl StartMarketSettlement {
+    /// Insert a feed parser.
+    pub fn insert_feed_parser(
+        &mut self,
+        provider: PriceProviderKind,
+        map: FeedAddressMap,
+    ) -> crate::Result<()> {
+        self.feeds_parser
+            .insert_pull_oracle_feed_parser(provider, map);
+        Ok(())
+    }
+}
+
+/// Hint for [`StartMarketSettlement`].
+pub type StartMarketSettlementHint = UpdateClosedStateHint;
+
+impl IntoAtomicGroup for StartMarketSettlement {
+    type Hint = StartMarketSettlementHint;
+
+    fn into_atomic_group(self, hint: &Self::Hint) -> gmsol_solana_utils::Result<AtomicGroup> {
+        let authority = &self.payer.0;
+        let market = self.store_program.find_market_address(&self.market_token);
+        let feeds = self
+            .feeds_parser
+            .parse(&hint.to_tokens_with_feeds()?)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(gmsol_solana_utils::Error::custom)?;
+        let start = self
+            .store_program
+            .anchor_instruction(args::StartMarketSettlement {})
+            .anchor_accounts(
+                accounts::StartMarketSettlement {
+                    authority: *authority,
+                    store: self.store_program.store.0,
+                    token_map: hint.token_map.0,
+                    oracle: self.oracle.0,
+                    market,
+                },
+                false,
+            )
+            .accounts(feeds)
+            .build();
+        Ok(AtomicGroup::with_instructions(authority, [start]))
+    }
+}
+
+impl FromRpcClientWith<StartMarketSettlement> for StartMarketSettlementHint {
+    async fn from_rpc_client_with<'a>(
+        builder: &'a StartMarketSettlement,
+        client: &'a impl gmsol_solana_utils::client_traits::RpcClient,
+    ) -> gmsol_solana_utils::Result<Self> {
+        // The required accounts are the same as `update_closed_state`.
+        let update = UpdateClosedState::builder()
+            .payer(builder.payer)
+            .store_program(builder.store_program.clone())
+            .oracle(builder.oracle)
+            .market_token(builder.market_token)
+            .build();
+        <Self as FromRpcClientWith<UpdateClosedState>>::from_rpc_client_with(&update, client).await
+    }
+}
+
 /// Builder for `update_fees_state` instruction.
 #[cfg_attr(js, derive(tsify_next::Tsify))]
 #[cfg_attr(js, tsify(from_wasm_abi))]
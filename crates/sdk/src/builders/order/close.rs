@@ -4,6 +4,7 @@ use gmsol_programs::{
     gmsol_store::client::{accounts, args},
 };
 use gmsol_solana_utils::{AtomicGroup, IntoAtomicGroup, ProgramExt};
+use solana_sdk::instruction::AccountMeta;
 use typed_builder::TypedBuilder;
 
 use crate::{
@@ -70,6 +71,15 @@ pub struct CloseOrderHint {
     pub should_unwrap_native_token: bool,
     /// Callback.
     pub callback: Option<Callback>,
+    /// The position referenced by the order, if it has not been closed.
+    #[cfg_attr(serde, serde(default))]
+    #[builder(default, setter(into))]
+    pub position: Option<StringPubkey>,
+    /// The linked orders, required if the order is the parent of linked orders
+    /// that have not been resolved.
+    #[cfg_attr(serde, serde(default))]
+    #[builder(default)]
+    pub pending_linked_orders: Vec<StringPubkey>,
 }
 
 impl IntoAtomicGroup for CloseOrder {
@@ -173,6 +183,18 @@ impl IntoAtomicGroup for CloseOrder {
                 },
                 true,
             )
+            .anchor_accounts(
+                hint.pending_linked_orders
+                    .iter()
+                    .map(|order| AccountMeta::new_readonly(order.0, false))
+                    .chain(
+                        hint.position
+                            .as_deref()
+                            .map(|position| AccountMeta::new(*position, false)),
+                    )
+                    .collect::<Vec<_>>(),
+                false,
+            )
             .build();
         Ok(AtomicGroup::with_instructions(&payer, Some(close)))
     }
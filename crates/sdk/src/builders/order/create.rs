@@ -1,6 +1,8 @@
 use anchor_spl::associated_token::{self, get_associated_token_address_with_program_id};
 use gmsol_programs::gmsol_store::client::args;
-use gmsol_programs::gmsol_store::types::CreateOrderParams as StoreCreateOrderParams;
+use gmsol_programs::gmsol_store::types::{
+    CreateOrderParams as StoreCreateOrderParams, CreateOrderParamsExt as StoreCreateOrderParamsExt,
+};
 use gmsol_programs::gmsol_store::{client::accounts, types::OrderKind};
 use gmsol_solana_utils::client_traits::FromRpcClientWith;
 use gmsol_solana_utils::ProgramExt;
@@ -39,6 +41,10 @@ pub enum CreateOrderKind {
     LimitDecrease,
     /// Stop-loss Decrease.
     StopLossDecrease,
+    /// Trailing-stop Decrease.
+    TrailingStopDecrease,
+    /// Stop Increase.
+    StopIncrease,
 }
 
 impl From<CreateOrderKind> for OrderKind {
@@ -51,6 +57,8 @@ impl From<CreateOrderKind> for OrderKind {
             CreateOrderKind::LimitIncrease => Self::LimitIncrease,
             CreateOrderKind::LimitDecrease => Self::LimitDecrease,
             CreateOrderKind::StopLossDecrease => Self::StopLossDecrease,
+            CreateOrderKind::TrailingStopDecrease => Self::TrailingStopDecrease,
+            CreateOrderKind::StopIncrease => Self::StopIncrease,
         }
     }
 }
@@ -63,18 +71,84 @@ impl CreateOrderKind {
 
     /// Returns whether the order kind is "increase".
     pub fn is_increase(&self) -> bool {
-        matches!(self, Self::MarketIncrease | Self::LimitIncrease)
+        matches!(
+            self,
+            Self::MarketIncrease | Self::LimitIncrease | Self::StopIncrease
+        )
     }
 
     /// Returns whether the ordr kind is "decrease".
     pub fn is_decrease(&self) -> bool {
         matches!(
             self,
-            Self::MarketDecrease | Self::LimitDecrease | Self::StopLossDecrease
+            Self::MarketDecrease
+                | Self::LimitDecrease
+                | Self::StopLossDecrease
+                | Self::TrailingStopDecrease
         )
     }
 }
 
+/// Trail distance for trailing-stop orders.
+#[cfg_attr(js, derive(tsify_next::Tsify))]
+#[cfg_attr(js, tsify(from_wasm_abi))]
+#[cfg_attr(serde, derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy)]
+pub enum TrailDistance {
+    /// Absolute distance (in unit price).
+    Absolute(u128),
+    /// Distance as a factor of the mark price.
+    Factor(u128),
+}
+
+impl TrailDistance {
+    /// Returns the trigger price for the given mark price (in unit price).
+    pub fn trigger_price(&self, mark_price: u128, is_long: bool) -> Option<u128> {
+        use gmsol_model::utils::apply_factor;
+        use gmsol_programs::constants::MARKET_DECIMALS;
+
+        let distance = match self {
+            Self::Absolute(distance) => *distance,
+            Self::Factor(factor) => apply_factor::<_, { MARKET_DECIMALS }>(&mark_price, factor)?,
+        };
+        if is_long {
+            Some(mark_price.saturating_sub(distance))
+        } else {
+            mark_price.checked_add(distance)
+        }
+    }
+}
+
+impl From<TrailDistance> for gmsol_programs::gmsol_store::types::TrailDistance {
+    fn from(distance: TrailDistance) -> Self {
+        match distance {
+            TrailDistance::Absolute(distance) => Self::Absolute(distance),
+            TrailDistance::Factor(factor) => Self::Factor(factor),
+        }
+    }
+}
+
+/// TWAP params for splitting a market order into slices executed at a fixed interval.
+#[cfg_attr(js, derive(tsify_next::Tsify))]
+#[cfg_attr(js, tsify(from_wasm_abi))]
+#[cfg_attr(serde, derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy)]
+pub struct TwapParams {
+    /// The number of slices.
+    pub slices: u16,
+    /// The interval between two slices in seconds.
+    pub interval: u32,
+}
+
+impl From<TwapParams> for gmsol_programs::gmsol_store::types::TwapParams {
+    fn from(params: TwapParams) -> Self {
+        Self {
+            slices: params.slices,
+            interval: params.interval,
+        }
+    }
+}
+
 /// Swap type for decreasing position.
 #[cfg_attr(js, derive(tsify_next::Tsify))]
 #[cfg_attr(js, tsify(from_wasm_abi))]
@@ -141,6 +215,8 @@ pub struct CreateOrderParams {
     #[builder(default)]
     pub min_output: u128,
     /// Trigger price (in unit price).
+    ///
+    /// For trailing-stop orders, it is the initial mark price.
     #[cfg_attr(serde, serde(default))]
     #[builder(default, setter(strip_option))]
     pub trigger_price: Option<u128>,
@@ -156,6 +232,34 @@ pub struct CreateOrderParams {
     #[cfg_attr(serde, serde(default))]
     #[builder(default, setter(strip_option))]
     pub valid_from_ts: Option<i64>,
+    /// Timestamp after which the order can no longer be executed.
+    #[cfg_attr(serde, serde(default))]
+    #[builder(default, setter(strip_option))]
+    pub valid_until_ts: Option<i64>,
+    /// Trail distance. Required for trailing-stop orders only.
+    #[cfg_attr(serde, serde(default))]
+    #[builder(default, setter(strip_option))]
+    pub trail_distance: Option<TrailDistance>,
+    /// Split the order into slices executed at a fixed interval.
+    /// Only market increase and market decrease orders can be TWAP orders.
+    #[cfg_attr(serde, serde(default))]
+    #[builder(default, setter(strip_option))]
+    pub twap: Option<TwapParams>,
+}
+
+impl CreateOrderParams {
+    /// Returns the trigger price to be compared with the index price.
+    ///
+    /// For trailing-stop orders, it is derived from the mark price and the trail distance.
+    pub fn effective_trigger_price(&self, kind: CreateOrderKind) -> Option<u128> {
+        let trigger_price = self.trigger_price?;
+        match kind {
+            CreateOrderKind::TrailingStopDecrease => self
+                .trail_distance?
+                .trigger_price(trigger_price, self.is_long),
+            _ => Some(trigger_price),
+        }
+    }
 }
 
 /// Builder for the `create_order` instruction.
@@ -304,7 +408,9 @@ impl IntoAtomicGroup for CreateOrder {
                     false,
                 )
             }
-            CreateOrderKind::MarketIncrease | CreateOrderKind::LimitIncrease => (
+            CreateOrderKind::MarketIncrease
+            | CreateOrderKind::LimitIncrease
+            | CreateOrderKind::StopIncrease => (
                 Some(
                     self.pay_token
                         .as_deref()
@@ -318,7 +424,8 @@ impl IntoAtomicGroup for CreateOrder {
             ),
             CreateOrderKind::MarketDecrease
             | CreateOrderKind::LimitDecrease
-            | CreateOrderKind::StopLossDecrease => (
+            | CreateOrderKind::StopLossDecrease
+            | CreateOrderKind::TrailingStopDecrease => (
                 None,
                 Some(
                     self.receive_token
@@ -393,6 +500,14 @@ impl IntoAtomicGroup for CreateOrder {
             should_unwrap_native_token: self.unwrap_native_on_receive,
             valid_from_ts: self.params.valid_from_ts,
         };
+        let ext = (self.params.trail_distance.is_some()
+            || self.params.twap.is_some()
+            || self.params.valid_until_ts.is_some())
+        .then(|| StoreCreateOrderParamsExt {
+            trail_distance: self.params.trail_distance.map(Into::into),
+            twap: self.params.twap.map(Into::into),
+            valid_until_ts: self.params.valid_until_ts,
+        });
 
         if is_position_order && self.skip_position_creation && self.force_position_creation {
             return Err(crate::SolanaUtilsError::custom(
@@ -425,13 +540,21 @@ impl IntoAtomicGroup for CreateOrder {
             callback_partitioned_data_account,
         } = self.program.get_callback_params(self.callback.as_ref());
 
-        let create = self
-            .program
-            .anchor_instruction(args::CreateOrderV2 {
+        // `create_order_v3` is only required when extended params are provided.
+        let create = match ext {
+            Some(ext) => self.program.anchor_instruction(args::CreateOrderV3 {
                 nonce: nonce.to_bytes(),
                 params,
+                ext,
                 callback_version,
-            })
+            }),
+            None => self.program.anchor_instruction(args::CreateOrderV2 {
+                nonce: nonce.to_bytes(),
+                params,
+                callback_version,
+            }),
+        };
+        let create = create
             .anchor_accounts(
                 accounts::CreateOrderV2 {
                     owner,
@@ -543,6 +666,46 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn create_twap_order() -> crate::Result<()> {
+        use gmsol_programs::anchor_lang::Discriminator;
+
+        let long_token = Pubkey::new_unique();
+        let short_token = Pubkey::new_unique();
+        let hint = CreateOrderHint::builder()
+            .long_token(long_token)
+            .short_token(short_token)
+            .build();
+        let create = |twap: Option<TwapParams>| {
+            let mut params = CreateOrderParams::builder()
+                .market_token(Pubkey::new_unique())
+                .is_long(true)
+                .size(1_000 * crate::constants::MARKET_USD_UNIT)
+                .build();
+            params.twap = twap;
+            CreateOrder::builder()
+                .payer(Pubkey::new_unique())
+                .kind(CreateOrderKind::MarketIncrease)
+                .collateral_or_swap_out_token(long_token)
+                .params(params)
+                .skip_position_creation(true)
+                .build()
+                .into_atomic_group(&hint)
+        };
+
+        let twap = create(Some(TwapParams {
+            slices: 4,
+            interval: 60,
+        }))?;
+        let ix = twap.last().expect("must have the create instruction");
+        assert!(ix.data.starts_with(args::CreateOrderV3::DISCRIMINATOR));
+
+        let market = create(None)?;
+        let ix = market.last().expect("must have the create instruction");
+        assert!(ix.data.starts_with(args::CreateOrderV2::DISCRIMINATOR));
+        Ok(())
+    }
+
     #[async_test]
     async fn create_order_with_rpc() -> crate::Result<()> {
         let market_token: Pubkey = "5sdFW7wrKsxxYHMXoqDmNHkGyCWsbLEFb1x1gzBBm4Hx".parse()?;
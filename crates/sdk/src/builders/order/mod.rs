@@ -17,6 +17,7 @@ pub use self::{
     close::{CloseOrder, CloseOrderHint},
     create::{
         CreateOrder, CreateOrderHint, CreateOrderKind, CreateOrderParams, DecreasePositionSwapType,
+        TrailDistance, TwapParams,
     },
     position::PreparePosition,
     update::{UpdateOrder, UpdateOrderHint, UpdateOrderParams},
@@ -121,6 +121,11 @@ impl StoreProgram {
         pda::find_user_address(&self.store, owner, &self.id).0
     }
 
+    /// Find user stats address.
+    pub fn find_user_stats_address(&self, owner: &Pubkey) -> Pubkey {
+        pda::find_user_stats_address(&self.store, owner, &self.id).0
+    }
+
     /// Find position address.
     pub fn find_position_address(
         &self,
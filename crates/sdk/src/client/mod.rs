@@ -496,6 +496,26 @@ impl<C: Clone + Deref<Target = impl Signer>> Client<C> {
         crate::pda::find_user_address(store, owner, self.store_program_id()).0
     }
 
+    /// Find PDA for user stats account.
+    pub fn find_user_stats_address(&self, store: &Pubkey, owner: &Pubkey) -> Pubkey {
+        crate::pda::find_user_stats_address(store, owner, self.store_program_id()).0
+    }
+
+    /// Find PDA for delegation account.
+    pub fn find_delegation_address(
+        &self,
+        store: &Pubkey,
+        owner: &Pubkey,
+        delegate: &Pubkey,
+    ) -> Pubkey {
+        crate::pda::find_delegation_address(store, owner, delegate, self.store_program_id()).0
+    }
+
+    /// Find PDA for the order intent authority of the owner.
+    pub fn find_intent_authority_address(&self, store: &Pubkey, owner: &Pubkey) -> Pubkey {
+        crate::pda::find_intent_authority_address(store, owner, self.store_program_id()).0
+    }
+
     /// Find PDA for referral code.
     pub fn find_referral_code_address(&self, store: &Pubkey, code: ReferralCodeBytes) -> Pubkey {
         crate::pda::find_referral_code_address(store, code, self.store_program_id()).0
@@ -572,6 +592,11 @@ impl<C: Clone + Deref<Target = impl Signer>> Client<C> {
         .0
     }
 
+    /// Find PDA for price publishers.
+    pub fn find_price_publishers_address(&self, store: &Pubkey) -> Pubkey {
+        crate::pda::find_price_publishers_address(store, self.store_program_id()).0
+    }
+
     /// Find PDA for treasury global config.
     pub fn find_treasury_config_address(&self, store: &Pubkey) -> Pubkey {
         crate::pda::find_treasury_config_address(store, self.treasury_program_id()).0
@@ -791,6 +816,15 @@ impl<C: Clone + Deref<Target = impl Signer>> Client<C> {
             .0)
     }
 
+    /// Fetch user stats account with its address.
+    pub async fn user_stats(&self, address: &Pubkey) -> crate::Result<store_accounts::UserStats> {
+        Ok(self
+            .account::<ZeroCopy<store_accounts::UserStats>>(address)
+            .await?
+            .ok_or(crate::Error::NotFound)?
+            .0)
+    }
+
     /// Fetch the [`TokenMap`] address of the given store.
     pub async fn authorized_token_map_address(
         &self,
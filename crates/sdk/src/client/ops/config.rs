@@ -21,6 +21,26 @@ pub trait ConfigOps<C> {
         enable: bool,
     ) -> TransactionBuilder<C>;
 
+    /// Toggle feature for the given market.
+    fn toggle_market_feature(
+        &self,
+        store: &Pubkey,
+        market_token: &Pubkey,
+        domain: DomainDisabledFlag,
+        action: ActionDisabledFlag,
+        enable: bool,
+    ) -> TransactionBuilder<C>;
+
+    /// Toggle feature for the given GLV.
+    fn toggle_glv_feature(
+        &self,
+        store: &Pubkey,
+        glv_token: &Pubkey,
+        domain: DomainDisabledFlag,
+        action: ActionDisabledFlag,
+        enable: bool,
+    ) -> TransactionBuilder<C>;
+
     /// Insert a global amount.
     fn insert_global_amount(
         &self,
@@ -45,6 +65,14 @@ pub trait ConfigOps<C> {
         address: &Pubkey,
     ) -> TransactionBuilder<C>;
 
+    /// Set order fee discount tiers by trading volume.
+    fn set_volume_tiers(
+        &self,
+        store: &Pubkey,
+        thresholds: Vec<u128>,
+        factors: Vec<Factor>,
+    ) -> TransactionBuilder<C>;
+
     /// Insert a global amount by key.
     fn insert_global_amount_by_key(
         &self,
@@ -99,6 +127,48 @@ impl<C: Deref<Target = impl Signer> + Clone> ConfigOps<C> for crate::Client<C> {
             })
     }
 
+    fn toggle_market_feature(
+        &self,
+        store: &Pubkey,
+        market_token: &Pubkey,
+        domain: DomainDisabledFlag,
+        action: ActionDisabledFlag,
+        enable: bool,
+    ) -> TransactionBuilder<C> {
+        self.store_transaction()
+            .anchor_args(args::ToggleMarketFeature {
+                domain: domain.to_string(),
+                action: action.to_string(),
+                enable,
+            })
+            .anchor_accounts(accounts::ToggleMarketFeature {
+                authority: self.payer(),
+                store: *store,
+                market: self.find_market_address(store, market_token),
+            })
+    }
+
+    fn toggle_glv_feature(
+        &self,
+        store: &Pubkey,
+        glv_token: &Pubkey,
+        domain: DomainDisabledFlag,
+        action: ActionDisabledFlag,
+        enable: bool,
+    ) -> TransactionBuilder<C> {
+        self.store_transaction()
+            .anchor_args(args::ToggleGlvFeature {
+                domain: domain.to_string(),
+                action: action.to_string(),
+                enable,
+            })
+            .anchor_accounts(accounts::ToggleGlvFeature {
+                authority: self.payer(),
+                store: *store,
+                glv: self.find_glv_address(glv_token),
+            })
+    }
+
     fn insert_global_amount(
         &self,
         store: &Pubkey,
@@ -152,4 +222,21 @@ impl<C: Deref<Target = impl Signer> + Clone> ConfigOps<C> for crate::Client<C> {
                 store: *store,
             })
     }
+
+    fn set_volume_tiers(
+        &self,
+        store: &Pubkey,
+        thresholds: Vec<u128>,
+        factors: Vec<Factor>,
+    ) -> TransactionBuilder<C> {
+        self.store_transaction()
+            .anchor_args(args::SetVolumeTiers {
+                thresholds,
+                factors,
+            })
+            .anchor_accounts(accounts::SetVolumeTiers {
+                authority: self.payer(),
+                store: *store,
+            })
+    }
 }
@@ -0,0 +1,118 @@
+use std::ops::Deref;
+
+use gmsol_programs::gmsol_store::{
+    client::{accounts, args},
+    types::{DelegationParams, UpdateOrderParams},
+};
+use gmsol_solana_utils::transaction_builder::TransactionBuilder;
+use solana_sdk::{pubkey::Pubkey, signer::Signer, system_program};
+
+/// Operations for delegations.
+///
+/// Delegated orders are created with
+/// [`CreateOrderBuilder::delegated_by`](crate::client::ops::exchange::order::CreateOrderBuilder::delegated_by).
+pub trait DelegationOps<C> {
+    /// Authorize the delegate to trade on behalf of the payer.
+    ///
+    /// To let the delegate create increase orders, the payer is expected to approve
+    /// the delegation account to spend from the source token accounts.
+    fn create_delegation(
+        &self,
+        store: &Pubkey,
+        delegate: &Pubkey,
+        params: DelegationParams,
+    ) -> TransactionBuilder<C, Pubkey>;
+
+    /// Revoke the delegation granted to the delegate by the payer.
+    fn revoke_delegation(&self, store: &Pubkey, delegate: &Pubkey) -> TransactionBuilder<C>;
+
+    /// Update an order of the owner as a delegate.
+    fn update_order_by_delegate(
+        &self,
+        store: &Pubkey,
+        owner: &Pubkey,
+        market_token: &Pubkey,
+        order: &Pubkey,
+        params: UpdateOrderParams,
+    ) -> TransactionBuilder<C>;
+
+    /// Cancel an order of the owner as a delegate.
+    fn cancel_order_by_delegate(
+        &self,
+        store: &Pubkey,
+        owner: &Pubkey,
+        order: &Pubkey,
+    ) -> TransactionBuilder<C>;
+}
+
+impl<C: Deref<Target = impl Signer> + Clone> DelegationOps<C> for crate::Client<C> {
+    fn create_delegation(
+        &self,
+        store: &Pubkey,
+        delegate: &Pubkey,
+        params: DelegationParams,
+    ) -> TransactionBuilder<C, Pubkey> {
+        let owner = self.payer();
+        let delegation = self.find_delegation_address(store, &owner, delegate);
+        self.store_transaction()
+            .anchor_accounts(accounts::CreateDelegation {
+                owner,
+                store: *store,
+                delegate: *delegate,
+                delegation,
+                system_program: system_program::ID,
+            })
+            .anchor_args(args::CreateDelegation { params })
+            .output(delegation)
+    }
+
+    fn revoke_delegation(&self, store: &Pubkey, delegate: &Pubkey) -> TransactionBuilder<C> {
+        let owner = self.payer();
+        self.store_transaction()
+            .anchor_accounts(accounts::RevokeDelegation {
+                owner,
+                store: *store,
+                delegation: self.find_delegation_address(store, &owner, delegate),
+            })
+            .anchor_args(args::RevokeDelegation {})
+    }
+
+    fn update_order_by_delegate(
+        &self,
+        store: &Pubkey,
+        owner: &Pubkey,
+        market_token: &Pubkey,
+        order: &Pubkey,
+        params: UpdateOrderParams,
+    ) -> TransactionBuilder<C> {
+        let delegate = self.payer();
+        self.store_transaction()
+            .anchor_accounts(accounts::UpdateOrderByDelegate {
+                delegate,
+                store: *store,
+                market: self.find_market_address(store, market_token),
+                delegation: self.find_delegation_address(store, owner, &delegate),
+                order: *order,
+                event_authority: self.store_event_authority(),
+                program: *self.store_program_id(),
+            })
+            .anchor_args(args::UpdateOrderByDelegate { params })
+    }
+
+    fn cancel_order_by_delegate(
+        &self,
+        store: &Pubkey,
+        owner: &Pubkey,
+        order: &Pubkey,
+    ) -> TransactionBuilder<C> {
+        let delegate = self.payer();
+        self.store_transaction()
+            .anchor_accounts(accounts::CancelOrderByDelegate {
+                delegate,
+                store: *store,
+                delegation: self.find_delegation_address(store, owner, &delegate),
+                order: *order,
+            })
+            .anchor_args(args::CancelOrderByDelegate {})
+    }
+}
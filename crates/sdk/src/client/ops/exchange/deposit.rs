@@ -15,6 +15,7 @@ use gmsol_solana_utils::{
 };
 use gmsol_utils::{
     action::ActionFlag,
+    market::MarketMeta,
     oracle::PriceProviderKind,
     pubkey::optional_address,
     swap::SwapActionParams,
@@ -778,3 +779,317 @@ impl<C> SetExecutionFee for ExecuteDepositBuilder<'_, C> {
         self
     }
 }
+
+/// Default max number of deposits to execute in one transaction.
+pub const DEFAULT_MAX_DEPOSITS_PER_TRANSACTION: usize = 3;
+
+/// Execute Deposits Builder.
+///
+/// Executes deposits of the same market in batch, sharing one set of oracle prices.
+/// Only deposits without swap paths can be executed in batch.
+pub struct ExecuteDepositsBuilder<'a, C> {
+    client: &'a Client<C>,
+    store: Pubkey,
+    oracle: Pubkey,
+    market_token: Pubkey,
+    deposits: Vec<Pubkey>,
+    execution_fee: u64,
+    feeds_parser: FeedsParser,
+    hint: Option<ExecuteDepositsHint>,
+    token_map: Option<Pubkey>,
+    max_deposits_per_transaction: usize,
+    close: bool,
+}
+
+/// Hint for executing deposits in batch.
+#[derive(Clone, Debug)]
+pub struct ExecuteDepositsHint {
+    long_token: Pubkey,
+    short_token: Pubkey,
+    /// Feeds.
+    pub feeds: TokensWithFeed,
+    deposits: Vec<(Pubkey, CloseDepositHint)>,
+    virtual_inventories: BTreeSet<Pubkey>,
+}
+
+impl ExecuteDepositsHint {
+    /// Create a new hint for the deposits.
+    pub fn new<'d>(
+        market: &MarketMeta,
+        deposits: impl IntoIterator<Item = (&'d Pubkey, &'d Deposit)>,
+        map: &impl TokenMapAccess,
+        virtual_inventories: BTreeSet<Pubkey>,
+    ) -> crate::Result<Self> {
+        let feeds = TokensWithFeed::try_from_token_map(
+            map,
+            &BTreeSet::from([
+                market.index_token_mint,
+                market.long_token_mint,
+                market.short_token_mint,
+            ]),
+        )
+        .map_err(crate::Error::custom)?;
+        let deposits = deposits
+            .into_iter()
+            .map(|(address, deposit)| {
+                let swap: SwapActionParams = deposit.swap.into();
+                if swap.primary_length() != 0 || swap.secondary_length() != 0 {
+                    return Err(crate::Error::custom(format!(
+                        "deposit `{address}` with swap paths cannot be executed in batch"
+                    )));
+                }
+                if deposit.tokens.market_token.token != market.market_token_mint {
+                    return Err(crate::Error::custom(format!(
+                        "deposit `{address}` does not belong to the market"
+                    )));
+                }
+                Ok((*address, CloseDepositHint::new(deposit)))
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
+        Ok(Self {
+            long_token: market.long_token_mint,
+            short_token: market.short_token_mint,
+            feeds,
+            deposits,
+            virtual_inventories,
+        })
+    }
+}
+
+impl<'a, S, C> ExecuteDepositsBuilder<'a, C>
+where
+    C: Deref<Target = S> + Clone,
+    S: Signer,
+{
+    pub(super) fn new(
+        client: &'a Client<C>,
+        store: &Pubkey,
+        oracle: &Pubkey,
+        market_token: &Pubkey,
+        deposits: impl IntoIterator<Item = Pubkey>,
+    ) -> Self {
+        Self {
+            client,
+            store: *store,
+            oracle: *oracle,
+            market_token: *market_token,
+            deposits: deposits.into_iter().collect(),
+            execution_fee: 0,
+            feeds_parser: Default::default(),
+            hint: None,
+            token_map: None,
+            max_deposits_per_transaction: DEFAULT_MAX_DEPOSITS_PER_TRANSACTION,
+            close: true,
+        }
+    }
+
+    /// Set whether to close the deposits after execution.
+    pub fn close(&mut self, close: bool) -> &mut Self {
+        self.close = close;
+        self
+    }
+
+    /// Set the max number of deposits to execute in one transaction.
+    pub fn max_deposits_per_transaction(&mut self, max: usize) -> &mut Self {
+        self.max_deposits_per_transaction = max.max(1);
+        self
+    }
+
+    /// Set token map.
+    pub fn token_map(&mut self, address: Pubkey) -> &mut Self {
+        self.token_map = Some(address);
+        self
+    }
+
+    /// Set hint.
+    pub fn hint(&mut self, hint: ExecuteDepositsHint) -> &mut Self {
+        self.hint = Some(hint);
+        self
+    }
+
+    /// Prepare [`ExecuteDepositsHint`].
+    pub async fn prepare_hint(&mut self) -> crate::Result<ExecuteDepositsHint> {
+        match &self.hint {
+            Some(hint) => Ok(hint.clone()),
+            None => {
+                let map = self.client.authorized_token_map(&self.store).await?;
+                let market = self
+                    .client
+                    .market_by_token(&self.store, &self.market_token)
+                    .await?;
+                let meta: MarketMeta = market.meta.into();
+                let mut deposits = Vec::with_capacity(self.deposits.len());
+                for address in self.deposits.iter() {
+                    deposits.push((*address, self.client.deposit(address).await?));
+                }
+                let virtual_inventories = VirtualInventoryCollector::default()
+                    .insert_market_token(&self.market_token)
+                    .collect(self.client, &self.store)
+                    .await?;
+                let hint = ExecuteDepositsHint::new(
+                    &meta,
+                    deposits.iter().map(|(address, deposit)| (address, deposit)),
+                    &map,
+                    virtual_inventories,
+                )?;
+                self.hint = Some(hint.clone());
+                Ok(hint)
+            }
+        }
+    }
+
+    async fn get_token_map(&self) -> crate::Result<Pubkey> {
+        if let Some(address) = self.token_map {
+            Ok(address)
+        } else {
+            Ok(self
+                .client
+                .authorized_token_map_address(&self.store)
+                .await?
+                .ok_or(crate::Error::NotFound)?)
+        }
+    }
+
+    async fn build_txns(&mut self) -> crate::Result<Vec<TransactionBuilder<'a, C>>> {
+        let token_map = self.get_token_map().await?;
+        let hint = self.prepare_hint().await?;
+        let Self {
+            client,
+            store,
+            oracle,
+            market_token,
+            execution_fee,
+            max_deposits_per_transaction,
+            ..
+        } = &self;
+        let authority = client.payer();
+        let program_id = client.store_program_id();
+        let feeds = self
+            .feeds_parser
+            .parse(&hint.feeds)
+            .collect::<Result<Vec<_>, _>>()?;
+        let virtual_inventories = hint
+            .virtual_inventories
+            .iter()
+            .map(|pubkey| AccountMeta::new(*pubkey, false))
+            .collect::<Vec<_>>();
+
+        let mut txns = Vec::default();
+        for chunk in hint.deposits.chunks(*max_deposits_per_transaction) {
+            let deposits = chunk.iter().flat_map(|(deposit, close)| {
+                [
+                    Some(*deposit),
+                    Some(close.market_token_account),
+                    close.initial_long_token_account,
+                    close.initial_short_token_account,
+                ]
+                .map(|address| AccountMeta::new(address.unwrap_or(*program_id), false))
+            });
+            let count =
+                u8::try_from(chunk.len()).map_err(|_| crate::Error::custom("too many deposits"))?;
+            let compute_budget = u32::try_from(chunk.len())
+                .ok()
+                .and_then(|len| len.checked_mul(EXECUTE_DEPOSIT_COMPUTE_BUDGET))
+                .ok_or_else(|| crate::Error::custom("too many deposits"))?;
+            let execute = client
+                .store_transaction()
+                .accounts(fix_optional_account_metas(
+                    accounts::ExecuteDeposits {
+                        authority,
+                        store: *store,
+                        token_map,
+                        oracle: *oracle,
+                        market: client.find_market_address(store, market_token),
+                        market_token: *market_token,
+                        long_token: hint.long_token,
+                        short_token: hint.short_token,
+                        long_token_vault: client.find_market_vault_address(store, &hint.long_token),
+                        short_token_vault: client
+                            .find_market_vault_address(store, &hint.short_token),
+                        token_program: anchor_spl::token::ID,
+                        system_program: system_program::ID,
+                        chainlink_program: None,
+                        event_authority: client.store_event_authority(),
+                        program: *program_id,
+                    },
+                    &ID,
+                    program_id,
+                ))
+                .anchor_args(args::ExecuteDeposits {
+                    execution_fee: *execution_fee,
+                    count,
+                })
+                .accounts(
+                    feeds
+                        .iter()
+                        .cloned()
+                        .chain(deposits)
+                        .chain(virtual_inventories.iter().cloned())
+                        .collect::<Vec<_>>(),
+                )
+                .compute_budget(ComputeBudget::default().with_limit(compute_budget));
+            txns.push(execute);
+
+            if self.close {
+                for (deposit, close) in chunk {
+                    txns.push(
+                        client
+                            .close_deposit(store, deposit)
+                            .hint(*close)
+                            .reason("executed")
+                            .build()
+                            .await?,
+                    );
+                }
+            }
+        }
+
+        Ok(txns)
+    }
+}
+
+impl<'a, C: Deref<Target = impl Signer> + Clone> MakeBundleBuilder<'a, C>
+    for ExecuteDepositsBuilder<'a, C>
+{
+    async fn build_with_options(
+        &mut self,
+        options: BundleOptions,
+    ) -> gmsol_solana_utils::Result<BundleBuilder<'a, C>> {
+        let mut tx = self.client.bundle_with_options(options);
+
+        tx.push_many(
+            self.build_txns()
+                .await
+                .map_err(gmsol_solana_utils::Error::custom)?,
+            false,
+        )?;
+
+        Ok(tx)
+    }
+}
+
+impl<C: Deref<Target = impl Signer> + Clone> PullOraclePriceConsumer
+    for ExecuteDepositsBuilder<'_, C>
+{
+    async fn feed_ids(&mut self) -> crate::Result<FeedIds> {
+        let hint = self.prepare_hint().await?;
+        Ok(FeedIds::new(self.store, hint.feeds))
+    }
+
+    fn process_feeds(
+        &mut self,
+        provider: PriceProviderKind,
+        map: FeedAddressMap,
+    ) -> crate::Result<()> {
+        self.feeds_parser
+            .insert_pull_oracle_feed_parser(provider, map);
+        Ok(())
+    }
+}
+
+impl<C> SetExecutionFee for ExecuteDepositsBuilder<'_, C> {
+    fn set_execution_fee(&mut self, lamports: u64) -> &mut Self {
+        self.execution_fee = lamports;
+        self
+    }
+}
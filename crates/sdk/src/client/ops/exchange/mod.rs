@@ -28,7 +28,9 @@ use std::{
     ops::Deref,
 };
 
-use deposit::{CloseDepositBuilder, CreateDepositBuilder, ExecuteDepositBuilder};
+use deposit::{
+    CloseDepositBuilder, CreateDepositBuilder, ExecuteDepositBuilder, ExecuteDepositsBuilder,
+};
 use glv_deposit::{CloseGlvDepositBuilder, CreateGlvDepositBuilder, ExecuteGlvDepositBuilder};
 use glv_shift::{CloseGlvShiftBuilder, CreateGlvShiftBuilder, ExecuteGlvShiftBuilder};
 use glv_withdrawal::{
@@ -36,27 +38,34 @@ use glv_withdrawal::{
 };
 use gmsol_programs::gmsol_store::{
     client::{accounts, args},
-    types::UpdateOrderParams,
+    types::{TrailDistance, UpdateOrderParams},
 };
 use gmsol_solana_utils::{transaction_builder::TransactionBuilder, IntoAtomicGroup};
 use gmsol_utils::{
+    market::MarketMeta,
     order::{OrderKind, PositionCutKind},
     pubkey::optional_address,
     swap::SwapActionParams,
 };
-use market_state::{UpdateClosedStateBuilder, UpdateFeesStateBuilder};
+use market_state::{
+    StartMarketSettlementBuilder, UpdateClosedStateBuilder, UpdateFeesStateBuilder,
+};
 use order::{
-    CloseOrderBuilder, CreateOrderBuilder, ExecuteOrderBuilder, OrderParams, PositionCutBuilder,
-    UpdateAdlBuilder,
+    CloseOrderBuilder, CreateOrderBuilder, ExecuteOrderBuilder, ExecuteOrdersBuilder, OrderParams,
+    PositionCutBuilder, SwitchPositionCollateralBuilder, UpdateAdlBuilder,
+    UpdateTrailingStopBuilder,
 };
 use shift::{CloseShiftBuilder, CreateShiftBuilder, ExecuteShiftBuilder};
-use solana_sdk::{pubkey::Pubkey, signer::Signer};
-use withdrawal::{CloseWithdrawalBuilder, CreateWithdrawalBuilder, ExecuteWithdrawalBuilder};
+use solana_sdk::{instruction::AccountMeta, pubkey::Pubkey, signer::Signer};
+use withdrawal::{
+    CloseWithdrawalBuilder, CreateWithdrawalBuilder, ExecuteWithdrawalBuilder,
+    ExecuteWithdrawalsBuilder,
+};
 
 use crate::{
     builders::{
         callback::{Callback, CallbackParams},
-        market_state::{UpdateClosedState, UpdateFeesState},
+        market_state::{StartMarketSettlement, UpdateClosedState, UpdateFeesState},
         order::update::SetShouldKeepPositionAccount,
         position::CloseEmptyPosition,
     },
@@ -87,6 +96,15 @@ pub trait ExchangeOps<C> {
         cancel_on_execution_error: bool,
     ) -> ExecuteDepositBuilder<C>;
 
+    /// Execute deposits of the given market in batch.
+    fn execute_deposits(
+        &self,
+        store: &Pubkey,
+        oracle: &Pubkey,
+        market_token: &Pubkey,
+        deposits: impl IntoIterator<Item = Pubkey>,
+    ) -> ExecuteDepositsBuilder<C>;
+
     /// Create a withdrawal.
     fn create_withdrawal(
         &self,
@@ -107,6 +125,15 @@ pub trait ExchangeOps<C> {
         cancel_on_execution_error: bool,
     ) -> ExecuteWithdrawalBuilder<C>;
 
+    /// Execute withdrawals of the given market in batch.
+    fn execute_withdrawals(
+        &self,
+        store: &Pubkey,
+        oracle: &Pubkey,
+        market_token: &Pubkey,
+        withdrawals: impl IntoIterator<Item = Pubkey>,
+    ) -> ExecuteWithdrawalsBuilder<C>;
+
     /// Create shift.
     fn create_shift(
         &self,
@@ -242,6 +269,32 @@ pub trait ExchangeOps<C> {
         self.create_order(store, market_token, is_collateral_token_long, params)
     }
 
+    /// Create a stop increase order.
+    #[allow(clippy::too_many_arguments)]
+    fn stop_increase(
+        &self,
+        store: &Pubkey,
+        market_token: &Pubkey,
+        is_long: bool,
+        increment_size_in_usd: u128,
+        price: u128,
+        is_collateral_token_long: bool,
+        initial_collateral_amount: u64,
+    ) -> CreateOrderBuilder<C> {
+        let params = OrderParams {
+            kind: OrderKind::StopIncrease,
+            decrease_position_swap_type: None,
+            min_output_amount: 0,
+            size_delta_usd: increment_size_in_usd,
+            initial_collateral_delta_amount: initial_collateral_amount,
+            acceptable_price: None,
+            trigger_price: Some(price),
+            is_long,
+            valid_from_ts: None,
+        };
+        self.create_order(store, market_token, is_collateral_token_long, params)
+    }
+
     /// Create a limit decrease order.
     #[allow(clippy::too_many_arguments)]
     fn limit_decrease(
@@ -294,6 +347,41 @@ pub trait ExchangeOps<C> {
         self.create_order(store, market_token, is_collateral_token_long, params)
     }
 
+    /// Create a trailing-stop decrease order.
+    ///
+    /// The `mark_price` is the initial high-water mark (long) or low-water mark (short).
+    #[allow(clippy::too_many_arguments)]
+    fn trailing_stop<S>(
+        &self,
+        store: &Pubkey,
+        market_token: &Pubkey,
+        is_long: bool,
+        decrement_size_in_usd: u128,
+        mark_price: u128,
+        trail_distance: TrailDistance,
+        is_collateral_token_long: bool,
+        collateral_withdrawal_amount: u64,
+    ) -> CreateOrderBuilder<C>
+    where
+        C: Deref<Target = S> + Clone,
+        S: Signer,
+    {
+        let params = OrderParams {
+            kind: OrderKind::TrailingStopDecrease,
+            decrease_position_swap_type: None,
+            min_output_amount: 0,
+            size_delta_usd: decrement_size_in_usd,
+            initial_collateral_delta_amount: collateral_withdrawal_amount,
+            acceptable_price: None,
+            trigger_price: Some(mark_price),
+            is_long,
+            valid_from_ts: None,
+        };
+        let mut builder = self.create_order(store, market_token, is_collateral_token_long, params);
+        builder.trail_distance(trail_distance);
+        builder
+    }
+
     /// Create a limit swap order.
     #[allow(clippy::too_many_arguments)]
     fn limit_swap<'a, S>(
@@ -347,6 +435,15 @@ pub trait ExchangeOps<C> {
         cancel_on_execution_error: bool,
     ) -> crate::Result<ExecuteOrderBuilder<C>>;
 
+    /// Execute market increase orders of the given market in batch.
+    fn execute_orders(
+        &self,
+        store: &Pubkey,
+        oracle: &Pubkey,
+        market_token: &Pubkey,
+        orders: impl IntoIterator<Item = Pubkey>,
+    ) -> ExecuteOrdersBuilder<C>;
+
     /// Close an order.
     fn close_order(&self, order: &Pubkey) -> crate::Result<CloseOrderBuilder<C>>;
 
@@ -358,6 +455,35 @@ pub trait ExchangeOps<C> {
         position_hint: Option<&Pubkey>,
     ) -> impl Future<Output = crate::Result<TransactionBuilder<C>>>;
 
+    /// Cancel and close an expired order.
+    fn cancel_expired_order(&self, order: &Pubkey) -> crate::Result<CloseOrderBuilder<C>>;
+
+    /// Link two decrease orders (e.g., a take-profit and a stop-loss order) to an increase order.
+    fn link_orders(
+        &self,
+        store: &Pubkey,
+        parent: &Pubkey,
+        first: &Pubkey,
+        second: &Pubkey,
+    ) -> TransactionBuilder<C>;
+
+    /// Activate the linked orders of an executed parent order, or cancel them if the
+    /// parent order has been cancelled.
+    fn activate_linked_orders(
+        &self,
+        store: &Pubkey,
+        parent: &Pubkey,
+        linked_orders: &[Pubkey; 2],
+    ) -> TransactionBuilder<C>;
+
+    /// Cancel a linked order after its sibling has been executed.
+    fn cancel_linked_order(
+        &self,
+        store: &Pubkey,
+        order: &Pubkey,
+        sibling: &Pubkey,
+    ) -> TransactionBuilder<C>;
+
     /// Close empty position.
     fn close_empty_position(
         &self,
@@ -365,6 +491,60 @@ pub trait ExchangeOps<C> {
         position: &Pubkey,
     ) -> crate::Result<TransactionBuilder<C>>;
 
+    /// Initiate a transfer of the position to `next_owner`.
+    fn transfer_position(
+        &self,
+        store: &Pubkey,
+        position: &Pubkey,
+        next_owner: &Pubkey,
+    ) -> TransactionBuilder<C>;
+
+    /// Cancel the transfer of the position.
+    fn cancel_position_transfer(&self, store: &Pubkey, position: &Pubkey) -> TransactionBuilder<C>;
+
+    /// Accept the transfer of the position, with the payer as the next owner.
+    ///
+    /// Returns the address of the position of the next owner.
+    fn accept_position(
+        &self,
+        store: &Pubkey,
+        position: &Pubkey,
+    ) -> impl Future<Output = crate::Result<TransactionBuilder<C, Pubkey>>>;
+
+    /// Count the orders of the position created before the order count was introduced.
+    ///
+    /// The orders of the position owner in the position market that have not been counted
+    /// are fetched and provided.
+    fn sync_position_order_count(
+        &self,
+        store: &Pubkey,
+        position: &Pubkey,
+    ) -> impl Future<Output = crate::Result<TransactionBuilder<C>>>;
+
+    /// Request to switch the collateral token of the position to the opposite token.
+    ///
+    /// Returns the address of the position with the opposite collateral token.
+    fn request_position_collateral_switch(
+        &self,
+        store: &Pubkey,
+        position: &Pubkey,
+        min_collateral_amount: u64,
+    ) -> impl Future<Output = crate::Result<TransactionBuilder<C, Pubkey>>>;
+
+    /// Cancel the collateral switch request of the position.
+    fn cancel_position_collateral_switch(
+        &self,
+        store: &Pubkey,
+        position: &Pubkey,
+    ) -> TransactionBuilder<C>;
+
+    /// Switch the collateral token of the position.
+    fn switch_position_collateral(
+        &self,
+        oracle: &Pubkey,
+        position: &Pubkey,
+    ) -> SwitchPositionCollateralBuilder<C>;
+
     /// Liquidate a position.
     fn liquidate(&self, oracle: &Pubkey, position: &Pubkey)
         -> crate::Result<PositionCutBuilder<C>>;
@@ -377,6 +557,13 @@ pub trait ExchangeOps<C> {
         size_delta_usd: u128,
     ) -> crate::Result<PositionCutBuilder<C>>;
 
+    /// Force-close a position of a settled market.
+    fn settle_position(
+        &self,
+        oracle: &Pubkey,
+        position: &Pubkey,
+    ) -> crate::Result<PositionCutBuilder<C>>;
+
     /// Update ADL state.
     fn update_adl(
         &self,
@@ -387,6 +574,10 @@ pub trait ExchangeOps<C> {
         for_short: bool,
     ) -> crate::Result<UpdateAdlBuilder<C>>;
 
+    /// Move the mark of a trailing-stop order.
+    fn update_trailing_stop(&self, oracle: &Pubkey, order: &Pubkey)
+        -> UpdateTrailingStopBuilder<C>;
+
     /// Create a GLV deposit.
     fn create_glv_deposit(
         &self,
@@ -450,6 +641,14 @@ pub trait ExchangeOps<C> {
         market_token: &Pubkey,
     ) -> UpdateClosedStateBuilder<C>;
 
+    /// Start the settlement of the given market.
+    fn start_market_settlement(
+        &self,
+        store: &Pubkey,
+        oracle: &Pubkey,
+        market_token: &Pubkey,
+    ) -> StartMarketSettlementBuilder<C>;
+
     fn update_fees_state(
         &self,
         store: &Pubkey,
@@ -494,6 +693,16 @@ impl<C: Deref<Target = impl Signer> + Clone> ExchangeOps<C> for Client<C> {
         ExecuteDepositBuilder::new(self, store, oracle, deposit, cancel_on_execution_error)
     }
 
+    fn execute_deposits(
+        &self,
+        store: &Pubkey,
+        oracle: &Pubkey,
+        market_token: &Pubkey,
+        deposits: impl IntoIterator<Item = Pubkey>,
+    ) -> ExecuteDepositsBuilder<C> {
+        ExecuteDepositsBuilder::new(self, store, oracle, market_token, deposits)
+    }
+
     fn create_withdrawal(
         &self,
         store: &Pubkey,
@@ -517,6 +726,16 @@ impl<C: Deref<Target = impl Signer> + Clone> ExchangeOps<C> for Client<C> {
         ExecuteWithdrawalBuilder::new(self, store, oracle, withdrawal, cancel_on_execution_error)
     }
 
+    fn execute_withdrawals(
+        &self,
+        store: &Pubkey,
+        oracle: &Pubkey,
+        market_token: &Pubkey,
+        withdrawals: impl IntoIterator<Item = Pubkey>,
+    ) -> ExecuteWithdrawalsBuilder<C> {
+        ExecuteWithdrawalsBuilder::new(self, store, oracle, market_token, withdrawals)
+    }
+
     fn create_shift(
         &self,
         store: &Pubkey,
@@ -599,6 +818,16 @@ impl<C: Deref<Target = impl Signer> + Clone> ExchangeOps<C> for Client<C> {
         ExecuteOrderBuilder::try_new(self, store, oracle, order, cancel_on_execution_error)
     }
 
+    fn execute_orders(
+        &self,
+        store: &Pubkey,
+        oracle: &Pubkey,
+        market_token: &Pubkey,
+        orders: impl IntoIterator<Item = Pubkey>,
+    ) -> ExecuteOrdersBuilder<C> {
+        ExecuteOrdersBuilder::new(self, store, oracle, market_token, orders)
+    }
+
     fn close_order(&self, order: &Pubkey) -> crate::Result<CloseOrderBuilder<C>> {
         Ok(CloseOrderBuilder::new(self, order))
     }
@@ -634,6 +863,73 @@ impl<C: Deref<Target = impl Signer> + Clone> ExchangeOps<C> for Client<C> {
             }))
     }
 
+    fn cancel_expired_order(&self, order: &Pubkey) -> crate::Result<CloseOrderBuilder<C>> {
+        let mut builder = CloseOrderBuilder::new(self, order);
+        builder.cancel_expired(true);
+        Ok(builder)
+    }
+
+    fn link_orders(
+        &self,
+        store: &Pubkey,
+        parent: &Pubkey,
+        first: &Pubkey,
+        second: &Pubkey,
+    ) -> TransactionBuilder<C> {
+        self.store_transaction()
+            .anchor_args(args::LinkOrders {})
+            .anchor_accounts(accounts::LinkOrders {
+                owner: self.payer(),
+                store: *store,
+                parent: *parent,
+                first: *first,
+                second: *second,
+                event_authority: self.store_event_authority(),
+                program: *self.store_program_id(),
+            })
+    }
+
+    fn activate_linked_orders(
+        &self,
+        store: &Pubkey,
+        parent: &Pubkey,
+        linked_orders: &[Pubkey; 2],
+    ) -> TransactionBuilder<C> {
+        self.store_transaction()
+            .anchor_args(args::ActivateLinkedOrders {})
+            .anchor_accounts(accounts::ActivateLinkedOrders {
+                authority: self.payer(),
+                store: *store,
+                parent: *parent,
+                event_authority: self.store_event_authority(),
+                program: *self.store_program_id(),
+            })
+            .accounts(
+                linked_orders
+                    .iter()
+                    .map(|order| AccountMeta::new(*order, false))
+                    .collect(),
+            )
+    }
+
+    fn cancel_linked_order(
+        &self,
+        store: &Pubkey,
+        order: &Pubkey,
+        sibling: &Pubkey,
+    ) -> TransactionBuilder<C> {
+        self.store_transaction()
+            .anchor_args(args::CancelLinkedOrder {})
+            .anchor_accounts(accounts::CancelLinkedOrder {
+                authority: self.payer(),
+                store: *store,
+                order: *order,
+                sibling: *sibling,
+                event_authority: self.store_event_authority(),
+                program: *self.store_program_id(),
+            })
+    }
+
     fn close_empty_position(
         &self,
         store: &Pubkey,
@@ -649,6 +945,146 @@ impl<C: Deref<Target = impl Signer> + Clone> ExchangeOps<C> for Client<C> {
         Ok(txn)
     }
 
+    fn transfer_position(
+        &self,
+        store: &Pubkey,
+        position: &Pubkey,
+        next_owner: &Pubkey,
+    ) -> TransactionBuilder<C> {
+        self.store_transaction()
+            .anchor_args(args::TransferPosition {})
+            .anchor_accounts(accounts::TransferPosition {
+                owner: self.payer(),
+                store: *store,
+                position: *position,
+                next_owner: *next_owner,
+            })
+    }
+
+    fn cancel_position_transfer(&self, store: &Pubkey, position: &Pubkey) -> TransactionBuilder<C> {
+        self.store_transaction()
+            .anchor_args(args::CancelPositionTransfer {})
+            .anchor_accounts(accounts::CancelPositionTransfer {
+                owner: self.payer(),
+                store: *store,
+                position: *position,
+            })
+    }
+
+    async fn accept_position(
+        &self,
+        store: &Pubkey,
+        position: &Pubkey,
+    ) -> crate::Result<TransactionBuilder<C, Pubkey>> {
+        let next_owner = self.payer();
+        let account = self.position(position).await?;
+        let target = self.find_position_address(
+            store,
+            &next_owner,
+            &account.market_token,
+            &account.collateral_token,
+            account.try_is_long()?,
+        )?;
+        Ok(self
+            .store_transaction()
+            .anchor_args(args::AcceptPosition {})
+            .anchor_accounts(accounts::AcceptPosition {
+                next_owner,
+                owner: account.owner,
+                store: *store,
+                market: self.find_market_address(store, &account.market_token),
+                position: *position,
+                target,
+                system_program: solana_sdk::system_program::ID,
+            })
+            .output(target))
+    }
+
+    async fn sync_position_order_count(
+        &self,
+        store: &Pubkey,
+        position: &Pubkey,
+    ) -> crate::Result<TransactionBuilder<C>> {
+        let account = self.position(position).await?;
+        let orders = self
+            .orders(store, Some(&account.owner), Some(&account.market_token))
+            .await?
+            .into_iter()
+            .filter(|(_, order)| {
+                order.params.position == *position && !order.is_counted_by_position()
+            })
+            .map(|(address, _)| AccountMeta::new(address, false))
+            .collect();
+        Ok(self
+            .store_transaction()
+            .anchor_args(args::SyncPositionOrderCount {})
+            .anchor_accounts(accounts::SyncPositionOrderCount {
+                authority: self.payer(),
+                store: *store,
+                position: *position,
+            })
+            .accounts(orders))
+    }
+
+    async fn request_position_collateral_switch(
+        &self,
+        store: &Pubkey,
+        position: &Pubkey,
+        min_collateral_amount: u64,
+    ) -> crate::Result<TransactionBuilder<C, Pubkey>> {
+        let owner = self.payer();
+        let account = self.position(position).await?;
+        let market = self.find_market_address(store, &account.market_token);
+        let meta: MarketMeta = self.market(&market).await?.meta.into();
+        let collateral_token = *meta
+            .opposite_token(&account.collateral_token)
+            .map_err(crate::Error::custom)?;
+        let target = self.find_position_address(
+            store,
+            &owner,
+            &account.market_token,
+            &collateral_token,
+            account.try_is_long()?,
+        )?;
+        Ok(self
+            .store_transaction()
+            .anchor_args(args::RequestPositionCollateralSwitch {
+                min_collateral_amount,
+            })
+            .anchor_accounts(accounts::RequestPositionCollateralSwitch {
+                owner,
+                store: *store,
+                market,
+                position: *position,
+                collateral_token,
+                target,
+                system_program: solana_sdk::system_program::ID,
+            })
+            .output(target))
+    }
+
+    fn cancel_position_collateral_switch(
+        &self,
+        store: &Pubkey,
+        position: &Pubkey,
+    ) -> TransactionBuilder<C> {
+        self.store_transaction()
+            .anchor_args(args::CancelPositionCollateralSwitch {})
+            .anchor_accounts(accounts::CancelPositionCollateralSwitch {
+                owner: self.payer(),
+                store: *store,
+                position: *position,
+            })
+    }
+
+    fn switch_position_collateral(
+        &self,
+        oracle: &Pubkey,
+        position: &Pubkey,
+    ) -> SwitchPositionCollateralBuilder<C> {
+        SwitchPositionCollateralBuilder::new(self, oracle, position)
+    }
+
     fn liquidate(
         &self,
         oracle: &Pubkey,
@@ -671,6 +1107,14 @@ impl<C: Deref<Target = impl Signer> + Clone> ExchangeOps<C> for Client<C> {
         )
     }
 
+    fn settle_position(
+        &self,
+        oracle: &Pubkey,
+        position: &Pubkey,
+    ) -> crate::Result<PositionCutBuilder<C>> {
+        PositionCutBuilder::try_new(self, PositionCutKind::Settle, oracle, position)
+    }
+
     fn update_adl(
         &self,
         store: &Pubkey,
@@ -682,6 +1126,14 @@ impl<C: Deref<Target = impl Signer> + Clone> ExchangeOps<C> for Client<C> {
         UpdateAdlBuilder::try_new(self, store, oracle, market_token, for_long, for_short)
     }
 
+    fn update_trailing_stop(
+        &self,
+        oracle: &Pubkey,
+        order: &Pubkey,
+    ) -> UpdateTrailingStopBuilder<C> {
+        UpdateTrailingStopBuilder::new(self, oracle, order)
+    }
+
     fn create_glv_deposit(
         &self,
         store: &Pubkey,
@@ -777,6 +1229,23 @@ impl<C: Deref<Target = impl Signer> + Clone> ExchangeOps<C> for Client<C> {
         )
     }
 
+    fn start_market_settlement(
+        &self,
+        store: &Pubkey,
+        oracle: &Pubkey,
+        market_token: &Pubkey,
+    ) -> StartMarketSettlementBuilder<C> {
+        StartMarketSettlementBuilder::new(
+            self,
+            StartMarketSettlement::builder()
+                .payer(self.payer())
+                .market_token(*market_token)
+                .store_program(self.store_program_for_builders(store))
+                .oracle(*oracle)
+                .build(),
+        )
+    }
+
     fn update_fees_state(
         &self,
         store: &Pubkey,
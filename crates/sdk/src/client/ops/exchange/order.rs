@@ -8,7 +8,10 @@ use anchor_spl::associated_token::get_associated_token_address;
 use gmsol_programs::gmsol_store::{
     accounts::{Market, Order, Position, Store, UserHeader},
     client::{accounts, args},
-    types::{CreateOrderParams, DecreasePositionSwapType},
+    types::{
+        CreateOrderParams, CreateOrderParamsExt, DecreasePositionSwapType, TrailDistance,
+        TwapParams,
+    },
     ID,
 };
 use gmsol_solana_utils::{
@@ -19,12 +22,12 @@ use gmsol_solana_utils::{
 };
 use gmsol_utils::{
     action::ActionFlag,
-    market::MarketMeta,
+    market::{MarketConfigKey, MarketMeta},
     oracle::PriceProviderKind,
     order::{OrderKind, PositionCutKind},
     pubkey::optional_address,
     swap::SwapActionParams,
-    token_config::{token_records, TokenMapAccess, TokensWithFeed},
+    token_config::{TokenMapAccess, TokensWithFeed},
 };
 use solana_sdk::{
     address_lookup_table::AddressLookupTableAccount, instruction::AccountMeta, pubkey::Pubkey,
@@ -122,6 +125,10 @@ pub struct CreateOrderBuilder<'a, C> {
     callback: Option<Callback>,
     alts: HashMap<Pubkey, Vec<Pubkey>>,
     prepare_final_output_token_escrow: bool,
+    trail_distance: Option<TrailDistance>,
+    twap: Option<TwapParams>,
+    valid_until_ts: Option<i64>,
+    delegated_owner: Option<Pubkey>,
 }
 
 /// Create Order Hint.
@@ -162,6 +169,10 @@ where
             callback: None,
             alts: Default::default(),
             prepare_final_output_token_escrow: false,
+            trail_distance: None,
+            twap: None,
+            valid_until_ts: None,
+            delegated_owner: None,
         }
     }
 
@@ -252,6 +263,31 @@ where
         self
     }
 
+    /// Set valid until ts (limit, stop-loss and trailing-stop orders only).
+    pub fn valid_until_ts(&mut self, ts: i64) -> &mut Self {
+        self.valid_until_ts = Some(ts);
+        self
+    }
+
+    /// Set trail distance (trailing-stop orders only).
+    pub fn trail_distance(&mut self, distance: TrailDistance) -> &mut Self {
+        self.trail_distance = Some(distance);
+        self
+    }
+
+    /// Split the order into `slices` executed every `interval` seconds
+    /// (market increase and market decrease orders only).
+    ///
+    /// Since the execution fee is shared by all slices, it is raised to
+    /// `slices` times [`MIN_EXECUTION_LAMPORTS`] if it is lower.
+    pub fn twap(&mut self, slices: u16, interval: u32) -> &mut Self {
+        self.execution_fee = self
+            .execution_fee
+            .max(MIN_EXECUTION_LAMPORTS.saturating_mul(slices.into()));
+        self.twap = Some(TwapParams { slices, interval });
+        self
+    }
+
     /// Set whether to unwrap native token.
     /// Defaults to should unwrap.
     pub fn should_unwrap_native_token(&mut self, should_unwrap: bool) -> &mut Self {
@@ -279,6 +315,21 @@ where
         self
     }
 
+    /// Create the order as a delegate of the given owner.
+    ///
+    /// The payer must have been authorized by the owner with a delegation account,
+    /// and the receiver will be set to the owner. Only increase and decrease orders
+    /// are supported, and the position must have been prepared by the owner.
+    pub fn delegated_by(&mut self, owner: Pubkey) -> &mut Self {
+        self.delegated_owner = Some(owner);
+        self.receiver = owner;
+        self
+    }
+
+    fn owner(&self) -> Pubkey {
+        self.delegated_owner.unwrap_or_else(|| self.client.payer())
+    }
+
     /// Set callback.
     pub fn callback(&mut self, callback: Option<Callback>) -> &mut Self {
         self.callback = callback;
@@ -338,11 +389,13 @@ where
             | OrderKind::MarketDecrease
             | OrderKind::Liquidation
             | OrderKind::LimitIncrease
+            | OrderKind::StopIncrease
             | OrderKind::LimitDecrease
-            | OrderKind::StopLossDecrease => {
+            | OrderKind::StopLossDecrease
+            | OrderKind::TrailingStopDecrease => {
                 let position = self.client.find_position_address(
                     &self.store,
-                    &self.client.payer(),
+                    &self.owner(),
                     &self.market_token,
                     &output_token,
                     self.params.is_long,
@@ -364,6 +417,7 @@ where
             OrderKind::MarketIncrease
             | OrderKind::MarketSwap
             | OrderKind::LimitIncrease
+            | OrderKind::StopIncrease
             | OrderKind::LimitSwap => {
                 if self.initial_token.is_empty() {
                     let output_token = self.output_token().await?;
@@ -371,7 +425,7 @@ where
                 }
                 let Some((token, account)) = self
                     .initial_token
-                    .get_or_fetch_token_and_token_account(self.client, Some(&self.client.payer()))
+                    .get_or_fetch_token_and_token_account(self.client, Some(&self.owner()))
                     .await?
                 else {
                     return Err(crate::Error::custom(
@@ -383,7 +437,8 @@ where
             OrderKind::MarketDecrease
             | OrderKind::Liquidation
             | OrderKind::LimitDecrease
-            | OrderKind::StopLossDecrease => Ok(None),
+            | OrderKind::StopLossDecrease
+            | OrderKind::TrailingStopDecrease => Ok(None),
             kind => Err(crate::Error::custom(format!(
                 "unsupported order kind: {kind:?}"
             ))),
@@ -392,7 +447,10 @@ where
 
     async fn get_final_output_token(&mut self) -> crate::Result<Pubkey> {
         match &self.params.kind {
-            OrderKind::MarketDecrease | OrderKind::LimitDecrease | OrderKind::StopLossDecrease => {
+            OrderKind::MarketDecrease
+            | OrderKind::LimitDecrease
+            | OrderKind::StopLossDecrease
+            | OrderKind::TrailingStopDecrease => {
                 if self.final_token.is_none() {
                     let output_token = self.output_token().await?;
                     self.final_token = Some(output_token);
@@ -402,6 +460,7 @@ where
             OrderKind::MarketIncrease
             | OrderKind::MarketSwap
             | OrderKind::LimitIncrease
+            | OrderKind::StopIncrease
             | OrderKind::LimitSwap => Ok(self.output_token().await?),
             kind => Err(crate::Error::custom(format!(
                 "unsupported order kind: {kind:?}"
@@ -441,6 +500,21 @@ where
         Ok(params)
     }
 
+    /// Get [`CreateOrderParamsExt`].
+    pub fn create_order_params_ext(&self) -> CreateOrderParamsExt {
+        CreateOrderParamsExt {
+            trail_distance: self.trail_distance.clone(),
+            twap: self.twap.clone(),
+            valid_until_ts: self.valid_until_ts,
+        }
+    }
+
+    /// Returns whether any extended params are set, in which case
+    /// `create_order_v3` must be used.
+    fn has_ext_params(&self) -> bool {
+        self.trail_distance.is_some() || self.twap.is_some() || self.valid_until_ts.is_some()
+    }
+
     /// Create [`TransactionBuilder`] to prepare account.
     pub async fn build_prepare_position(
         &mut self,
@@ -470,9 +544,11 @@ where
         let token_program_id = anchor_spl::token::ID;
 
         let nonce = self.nonce.unwrap_or_else(|| generate_nonce().to_bytes());
-        let owner = &self.client.payer();
+        let payer = self.client.payer();
+        let owner = &self.owner();
         let receiver = self.receiver;
-        let order = self.client.find_order_address(&self.store, owner, &nonce);
+        // The order is always derived from the payer, which is the delegate for delegated orders.
+        let order = self.client.find_order_address(&self.store, &payer, &nonce);
         let (initial_collateral_token, initial_collateral_token_account) =
             self.initial_collateral_accounts().await?.unzip();
         let final_output_token = self.get_final_output_token().await?;
@@ -487,6 +563,7 @@ where
                 | OrderKind::LimitDecrease
                 | OrderKind::MarketDecrease
                 | OrderKind::StopLossDecrease
+                | OrderKind::TrailingStopDecrease
         );
         let (long_token, short_token) = if is_swap {
             (None, None)
@@ -522,6 +599,8 @@ where
 
         let kind = self.params.kind;
         let params = self.create_order_params()?;
+        let ext = self.create_order_params_ext();
+        let has_ext_params = self.has_ext_params();
 
         let mut prepare = match kind {
             OrderKind::MarketSwap | OrderKind::LimitSwap => {
@@ -546,7 +625,7 @@ where
                 );
                 escrow.merge(ata)
             }
-            OrderKind::MarketIncrease | OrderKind::LimitIncrease => {
+            OrderKind::MarketIncrease | OrderKind::LimitIncrease | OrderKind::StopIncrease => {
                 let initial_collateral_token = initial_collateral_token.ok_or(
                     crate::Error::custom("initial collateral token is not provided"),
                 )?;
@@ -601,12 +680,17 @@ where
                     })
                     .anchor_args(args::PreparePosition { params });
 
-                escrow
-                    .merge(long_token_ata)
-                    .merge(short_token_ata)
-                    .merge(prepare_position)
+                let prepared = escrow.merge(long_token_ata).merge(short_token_ata);
+                if self.delegated_owner.is_some() {
+                    prepared
+                } else {
+                    prepared.merge(prepare_position)
+                }
             }
-            OrderKind::MarketDecrease | OrderKind::LimitDecrease | OrderKind::StopLossDecrease => {
+            OrderKind::MarketDecrease
+            | OrderKind::LimitDecrease
+            | OrderKind::StopLossDecrease
+            | OrderKind::TrailingStopDecrease => {
                 let long_token =
                     long_token.ok_or(crate::Error::custom("long token is not provided"))?;
                 let short_token =
@@ -666,7 +750,9 @@ where
                 system_program: system_program::ID,
             })
             .anchor_args(args::PrepareUser {});
-        prepare = prepare.merge(prepare_user);
+        if self.delegated_owner.is_none() {
+            prepare = prepare.merge(prepare_user);
+        }
 
         let CallbackParams {
             callback_version,
@@ -691,56 +777,114 @@ where
             }
         }
 
-        let create = self
-            .client
-            .store_transaction()
-            .accounts(fix_optional_account_metas(
-                accounts::CreateOrderV2 {
-                    store: self.store,
-                    order,
-                    position,
-                    market: self.market(),
-                    owner: *owner,
-                    receiver,
-                    user,
-                    initial_collateral_token,
-                    final_output_token,
-                    long_token,
-                    short_token,
-                    initial_collateral_token_escrow,
-                    final_output_token_escrow: final_output_token_accounts
-                        .map(|(escrow, _)| escrow),
-                    long_token_escrow: long_token_accounts.map(|(escrow, _)| escrow),
-                    short_token_escrow: short_token_accounts.map(|(escrow, _)| escrow),
-                    initial_collateral_token_source: initial_collateral_token_account,
-                    system_program: system_program::ID,
-                    token_program: anchor_spl::token::ID,
-                    associated_token_program: anchor_spl::associated_token::ID,
-                    callback_authority,
-                    callback_program,
-                    callback_shared_data_account,
-                    callback_partitioned_data_account,
-                    event_authority: self.client.store_event_authority(),
-                    program: *self.client.store_program_id(),
-                },
-                &ID,
-                self.client.store_program_id(),
-            ))
-            .anchor_args(args::CreateOrderV2 {
-                nonce,
-                params,
-                callback_version,
-            })
-            .accounts(
-                self.swap_path
-                    .iter()
-                    .map(|mint| AccountMeta {
-                        pubkey: self.client.find_market_address(&self.store, mint),
-                        is_signer: false,
-                        is_writable: false,
-                    })
-                    .collect::<Vec<_>>(),
-            );
+        let create = if self.delegated_owner.is_some() {
+            if callback_version.is_some() {
+                return Err(crate::Error::custom(
+                    "callback is not supported for delegated orders",
+                ));
+            }
+            let missing_position = || crate::Error::custom("position is not provided");
+            let missing_token = || crate::Error::custom("long or short token is not provided");
+            self.client
+                .store_transaction()
+                .accounts(fix_optional_account_metas(
+                    accounts::CreateOrderByDelegate {
+                        delegate: payer,
+                        owner: *owner,
+                        store: self.store,
+                        market: self.market(),
+                        delegation: self
+                            .client
+                            .find_delegation_address(&self.store, owner, &payer),
+                        user,
+                        order,
+                        position: position.ok_or_else(missing_position)?,
+                        initial_collateral_token,
+                        final_output_token,
+                        long_token: long_token.ok_or_else(missing_token)?,
+                        short_token: short_token.ok_or_else(missing_token)?,
+                        initial_collateral_token_escrow,
+                        final_output_token_escrow: final_output_token_accounts
+                            .map(|(escrow, _)| escrow),
+                        long_token_escrow: long_token_accounts
+                            .map(|(escrow, _)| escrow)
+                            .ok_or_else(missing_token)?,
+                        short_token_escrow: short_token_accounts
+                            .map(|(escrow, _)| escrow)
+                            .ok_or_else(missing_token)?,
+                        initial_collateral_token_source: initial_collateral_token_account,
+                        system_program: system_program::ID,
+                        token_program: anchor_spl::token::ID,
+                        associated_token_program: anchor_spl::associated_token::ID,
+                        event_authority: self.client.store_event_authority(),
+                        program: *self.client.store_program_id(),
+                    },
+                    &ID,
+                    self.client.store_program_id(),
+                ))
+                .anchor_args(args::CreateOrderByDelegate { nonce, params, ext })
+        } else {
+            let create = self
+                .client
+                .store_transaction()
+                .accounts(fix_optional_account_metas(
+                    accounts::CreateOrderV2 {
+                        store: self.store,
+                        order,
+                        position,
+                        market: self.market(),
+                        owner: *owner,
+                        receiver,
+                        user,
+                        initial_collateral_token,
+                        final_output_token,
+                        long_token,
+                        short_token,
+                        initial_collateral_token_escrow,
+                        final_output_token_escrow: final_output_token_accounts
+                            .map(|(escrow, _)| escrow),
+                        long_token_escrow: long_token_accounts.map(|(escrow, _)| escrow),
+                        short_token_escrow: short_token_accounts.map(|(escrow, _)| escrow),
+                        initial_collateral_token_source: initial_collateral_token_account,
+                        system_program: system_program::ID,
+                        token_program: anchor_spl::token::ID,
+                        associated_token_program: anchor_spl::associated_token::ID,
+                        callback_authority,
+                        callback_program,
+                        callback_shared_data_account,
+                        callback_partitioned_data_account,
+                        event_authority: self.client.store_event_authority(),
+                        program: *self.client.store_program_id(),
+                    },
+                    &ID,
+                    self.client.store_program_id(),
+                ));
+            // `create_order_v3` is only required when extended params are provided.
+            if has_ext_params {
+                create.anchor_args(args::CreateOrderV3 {
+                    nonce,
+                    params,
+                    ext,
+                    callback_version,
+                })
+            } else {
+                create.anchor_args(args::CreateOrderV2 {
+                    nonce,
+                    params,
+                    callback_version,
+                })
+            }
+        }
+        .accounts(
+            self.swap_path
+                .iter()
+                .map(|mint| AccountMeta {
+                    pubkey: self.client.find_market_address(&self.store, mint),
+                    is_signer: false,
+                    is_writable: false,
+                })
+                .collect::<Vec<_>>(),
+        );
 
         Ok((
             prepare.merge(create).lookup_tables(self.alts.clone()),
@@ -779,6 +923,7 @@ pub struct ExecuteOrderHint {
     receiver: Pubkey,
     rent_receiver: Pubkey,
     user: Pubkey,
+    user_stats: Option<Pubkey>,
     referrer: Option<Pubkey>,
     initial_collateral_token_and_account: Option<(Pubkey, Pubkey)>,
     final_output_token_and_account: Option<(Pubkey, Pubkey)>,
@@ -793,6 +938,10 @@ pub struct ExecuteOrderHint {
     should_unwrap_native_token: bool,
     callback: Option<Callback>,
     virtual_inventories: BTreeSet<Pubkey>,
+    pending_linked_orders: Option<[Pubkey; 2]>,
+    linked_sibling: Option<Pubkey>,
+    sibling_position: Option<Pubkey>,
+    has_remaining_slices: bool,
 }
 
 impl ExecuteOrderHint {
@@ -928,6 +1077,9 @@ where
             receiver: order.header.receiver,
             rent_receiver,
             user: user_address,
+            user_stats: user
+                .filter(|user| user.has_stats())
+                .map(|_| self.client.find_user_stats_address(&self.store, &owner)),
             referrer,
             long_token_mint: market.meta.long_token_mint,
             short_token_mint: market.meta.short_token_mint,
@@ -948,6 +1100,17 @@ where
                 .get_flag(ActionFlag::ShouldUnwrapNativeToken),
             callback: Callback::from_header(&order.header)?,
             virtual_inventories,
+            pending_linked_orders: order
+                .has_pending_linked_orders()
+                .then_some(order.linked_orders),
+            linked_sibling: order.linked_sibling()?.copied(),
+            sibling_position: find_sibling_position(
+                &self.store,
+                order,
+                market,
+                self.client.store_program_id(),
+            )?,
+            has_remaining_slices: params.twap_remaining_slices() > 1,
         });
         Ok(self)
     }
@@ -1071,7 +1234,10 @@ where
         } = self.client.get_callback_params(hint.callback.as_ref());
 
         let mut execute_order = match kind {
-            OrderKind::MarketDecrease | OrderKind::LimitDecrease | OrderKind::StopLossDecrease => {
+            OrderKind::MarketDecrease
+            | OrderKind::LimitDecrease
+            | OrderKind::StopLossDecrease
+            | OrderKind::TrailingStopDecrease => {
                 require_claimable_accounts = true;
 
                 self.client
@@ -1220,12 +1386,27 @@ where
         } else {
             EXECUTE_ORDER_COMPUTE_BUDGET
         };
+        // The sibling of a linked order is cancelled during the execution.
+        let linked_sibling = hint
+            .linked_sibling
+            .map(|sibling| AccountMeta::new(sibling, false));
+        // The sibling position counts towards the max position size cap.
+        let sibling_position = hint
+            .sibling_position
+            .map(|position| AccountMeta::new_readonly(position, false));
+        // The user stats account must be the last one if the owner has prepared it.
+        let user_stats = hint
+            .user_stats
+            .map(|user_stats| AccountMeta::new(user_stats, false));
         execute_order = execute_order
             .accounts(
                 feeds
                     .into_iter()
                     .chain(swap_markets)
                     .chain(virtual_inventories)
+                    .chain(linked_sibling)
+                    .chain(sibling_position)
+                    .chain(user_stats)
                     .collect::<Vec<_>>(),
             )
             .compute_budget(ComputeBudget::default().with_limit(execute_cu))
@@ -1252,7 +1433,23 @@ where
             execute_order = prepare_event_buffer.merge(execute_order);
         }
 
-        if self.close {
+        // A TWAP order stays pending until its last slice is executed.
+        let is_last_execution = !hint.has_remaining_slices;
+
+        // The linked orders must be resolved before the parent order can be closed.
+        if let Some(linked_orders) = hint
+            .pending_linked_orders
+            .as_ref()
+            .filter(|_| is_last_execution)
+        {
+            execute_order = execute_order.merge(self.client.activate_linked_orders(
+                &self.store,
+                &self.order,
+                linked_orders,
+            ));
+        }
+
+        if self.close && is_last_execution {
             let mut close = self
                 .client
                 .close_order(&self.order)?
@@ -1270,6 +1467,9 @@ where
                     rent_receiver: hint.rent_receiver,
                     should_unwrap_native_token: hint.should_unwrap_native_token,
                     callback: hint.callback,
+                    position: hint.position,
+                    // The linked orders are resolved before the close.
+                    pending_linked_orders: None,
                 })
                 .build()
                 .await?;
@@ -1364,126 +1564,575 @@ impl<C> SetExecutionFee for ExecuteOrderBuilder<'_, C> {
     }
 }
 
-/// Close Order Builder.
-pub struct CloseOrderBuilder<'a, C> {
+/// Default max number of orders to execute in one transaction.
+pub const DEFAULT_MAX_ORDERS_PER_TRANSACTION: usize = 2;
+
+/// Execute Orders Builder.
+///
+/// Executes market increase orders of the same market in batch, sharing one set of oracle
+/// prices. Only orders without swap paths, callbacks or linked orders can be executed in batch.
+pub struct ExecuteOrdersBuilder<'a, C> {
     client: &'a crate::Client<C>,
-    order: Pubkey,
-    hint: Option<CloseOrderHint>,
-    reason: String,
-    skip_callback: bool,
+    store: Pubkey,
+    oracle: Pubkey,
+    market_token: Pubkey,
+    orders: Vec<Pubkey>,
+    execution_fee: u64,
+    feeds_parser: FeedsParser,
+    hint: Option<ExecuteOrdersHint>,
+    token_map: Option<Pubkey>,
+    max_orders_per_transaction: usize,
+    close: bool,
+    event_buffer_index: u16,
 }
 
-/// Close Order Hint.
+/// Hint for executing orders in batch.
 #[derive(Clone)]
-pub struct CloseOrderHint {
-    pub(super) owner: Pubkey,
-    pub(super) receiver: Pubkey,
-    pub(super) store: Pubkey,
-    pub(super) initial_collateral_token_and_account: Option<(Pubkey, Pubkey)>,
-    pub(super) final_output_token_and_account: Option<(Pubkey, Pubkey)>,
-    pub(super) long_token_and_account: Option<(Pubkey, Pubkey)>,
-    pub(super) short_token_and_account: Option<(Pubkey, Pubkey)>,
-    pub(super) user: Pubkey,
-    pub(super) referrer: Option<Pubkey>,
-    pub(super) rent_receiver: Pubkey,
-    pub(super) should_unwrap_native_token: bool,
-    pub(super) callback: Option<Callback>,
+pub struct ExecuteOrdersHint {
+    long_token: Pubkey,
+    short_token: Pubkey,
+    /// Feeds.
+    pub feeds: TokensWithFeed,
+    orders: Vec<ExecuteOrdersItem>,
+    virtual_inventories: BTreeSet<Pubkey>,
 }
 
-impl CloseOrderHint {
-    /// Create hint from order and user account.
-    pub fn new(
-        order: &Order,
-        user: Option<&UserHeader>,
+#[derive(Clone)]
+struct ExecuteOrdersItem {
+    order: Pubkey,
+    user_stats: Option<Pubkey>,
+    sibling_position: Option<Pubkey>,
+    has_remaining_slices: bool,
+    close: CloseOrderHint,
+}
+
+impl ExecuteOrdersHint {
+    /// Create a new hint for the orders.
+    pub fn new<'o>(
+        market: &Market,
+        orders: impl IntoIterator<Item = (&'o Pubkey, &'o Order, Option<&'o UserHeader>)>,
+        map: &impl TokenMapAccess,
+        virtual_inventories: BTreeSet<Pubkey>,
         program_id: &Pubkey,
     ) -> crate::Result<Self> {
-        let tokens = &order.tokens;
-        let owner = order.header.owner;
-        let store = order.header.store;
-        let user_address = crate::pda::find_user_address(&store, &owner, program_id).0;
-        let referrer = user.and_then(|user| optional_address(&user.referral.referrer).copied());
-        let rent_receiver = order.header.rent_receiver;
+        let meta = &market.meta;
+        let feeds = TokensWithFeed::try_from_token_map(
+            map,
+            &BTreeSet::from([
+                meta.index_token_mint,
+                meta.long_token_mint,
+                meta.short_token_mint,
+            ]),
+        )
+        .map_err(crate::Error::custom)?;
+        let orders = orders
+            .into_iter()
+            .map(|(address, order, user)| {
+                if order.market_token != meta.market_token_mint {
+                    return Err(crate::Error::custom(format!(
+                        "order `{address}` does not belong to the market"
+                    )));
+                }
+                if !matches!(order.params.kind()?, OrderKind::MarketIncrease) {
+                    return Err(crate::Error::custom(format!(
+                        "order `{address}` is not a market increase order"
+                    )));
+                }
+                let swap = SwapActionParams::from(order.swap);
+                if swap.primary_length() != 0 || swap.secondary_length() != 0 {
+                    return Err(crate::Error::custom(format!(
+                        "order `{address}` with swap paths cannot be executed in batch"
+                    )));
+                }
+                let close = CloseOrderHint::new(order, user, program_id)?;
+                if close.callback.is_some()
+                    || close.pending_linked_orders.is_some()
+                    || order.linked_sibling()?.is_some()
+                {
+                    return Err(crate::Error::custom(format!(
+                        "order `{address}` with callbacks or linked orders cannot be executed in batch"
+                    )));
+                }
+                Ok(ExecuteOrdersItem {
+                    order: *address,
+                    user_stats: user.filter(|user| user.has_stats()).map(|_| {
+                        crate::pda::find_user_stats_address(
+                            &order.header.store,
+                            &order.header.owner,
+                            program_id,
+                        )
+                        .0
+                    }),
+                    sibling_position: find_sibling_position(
+                        &order.header.store,
+                        order,
+                        market,
+                        program_id,
+                    )?,
+                    has_remaining_slices: order.params.twap_remaining_slices() > 1,
+                    close,
+                })
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
         Ok(Self {
-            owner,
-            receiver: order.header.receiver,
-            store,
-            user: user_address,
-            referrer,
-            initial_collateral_token_and_account: tokens.initial_collateral.token_and_account(),
-            final_output_token_and_account: tokens.final_output_token.token_and_account(),
-            long_token_and_account: tokens.long_token.token_and_account(),
-            short_token_and_account: tokens.short_token.token_and_account(),
-            rent_receiver,
-            should_unwrap_native_token: order
-                .header
-                .flags
-                .get_flag(ActionFlag::ShouldUnwrapNativeToken),
-            callback: Callback::from_header(&order.header)?,
+            long_token: meta.long_token_mint,
+            short_token: meta.short_token_mint,
+            feeds,
+            orders,
+            virtual_inventories,
         })
     }
 }
 
-impl<'a, S, C> CloseOrderBuilder<'a, C>
+impl<'a, S, C> ExecuteOrdersBuilder<'a, C>
 where
     C: Deref<Target = S> + Clone,
     S: Signer,
 {
-    pub(super) fn new(client: &'a crate::Client<C>, order: &Pubkey) -> Self {
+    pub(super) fn new(
+        client: &'a crate::Client<C>,
+        store: &Pubkey,
+        oracle: &Pubkey,
+        market_token: &Pubkey,
+        orders: impl IntoIterator<Item = Pubkey>,
+    ) -> Self {
         Self {
             client,
-            order: *order,
+            store: *store,
+            oracle: *oracle,
+            market_token: *market_token,
+            orders: orders.into_iter().collect(),
+            execution_fee: 0,
+            feeds_parser: Default::default(),
             hint: None,
-            reason: "cancelled".into(),
-            skip_callback: false,
+            token_map: None,
+            max_orders_per_transaction: DEFAULT_MAX_ORDERS_PER_TRANSACTION,
+            close: true,
+            event_buffer_index: 0,
         }
     }
 
-    /// Set hint with the given order.
-    pub fn hint_with_order(
-        &mut self,
-        order: &Order,
-        user: Option<&UserHeader>,
-        program_id: &Pubkey,
-    ) -> crate::Result<&mut Self> {
-        Ok(self.hint(CloseOrderHint::new(order, user, program_id)?))
+    /// Set whether to close the orders after execution.
+    pub fn close(&mut self, close: bool) -> &mut Self {
+        self.close = close;
+        self
     }
 
-    /// Set hint.
-    pub fn hint(&mut self, hint: CloseOrderHint) -> &mut Self {
-        self.hint = Some(hint);
+    /// Set event buffer index.
+    pub fn event_buffer_index(&mut self, index: u16) -> &mut Self {
+        self.event_buffer_index = index;
         self
     }
 
-    /// Set reason.
-    pub fn reason(&mut self, reason: impl ToString) -> &mut Self {
-        self.reason = reason.to_string();
+    /// Set the max number of orders to execute in one transaction.
+    pub fn max_orders_per_transaction(&mut self, max: usize) -> &mut Self {
+        self.max_orders_per_transaction = max.max(1);
         self
     }
 
-    /// Set whether to skip callback.
-    pub fn skip_callback(&mut self, skip: bool) -> &mut Self {
-        self.skip_callback = skip;
+    /// Set token map.
+    pub fn token_map(&mut self, address: Pubkey) -> &mut Self {
+        self.token_map = Some(address);
         self
     }
 
-    async fn prepare_hint(&mut self) -> crate::Result<CloseOrderHint> {
+    /// Set hint.
+    pub fn hint(&mut self, hint: ExecuteOrdersHint) -> &mut Self {
+        self.hint = Some(hint);
+        self
+    }
+
+    /// Prepare [`ExecuteOrdersHint`].
+    pub async fn prepare_hint(&mut self) -> crate::Result<ExecuteOrdersHint> {
         match &self.hint {
             Some(hint) => Ok(hint.clone()),
             None => {
-                let order: ZeroCopy<Order> = self
-                    .client
-                    .account_with_config(&self.order, Default::default())
-                    .await?
-                    .into_value()
-                    .ok_or(crate::Error::custom("order not found"))?;
-                let user = self
+                let map = self.client.authorized_token_map(&self.store).await?;
+                let market = self
                     .client
-                    .find_user_address(&order.0.header.store, &order.0.header.owner);
-                let user = self.client.account::<ZeroCopy<_>>(&user).await?;
-                let hint = CloseOrderHint::new(
-                    &order.0,
-                    user.as_ref().map(|user| &user.0),
-                    self.client.store_program_id(),
+                    .market_by_token(&self.store, &self.market_token)
+                    .await?;
+                let mut orders = Vec::with_capacity(self.orders.len());
+                for address in self.orders.iter() {
+                    let order = self.client.order(address).await?;
+                    let user = self
+                        .client
+                        .find_user_address(&self.store, &order.header.owner);
+                    let user = self
+                        .client
+                        .account::<ZeroCopy<UserHeader>>(&user)
+                        .await?
+                        .map(|user| user.0);
+                    orders.push((*address, order, user));
+                }
+                let virtual_inventories = VirtualInventoryCollector::default()
+                    .insert_market_token(&self.market_token)
+                    .collect(self.client, &self.store)
+                    .await?;
+                let hint = ExecuteOrdersHint::new(
+                    &market,
+                    orders
+                        .iter()
+                        .map(|(address, order, user)| (address, order, user.as_ref())),
+                    &map,
+                    virtual_inventories,
+                    self.client.store_program_id(),
+                )?;
+                self.hint = Some(hint.clone());
+                Ok(hint)
+            }
+        }
+    }
+
+    async fn get_token_map(&self) -> crate::Result<Pubkey> {
+        if let Some(address) = self.token_map {
+            Ok(address)
+        } else {
+            Ok(self
+                .client
+                .authorized_token_map_address(&self.store)
+                .await?
+                .ok_or(crate::Error::NotFound)?)
+        }
+    }
+
+    async fn build_txns(&mut self) -> crate::Result<Vec<TransactionBuilder<'a, C>>> {
+        let token_map = self.get_token_map().await?;
+        let hint = self.prepare_hint().await?;
+        let Self {
+            client,
+            store,
+            oracle,
+            market_token,
+            execution_fee,
+            max_orders_per_transaction,
+            event_buffer_index,
+            ..
+        } = &self;
+        let authority = client.payer();
+        let program_id = client.store_program_id();
+        let feeds = self
+            .feeds_parser
+            .parse(&hint.feeds)
+            .collect::<Result<Vec<_>, _>>()?;
+        let virtual_inventories = hint
+            .virtual_inventories
+            .iter()
+            .map(|pubkey| AccountMeta::new(*pubkey, false))
+            .collect::<Vec<_>>();
+        let event = client.find_trade_event_buffer_address(store, &authority, *event_buffer_index);
+
+        let mut txns = Vec::default();
+        for chunk in hint.orders.chunks(*max_orders_per_transaction) {
+            let orders = chunk.iter().flat_map(|item| {
+                let close = &item.close;
+                [
+                    Some(item.order),
+                    Some(close.owner),
+                    Some(close.user),
+                    item.user_stats,
+                    close.position,
+                    item.sibling_position,
+                    close
+                        .initial_collateral_token_and_account
+                        .map(|(_, account)| account),
+                    close.long_token_and_account.map(|(_, account)| account),
+                    close.short_token_and_account.map(|(_, account)| account),
+                ]
+                .map(|address| AccountMeta::new(address.unwrap_or(*program_id), false))
+            });
+            let count =
+                u8::try_from(chunk.len()).map_err(|_| crate::Error::custom("too many orders"))?;
+            let compute_budget = u32::try_from(chunk.len())
+                .ok()
+                .and_then(|len| len.checked_mul(EXECUTE_ORDER_COMPUTE_BUDGET))
+                .ok_or_else(|| crate::Error::custom("too many orders"))?;
+            let prepare_event_buffer = client
+                .store_transaction()
+                .anchor_accounts(accounts::PrepareTradeEventBuffer {
+                    authority,
+                    store: *store,
+                    event,
+                    system_program: system_program::ID,
+                })
+                .anchor_args(args::PrepareTradeEventBuffer {
+                    index: *event_buffer_index,
+                });
+            let execute = client
+                .store_transaction()
+                .anchor_accounts(accounts::ExecuteOrders {
+                    authority,
+                    store: *store,
+                    token_map,
+                    oracle: *oracle,
+                    market: client.find_market_address(store, market_token),
+                    event,
+                    long_token: hint.long_token,
+                    short_token: hint.short_token,
+                    long_token_vault: client.find_market_vault_address(store, &hint.long_token),
+                    short_token_vault: client.find_market_vault_address(store, &hint.short_token),
+                    token_program: anchor_spl::token::ID,
+                    system_program: system_program::ID,
+                    event_authority: client.store_event_authority(),
+                    program: *program_id,
+                })
+                .anchor_args(args::ExecuteOrders {
+                    execution_fee: *execution_fee,
+                    count,
+                })
+                .accounts(
+                    feeds
+                        .iter()
+                        .cloned()
+                        .chain(orders)
+                        .chain(virtual_inventories.iter().cloned())
+                        .collect::<Vec<_>>(),
+                )
+                .compute_budget(ComputeBudget::default().with_limit(compute_budget));
+            txns.push(prepare_event_buffer.merge(execute));
+
+            if self.close {
+                // A TWAP order stays pending until its last slice is executed.
+                for item in chunk.iter().filter(|item| !item.has_remaining_slices) {
+                    txns.push(
+                        client
+                            .close_order(&item.order)?
+                            .hint(item.close.clone())
+                            .reason("executed")
+                            .build()
+                            .await?,
+                    );
+                }
+            }
+        }
+
+        Ok(txns)
+    }
+}
+
+impl<'a, C: Deref<Target = impl Signer> + Clone> MakeBundleBuilder<'a, C>
+    for ExecuteOrdersBuilder<'a, C>
+{
+    async fn build_with_options(
+        &mut self,
+        options: BundleOptions,
+    ) -> gmsol_solana_utils::Result<BundleBuilder<'a, C>> {
+        let mut tx = self.client.bundle_with_options(options);
+
+        tx.push_many(
+            self.build_txns()
+                .await
+                .map_err(gmsol_solana_utils::Error::custom)?,
+            false,
+        )?;
+
+        Ok(tx)
+    }
+}
+
+impl<C: Deref<Target = impl Signer> + Clone> PullOraclePriceConsumer
+    for ExecuteOrdersBuilder<'_, C>
+{
+    async fn feed_ids(&mut self) -> crate::Result<FeedIds> {
+        let hint = self.prepare_hint().await?;
+        Ok(FeedIds::new(self.store, hint.feeds))
+    }
+
+    fn process_feeds(
+        &mut self,
+        provider: PriceProviderKind,
+        map: FeedAddressMap,
+    ) -> crate::Result<()> {
+        self.feeds_parser
+            .insert_pull_oracle_feed_parser(provider, map);
+        Ok(())
+    }
+}
+
+impl<C> SetExecutionFee for ExecuteOrdersBuilder<'_, C> {
+    fn set_execution_fee(&mut self, lamports: u64) -> &mut Self {
+        self.execution_fee = lamports;
+        self
+    }
+}
+
+/// Returns the position of the same owner and side with the other collateral token,
+/// which is required for increase orders when the max position size of the side is capped.
+fn find_sibling_position(
+    store: &Pubkey,
+    order: &Order,
+    market: &Market,
+    program_id: &Pubkey,
+) -> crate::Result<Option<Pubkey>> {
+    let params = &order.params;
+    let meta = &market.meta;
+    if !params.kind()?.is_increase_position() || meta.long_token_mint == meta.short_token_mint {
+        return Ok(None);
+    }
+    let is_long = params.side()?.is_long();
+    let key = if is_long {
+        MarketConfigKey::MaxPositionSizeUsdForLong
+    } else {
+        MarketConfigKey::MaxPositionSizeUsdForShort
+    };
+    if market.config.get(key).copied().unwrap_or_default() == 0 {
+        return Ok(None);
+    }
+    let collateral_token = if params.collateral_token == meta.long_token_mint {
+        &meta.short_token_mint
+    } else {
+        &meta.long_token_mint
+    };
+    let position = crate::pda::find_position_address(
+        store,
+        &order.header.owner,
+        &order.market_token,
+        collateral_token,
+        is_long,
+        program_id,
+    )
+    .0;
+    Ok(Some(position))
+}
+
+/// Close Order Builder.
+pub struct CloseOrderBuilder<'a, C> {
+    client: &'a crate::Client<C>,
+    order: Pubkey,
+    hint: Option<CloseOrderHint>,
+    reason: String,
+    skip_callback: bool,
+    cancel_expired: bool,
+}
+
+/// Close Order Hint.
+#[derive(Clone)]
+pub struct CloseOrderHint {
+    pub(super) owner: Pubkey,
+    pub(super) receiver: Pubkey,
+    pub(super) store: Pubkey,
+    pub(super) initial_collateral_token_and_account: Option<(Pubkey, Pubkey)>,
+    pub(super) final_output_token_and_account: Option<(Pubkey, Pubkey)>,
+    pub(super) long_token_and_account: Option<(Pubkey, Pubkey)>,
+    pub(super) short_token_and_account: Option<(Pubkey, Pubkey)>,
+    pub(super) user: Pubkey,
+    pub(super) referrer: Option<Pubkey>,
+    pub(super) rent_receiver: Pubkey,
+    pub(super) should_unwrap_native_token: bool,
+    pub(super) callback: Option<Callback>,
+    pub(super) position: Option<Pubkey>,
+    pub(super) pending_linked_orders: Option<[Pubkey; 2]>,
+}
+
+impl CloseOrderHint {
+    /// Create hint from order and user account.
+    pub fn new(
+        order: &Order,
+        user: Option<&UserHeader>,
+        program_id: &Pubkey,
+    ) -> crate::Result<Self> {
+        let tokens = &order.tokens;
+        let owner = order.header.owner;
+        let store = order.header.store;
+        let user_address = crate::pda::find_user_address(&store, &owner, program_id).0;
+        let referrer = user.and_then(|user| optional_address(&user.referral.referrer).copied());
+        let rent_receiver = order.header.rent_receiver;
+        Ok(Self {
+            owner,
+            receiver: order.header.receiver,
+            store,
+            user: user_address,
+            referrer,
+            initial_collateral_token_and_account: tokens.initial_collateral.token_and_account(),
+            final_output_token_and_account: tokens.final_output_token.token_and_account(),
+            long_token_and_account: tokens.long_token.token_and_account(),
+            short_token_and_account: tokens.short_token.token_and_account(),
+            rent_receiver,
+            should_unwrap_native_token: order
+                .header
+                .flags
+                .get_flag(ActionFlag::ShouldUnwrapNativeToken),
+            callback: Callback::from_header(&order.header)?,
+            position: optional_address(&order.params.position).copied(),
+            pending_linked_orders: order
+                .has_pending_linked_orders()
+                .then_some(order.linked_orders),
+        })
+    }
+}
+
+impl<'a, S, C> CloseOrderBuilder<'a, C>
+where
+    C: Deref<Target = S> + Clone,
+    S: Signer,
+{
+    pub(super) fn new(client: &'a crate::Client<C>, order: &Pubkey) -> Self {
+        Self {
+            client,
+            order: *order,
+            hint: None,
+            reason: "cancelled".into(),
+            skip_callback: false,
+            cancel_expired: false,
+        }
+    }
+
+    /// Set hint with the given order.
+    pub fn hint_with_order(
+        &mut self,
+        order: &Order,
+        user: Option<&UserHeader>,
+        program_id: &Pubkey,
+    ) -> crate::Result<&mut Self> {
+        Ok(self.hint(CloseOrderHint::new(order, user, program_id)?))
+    }
+
+    /// Set hint.
+    pub fn hint(&mut self, hint: CloseOrderHint) -> &mut Self {
+        self.hint = Some(hint);
+        self
+    }
+
+    /// Set reason.
+    pub fn reason(&mut self, reason: impl ToString) -> &mut Self {
+        self.reason = reason.to_string();
+        self
+    }
+
+    /// Set whether to skip callback.
+    pub fn skip_callback(&mut self, skip: bool) -> &mut Self {
+        self.skip_callback = skip;
+        self
+    }
+
+    /// Set whether to cancel the order because it has expired.
+    ///
+    /// If set, the order will be closed by `cancel_expired_order` and the reason is ignored.
+    pub fn cancel_expired(&mut self, cancel_expired: bool) -> &mut Self {
+        self.cancel_expired = cancel_expired;
+        self
+    }
+
+    async fn prepare_hint(&mut self) -> crate::Result<CloseOrderHint> {
+        match &self.hint {
+            Some(hint) => Ok(hint.clone()),
+            None => {
+                let order: ZeroCopy<Order> = self
+                    .client
+                    .account_with_config(&self.order, Default::default())
+                    .await?
+                    .into_value()
+                    .ok_or(crate::Error::custom("order not found"))?;
+                let user = self
+                    .client
+                    .find_user_address(&order.0.header.store, &order.0.header.owner);
+                let user = self.client.account::<ZeroCopy<_>>(&user).await?;
+                // The position is always provided since it is required by counted orders,
+                // even if it has been closed.
+                let hint = CloseOrderHint::new(
+                    &order.0,
+                    user.as_ref().map(|user| &user.0),
+                    self.client.store_program_id(),
                 )?;
                 self.hint = Some(hint.clone());
                 Ok(hint)
@@ -1510,7 +2159,7 @@ where
                 .then_some(hint.callback.as_ref())
                 .flatten(),
         );
-        Ok(self
+        let txn = self
             .client
             .store_transaction()
             .accounts(fix_optional_account_metas(
@@ -1567,10 +2216,30 @@ where
                 },
                 &ID,
                 self.client.store_program_id(),
-            ))
-            .anchor_args(args::CloseOrderV2 {
+            ));
+        let txn = if self.cancel_expired {
+            txn.anchor_args(args::CancelExpiredOrder {})
+        } else {
+            txn.anchor_args(args::CloseOrderV2 {
                 reason: self.reason.clone(),
-            }))
+            })
+        };
+        // The pending linked orders will be cancelled along with the expired order.
+        Ok(txn.accounts(
+            hint.pending_linked_orders
+                .iter()
+                .flatten()
+                .map(|order| AccountMeta {
+                    pubkey: *order,
+                    is_signer: false,
+                    is_writable: self.cancel_expired,
+                })
+                .chain(
+                    hint.position
+                        .map(|position| AccountMeta::new(position, false)),
+                )
+                .collect(),
+        ))
     }
 }
 
@@ -1734,6 +2403,7 @@ pub struct PositionCutHint {
     store_address: Pubkey,
     owner: Pubkey,
     user: Pubkey,
+    user_stats: Option<Pubkey>,
     referrer: Option<Pubkey>,
     store: Arc<Store>,
     collateral_token: Pubkey,
@@ -1795,26 +2465,27 @@ impl PositionCutHint {
         program_id: &Pubkey,
         virtual_inventories: BTreeSet<Pubkey>,
     ) -> crate::Result<Self> {
-        let records = token_records(
+        let tokens_with_feed = TokensWithFeed::try_from_token_map(
             token_map,
-            &[
+            &BTreeSet::from([
                 market_meta.index_token_mint,
                 market_meta.long_token_mint,
                 market_meta.short_token_mint,
-            ]
-            .into(),
+            ]),
         )
         .map_err(crate::Error::custom)?;
-        let tokens_with_feed =
-            TokensWithFeed::try_from_records(records).map_err(crate::Error::custom)?;
         let user_address =
             crate::pda::find_user_address(&position.store, &position.owner, program_id).0;
+        let user_stats = user.filter(|user| user.has_stats()).map(|_| {
+            crate::pda::find_user_stats_address(&position.store, &position.owner, program_id).0
+        });
         let referrer = user.and_then(|user| optional_address(&user.referral.referrer).copied());
 
         Ok(Self {
             store_address: position.store,
             owner: position.owner,
             user: user_address,
+            user_stats,
             referrer,
             token_map: optional_address(&store.token_map)
                 .copied()
@@ -2057,16 +2728,62 @@ impl<'a, C: Deref<Target = impl Signer> + Clone> PositionCutBuilder<'a, C> {
                         execution_fee: self.execution_fee,
                     })
             }
+            PositionCutKind::Settle => {
+                exec_builder = exec_builder
+                    .accounts(fix_optional_account_metas(
+                        accounts::SettlePosition {
+                            authority: payer,
+                            owner,
+                            user: hint.user,
+                            store,
+                            token_map: hint.token_map,
+                            oracle: self.oracle,
+                            market: hint.market,
+                            order,
+                            position: self.position,
+                            event,
+                            long_token: long_token_mint,
+                            short_token: short_token_mint,
+                            long_token_escrow,
+                            short_token_escrow,
+                            long_token_vault,
+                            short_token_vault,
+                            claimable_long_token_account_for_user,
+                            claimable_short_token_account_for_user,
+                            claimable_pnl_token_account_for_holding,
+                            system_program: system_program::ID,
+                            token_program: anchor_spl::token::ID,
+                            associated_token_program: anchor_spl::associated_token::ID,
+                            event_authority: self.client.store_event_authority(),
+                            program: *self.client.store_program_id(),
+                            chainlink_program: None,
+                        },
+                        &ID,
+                        self.client.store_program_id(),
+                    ))
+                    .anchor_args(args::SettlePosition {
+                        nonce,
+                        recent_timestamp: self.recent_timestamp,
+                        execution_fee: self.execution_fee,
+                    });
+            }
         }
 
+        // The user stats account must be the last one if the owner has prepared it.
+        let user_stats = hint
+            .user_stats
+            .map(|user_stats| AccountMeta::new(user_stats, false))
+            .into_iter()
+            .collect();
         exec_builder = exec_builder
             .accounts(feeds)
             .accounts(virtual_inventories)
+            .accounts(user_stats)
             .compute_budget(ComputeBudget::default().with_limit(POSITION_CUT_COMPUTE_BUDGET))
             .lookup_tables(self.alts.clone());
 
         let is_full_close = match self.kind {
-            PositionCutKind::Liquidate => true,
+            PositionCutKind::Liquidate | PositionCutKind::Settle => true,
             PositionCutKind::AutoDeleverage(size) => size >= hint.position_size,
         };
 
@@ -2090,6 +2807,8 @@ impl<'a, C: Deref<Target = impl Signer> + Clone> PositionCutBuilder<'a, C> {
                     rent_receiver: if is_full_close { owner } else { payer },
                     should_unwrap_native_token: true,
                     callback: None,
+                    position: (!is_full_close).then_some(self.position),
+                    pending_linked_orders: None,
                 })
                 .reason("position cut")
                 .build()
@@ -2290,18 +3009,15 @@ impl UpdateAdlHint {
         let token_map = client.token_map(&token_map_address).await?;
         let meta: MarketMeta = market.meta.into();
 
-        let records = token_records(
+        let tokens_with_feed = TokensWithFeed::try_from_token_map(
             &token_map,
-            &[
+            &BTreeSet::from([
                 meta.index_token_mint,
                 meta.long_token_mint,
                 meta.short_token_mint,
-            ]
-            .into(),
+            ]),
         )
         .map_err(crate::Error::custom)?;
-        let tokens_with_feed =
-            TokensWithFeed::try_from_records(records).map_err(crate::Error::custom)?;
 
         Ok(Self {
             token_map: token_map_address,
@@ -2361,3 +3077,322 @@ impl<C> SetExecutionFee for UpdateAdlBuilder<'_, C> {
         self
     }
 }
+
+/// Update trailing-stop order builder.
+pub struct UpdateTrailingStopBuilder<'a, C> {
+    client: &'a crate::Client<C>,
+    oracle: Pubkey,
+    order: Pubkey,
+    hint: Option<UpdateTrailingStopHint>,
+    feeds_parser: FeedsParser,
+    alts: HashMap<Pubkey, Vec<Pubkey>>,
+}
+
+/// Hint for `update_trailing_stop`.
+#[derive(Clone)]
+pub struct UpdateTrailingStopHint {
+    store: Pubkey,
+    market: Pubkey,
+    inner: UpdateAdlHint,
+}
+
+impl UpdateTrailingStopHint {
+    /// Get feeds.
+    pub fn feeds(&self) -> &TokensWithFeed {
+        self.inner.feeds()
+    }
+}
+
+impl<'a, C: Deref<Target = impl Signer> + Clone> UpdateTrailingStopBuilder<'a, C> {
+    pub(super) fn new(client: &'a crate::Client<C>, oracle: &Pubkey, order: &Pubkey) -> Self {
+        Self {
+            client,
+            oracle: *oracle,
+            order: *order,
+            hint: None,
+            feeds_parser: FeedsParser::default(),
+            alts: Default::default(),
+        }
+    }
+
+    /// Insert an Address Lookup Table.
+    pub fn add_alt(&mut self, account: AddressLookupTableAccount) -> &mut Self {
+        self.alts.insert(account.key, account.addresses);
+        self
+    }
+
+    /// Prepare hint for updating the trailing-stop order.
+    pub async fn prepare_hint(&mut self) -> crate::Result<UpdateTrailingStopHint> {
+        match &self.hint {
+            Some(hint) => Ok(hint.clone()),
+            None => {
+                let order = self.client.order(&self.order).await?;
+                let market = self.client.market(&order.header.market).await?;
+                let hint = UpdateTrailingStopHint {
+                    store: order.header.store,
+                    market: order.header.market,
+                    inner: UpdateAdlHint::from_market(self.client, &market).await?,
+                };
+                self.hint = Some(hint.clone());
+                Ok(hint)
+            }
+        }
+    }
+
+    /// Build [`TransactionBuilder`] for updating the mark of the trailing-stop order.
+    pub async fn build(&mut self) -> crate::Result<TransactionBuilder<'a, C>> {
+        let hint = self.prepare_hint().await?;
+        let feeds = self
+            .feeds_parser
+            .parse(hint.feeds())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let rpc = self
+            .client
+            .store_transaction()
+            .accounts(fix_optional_account_metas(
+                accounts::UpdateTrailingStop {
+                    authority: self.client.payer(),
+                    store: hint.store,
+                    token_map: hint.inner.token_map,
+                    oracle: self.oracle,
+                    market: hint.market,
+                    order: self.order,
+                    chainlink_program: None,
+                    event_authority: self.client.store_event_authority(),
+                    program: *self.client.store_program_id(),
+                },
+                &ID,
+                self.client.store_program_id(),
+            ))
+            .anchor_args(args::UpdateTrailingStop {})
+            .accounts(feeds)
+            .lookup_tables(self.alts.clone());
+
+        Ok(rpc)
+    }
+}
+
+impl<'a, C: Deref<Target = impl Signer> + Clone> MakeBundleBuilder<'a, C>
+    for UpdateTrailingStopBuilder<'a, C>
+{
+    async fn build_with_options(
+        &mut self,
+        options: BundleOptions,
+    ) -> gmsol_solana_utils::Result<BundleBuilder<'a, C>> {
+        let mut bundle = self.client.bundle_with_options(options);
+
+        bundle.push(
+            self.build()
+                .await
+                .map_err(gmsol_solana_utils::Error::custom)?,
+        )?;
+
+        Ok(bundle)
+    }
+}
+
+impl<C: Deref<Target = impl Signer> + Clone> PullOraclePriceConsumer
+    for UpdateTrailingStopBuilder<'_, C>
+{
+    async fn feed_ids(&mut self) -> crate::Result<FeedIds> {
+        let hint = self.prepare_hint().await?;
+        Ok(FeedIds::new(hint.store, hint.inner.tokens_with_feed))
+    }
+
+    fn process_feeds(
+        &mut self,
+        provider: PriceProviderKind,
+        map: FeedAddressMap,
+    ) -> crate::Result<()> {
+        self.feeds_parser
+            .insert_pull_oracle_feed_parser(provider, map);
+        Ok(())
+    }
+}
+
+impl<C> SetExecutionFee for UpdateTrailingStopBuilder<'_, C> {
+    fn is_execution_fee_estimation_required(&self) -> bool {
+        false
+    }
+
+    fn set_execution_fee(&mut self, _lamports: u64) -> &mut Self {
+        self
+    }
+}
+
+/// Switch position collateral builder.
+pub struct SwitchPositionCollateralBuilder<'a, C> {
+    client: &'a crate::Client<C>,
+    oracle: Pubkey,
+    position: Pubkey,
+    hint: Option<SwitchPositionCollateralHint>,
+    feeds_parser: FeedsParser,
+    alts: HashMap<Pubkey, Vec<Pubkey>>,
+}
+
+/// Hint for `switch_position_collateral`.
+#[derive(Clone)]
+pub struct SwitchPositionCollateralHint {
+    store: Pubkey,
+    owner: Pubkey,
+    market: Pubkey,
+    target: Pubkey,
+    virtual_inventories: BTreeSet<Pubkey>,
+    inner: UpdateAdlHint,
+}
+
+impl SwitchPositionCollateralHint {
+    /// Get feeds.
+    pub fn feeds(&self) -> &TokensWithFeed {
+        self.inner.feeds()
+    }
+}
+
+impl<'a, C: Deref<Target = impl Signer> + Clone> SwitchPositionCollateralBuilder<'a, C> {
+    pub(super) fn new(client: &'a crate::Client<C>, oracle: &Pubkey, position: &Pubkey) -> Self {
+        Self {
+            client,
+            oracle: *oracle,
+            position: *position,
+            hint: None,
+            feeds_parser: FeedsParser::default(),
+            alts: Default::default(),
+        }
+    }
+
+    /// Insert an Address Lookup Table.
+    pub fn add_alt(&mut self, account: AddressLookupTableAccount) -> &mut Self {
+        self.alts.insert(account.key, account.addresses);
+        self
+    }
+
+    /// Prepare hint for switching the collateral token of the position.
+    pub async fn prepare_hint(&mut self) -> crate::Result<SwitchPositionCollateralHint> {
+        match &self.hint {
+            Some(hint) => Ok(hint.clone()),
+            None => {
+                let position = self.client.position(&self.position).await?;
+                let store = position.store;
+                let market_address = self
+                    .client
+                    .find_market_address(&store, &position.market_token);
+                let market = self.client.market(&market_address).await?;
+                let meta: MarketMeta = market.meta.into();
+                let collateral_token = meta
+                    .opposite_token(&position.collateral_token)
+                    .map_err(crate::Error::custom)?;
+                let target = self.client.find_position_address(
+                    &store,
+                    &position.owner,
+                    &position.market_token,
+                    collateral_token,
+                    position.try_is_long()?,
+                )?;
+                let virtual_inventories = VirtualInventoryCollector::default()
+                    .insert_market_token(&position.market_token)
+                    .collect(self.client, &store)
+                    .await?;
+                let hint = SwitchPositionCollateralHint {
+                    store,
+                    owner: position.owner,
+                    market: market_address,
+                    target,
+                    virtual_inventories,
+                    inner: UpdateAdlHint::from_market(self.client, &market).await?,
+                };
+                self.hint = Some(hint.clone());
+                Ok(hint)
+            }
+        }
+    }
+
+    /// Build [`TransactionBuilder`] for switching the collateral token of the position.
+    pub async fn build(&mut self) -> crate::Result<TransactionBuilder<'a, C>> {
+        let hint = self.prepare_hint().await?;
+        let feeds = self
+            .feeds_parser
+            .parse(hint.feeds())
+            .collect::<Result<Vec<_>, _>>()?;
+        let virtual_inventories = hint
+            .virtual_inventories
+            .iter()
+            .map(|pubkey| AccountMeta::new(*pubkey, false))
+            .collect::<Vec<_>>();
+
+        let rpc = self
+            .client
+            .store_transaction()
+            .accounts(fix_optional_account_metas(
+                accounts::SwitchPositionCollateral {
+                    authority: self.client.payer(),
+                    owner: hint.owner,
+                    store: hint.store,
+                    token_map: hint.inner.token_map,
+                    oracle: self.oracle,
+                    market: hint.market,
+                    position: self.position,
+                    target: hint.target,
+                    chainlink_program: None,
+                    event_authority: self.client.store_event_authority(),
+                    program: *self.client.store_program_id(),
+                },
+                &ID,
+                self.client.store_program_id(),
+            ))
+            .anchor_args(args::SwitchPositionCollateral {})
+            .accounts(feeds)
+            .accounts(virtual_inventories)
+            .lookup_tables(self.alts.clone());
+
+        Ok(rpc)
+    }
+}
+
+impl<'a, C: Deref<Target = impl Signer> + Clone> MakeBundleBuilder<'a, C>
+    for SwitchPositionCollateralBuilder<'a, C>
+{
+    async fn build_with_options(
+        &mut self,
+        options: BundleOptions,
+    ) -> gmsol_solana_utils::Result<BundleBuilder<'a, C>> {
+        let mut bundle = self.client.bundle_with_options(options);
+
+        bundle.push(
+            self.build()
+                .await
+                .map_err(gmsol_solana_utils::Error::custom)?,
+        )?;
+
+        Ok(bundle)
+    }
+}
+
+impl<C: Deref<Target = impl Signer> + Clone> PullOraclePriceConsumer
+    for SwitchPositionCollateralBuilder<'_, C>
+{
+    async fn feed_ids(&mut self) -> crate::Result<FeedIds> {
+        let hint = self.prepare_hint().await?;
+        Ok(FeedIds::new(hint.store, hint.inner.tokens_with_feed))
+    }
+
+    fn process_feeds(
+        &mut self,
+        provider: PriceProviderKind,
+        map: FeedAddressMap,
+    ) -> crate::Result<()> {
+        self.feeds_parser
+            .insert_pull_oracle_feed_parser(provider, map);
+        Ok(())
+    }
+}
+
+impl<C> SetExecutionFee for SwitchPositionCollateralBuilder<'_, C> {
+    fn is_execution_fee_estimation_required(&self) -> bool {
+        false
+    }
+
+    fn set_execution_fee(&mut self, _lamports: u64) -> &mut Self {
+        self
+    }
+}
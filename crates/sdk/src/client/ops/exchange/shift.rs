@@ -18,7 +18,7 @@ use gmsol_utils::{
     market::{ordered_tokens, HasMarketMeta, MarketMeta},
     oracle::PriceProviderKind,
     pubkey::optional_address,
-    token_config::{token_records, TokenMapAccess, TokensWithFeed},
+    token_config::{TokenMapAccess, TokensWithFeed},
 };
 use solana_sdk::{pubkey::Pubkey, signer::Signer, system_program};
 
@@ -357,9 +357,8 @@ impl ExecuteShiftHint {
         virtual_inventories: BTreeSet<Pubkey>,
     ) -> crate::Result<Self> {
         let ordered_tokens = ordered_tokens(from_market, to_market);
-        let token_records = token_records(map, &ordered_tokens).map_err(crate::Error::custom)?;
-        let feeds =
-            TokensWithFeed::try_from_records(token_records).map_err(crate::Error::custom)?;
+        let feeds = TokensWithFeed::try_from_token_map(map, &ordered_tokens)
+            .map_err(crate::Error::custom)?;
 
         let CloseShiftHint {
             store: store_address,
@@ -4,7 +4,7 @@ use anchor_spl::associated_token::get_associated_token_address_with_program_id;
 use gmsol_model::{price::Prices, PnlFactorKind};
 use gmsol_programs::gmsol_store::{
     client::{accounts, args},
-    types::EntryArgs,
+    types::{EntryArgs, MarketScheduleParams},
 };
 use gmsol_solana_utils::{
     make_bundle_builder::MakeBundleBuilder, transaction_builder::TransactionBuilder,
@@ -13,7 +13,7 @@ use gmsol_solana_utils::{
 use gmsol_utils::{
     market::{MarketConfigFactor, MarketConfigFlag, MarketConfigKey, MarketMeta},
     oracle::PriceProviderKind,
-    token_config::{token_records, TokensWithFeed},
+    token_config::TokensWithFeed,
 };
 use indexmap::IndexMap;
 use solana_sdk::{pubkey::Pubkey, signer::Signer, system_program};
@@ -161,6 +161,28 @@ pub trait MarketOps<C> {
         enable: bool,
     ) -> TransactionBuilder<C>;
 
+    /// Reset the circuit breaker of the market.
+    fn reset_market_circuit_breaker(
+        &self,
+        store: &Pubkey,
+        market_token: &Pubkey,
+    ) -> TransactionBuilder<C>;
+
+    /// Complete the settlement of the market.
+    fn complete_market_settlement(
+        &self,
+        store: &Pubkey,
+        market_token: &Pubkey,
+    ) -> TransactionBuilder<C>;
+
+    /// Set the trading-hours schedule of the market.
+    fn set_market_schedule(
+        &self,
+        store: &Pubkey,
+        market_token: &Pubkey,
+        params: MarketScheduleParams,
+    ) -> TransactionBuilder<C>;
+
     /// Initialize Market Config Buffer.
     fn initialize_market_config_buffer<'a>(
         &'a self,
@@ -449,6 +471,49 @@ impl<C: Deref<Target = impl Signer> + Clone> MarketOps<C> for crate::Client<C> {
             })
     }
 
+    fn reset_market_circuit_breaker(
+        &self,
+        store: &Pubkey,
+        market_token: &Pubkey,
+    ) -> TransactionBuilder<C> {
+        self.store_transaction()
+            .anchor_args(args::ResetMarketCircuitBreaker {})
+            .anchor_accounts(accounts::ResetMarketCircuitBreaker {
+                authority: self.payer(),
+                store: *store,
+                market: self.find_market_address(store, market_token),
+            })
+    }
+
+    fn complete_market_settlement(
+        &self,
+        store: &Pubkey,
+        market_token: &Pubkey,
+    ) -> TransactionBuilder<C> {
+        self.store_transaction()
+            .anchor_args(args::CompleteMarketSettlement {})
+            .anchor_accounts(accounts::CompleteMarketSettlement {
+                authority: self.payer(),
+                store: *store,
+                market: self.find_market_address(store, market_token),
+            })
+    }
+
+    fn set_market_schedule(
+        &self,
+        store: &Pubkey,
+        market_token: &Pubkey,
+        params: MarketScheduleParams,
+    ) -> TransactionBuilder<C> {
+        self.store_transaction()
+            .anchor_args(args::SetMarketSchedule { params })
+            .anchor_accounts(accounts::SetMarketSchedule {
+                authority: self.payer(),
+                store: *store,
+                market: self.find_market_address(store, market_token),
+            })
+    }
+
     fn initialize_market_config_buffer<'a>(
         &'a self,
         store: &Pubkey,
@@ -719,8 +784,8 @@ impl<'a, C: Deref<Target = impl Signer> + Clone> GetMarketTokenValueBuilder<'a,
             .await?;
         let token_map = self.client.token_map(&token_map_address).await?;
         let tokens = ordered_tokens(&market.meta.into());
-        let records = token_records(&token_map, &tokens).map_err(crate::Error::custom)?;
-        let feeds = TokensWithFeed::try_from_records(records).map_err(crate::Error::custom)?;
+        let feeds = TokensWithFeed::try_from_token_map(&token_map, &tokens)
+            .map_err(crate::Error::custom)?;
         let hint = GetMarketTokenValueHint {
             token_map: token_map_address,
             feeds,
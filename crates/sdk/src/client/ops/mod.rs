@@ -60,15 +60,23 @@ pub mod liquidity_provider;
 /// Operations for builder fees.
 pub mod builder_fee;
 
+/// Operations for delegations.
+pub mod delegation;
+
+/// Operations for order intents.
+pub mod order_intent;
+
 pub use alt::AddressLookupTableOps;
 pub use builder_fee::BuilderFeeOps;
 pub use config::ConfigOps;
+pub use delegation::DelegationOps;
 pub use exchange::ExchangeOps;
 pub use glv::GlvOps;
 pub use gt::GtOps;
 pub use idl::IdlOps;
 pub use market::MarketOps;
 pub use oracle::OracleOps;
+pub use order_intent::OrderIntentOps;
 pub use role::RoleOps;
 pub use store::StoreOps;
 pub use system::SystemProgramOps;
@@ -5,11 +5,25 @@ use gmsol_programs::gmsol_store::{
     client::{accounts, args},
 };
 use gmsol_solana_utils::transaction_builder::TransactionBuilder;
-use gmsol_utils::oracle::PriceProviderKind;
+use gmsol_utils::{oracle::PriceProviderKind, price::PublisherPrice};
 use solana_sdk::{
-    pubkey::Pubkey, signer::Signer, system_instruction::create_account, system_program,
+    pubkey::Pubkey, signature::Signature, signer::Signer, system_instruction::create_account,
+    system_program, sysvar::instructions,
 };
 
+use super::order_intent::ed25519_verify_signatures_instruction;
+
+/// Sign the given publisher price with the publisher.
+pub fn sign_publisher_price(
+    publisher: &impl Signer,
+    price: &PublisherPrice,
+) -> crate::Result<Signature> {
+    let message = price.to_message().map_err(crate::Error::custom)?;
+    publisher
+        .try_sign_message(&message)
+        .map_err(crate::Error::custom)
+}
+
 /// Arguments for updating a Chainlink price feed.
 #[cfg(feature = "gmsol-chainlink-datastreams")]
 pub struct ChainlinkPriceFeedUpdateArgs<'a> {
@@ -43,6 +57,35 @@ pub trait OracleOps<C> {
         feed_id: &Pubkey,
     ) -> (TransactionBuilder<C>, Pubkey);
 
+    /// Set the sampling interval of the price history of the given price feed.
+    fn set_price_feed_history_interval(
+        &self,
+        store: &Pubkey,
+        price_feed: &Pubkey,
+        interval: u32,
+    ) -> TransactionBuilder<C>;
+
+    /// Initialize the price publishers account of the store.
+    fn initialize_price_publishers(&self, store: &Pubkey) -> (TransactionBuilder<C>, Pubkey);
+
+    /// Set the allow-listed price publishers, the quorum and the max age of their prices.
+    fn set_price_publishers(
+        &self,
+        store: &Pubkey,
+        publishers: &[Pubkey],
+        quorum: u8,
+        max_age: u32,
+    ) -> TransactionBuilder<C>;
+
+    /// Update price feed with the prices signed by the publishers.
+    fn update_price_feed_with_publishers(
+        &self,
+        store: &Pubkey,
+        price_feed: &Pubkey,
+        prices: &[(Pubkey, Signature, PublisherPrice)],
+        idempotent: bool,
+    ) -> crate::Result<TransactionBuilder<C>>;
+
     /// Update price feed with chainlink.
     #[cfg(feature = "gmsol-chainlink-datastreams")]
     fn update_price_feed_with_chainlink_and_authority<'a>(
@@ -144,6 +187,87 @@ impl<C: Deref<Target = impl Signer> + Clone> OracleOps<C> for crate::Client<C> {
         (rpc, price_feed)
     }
 
+    fn set_price_feed_history_interval(
+        &self,
+        store: &Pubkey,
+        price_feed: &Pubkey,
+        interval: u32,
+    ) -> TransactionBuilder<C> {
+        self.store_transaction()
+            .anchor_accounts(accounts::SetPriceFeedHistoryInterval {
+                authority: self.payer(),
+                store: *store,
+                price_feed: *price_feed,
+            })
+            .anchor_args(args::SetPriceFeedHistoryInterval { interval })
+    }
+
+    fn initialize_price_publishers(&self, store: &Pubkey) -> (TransactionBuilder<C>, Pubkey) {
+        let price_publishers = self.find_price_publishers_address(store);
+        let rpc = self
+            .store_transaction()
+            .anchor_accounts(accounts::InitializePricePublishers {
+                authority: self.payer(),
+                store: *store,
+                price_publishers,
+                system_program: system_program::ID,
+            })
+            .anchor_args(args::InitializePricePublishers {});
+        (rpc, price_publishers)
+    }
+
+    fn set_price_publishers(
+        &self,
+        store: &Pubkey,
+        publishers: &[Pubkey],
+        quorum: u8,
+        max_age: u32,
+    ) -> TransactionBuilder<C> {
+        self.store_transaction()
+            .anchor_accounts(accounts::SetPricePublishers {
+                authority: self.payer(),
+                store: *store,
+                price_publishers: self.find_price_publishers_address(store),
+            })
+            .anchor_args(args::SetPricePublishers {
+                publishers: publishers.to_vec(),
+                quorum,
+                max_age,
+            })
+    }
+
+    fn update_price_feed_with_publishers(
+        &self,
+        store: &Pubkey,
+        price_feed: &Pubkey,
+        prices: &[(Pubkey, Signature, PublisherPrice)],
+        idempotent: bool,
+    ) -> crate::Result<TransactionBuilder<C>> {
+        let messages = prices
+            .iter()
+            .map(|(_, _, price)| price.to_message().map_err(crate::Error::custom))
+            .collect::<crate::Result<Vec<_>>>()?;
+        let signatures = prices
+            .iter()
+            .zip(messages.iter())
+            .map(|((publisher, signature, _), message)| (publisher, signature, message.as_slice()))
+            .collect::<Vec<_>>();
+        // The ed25519 program instruction must be right before the main instruction.
+        let verify = ed25519_verify_signatures_instruction(&signatures)?;
+
+        Ok(self
+            .store_transaction()
+            .pre_instruction(verify, true)
+            .anchor_accounts(accounts::UpdatePriceFeedWithPublishers {
+                authority: self.payer(),
+                store: *store,
+                price_publishers: self.find_price_publishers_address(store),
+                price_feed: *price_feed,
+                instructions_sysvar: instructions::ID,
+            })
+            .anchor_args(args::UpdatePriceFeedWithPublishers { idempotent }))
+    }
+
     #[cfg(feature = "gmsol-chainlink-datastreams")]
     fn update_price_feed_with_chainlink_and_authority<'a>(
         &'a self,
@@ -0,0 +1,249 @@
+use std::ops::Deref;
+
+use anchor_lang::AnchorSerialize;
+use anchor_spl::associated_token::{
+    get_associated_token_address,
+    spl_associated_token_account::instruction::create_associated_token_account_idempotent,
+};
+use gmsol_programs::gmsol_store::{
+    client::{accounts, args},
+    types::OrderIntent,
+};
+use gmsol_solana_utils::transaction_builder::TransactionBuilder;
+use gmsol_utils::{market::MarketMeta, order::OrderKind};
+use solana_sdk::{
+    ed25519_program, instruction::Instruction, pubkey::Pubkey, signature::Signature,
+    signer::Signer, system_program, sysvar::instructions,
+};
+
+use crate::builders::utils::generate_nonce;
+
+/// The domain prefix of order intent messages.
+pub const ORDER_INTENT_DOMAIN: &[u8] = b"gmsol-store:order-intent:v1";
+
+/// Get the message to sign for the given order intent.
+pub fn order_intent_message(intent: &OrderIntent) -> crate::Result<Vec<u8>> {
+    let mut message = ORDER_INTENT_DOMAIN.to_vec();
+    intent
+        .serialize(&mut message)
+        .map_err(crate::Error::custom)?;
+    Ok(message)
+}
+
+/// Sign the given order intent with the owner.
+pub fn sign_order_intent(owner: &impl Signer, intent: &OrderIntent) -> crate::Result<Signature> {
+    let message = order_intent_message(intent)?;
+    owner
+        .try_sign_message(&message)
+        .map_err(crate::Error::custom)
+}
+
+/// Create an ed25519 program instruction verifying the signature of `pubkey` over `message`.
+pub fn ed25519_verify_instruction(
+    pubkey: &Pubkey,
+    signature: &Signature,
+    message: &[u8],
+) -> crate::Result<Instruction> {
+    ed25519_verify_signatures_instruction(&[(pubkey, signature, message)])
+}
+
+/// Create an ed25519 program instruction verifying multiple signatures,
+/// each of the given public key over the given message.
+pub fn ed25519_verify_signatures_instruction(
+    signatures: &[(&Pubkey, &Signature, &[u8])],
+) -> crate::Result<Instruction> {
+    const HEADER_START: usize = 2;
+    const OFFSETS_LEN: usize = 14;
+    const PUBKEY_LEN: usize = 32;
+    const SIGNATURE_LEN: usize = 64;
+
+    let to_u16 = |value: usize| u16::try_from(value).map_err(crate::Error::custom);
+
+    let count = u8::try_from(signatures.len()).map_err(crate::Error::custom)?;
+    let mut offset = HEADER_START + signatures.len() * OFFSETS_LEN;
+    let mut data = vec![count, 0];
+    let mut payload = vec![];
+    for (pubkey, signature, message) in signatures {
+        let pubkey_offset = offset;
+        let signature_offset = pubkey_offset + PUBKEY_LEN;
+        let message_offset = signature_offset + SIGNATURE_LEN;
+        offset = message_offset + message.len();
+        for value in [
+            to_u16(signature_offset)?,
+            u16::MAX,
+            to_u16(pubkey_offset)?,
+            u16::MAX,
+            to_u16(message_offset)?,
+            to_u16(message.len())?,
+            u16::MAX,
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        payload.extend_from_slice(pubkey.as_ref());
+        payload.extend_from_slice(signature.as_ref());
+        payload.extend_from_slice(message);
+    }
+    data.extend_from_slice(&payload);
+
+    Ok(Instruction {
+        program_id: ed25519_program::ID,
+        accounts: vec![],
+        data,
+    })
+}
+
+/// Operations for order intents.
+pub trait OrderIntentOps<C> {
+    /// Approve the intent authority of the payer to spend `amount` from its
+    /// associated token account of `token`.
+    fn approve_intent_authority(
+        &self,
+        store: &Pubkey,
+        token: &Pubkey,
+        amount: u64,
+    ) -> crate::Result<TransactionBuilder<C>>;
+
+    /// Create an order from an intent signed by the owner, with the payer as the relayer.
+    ///
+    /// The tokens of the owner are taken from its associated token accounts, and the
+    /// relayer fee is deducted from the initial collateral and paid to the associated
+    /// token account of the payer.
+    fn create_order_with_intent(
+        &self,
+        owner: &Pubkey,
+        intent: OrderIntent,
+        signature: &Signature,
+        meta: &MarketMeta,
+    ) -> crate::Result<TransactionBuilder<C, Pubkey>>;
+}
+
+impl<C: Deref<Target = impl Signer> + Clone> OrderIntentOps<C> for crate::Client<C> {
+    fn approve_intent_authority(
+        &self,
+        store: &Pubkey,
+        token: &Pubkey,
+        amount: u64,
+    ) -> crate::Result<TransactionBuilder<C>> {
+        use anchor_spl::token::spl_token;
+
+        let owner = self.payer();
+        let ix = spl_token::instruction::approve(
+            &spl_token::ID,
+            &get_associated_token_address(&owner, token),
+            &self.find_intent_authority_address(store, &owner),
+            &owner,
+            &[],
+            amount,
+        )
+        .map_err(crate::Error::custom)?;
+        Ok(self
+            .store_transaction()
+            .program(spl_token::ID)
+            .pre_instruction(ix, true))
+    }
+
+    fn create_order_with_intent(
+        &self,
+        owner: &Pubkey,
+        intent: OrderIntent,
+        signature: &Signature,
+        meta: &MarketMeta,
+    ) -> crate::Result<TransactionBuilder<C, Pubkey>> {
+        let message = order_intent_message(&intent)?;
+        let verify = ed25519_verify_instruction(owner, signature, &message)?;
+
+        let relayer = self.payer();
+        if intent.relayer != relayer {
+            return Err(crate::Error::custom(
+                "the intent is not signed for the payer as the relayer",
+            ));
+        }
+        let store = &intent.store;
+        let params = &intent.params;
+        let kind = OrderKind::from(params.kind);
+        let is_increase = kind.is_increase_position();
+        if !is_increase && !kind.is_decrease_position() {
+            return Err(crate::Error::custom(
+                "only increase and decrease orders are supported",
+            ));
+        }
+
+        let nonce = generate_nonce().to_bytes();
+        let order = self.find_order_address(store, &relayer, &nonce);
+        let collateral_token = if params.is_collateral_long {
+            meta.long_token_mint
+        } else {
+            meta.short_token_mint
+        };
+        let position = self.find_position_address(
+            store,
+            owner,
+            &meta.market_token_mint,
+            &collateral_token,
+            params.is_long,
+        )?;
+        let has_relayer_fee = intent.relayer_fee != 0;
+
+        // The escrow accounts and the token accounts of the owner are prepared by the relayer.
+        let token_program_id = anchor_spl::token::ID;
+        let mut prepare = vec![];
+        for token in [&meta.long_token_mint, &meta.short_token_mint] {
+            for wallet in [&order, owner] {
+                prepare.push(create_associated_token_account_idempotent(
+                    &relayer,
+                    wallet,
+                    token,
+                    &token_program_id,
+                ));
+            }
+        }
+        if has_relayer_fee {
+            prepare.push(create_associated_token_account_idempotent(
+                &relayer,
+                &relayer,
+                &collateral_token,
+                &token_program_id,
+            ));
+        }
+        // The ed25519 program instruction must be right before the main instruction.
+        prepare.push(verify);
+
+        Ok(self
+            .store_transaction()
+            .pre_instructions(prepare, true)
+            .anchor_accounts(accounts::CreateOrderWithIntent {
+                relayer,
+                owner: *owner,
+                store: *store,
+                market: self.find_market_address(store, &meta.market_token_mint),
+                user: self.find_user_address(store, owner),
+                order,
+                position,
+                intent_authority: self.find_intent_authority_address(store, owner),
+                initial_collateral_token: is_increase.then_some(collateral_token),
+                final_output_token: collateral_token,
+                long_token: meta.long_token_mint,
+                short_token: meta.short_token_mint,
+                initial_collateral_token_escrow: is_increase
+                    .then(|| get_associated_token_address(&order, &collateral_token)),
+                final_output_token_escrow: Some(get_associated_token_address(
+                    &order,
+                    &collateral_token,
+                )),
+                long_token_escrow: get_associated_token_address(&order, &meta.long_token_mint),
+                short_token_escrow: get_associated_token_address(&order, &meta.short_token_mint),
+                initial_collateral_token_source: is_increase
+                    .then(|| get_associated_token_address(owner, &collateral_token)),
+                relayer_fee_receiver: has_relayer_fee
+                    .then(|| get_associated_token_address(&relayer, &collateral_token)),
+                instructions_sysvar: instructions::ID,
+                system_program: system_program::ID,
+                token_program: anchor_spl::token::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                event_authority: self.store_event_authority(),
+                program: *self.store_program_id(),
+            })
+            .anchor_args(args::CreateOrderWithIntent { nonce, intent })
+            .output(order))
+    }
+}
@@ -94,6 +94,28 @@ pub trait TokenConfigOps<C> {
         provider: PriceProviderKind,
         update: UpdateFeedConfig,
     ) -> crate::Result<TransactionBuilder<C>>;
+
+    /// Set the providers whose prices are aggregated with the price of the
+    /// expected provider. Passing empty `providers` disables price aggregation.
+    fn set_price_aggregation(
+        &self,
+        store: &Pubkey,
+        token_map: &Pubkey,
+        token: &Pubkey,
+        providers: &[PriceProviderKind],
+        max_spread_factor: u128,
+    ) -> TransactionBuilder<C>;
+
+    /// Set the min spread factor and the spread multiplier factor of the prices
+    /// of the given token. Passing `0` disables the corresponding widening.
+    fn set_price_spread(
+        &self,
+        store: &Pubkey,
+        token_map: &Pubkey,
+        token: &Pubkey,
+        min_spread_factor: u128,
+        spread_multiplier_factor: u128,
+    ) -> TransactionBuilder<C>;
 }
 
 impl<C: Deref<Target = impl Signer> + Clone> TokenConfigOps<C> for crate::Client<C> {
@@ -283,6 +305,50 @@ impl<C: Deref<Target = impl Signer> + Clone> TokenConfigOps<C> for crate::Client
             });
         Ok(txn)
     }
+
+    fn set_price_aggregation(
+        &self,
+        store: &Pubkey,
+        token_map: &Pubkey,
+        token: &Pubkey,
+        providers: &[PriceProviderKind],
+        max_spread_factor: u128,
+    ) -> TransactionBuilder<C> {
+        let authority = self.payer();
+        self.store_transaction()
+            .anchor_accounts(accounts::SetPriceAggregation {
+                authority,
+                store: *store,
+                token_map: *token_map,
+            })
+            .anchor_args(args::SetPriceAggregation {
+                token: *token,
+                providers: providers.iter().map(|kind| *kind as u8).collect(),
+                max_spread_factor,
+            })
+    }
+
+    fn set_price_spread(
+        &self,
+        store: &Pubkey,
+        token_map: &Pubkey,
+        token: &Pubkey,
+        min_spread_factor: u128,
+        spread_multiplier_factor: u128,
+    ) -> TransactionBuilder<C> {
+        let authority = self.payer();
+        self.store_transaction()
+            .anchor_accounts(accounts::SetPriceSpread {
+                authority,
+                store: *store,
+                token_map: *token_map,
+            })
+            .anchor_args(args::SetPriceSpread {
+                token: *token,
+                min_spread_factor,
+                spread_multiplier_factor,
+            })
+    }
 }
 
 /// Contains updated parameters for the feed config.
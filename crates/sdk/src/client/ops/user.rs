@@ -15,6 +15,9 @@ pub trait UserOps<C> {
     /// Prepare User.
     fn prepare_user(&self, store: &Pubkey) -> crate::Result<TransactionBuilder<C>>;
 
+    /// Prepare User Stats. The User Account must have been prepared.
+    fn prepare_user_stats(&self, store: &Pubkey) -> crate::Result<TransactionBuilder<C>>;
+
     /// Initialize Referral Code.
     fn initialize_referral_code(
         &self,
@@ -80,6 +83,23 @@ impl<C: Deref<Target = impl Signer> + Clone> UserOps<C> for crate::Client<C> {
         Ok(rpc)
     }
 
+    fn prepare_user_stats(&self, store: &Pubkey) -> crate::Result<TransactionBuilder<C>> {
+        let owner = self.payer();
+        let user = self.find_user_address(store, &owner);
+        let user_stats = self.find_user_stats_address(store, &owner);
+        let rpc = self
+            .store_transaction()
+            .anchor_accounts(accounts::PrepareUserStats {
+                owner,
+                store: *store,
+                user,
+                user_stats,
+                system_program: system_program::ID,
+            })
+            .anchor_args(args::PrepareUserStats {});
+        Ok(rpc)
+    }
+
     fn initialize_referral_code(
         &self,
         store: &Pubkey,
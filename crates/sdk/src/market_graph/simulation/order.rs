@@ -45,10 +45,13 @@ impl OrderSimulation<'_> {
         options: SimulationOptions,
     ) -> crate::Result<OrderSimulationOutput> {
         match self.kind {
-            CreateOrderKind::MarketIncrease | CreateOrderKind::LimitIncrease => self.increase(),
+            CreateOrderKind::MarketIncrease
+            | CreateOrderKind::LimitIncrease
+            | CreateOrderKind::StopIncrease => self.increase(),
             CreateOrderKind::MarketDecrease
             | CreateOrderKind::LimitDecrease
-            | CreateOrderKind::StopLossDecrease => self.decrease(),
+            | CreateOrderKind::StopLossDecrease
+            | CreateOrderKind::TrailingStopDecrease => self.decrease(),
             CreateOrderKind::MarketSwap | CreateOrderKind::LimitSwap => self.swap(options),
         }
     }
@@ -81,7 +84,10 @@ impl OrderSimulation<'_> {
             ..
         } = self;
 
-        if matches!(kind, CreateOrderKind::LimitIncrease) {
+        if matches!(
+            kind,
+            CreateOrderKind::LimitIncrease | CreateOrderKind::StopIncrease
+        ) {
             let Some(trigger_price) = params.trigger_price else {
                 return Err(crate::Error::custom("[sim] trigger price is required"));
             };
@@ -149,9 +155,11 @@ impl OrderSimulation<'_> {
 
         if matches!(
             kind,
-            CreateOrderKind::LimitDecrease | CreateOrderKind::StopLossDecrease
+            CreateOrderKind::LimitDecrease
+                | CreateOrderKind::StopLossDecrease
+                | CreateOrderKind::TrailingStopDecrease
         ) {
-            let Some(trigger_price) = params.trigger_price else {
+            let Some(trigger_price) = params.effective_trigger_price(kind) else {
                 return Err(crate::Error::custom("[sim] trigger price is required"));
             };
             let price = Price {
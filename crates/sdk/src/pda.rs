@@ -17,7 +17,7 @@ use gmsol_programs::gmsol_timelock::accounts as timelock_accounts;
 use gmsol_programs::gmsol_liquidity_provider::accounts as liquidity_provider_accounts;
 
 pub use gmsol_programs::gmsol_store::constants::{
-    USER_TOKEN_CONTROLLER_SEED, VIRTUAL_INVENTORY_FOR_POSITIONS_SEED,
+    INTENT_AUTHORITY_SEED, USER_TOKEN_CONTROLLER_SEED, VIRTUAL_INVENTORY_FOR_POSITIONS_SEED,
     VIRTUAL_INVENTORY_FOR_SWAPS_SEED,
 };
 
@@ -72,9 +72,15 @@ pub const TRADE_DATA_SEED: &[u8] = b"trade_event_data";
 /// Seed for [`User`](store_accounts::UserHeader).
 pub const USER_SEED: &[u8] = b"user";
 
+/// Seed for [`UserStats`](store_accounts::UserStats).
+pub const USER_STATS_SEED: &[u8] = b"user_stats";
+
 /// Seed for [`ReferralCodeV2`](store_accounts::ReferralCodeV2).
 pub const REFERRAL_CODE_SEED: &[u8] = b"referral_code";
 
+/// Seed for [`Delegation`](store_accounts::Delegation).
+pub const DELEGATION_SEED: &[u8] = b"delegation";
+
 /// Seed for GLV token mint.
 pub const GLV_TOKEN_SEED: &[u8] = b"glv_token";
 
@@ -96,6 +102,9 @@ pub const GT_EXCHANGE_SEED: &[u8] = b"gt_exchange";
 /// Seed for [`PriceFeed`](store_accounts::PriceFeed).
 pub const PRICE_FEED_SEED: &[u8] = b"price_feed";
 
+/// Seed for [`PricePublishers`](store_accounts::PricePublishers).
+pub const PRICE_PUBLISHERS_SEED: &[u8] = b"price_publishers";
+
 /// Seed for [`Config`](treasury_accounts::Config).
 #[cfg(treasury)]
 pub const TREASURY_CONFIG_SEED: &[u8] = b"config";
@@ -335,6 +344,51 @@ pub fn find_user_address(
     )
 }
 
+/// Find PDA for [`UserStats`](store_accounts::UserStats) account.
+pub fn find_user_stats_address(
+    store: &Pubkey,
+    owner: &Pubkey,
+    store_program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[USER_STATS_SEED, store.as_ref(), owner.as_ref()],
+        store_program_id,
+    )
+}
+
+/// Find PDA for [`Delegation`](store_accounts::Delegation) account.
+pub fn find_delegation_address(
+    store: &Pubkey,
+    owner: &Pubkey,
+    delegate: &Pubkey,
+    store_program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            DELEGATION_SEED,
+            store.as_ref(),
+            owner.as_ref(),
+            delegate.as_ref(),
+        ],
+        store_program_id,
+    )
+}
+
+/// Find PDA for the order intent authority of the owner.
+///
+/// The owner approves this PDA to spend from its token accounts for orders
+/// created from signed intents.
+pub fn find_intent_authority_address(
+    store: &Pubkey,
+    owner: &Pubkey,
+    store_program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[INTENT_AUTHORITY_SEED, store.as_ref(), owner.as_ref()],
+        store_program_id,
+    )
+}
+
 /// Find PDA for referral code account.
 pub fn find_referral_code_address(
     store: &Pubkey,
@@ -463,6 +517,11 @@ pub fn find_price_feed_address(
     )
 }
 
+/// Find PDA for price publishers account.
+pub fn find_price_publishers_address(store: &Pubkey, store_program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PRICE_PUBLISHERS_SEED, store.as_ref()], store_program_id)
+}
+
 /// Find PDA for virtual inventory for swaps account.
 pub fn find_virtual_inventory_for_swaps_address(
     store: &Pubkey,
@@ -76,12 +76,13 @@ impl OrderSimulation<'_> {
         options: SimulationOptions,
     ) -> crate::Result<OrderSimulationOutput> {
         match self.kind {
-            CreateOrderKind::MarketIncrease | CreateOrderKind::LimitIncrease => {
-                self.increase(options)
-            }
+            CreateOrderKind::MarketIncrease
+            | CreateOrderKind::LimitIncrease
+            | CreateOrderKind::StopIncrease => self.increase(options),
             CreateOrderKind::MarketDecrease
             | CreateOrderKind::LimitDecrease
-            | CreateOrderKind::StopLossDecrease => self.decrease(options),
+            | CreateOrderKind::StopLossDecrease
+            | CreateOrderKind::TrailingStopDecrease => self.decrease(options),
             CreateOrderKind::MarketSwap | CreateOrderKind::LimitSwap => self.swap(options),
         }
     }
@@ -101,9 +102,11 @@ impl OrderSimulation<'_> {
 
         match self.kind {
             CreateOrderKind::LimitIncrease
+            | CreateOrderKind::StopIncrease
             | CreateOrderKind::LimitDecrease
-            | CreateOrderKind::StopLossDecrease => {
-                let Some(trigger_price) = self.params.trigger_price else {
+            | CreateOrderKind::StopLossDecrease
+            | CreateOrderKind::TrailingStopDecrease => {
+                let Some(trigger_price) = self.params.effective_trigger_price(self.kind) else {
                     return Err(crate::Error::custom("[sim] trigger price is required"));
                 };
                 let token = self.get_market()?.meta.index_token_mint;
@@ -200,27 +203,42 @@ impl OrderSimulation<'_> {
 
         let prices = simulator.get_prices_for_market(&params.market_token)?;
 
-        if matches!(kind, CreateOrderKind::LimitIncrease) && !options.skip_limit_price_validation {
+        if matches!(
+            kind,
+            CreateOrderKind::LimitIncrease | CreateOrderKind::StopIncrease
+        ) && !options.skip_limit_price_validation
+        {
             let Some(trigger_price) = params.trigger_price else {
                 return Err(crate::Error::custom("[sim] trigger price is required"));
             };
 
             // Validate with trigger price.
             let index_price = &prices.index_token_price;
+            let is_stop = matches!(kind, CreateOrderKind::StopIncrease);
             if params.is_long {
                 let price = index_price.pick_price(true);
-                if *price > trigger_price {
+                if !is_stop && *price > trigger_price {
                     return Err(crate::Error::custom(format!(
                         "[sim] index price must be <= trigger price for a increase-long order, but {price} > {trigger_price}."
                     )));
                 }
+                if is_stop && *price < trigger_price {
+                    return Err(crate::Error::custom(format!(
+                        "[sim] index price must be >= trigger price for a stop-increase-long order, but {price} < {trigger_price}."
+                    )));
+                }
             } else {
                 let price = index_price.pick_price(false);
-                if *price < trigger_price {
+                if !is_stop && *price < trigger_price {
                     return Err(crate::Error::custom(format!(
                         "[sim] index price must be >= trigger price for a increase-short order, but {price} < {trigger_price}."
                     )));
                 }
+                if is_stop && *price > trigger_price {
+                    return Err(crate::Error::custom(format!(
+                        "[sim] index price must be <= trigger price for a stop-increase-short order, but {price} > {trigger_price}."
+                    )));
+                }
             }
         }
 
@@ -322,8 +340,8 @@ impl OrderSimulation<'_> {
                         }
                     }
                 }
-                CreateOrderKind::StopLossDecrease => {
-                    let Some(trigger_price) = params.trigger_price else {
+                CreateOrderKind::StopLossDecrease | CreateOrderKind::TrailingStopDecrease => {
+                    let Some(trigger_price) = params.effective_trigger_price(kind) else {
                         return Err(crate::Error::custom("[sim] trigger price is required"));
                     };
                     if is_long {
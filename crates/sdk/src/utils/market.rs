@@ -111,6 +111,21 @@ impl MarketDecimals {
             MarketConfigKey::MarketClosedMinCollateralFactorForLiquidation => MARKET_DECIMALS,
             MarketConfigKey::MarketClosedBorrowingFeeBaseFactor => MARKET_DECIMALS,
             MarketConfigKey::MarketClosedBorrowingFeeAboveOptimalUsageFactor => MARKET_DECIMALS,
+            MarketConfigKey::PartialLiquidationTargetCollateralFactor => MARKET_DECIMALS,
+            MarketConfigKey::CircuitBreakerMaxPriceChangeFactor => MARKET_DECIMALS,
+            MarketConfigKey::CircuitBreakerWindow => 0,
+            MarketConfigKey::MaxPositionSizeUsdForLong => MARKET_DECIMALS,
+            MarketConfigKey::MaxPositionSizeUsdForShort => MARKET_DECIMALS,
+            MarketConfigKey::SizeTier1MinPositionSizeUsd => MARKET_DECIMALS,
+            MarketConfigKey::SizeTier1MinCollateralFactor => MARKET_DECIMALS,
+            MarketConfigKey::SizeTier2MinPositionSizeUsd => MARKET_DECIMALS,
+            MarketConfigKey::SizeTier2MinCollateralFactor => MARKET_DECIMALS,
+            MarketConfigKey::SizeTier3MinPositionSizeUsd => MARKET_DECIMALS,
+            MarketConfigKey::SizeTier3MinCollateralFactor => MARKET_DECIMALS,
+            MarketConfigKey::SizeTier4MinPositionSizeUsd => MARKET_DECIMALS,
+            MarketConfigKey::SizeTier4MinCollateralFactor => MARKET_DECIMALS,
+            MarketConfigKey::PreCloseWindow => 0,
+            MarketConfigKey::GapToleranceFactor => MARKET_DECIMALS,
             key => {
                 return Err(crate::Error::custom(format!(
                     "the decimals of `{key}` is unknown"
@@ -1,5 +1,5 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     fmt,
     iter::{Peekable, Zip},
     slice::Iter,
@@ -113,6 +113,9 @@ impl FeedsParser {
     }
 
     /// Parse and sort by tokens.
+    ///
+    /// The feeds of the aggregated providers (the repeated tokens) are kept
+    /// after the feeds of all the tokens and sorted separately.
     pub fn parse_and_sort_by_tokens(
         &self,
         tokens_with_feed: &TokensWithFeed,
@@ -121,15 +124,21 @@ impl FeedsParser {
             .parse(tokens_with_feed)
             .collect::<crate::Result<Vec<_>>>()?;
 
-        let mut combined = tokens_with_feed
+        let mut seen = HashSet::new();
+        let (mut combined, mut aggregated): (Vec<_>, Vec<_>) = tokens_with_feed
             .tokens
             .iter()
             .zip(accounts)
-            .collect::<Vec<_>>();
+            .partition(|(token, _)| seen.insert(**token));
 
         combined.sort_by_key(|(key, _)| *key);
+        aggregated.sort_by_key(|(key, _)| *key);
 
-        Ok(combined.into_iter().map(|(_, account)| account).collect())
+        Ok(combined
+            .into_iter()
+            .chain(aggregated)
+            .map(|(_, account)| account)
+            .collect())
     }
 
     fn dispatch(&self, provider: &PriceProviderKind, feed: &Pubkey) -> crate::Result<AccountMeta> {
@@ -54,6 +54,12 @@ pub enum DomainDisabledFlag {
     ///
     /// An unset flag means the builder fee mechanism is enabled.
     BuilderFee = 15,
+    /// Trailing-stop Decrease Order.
+    TrailingStopDecrease = 16,
+    /// Stop Increase Order.
+    StopIncrease = 17,
+    /// Position settlement of a settled market.
+    Settlement = 18,
 }
 
 impl TryFrom<OrderKind> for DomainDisabledFlag {
@@ -70,6 +76,8 @@ impl TryFrom<OrderKind> for DomainDisabledFlag {
             OrderKind::LimitIncrease => Ok(Self::LimitIncrease),
             OrderKind::LimitDecrease => Ok(Self::LimitDecrease),
             OrderKind::StopLossDecrease => Ok(Self::StopLossDecrease),
+            OrderKind::TrailingStopDecrease => Ok(Self::TrailingStopDecrease),
+            OrderKind::StopIncrease => Ok(Self::StopIncrease),
         }
     }
 }
@@ -135,6 +143,19 @@ pub enum AmountKey {
     MinPositionAgeForManualClose,
     /// Maximum prices staleness allowed when the market is closed (in seconds).
     MarketClosedPricesMaxStaleness,
+    /// Delay after the end of its time window before a claimable account
+    /// of a user can be unlocked (in seconds).
+    /// This is a store config applied to all claimable accounts of users,
+    /// including the claimable collateral from capped price impact.
+    /// No delay is applied if zero.
+    ClaimableCollateralDelay,
+    /// Time an action must have been pending before anyone is allowed to execute it (in seconds).
+    /// Self-execution is disabled if zero. Must be less than
+    /// [`RequestExpiration`](Self::RequestExpiration), since the prices used by an
+    /// execution must be updated before the request expires.
+    SelfExecutionGracePeriod,
+    /// Max execution fee (in lamports) that can be claimed by a self-execution.
+    SelfExecutionMaxExecutionFee,
 }
 
 /// Factor keys.
@@ -324,6 +324,50 @@ pub enum MarketConfigKey {
     MarketClosedBorrowingFeeBaseFactor,
     /// Borrowing fee above optimal usage factor when market is closed.
     MarketClosedBorrowingFeeAboveOptimalUsageFactor,
+    /// Target collateral factor for partial liquidation.
+    /// Partial liquidation is disabled if zero.
+    PartialLiquidationTargetCollateralFactor,
+    /// Max price change factor of the circuit breaker.
+    /// The circuit breaker is disabled if zero.
+    CircuitBreakerMaxPriceChangeFactor,
+    /// The window (in seconds) of the circuit breaker.
+    CircuitBreakerWindow,
+    /// Max position size in USD per account for long.
+    /// The cap is applied to each position of the account separately.
+    /// No cap is applied if zero.
+    MaxPositionSizeUsdForLong,
+    /// Max position size in USD per account for short.
+    /// The cap is applied to each position of the account separately.
+    /// No cap is applied if zero.
+    MaxPositionSizeUsdForShort,
+    /// Min position size in USD of size tier 1.
+    /// The tier is disabled if zero.
+    SizeTier1MinPositionSizeUsd,
+    /// Min collateral factor of size tier 1.
+    SizeTier1MinCollateralFactor,
+    /// Min position size in USD of size tier 2.
+    /// The tier is disabled if zero.
+    SizeTier2MinPositionSizeUsd,
+    /// Min collateral factor of size tier 2.
+    SizeTier2MinCollateralFactor,
+    /// Min position size in USD of size tier 3.
+    /// The tier is disabled if zero.
+    SizeTier3MinPositionSizeUsd,
+    /// Min collateral factor of size tier 3.
+    SizeTier3MinCollateralFactor,
+    /// Min position size in USD of size tier 4.
+    /// The tier is disabled if zero.
+    SizeTier4MinPositionSizeUsd,
+    /// Min collateral factor of size tier 4.
+    SizeTier4MinCollateralFactor,
+    /// The window (in seconds) before the end of a trading session of the market
+    /// schedule, during which increase orders cannot be executed.
+    /// Disabled if zero.
+    PreCloseWindow,
+    /// Max gap between the index price and the trigger price of a limit or stop increase
+    /// order that has been pending across the close of the market schedule, as a factor
+    /// of the trigger price. The check is disabled if zero.
+    GapToleranceFactor,
 }
 
 /// Market Config Factors.
@@ -376,9 +420,39 @@ pub enum MarketFlag {
     GTEnabled,
     /// Indicates whether the market is closed.
     Closed,
+    /// Indicates whether the circuit breaker has been tripped.
+    CircuitBreakerTripped,
     // CHECK: cannot have more than `MAX_MARKET_FLAGS` flags.
 }
 
+/// Market Lifecycle.
+#[derive(
+    Clone,
+    Copy,
+    num_enum::IntoPrimitive,
+    num_enum::TryFromPrimitive,
+    PartialEq,
+    Eq,
+    strum::EnumString,
+    strum::Display,
+)]
+#[strum(serialize_all = "snake_case")]
+#[cfg_attr(feature = "debug", derive(Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[non_exhaustive]
+#[repr(u8)]
+pub enum MarketLifecycle {
+    /// The market is active.
+    Active,
+    /// The market is being settled. Increases and deposits are disabled,
+    /// and the settlement price has been frozen.
+    Settling,
+    /// The market has been settled. Remaining positions can be force-closed
+    /// at the settlement price.
+    Settled,
+}
+
 /// Virtual Inventory Flags.
 #[derive(num_enum::IntoPrimitive)]
 #[repr(u8)]
@@ -50,6 +50,8 @@ pub enum PriceProviderKind {
     Chainlink = 2,
     /// Switchboard On-Demand (V3) Data Feed.
     Switchboard = 3,
+    /// Prices signed off-chain by allow-listed publishers.
+    SignedPublisher = 4,
 }
 
 /// Convert pyth price value with confidence to [`Price`].
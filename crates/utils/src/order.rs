@@ -44,6 +44,16 @@ pub enum OrderKind {
     LimitDecrease,
     /// Stop-Loss Decrease.
     StopLossDecrease,
+    /// Trailing-Stop Decrease.
+    ///
+    /// The trigger price follows the best price seen since creation (the mark) at a
+    /// fixed trail distance, and the order fires once the price retraces by that distance.
+    TrailingStopDecrease,
+    /// Stop Increase.
+    ///
+    /// Increase position once the price crosses the trigger price in the adverse direction
+    /// (above for long, below for short), the mirror of [`LimitIncrease`](Self::LimitIncrease).
+    StopIncrease,
 }
 
 impl OrderKind {
@@ -62,7 +72,10 @@ impl OrderKind {
 
     /// Is increase position order.
     pub fn is_increase_position(&self) -> bool {
-        matches!(self, Self::LimitIncrease | Self::MarketIncrease)
+        matches!(
+            self,
+            Self::LimitIncrease | Self::MarketIncrease | Self::StopIncrease
+        )
     }
 
     /// Is decrease position order.
@@ -74,6 +87,7 @@ impl OrderKind {
                 | Self::Liquidation
                 | Self::AutoDeleveraging
                 | Self::StopLossDecrease
+                | Self::TrailingStopDecrease
         )
     }
 
@@ -81,6 +95,19 @@ impl OrderKind {
     pub fn is_market_decrease(&self) -> bool {
         matches!(self, Self::MarketDecrease)
     }
+
+    /// Is limit or stop order, i.e., an order waiting for its trigger condition.
+    pub fn is_trigger(&self) -> bool {
+        matches!(
+            self,
+            Self::LimitSwap
+                | Self::LimitIncrease
+                | Self::StopIncrease
+                | Self::LimitDecrease
+                | Self::StopLossDecrease
+                | Self::TrailingStopDecrease
+        )
+    }
 }
 
 /// Order side.
@@ -144,6 +171,8 @@ pub enum PositionCutKind {
     Liquidate,
     /// AutoDeleverage.
     AutoDeleverage(u128),
+    /// Settle the position of a settled market.
+    Settle,
 }
 
 impl PositionCutKind {
@@ -152,6 +181,7 @@ impl PositionCutKind {
         match self {
             Self::Liquidate => size_in_usd,
             Self::AutoDeleverage(delta) => size_in_usd.min(*delta),
+            Self::Settle => size_in_usd,
         }
     }
 
@@ -159,7 +189,7 @@ impl PositionCutKind {
     pub fn to_order_kind(&self) -> OrderKind {
         match self {
             Self::Liquidate => OrderKind::Liquidation,
-            Self::AutoDeleverage(_) => OrderKind::AutoDeleveraging,
+            Self::AutoDeleverage(_) | Self::Settle => OrderKind::AutoDeleveraging,
         }
     }
 }
@@ -187,5 +217,13 @@ crate::flags!(TradeFlag, 8, u8);
 pub enum OrderFlag {
     /// Whether to keep position account when empty.
     ShouldKeepPositionAccount,
+    /// Whether the trail distance of a trailing-stop order is a factor of the mark price.
+    IsTrailDistanceFactor,
+    /// Whether the order is the parent of linked orders that have not been resolved.
+    HasPendingLinkedOrders,
+    /// Whether the order is a linked order waiting for its parent to be executed.
+    IsPendingActivation,
+    /// Whether the order is counted in the number of orders of its position.
+    IsCountedByPosition,
     // CHECK: should have no more than `MAX_ORDER_FLAGS` of flags.
 }
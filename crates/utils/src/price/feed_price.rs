@@ -56,6 +56,11 @@ impl PriceFeedPrice {
         self.flags.set_flag(flag, value)
     }
 
+    /// Get decimals.
+    pub fn decimals(&self) -> u8 {
+        self.decimals
+    }
+
     /// Get ts.
     pub fn ts(&self) -> i64 {
         self.ts
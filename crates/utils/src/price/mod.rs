@@ -7,10 +7,14 @@ pub mod feed_price;
 /// Market status.
 pub mod market_status;
 
+/// Signed Publisher Price.
+pub mod publisher;
+
 pub use self::{
     decimal::{Decimal, DecimalError},
     feed_price::PriceFeedPrice,
     market_status::{MarketOpenness, MarketStatus, MarketStatusFlag, MarketStatusFlagContainer},
+    publisher::PublisherPrice,
 };
 use anchor_lang::prelude::*;
 
@@ -96,6 +100,21 @@ pub fn convert_to_u128_storage(mut num: U192, decimals: u8) -> Option<(u128, u8)
     Some((num.try_into().unwrap(), decimals - divisor_decimals))
 }
 
+/// Returns the median of the given values, rounded down.
+///
+/// The mean of the two middle values is used when the number of values is even.
+/// Returns `None` if `values` is empty.
+pub fn median(values: &mut [u128]) -> Option<u128> {
+    values.sort_unstable();
+    let mid = values.len() / 2;
+    if values.len() % 2 == 1 {
+        Some(values[mid])
+    } else {
+        let (a, b) = (*values.get(mid.checked_sub(1)?)?, values[mid]);
+        Some(a / 2 + b / 2 + (a % 2 + b % 2) / 2)
+    }
+}
+
 /// Price Feed Flags.
 #[repr(u8)]
 #[non_exhaustive]
@@ -141,6 +160,15 @@ mod tests {
         assert_eq!(find_divisor_decimals(&U192::MAX), 20);
     }
 
+    #[test]
+    fn test_median() {
+        assert_eq!(median(&mut []), None);
+        assert_eq!(median(&mut [3]), Some(3));
+        assert_eq!(median(&mut [4, 1]), Some(2));
+        assert_eq!(median(&mut [5, 1, 3]), Some(3));
+        assert_eq!(median(&mut [u128::MAX, u128::MAX]), Some(u128::MAX));
+    }
+
     #[test]
     fn test_convert_to_u128_storage() {
         assert_eq!(
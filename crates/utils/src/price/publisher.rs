@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+
+use super::{median, PriceFeedPrice, PriceFlag};
+
+/// A price signed off-chain by a price publisher.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+#[cfg_attr(feature = "debug", derive(Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PublisherPrice {
+    /// The price feed account to update.
+    pub feed: Pubkey,
+    /// Decimals of the price.
+    pub decimals: u8,
+    /// The timestamp at which the price is observed.
+    pub ts: i64,
+    /// Price.
+    pub price: u128,
+}
+
+impl PublisherPrice {
+    /// The domain prefix of the signed message.
+    pub const DOMAIN: &'static [u8] = b"gmsol-store:publisher-price:v1";
+
+    /// Get the message to sign.
+    pub fn to_message(&self) -> std::io::Result<Vec<u8>> {
+        let mut message = Self::DOMAIN.to_vec();
+        self.serialize(&mut message)?;
+        Ok(message)
+    }
+
+    /// Parse from a signed message.
+    ///
+    /// Returns `None` if the message is not a valid publisher price message.
+    pub fn from_message(message: &[u8]) -> Option<Self> {
+        let data = message.strip_prefix(Self::DOMAIN)?;
+        Self::try_from_slice(data).ok()
+    }
+}
+
+/// Aggregate the prices of publishers into a [`PriceFeedPrice`].
+///
+/// The median of the prices is used as the price. The min and max prices are the
+/// `k`-th lowest and highest prices, where `k = (quorum - 1) / 2` is the number of
+/// publishers of a quorum that can be outliers without moving the median, so that
+/// they cannot widen the band either. The timestamp is the oldest one, so that the
+/// aggregated price is never newer than any of its components.
+///
+/// Returns `None` if `prices` is empty or the decimals of the prices do not match.
+pub fn aggregate_publisher_prices(prices: &[PublisherPrice], quorum: u8) -> Option<PriceFeedPrice> {
+    let decimals = prices.first()?.decimals;
+    if prices.iter().any(|price| price.decimals != decimals) {
+        return None;
+    }
+    let ts = prices.iter().map(|price| price.ts).min()?;
+    let mut values = prices.iter().map(|price| price.price).collect::<Vec<_>>();
+    let price = median(&mut values)?;
+    // `values` is sorted by `median`.
+    let trimmed = usize::from(quorum.saturating_sub(1) / 2).min((values.len() - 1) / 2);
+    let min_price = values[trimmed];
+    let max_price = values[values.len() - 1 - trimmed];
+    let mut feed_price = PriceFeedPrice::new(decimals, ts, price, min_price, max_price, 0);
+    feed_price.set_flag(PriceFlag::Open, true);
+    Some(feed_price)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn price(ts: i64, price: u128) -> PublisherPrice {
+        PublisherPrice {
+            feed: Pubkey::default(),
+            decimals: 8,
+            ts,
+            price,
+        }
+    }
+
+    #[test]
+    fn message_round_trip() {
+        let price = price(1, 100);
+        let message = price.to_message().unwrap();
+        let parsed = PublisherPrice::from_message(&message).unwrap();
+        assert_eq!(parsed.ts, price.ts);
+        assert_eq!(parsed.price, price.price);
+        assert!(PublisherPrice::from_message(&message[1..]).is_none());
+        assert!(PublisherPrice::from_message(&message[..message.len() - 1]).is_none());
+    }
+
+    #[test]
+    fn aggregate() {
+        let aggregated =
+            aggregate_publisher_prices(&[price(3, 120), price(1, 100), price(2, 110)], 2).unwrap();
+        assert_eq!(aggregated.ts(), 1);
+        assert_eq!(*aggregated.price(), 110);
+        assert_eq!(*aggregated.min_price(), 100);
+        assert_eq!(*aggregated.max_price(), 120);
+
+        let mut other = price(1, 100);
+        other.decimals = 6;
+        assert!(aggregate_publisher_prices(&[price(1, 100), other], 2).is_none());
+        assert!(aggregate_publisher_prices(&[], 1).is_none());
+    }
+
+    #[test]
+    fn aggregate_ignores_outlier() {
+        let prices = [
+            price(1, 100),
+            price(1, 101),
+            price(1, 102),
+            price(1, 103),
+            price(1, 1_000_000),
+        ];
+        let aggregated = aggregate_publisher_prices(&prices, 3).unwrap();
+        assert_eq!(*aggregated.price(), 102);
+        assert_eq!(*aggregated.min_price(), 101);
+        assert_eq!(*aggregated.max_price(), 103);
+
+        // The band is not widened by an outlier at the quorum either.
+        let aggregated = aggregate_publisher_prices(&prices[2..], 3).unwrap();
+        assert_eq!(*aggregated.min_price(), 103);
+        assert_eq!(*aggregated.max_price(), 103);
+    }
+}
@@ -43,6 +43,9 @@ impl RoleKey {
 
     /// Addresses with this role can update the restricted list's market config.
     pub const MARKET_CONFIG_KEEPER: &'static str = "MARKET_CONFIG_KEEPER";
+
+    /// Addresses with this role are exempt from the per-account max position size caps.
+    pub const MARKET_MAKER: &'static str = "MARKET_MAKER";
 }
 
 impl Borrow<str> for RoleKey {
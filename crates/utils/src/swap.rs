@@ -125,12 +125,9 @@ impl SwapActionParams {
         })
     }
 
-    /// Convert to tokens with feed.
+    /// Convert to tokens with feed, including the feeds of the aggregated providers.
     pub fn to_feeds(&self, map: &impl TokenMapAccess) -> TokenConfigResult<TokensWithFeed> {
-        let records = self
-            .to_token_records(map)
-            .collect::<TokenConfigResult<Vec<_>>>()?;
-        TokensWithFeed::try_from_records(records)
+        TokensWithFeed::try_from_token_map(map, self.tokens())
     }
 
     /// Iterate over both swap paths, primary path first then secondary path.
@@ -25,9 +25,35 @@ pub const DEFAULT_TIMESTAMP_ADJUSTMENT: u32 = 0;
 pub const DEFAULT_MAX_DEVIATION_RATIO: u32 = 0;
 
 const MAX_FEEDS: usize = 4;
+/// Max number of providers whose prices are aggregated for a token,
+/// including the expected provider.
+pub const MAX_AGGREGATED_PROVIDERS: usize = 3;
 const MAX_FLAGS: usize = 8;
 const MAX_NAME_LEN: usize = 32;
 
+/// Returns the index of the feed slot of the given provider.
+///
+/// The [`SignedPublisher`](PriceProviderKind::SignedPublisher) provider reuses the
+/// slot of the deprecated `Chainlink` provider, so the layout of [`TokenConfig`]
+/// is kept unchanged.
+#[allow(deprecated)]
+fn feed_index(kind: &PriceProviderKind) -> usize {
+    match kind {
+        PriceProviderKind::SignedPublisher => PriceProviderKind::Chainlink as usize,
+        kind => *kind as usize,
+    }
+}
+
+/// Returns the other provider sharing the feed slot with the given provider, if any.
+#[allow(deprecated)]
+fn shared_slot_peer(kind: &PriceProviderKind) -> Option<PriceProviderKind> {
+    match kind {
+        PriceProviderKind::SignedPublisher => Some(PriceProviderKind::Chainlink),
+        PriceProviderKind::Chainlink => Some(PriceProviderKind::SignedPublisher),
+        _ => None,
+    }
+}
+
 /// Token config error.
 #[derive(Debug, thiserror::Error)]
 #[non_exhaustive]
@@ -50,6 +76,15 @@ pub enum TokenConfigError {
     /// Max deviation factor too small.
     #[error("max deviation factor too small")]
     MaxDeviationFactorTooSmall,
+    /// Spread factor too small.
+    #[error("spread factor too small")]
+    SpreadFactorTooSmall,
+    /// Invalid price aggregation config.
+    #[error("invalid price aggregation config: {0}")]
+    InvalidPriceAggregation(&'static str),
+    /// The feed slot is shared with another provider in use.
+    #[error("the feed slot is shared with another provider in use")]
+    SharedFeedSlotInUse,
 }
 
 pub(crate) type TokenConfigResult<T> = std::result::Result<T, TokenConfigError>;
@@ -91,8 +126,16 @@ pub struct TokenConfig {
     pub feeds: [FeedConfig; MAX_FEEDS],
     /// Heartbeat duration.
     pub heartbeat_duration: u32,
+    /// The maximum allowed spread ratio between the prices of the aggregated providers.
+    max_provider_spread_ratio: u32,
+    /// The minimum spread ratio between the max and min prices, relative to the mid price.
+    min_spread_ratio: u32,
+    /// The ratio by which the spread reported by the provider is multiplied.
+    spread_multiplier_ratio: u32,
+    /// The bitmap of the providers whose prices are aggregated with the expected provider's.
+    aggregated_providers: u8,
     #[cfg_attr(feature = "debug", debug(skip))]
-    reserved: [u8; 32],
+    reserved: [u8; 19],
 }
 
 #[cfg(feature = "display")]
@@ -111,6 +154,24 @@ impl std::fmt::Display for TokenConfig {
                 .map(|kind| kind.to_string())
                 .unwrap_or("*unknown*".to_string())
         )?;
+        if self.is_price_aggregation_enabled() {
+            let providers = self
+                .aggregated_providers()
+                .map(|kind| kind.to_string())
+                .collect::<Vec<_>>();
+            writeln!(f, "Aggregated Providers: {}", providers.join(", "))?;
+            writeln!(
+                f,
+                "Max Provider Spread Factor: {}",
+                self.max_provider_spread_factor().unwrap_or_default()
+            )?;
+        }
+        if let Some(factor) = self.min_spread_factor() {
+            writeln!(f, "Min Spread Factor: {factor}")?;
+        }
+        if let Some(factor) = self.spread_multiplier_factor() {
+            writeln!(f, "Spread Multiplier Factor: {factor}")?;
+        }
         Ok(())
     }
 }
@@ -118,7 +179,7 @@ impl std::fmt::Display for TokenConfig {
 impl TokenConfig {
     /// Get the corresponding price feed config.
     pub fn get_feed_config(&self, kind: &PriceProviderKind) -> TokenConfigResult<&FeedConfig> {
-        let index = *kind as usize;
+        let index = feed_index(kind);
         let config = self.feeds.get(index).ok_or(TokenConfigError::NotFound)?;
         if config.feed == DEFAULT_PUBKEY {
             Err(TokenConfigError::NotFound)
@@ -132,7 +193,8 @@ impl TokenConfig {
         &mut self,
         kind: &PriceProviderKind,
     ) -> TokenConfigResult<&mut FeedConfig> {
-        let index = *kind as usize;
+        self.validate_shared_feed_slot_available(kind)?;
+        let index = feed_index(kind);
         let config = self
             .feeds
             .get_mut(index)
@@ -153,7 +215,8 @@ impl TokenConfig {
         kind: &PriceProviderKind,
         new_config: FeedConfig,
     ) -> TokenConfigResult<()> {
-        let index = *kind as usize;
+        self.validate_shared_feed_slot_available(kind)?;
+        let index = feed_index(kind);
         let config = self
             .feeds
             .get_mut(index)
@@ -162,6 +225,35 @@ impl TokenConfig {
         Ok(())
     }
 
+    /// Returns an error if the feed slot of the given provider is shared with
+    /// another provider that is in use.
+    fn validate_shared_feed_slot_available(
+        &self,
+        kind: &PriceProviderKind,
+    ) -> TokenConfigResult<()> {
+        match shared_slot_peer(kind) {
+            Some(peer) if self.is_provider_used(&peer) => {
+                Err(TokenConfigError::SharedFeedSlotInUse)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Validate that the providers sharing a feed slot are not both in use.
+    ///
+    /// The [`SignedPublisher`](PriceProviderKind::SignedPublisher) provider shares its
+    /// feed slot with the deprecated `Chainlink` provider, so at most one of them can
+    /// be the expected provider or aggregated.
+    #[allow(deprecated)]
+    pub fn validate_shared_feed_slot(&self) -> TokenConfigResult<()> {
+        if self.is_provider_used(&PriceProviderKind::Chainlink)
+            && self.is_provider_used(&PriceProviderKind::SignedPublisher)
+        {
+            return Err(TokenConfigError::SharedFeedSlotInUse);
+        }
+        Ok(())
+    }
+
     /// Get the corresponding price feed address.
     pub fn get_feed(&self, kind: &PriceProviderKind) -> TokenConfigResult<Pubkey> {
         Ok(self.get_feed_config(kind)?.feed)
@@ -255,12 +347,149 @@ impl TokenConfig {
         self.heartbeat_duration
     }
 
+    /// Returns whether the prices of multiple providers are aggregated for this token.
+    pub fn is_price_aggregation_enabled(&self) -> bool {
+        self.aggregated_providers().next().is_some()
+    }
+
+    /// Returns an iterator over the providers whose prices are aggregated with
+    /// the price of the expected provider, in the order of provider index.
+    pub fn aggregated_providers(&self) -> impl Iterator<Item = PriceProviderKind> + '_ {
+        (0..u8::BITS as u8)
+            .filter(|index| {
+                *index != self.expected_provider && self.aggregated_providers & (1 << index) != 0
+            })
+            .filter_map(|index| PriceProviderKind::try_from(index).ok())
+    }
+
+    /// Returns whether the prices of the given provider are used for this token.
+    pub fn is_provider_used(&self, kind: &PriceProviderKind) -> bool {
+        self.expected_provider == *kind as u8 || self.aggregated_providers().any(|p| p == *kind)
+    }
+
+    /// Get the max spread factor between the prices of the aggregated providers.
+    pub fn max_provider_spread_factor(&self) -> Option<u128> {
+        let ratio = self.max_provider_spread_ratio;
+        if ratio == 0 {
+            None
+        } else {
+            Some(u128::from(ratio) * FeedConfig::RATIO_MULTIPLIER)
+        }
+    }
+
+    /// Set the providers whose prices are aggregated with the price of the
+    /// expected provider, and the max spread factor between their prices.
+    ///
+    /// Passing empty `providers` disables price aggregation.
+    pub fn set_price_aggregation(
+        &mut self,
+        providers: &[PriceProviderKind],
+        max_spread_factor: u128,
+    ) -> TokenConfigResult<()> {
+        if providers.is_empty() {
+            self.aggregated_providers = 0;
+            self.max_provider_spread_ratio = 0;
+            return Ok(());
+        }
+        if providers.len() >= MAX_AGGREGATED_PROVIDERS {
+            return Err(TokenConfigError::InvalidPriceAggregation(
+                "too many providers",
+            ));
+        }
+        let expected_provider = self.expected_provider()?;
+        let mut bitmap = 0u8;
+        for provider in providers {
+            if *provider == expected_provider {
+                return Err(TokenConfigError::InvalidPriceAggregation(
+                    "the expected provider cannot be aggregated",
+                ));
+            }
+            self.get_feed_config(provider)?;
+            let bit = 1 << (*provider as u8);
+            if bitmap & bit != 0 {
+                return Err(TokenConfigError::InvalidPriceAggregation(
+                    "duplicated providers",
+                ));
+            }
+            bitmap |= bit;
+        }
+        // The providers sharing a feed slot cannot be used together.
+        #[allow(deprecated)]
+        let shared = [
+            PriceProviderKind::Chainlink,
+            PriceProviderKind::SignedPublisher,
+        ];
+        if shared
+            .iter()
+            .all(|kind| *kind == expected_provider || bitmap & (1 << (*kind as u8)) != 0)
+        {
+            return Err(TokenConfigError::SharedFeedSlotInUse);
+        }
+        let ratio = (max_spread_factor / FeedConfig::RATIO_MULTIPLIER)
+            .try_into()
+            .map_err(|_| TokenConfigError::ExceedMaxRatio)?;
+        if ratio == 0 {
+            return Err(TokenConfigError::MaxDeviationFactorTooSmall);
+        }
+        self.aggregated_providers = bitmap;
+        self.max_provider_spread_ratio = ratio;
+        Ok(())
+    }
+
+    /// Get the min spread factor between the max and min prices, relative to the mid price.
+    pub fn min_spread_factor(&self) -> Option<u128> {
+        ratio_to_factor(self.min_spread_ratio)
+    }
+
+    /// Get the factor by which the spread reported by the provider is multiplied.
+    pub fn spread_multiplier_factor(&self) -> Option<u128> {
+        ratio_to_factor(self.spread_multiplier_ratio)
+    }
+
+    /// Returns whether the spread of the prices is widened for this token.
+    pub fn is_price_spread_enabled(&self) -> bool {
+        self.min_spread_ratio != 0 || self.spread_multiplier_ratio != 0
+    }
+
+    /// Set the min spread factor and the spread multiplier factor.
+    ///
+    /// Passing `0` disables the corresponding widening.
+    pub fn set_price_spread(
+        &mut self,
+        min_spread_factor: u128,
+        spread_multiplier_factor: u128,
+    ) -> TokenConfigResult<()> {
+        let min_spread_ratio = spread_factor_to_ratio(min_spread_factor)?;
+        let spread_multiplier_ratio = spread_factor_to_ratio(spread_multiplier_factor)?;
+        self.min_spread_ratio = min_spread_ratio;
+        self.spread_multiplier_ratio = spread_multiplier_ratio;
+        Ok(())
+    }
+
     /// Get token name.
     pub fn name(&self) -> TokenConfigResult<&str> {
         Ok(bytes_to_fixed_str(&self.name)?)
     }
 }
 
+/// Convert a non-zero spread factor into a ratio, where `0` stays `0`.
+fn spread_factor_to_ratio(factor: u128) -> TokenConfigResult<u32> {
+    if factor == 0 {
+        return Ok(0);
+    }
+    let ratio = (factor / FeedConfig::RATIO_MULTIPLIER)
+        .try_into()
+        .map_err(|_| TokenConfigError::ExceedMaxRatio)?;
+    if ratio == 0 {
+        return Err(TokenConfigError::SpreadFactorTooSmall);
+    }
+    Ok(ratio)
+}
+
+fn ratio_to_factor(ratio: u32) -> Option<u128> {
+    (ratio != 0).then(|| u128::from(ratio) * FeedConfig::RATIO_MULTIPLIER)
+}
+
 impl crate::InitSpace for TokenConfig {
     const INIT_SPACE: usize = std::mem::size_of::<Self>();
 }
@@ -431,7 +660,7 @@ impl UpdateTokenConfigParams {
         new_feed: Pubkey,
         new_timestamp_adjustment: Option<u32>,
     ) -> TokenConfigResult<Self> {
-        let index = *kind as usize;
+        let index = feed_index(kind);
         let feed = self
             .feeds
             .get_mut(index)
@@ -482,6 +711,17 @@ pub trait TokenMapAccess {
         Some([index_token, long_token, short_token])
     }
 
+    /// Returns the number of price feed accounts required for the given tokens,
+    /// including the feeds of the aggregated providers.
+    ///
+    /// Returns `None` if any of the tokens is not found.
+    fn num_feed_accounts(&self, tokens: &[Pubkey]) -> Option<usize> {
+        tokens.iter().try_fold(0usize, |num, token| {
+            let config = self.get(token)?;
+            num.checked_add(1 + config.aggregated_providers().count())
+        })
+    }
+
     /// Sort tokens by provider. This sort is stable.
     fn sort_tokens_by_provider(&self, tokens: &mut [Pubkey]) -> Result<()> {
         // Check the existence of token configs.
@@ -541,6 +781,16 @@ impl TokenRecord {
         ))
     }
 
+    /// Create the [`TokenRecord`]s of the aggregated providers from token config.
+    pub fn aggregated_from_config(
+        token: Pubkey,
+        config: &TokenConfig,
+    ) -> impl Iterator<Item = TokenConfigResult<Self>> + '_ {
+        config
+            .aggregated_providers()
+            .map(move |provider| Ok(Self::new(token, config.get_feed(&provider)?, provider)))
+    }
+
     /// Get token address.
     pub fn token(&self) -> &Pubkey {
         &self.token
@@ -561,10 +811,90 @@ impl TokenRecord {
 }
 
 impl TokensWithFeed {
+    /// Append the feeds of the aggregated providers of the tokens.
+    ///
+    /// The feeds are appended after the feeds of the expected providers, ordered by
+    /// token and then by provider index, which is the layout expected by the oracle.
+    /// This should be called at most once.
+    fn with_aggregated_feeds(mut self, token_map: &impl TokenMapAccess) -> TokenConfigResult<Self> {
+        let records = self
+            .tokens
+            .iter()
+            .map(|token| {
+                let config = token_map.get(token).ok_or(TokenConfigError::NotFound)?;
+                Ok(TokenRecord::aggregated_from_config(*token, config))
+            })
+            .collect::<TokenConfigResult<Vec<_>>>()?
+            .into_iter()
+            .flatten()
+            .collect::<TokenConfigResult<Vec<_>>>()?;
+        self.push_aggregated_records(records)?;
+        Ok(self)
+    }
+
+    fn records(&self) -> impl Iterator<Item = TokenRecord> + '_ {
+        self.providers
+            .iter()
+            .zip(&self.nums)
+            .flat_map(|(provider, num)| std::iter::repeat_n(*provider, usize::from(*num)))
+            .zip(self.tokens.iter().zip(&self.feeds))
+            .map(|(provider, (token, feed))| TokenRecord {
+                token: *token,
+                feed: *feed,
+                provider,
+            })
+    }
+
+    fn push_aggregated_records(&mut self, records: Vec<TokenRecord>) -> TokenConfigResult<()> {
+        for record in records {
+            match (self.providers.last(), self.nums.last_mut()) {
+                (Some(provider), Some(num)) if *provider == record.provider => {
+                    *num = num
+                        .checked_add(1)
+                        .ok_or(TokenConfigError::ExceedMaxLengthLimit)?;
+                }
+                _ => {
+                    self.providers.push(record.provider);
+                    self.nums.push(1);
+                }
+            }
+            self.tokens.push(record.token);
+            self.feeds.push(record.feed);
+        }
+        Ok(())
+    }
+
+    /// Create from the given tokens with the feeds configured in the token map,
+    /// including the feeds of their aggregated providers.
+    ///
+    /// This should be used whenever the feeds are built from a token map, so that
+    /// the feeds of the aggregated providers are never left out.
+    pub fn try_from_token_map<'a>(
+        token_map: &impl TokenMapAccess,
+        tokens: impl IntoIterator<Item = &'a Pubkey>,
+    ) -> TokenConfigResult<Self> {
+        let records = tokens
+            .into_iter()
+            .map(|token| {
+                let config = token_map.get(token).ok_or(TokenConfigError::NotFound)?;
+                TokenRecord::from_config(*token, config)
+            })
+            .collect::<TokenConfigResult<Vec<_>>>()?;
+        Self::try_from_records(records)?.with_aggregated_feeds(token_map)
+    }
+
     /// Create from token records.
+    ///
+    /// Only the first record of each token is used for its expected provider, the
+    /// following ones are treated as the feeds of its aggregated providers,
+    /// see [`try_from_token_map`](Self::try_from_token_map).
     /// # Panic
     /// Panics if the number of tokens of the same provider exceeds `u16`.
-    pub fn try_from_records(mut records: Vec<TokenRecord>) -> TokenConfigResult<Self> {
+    pub fn try_from_records(records: Vec<TokenRecord>) -> TokenConfigResult<Self> {
+        let mut seen = BTreeSet::new();
+        let (mut records, mut aggregated): (Vec<_>, Vec<_>) = records
+            .into_iter()
+            .partition(|record| seen.insert(record.token));
         records.sort_by_cached_key(|r| r.provider);
         let mut chunks = chunk_by(&records, |a, b| a.provider == b.provider);
         let capacity = chunks.size_hint().0;
@@ -577,27 +907,33 @@ impl TokensWithFeed {
             );
             TokenConfigResult::Ok(())
         })?;
-        Ok(Self {
+        let mut feeds = Self {
             tokens: records.iter().map(|r| r.token).collect(),
             feeds: records.iter().map(|r| r.feed).collect(),
             providers,
             nums,
-        })
+        };
+        aggregated.sort_by_cached_key(|r| {
+            (
+                feeds.tokens.iter().position(|token| *token == r.token),
+                r.provider,
+            )
+        });
+        feeds.push_aggregated_records(aggregated)?;
+        Ok(feeds)
     }
 }
 
-/// Collect token records for the give tokens.
+/// Collect token records for the give tokens,
+/// including the records of the aggregated providers.
+///
+/// The records are in the order of [`TokensWithFeed::try_from_token_map`].
 pub fn token_records<A: TokenMapAccess>(
     token_map: &A,
     tokens: &BTreeSet<Pubkey>,
 ) -> TokenConfigResult<Vec<TokenRecord>> {
-    tokens
-        .iter()
-        .map(|token| {
-            let config = token_map.get(token).ok_or(TokenConfigError::NotFound)?;
-            TokenRecord::from_config(*token, config)
-        })
-        .collect::<TokenConfigResult<Vec<_>>>()
+    let feeds = TokensWithFeed::try_from_token_map(token_map, tokens)?;
+    Ok(feeds.records().collect())
 }
 
 /// Tokens Collector.
@@ -646,17 +982,9 @@ impl TokensCollector {
         self.tokens.iter().copied().collect()
     }
 
-    /// Convert to [`TokensWithFeed`].
+    /// Convert to [`TokensWithFeed`], including the feeds of the aggregated providers.
     pub fn to_feeds(&self, token_map: &impl TokenMapAccess) -> TokenConfigResult<TokensWithFeed> {
-        let records = self
-            .tokens
-            .iter()
-            .map(|token| {
-                let config = token_map.get(token).ok_or(TokenConfigError::NotFound)?;
-                TokenRecord::from_config(*token, config)
-            })
-            .collect::<TokenConfigResult<Vec<_>>>()?;
-        TokensWithFeed::try_from_records(records)
+        TokensWithFeed::try_from_token_map(token_map, &self.tokens)
     }
 }
 
@@ -713,3 +1041,210 @@ mod market_status_tests {
         assert!(config.set_market_status_flag(MarketStatusFlag::AllowPreMarket, false));
     }
 }
+
+#[cfg(test)]
+mod price_aggregation_tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use bytemuck::Zeroable;
+
+    struct Map(HashMap<Pubkey, TokenConfig>);
+
+    impl TokenMapAccess for Map {
+        fn get(&self, token: &Pubkey) -> Option<&TokenConfig> {
+            self.0.get(token)
+        }
+    }
+
+    fn config() -> TokenConfig {
+        let mut config = TokenConfig::zeroed();
+        for kind in [
+            PriceProviderKind::ChainlinkDataStreams,
+            PriceProviderKind::Pyth,
+            PriceProviderKind::Switchboard,
+        ] {
+            config
+                .set_feed_config(&kind, FeedConfig::new(Pubkey::new_unique()))
+                .unwrap();
+        }
+        config
+    }
+
+    #[test]
+    fn set_price_aggregation() {
+        let factor = 10u128.pow(18);
+        let mut config = config();
+        assert!(!config.is_price_aggregation_enabled());
+        assert!(config
+            .set_price_aggregation(&[PriceProviderKind::ChainlinkDataStreams], factor)
+            .is_err());
+        assert!(config
+            .set_price_aggregation(&[PriceProviderKind::Pyth, PriceProviderKind::Pyth], factor)
+            .is_err());
+        assert!(config
+            .set_price_aggregation(&[PriceProviderKind::Pyth], 0)
+            .is_err());
+
+        config
+            .set_price_aggregation(
+                &[PriceProviderKind::Switchboard, PriceProviderKind::Pyth],
+                factor,
+            )
+            .unwrap();
+        assert!(config
+            .aggregated_providers()
+            .eq([PriceProviderKind::Pyth, PriceProviderKind::Switchboard]));
+        assert!(config.is_provider_used(&PriceProviderKind::Switchboard));
+        assert_eq!(config.max_provider_spread_factor(), Some(factor));
+
+        config.set_price_aggregation(&[], 0).unwrap();
+        assert!(!config.is_price_aggregation_enabled());
+    }
+
+    #[test]
+    fn set_price_spread() {
+        let mut config = config();
+        assert!(!config.is_price_spread_enabled());
+        assert!(matches!(
+            config.set_price_spread(1, 0),
+            Err(TokenConfigError::SpreadFactorTooSmall)
+        ));
+        assert!(config.set_price_spread(0, u128::MAX).is_err());
+        assert!(!config.is_price_spread_enabled());
+
+        config
+            .set_price_spread(10u128.pow(17), 2 * 10u128.pow(20))
+            .unwrap();
+        assert_eq!(config.min_spread_factor(), Some(10u128.pow(17)));
+        assert_eq!(config.spread_multiplier_factor(), Some(2 * 10u128.pow(20)));
+
+        config.set_price_spread(0, 0).unwrap();
+        assert!(!config.is_price_spread_enabled());
+        assert_eq!(config.min_spread_factor(), None);
+    }
+
+    #[test]
+    fn try_from_token_map() {
+        let [a, b] = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let mut config_a = config();
+        config_a
+            .set_price_aggregation(
+                &[PriceProviderKind::Pyth, PriceProviderKind::Switchboard],
+                10u128.pow(18),
+            )
+            .unwrap();
+        let config_b = config();
+        let map = Map(HashMap::from([(a, config_a), (b, config_b)]));
+
+        let tokens = [b, a];
+        assert_eq!(map.num_feed_accounts(&tokens), Some(4));
+
+        let feeds = TokensWithFeed::try_from_token_map(&map, &tokens).unwrap();
+        assert_eq!(feeds.tokens, [b, a, a, a]);
+        assert_eq!(feeds.providers, [0, 1, 3]);
+        assert_eq!(feeds.nums, [2, 1, 1]);
+        assert_eq!(
+            feeds.feeds[3],
+            config_a.get_feed(&PriceProviderKind::Switchboard).unwrap()
+        );
+
+        // The records of repeated tokens are placed after the primary feeds.
+        let tokens = BTreeSet::from([a, b]);
+        let records = token_records(&map, &tokens).unwrap();
+        let from_records = TokensWithFeed::try_from_records(records).unwrap();
+        let expected = TokensWithFeed::try_from_token_map(&map, &tokens).unwrap();
+        assert_eq!(from_records.tokens, expected.tokens);
+        assert_eq!(from_records.feeds, expected.feeds);
+        assert_eq!(from_records.providers, expected.providers);
+        assert_eq!(from_records.nums, expected.nums);
+    }
+}
+
+#[cfg(test)]
+mod layout_tests {
+    use super::*;
+
+    // The `TokenConfig` byte layout. These must never change.
+    const EXPECTED_TOKEN_CONFIG_SIZE: usize = 328;
+    const FEEDS_OFFSET: usize = 36;
+    const HEARTBEAT_DURATION_OFFSET: usize = 292;
+    const MAX_PROVIDER_SPREAD_RATIO_OFFSET: usize = 296;
+    const AGGREGATED_PROVIDERS_OFFSET: usize = 308;
+
+    #[test]
+    fn token_config_layout() {
+        assert_eq!(
+            std::mem::size_of::<TokenConfig>(),
+            EXPECTED_TOKEN_CONFIG_SIZE
+        );
+        assert_eq!(std::mem::offset_of!(TokenConfig, feeds), FEEDS_OFFSET);
+        assert_eq!(
+            std::mem::offset_of!(TokenConfig, heartbeat_duration),
+            HEARTBEAT_DURATION_OFFSET
+        );
+        assert_eq!(
+            std::mem::offset_of!(TokenConfig, max_provider_spread_ratio),
+            MAX_PROVIDER_SPREAD_RATIO_OFFSET
+        );
+        assert_eq!(
+            std::mem::offset_of!(TokenConfig, aggregated_providers),
+            AGGREGATED_PROVIDERS_OFFSET
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn signed_publisher_uses_chainlink_slot() {
+        let mut config: TokenConfig = bytemuck::Zeroable::zeroed();
+        let feed = Pubkey::new_unique();
+        config
+            .set_feed_config(&PriceProviderKind::SignedPublisher, FeedConfig::new(feed))
+            .unwrap();
+        assert_eq!(
+            config.feeds[PriceProviderKind::Chainlink as usize].feed,
+            feed
+        );
+        assert_eq!(
+            config
+                .get_feed(&PriceProviderKind::SignedPublisher)
+                .unwrap(),
+            feed
+        );
+
+        config.set_expected_provider(PriceProviderKind::Pyth);
+        config
+            .set_feed_config(
+                &PriceProviderKind::Pyth,
+                FeedConfig::new(Pubkey::new_unique()),
+            )
+            .unwrap();
+        config
+            .set_price_aggregation(&[PriceProviderKind::SignedPublisher], 10u128.pow(18))
+            .unwrap();
+        assert!(config
+            .aggregated_providers()
+            .eq([PriceProviderKind::SignedPublisher]));
+
+        // The slot cannot be configured or used for `Chainlink` while in use.
+        assert!(matches!(
+            config.set_feed_config(
+                &PriceProviderKind::Chainlink,
+                FeedConfig::new(Pubkey::new_unique())
+            ),
+            Err(TokenConfigError::SharedFeedSlotInUse)
+        ));
+        assert!(config
+            .get_feed_config_mut(&PriceProviderKind::Chainlink)
+            .is_err());
+        config.set_expected_provider(PriceProviderKind::Chainlink);
+        assert!(config.validate_shared_feed_slot().is_err());
+
+        config.set_expected_provider(PriceProviderKind::SignedPublisher);
+        config.validate_shared_feed_slot().unwrap();
+        assert!(matches!(
+            config.set_price_aggregation(&[PriceProviderKind::Chainlink], 10u128.pow(18)),
+            Err(TokenConfigError::SharedFeedSlotInUse)
+        ));
+    }
+}
@@ -8,5 +8,7 @@ pub const MAX_USER_FLAGS: usize = 8;
 pub enum UserFlag {
     /// Is initialized.
     Initialized,
+    /// Has a user stats account.
+    HasStats,
     // CHECK: should have no more than `MAX_USER_FLAGS` of flags.
 }
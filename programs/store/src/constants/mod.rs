@@ -31,6 +31,10 @@ pub const GT_MINT_SEED: &[u8] = b"gt";
 #[constant]
 pub const CLAIMABLE_ACCOUNT_SEED: &[u8] = b"claimable_account";
 
+/// Order Intent Authority Seed.
+#[constant]
+pub const INTENT_AUTHORITY_SEED: &[u8] = b"intent_authority";
+
 /// Decimals of a market token.
 #[constant]
 pub const MARKET_TOKEN_DECIMALS: u8 = 9;
@@ -287,3 +287,26 @@ impl gmsol_utils::InitSpace for MarketTokenValue {
 }
 
 impl Event for MarketTokenValue {}
+
+/// Event emitted when the circuit breaker of a market is tripped.
+#[event]
+#[cfg_attr(feature = "debug", derive(Debug))]
+#[derive(Clone, InitSpace)]
+pub struct MarketCircuitBreakerTripped {
+    /// Market token.
+    pub market_token: Pubkey,
+    /// The reference price.
+    pub ref_price: u128,
+    /// The timestamp at which the reference price was set.
+    pub ref_ts: i64,
+    /// The price that tripped the circuit breaker.
+    pub price: u128,
+    /// The timestamp at which the circuit breaker was tripped.
+    pub ts: i64,
+}
+
+impl gmsol_utils::InitSpace for MarketCircuitBreakerTripped {
+    const INIT_SPACE: usize = <Self as Space>::INIT_SPACE;
+}
+
+impl Event for MarketCircuitBreakerTripped {}
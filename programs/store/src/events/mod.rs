@@ -31,12 +31,16 @@ mod user;
 /// Builder fee events.
 mod builder_fee;
 
+/// Position events.
+mod position;
+
 pub use builder_fee::*;
 pub use deposit::*;
 pub use glv::*;
 pub use gt::*;
 pub use market::*;
 pub use order::*;
+pub use position::*;
 pub use shift::*;
 pub use swap::*;
 pub use trade::*;
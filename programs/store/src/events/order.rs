@@ -376,3 +376,48 @@ impl InitSpace for OrderUpdated {
 }
 
 impl Event for OrderUpdated {}
+
+/// An event indicating that a linked order has been cancelled.
+#[event]
+#[cfg_attr(feature = "debug", derive(Debug))]
+#[derive(Clone, InitSpace)]
+pub struct LinkedOrderCancelled {
+    /// Timestamp.
+    pub ts: i64,
+    /// Slot.
+    pub slot: u64,
+    /// Store.
+    pub store: Pubkey,
+    /// The cancelled linked order.
+    pub order: Pubkey,
+    /// The parent order.
+    pub parent: Pubkey,
+    /// The sibling order that has been executed.
+    /// `None` if the linked order is cancelled because the parent was not executed.
+    pub executed_sibling: Option<Pubkey>,
+}
+
+impl LinkedOrderCancelled {
+    pub(crate) fn new(
+        store: &Pubkey,
+        order: &Pubkey,
+        parent: &Pubkey,
+        executed_sibling: Option<&Pubkey>,
+    ) -> Result<Self> {
+        let clock = Clock::get()?;
+        Ok(Self {
+            ts: clock.unix_timestamp,
+            slot: clock.slot,
+            store: *store,
+            order: *order,
+            parent: *parent,
+            executed_sibling: executed_sibling.copied(),
+        })
+    }
+}
+
+impl InitSpace for LinkedOrderCancelled {
+    const INIT_SPACE: usize = <Self as Space>::INIT_SPACE;
+}
+
+impl Event for LinkedOrderCancelled {}
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use borsh::BorshSerialize;
+
+use super::Event;
+
+/// Position collateral switched event.
+#[event]
+#[cfg_attr(feature = "debug", derive(derive_more::Debug))]
+#[derive(InitSpace)]
+pub struct PositionCollateralSwitched {
+    /// Revision.
+    pub rev: u64,
+    /// Market token.
+    pub market_token: Pubkey,
+    /// Owner.
+    pub owner: Pubkey,
+    /// The position that has been switched from.
+    pub position: Pubkey,
+    /// The position that has been switched to.
+    pub target: Pubkey,
+    /// Whether the position is long.
+    pub is_long: bool,
+    /// Whether the previous collateral token is the long token.
+    pub is_collateral_long: bool,
+    /// The collateral amount before the switch.
+    pub collateral_amount: u128,
+    /// The funding fee amount paid in the previous collateral token.
+    pub funding_fee_amount: u128,
+    /// The collateral amount after the switch.
+    pub next_collateral_amount: u128,
+    #[cfg_attr(feature = "debug", debug(skip))]
+    reserved: [u8; 64],
+}
+
+impl gmsol_utils::InitSpace for PositionCollateralSwitched {
+    const INIT_SPACE: usize = <Self as Space>::INIT_SPACE;
+}
+
+impl Event for PositionCollateralSwitched {}
+
+impl PositionCollateralSwitched {
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        rev: u64,
+        market_token: Pubkey,
+        owner: Pubkey,
+        position: Pubkey,
+        target: Pubkey,
+        is_long: bool,
+        is_collateral_long: bool,
+        collateral_amount: u128,
+        funding_fee_amount: u128,
+        next_collateral_amount: u128,
+    ) -> Self {
+        Self {
+            rev,
+            market_token,
+            owner,
+            position,
+            target,
+            is_long,
+            is_collateral_long,
+            collateral_amount,
+            funding_fee_amount,
+            next_collateral_amount,
+            reserved: [0; 64],
+        }
+    }
+}
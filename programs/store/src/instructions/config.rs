@@ -30,7 +30,9 @@ pub(crate) fn unchecked_insert_amount(
     key: &str,
     amount: Amount,
 ) -> Result<()> {
-    *ctx.accounts.store.load_mut()?.get_amount_mut(key)? = amount;
+    let mut store = ctx.accounts.store.load_mut()?;
+    *store.get_amount_mut(key)? = amount;
+    store.validate_self_execution_grace_period()?;
     Ok(())
 }
 
@@ -57,3 +59,15 @@ pub(crate) fn unchecked_insert_address(
     *ctx.accounts.store.load_mut()?.get_address_mut(key)? = address;
     Ok(())
 }
+
+/// CHECK: only MARKET_KEEPER is allowed to invoke.
+pub(crate) fn unchecked_set_volume_tiers(
+    ctx: Context<InsertConfig>,
+    thresholds: &[u128],
+    factors: &[u128],
+) -> Result<()> {
+    ctx.accounts
+        .store
+        .load_mut()?
+        .set_volume_tiers(thresholds, factors)
+}
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use gmsol_utils::InitSpace;
+
+use crate::states::{
+    delegation::{Delegation, DelegationParams},
+    Seed, Store,
+};
+
+/// The accounts definition for [`create_delegation`](crate::gmsol_store::create_delegation).
+#[derive(Accounts)]
+pub struct CreateDelegation<'info> {
+    /// Owner.
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    /// Store.
+    pub store: AccountLoader<'info, Store>,
+    /// The delegate.
+    /// CHECK: only the address is used.
+    pub delegate: UncheckedAccount<'info>,
+    /// The delegation account to create.
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Delegation::INIT_SPACE,
+        seeds = [
+            Delegation::SEED,
+            store.key().as_ref(),
+            owner.key().as_ref(),
+            delegate.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub delegation: AccountLoader<'info, Delegation>,
+    /// The system program.
+    pub system_program: Program<'info, System>,
+}
+
+impl CreateDelegation<'_> {
+    pub(crate) fn invoke(ctx: Context<Self>, params: &DelegationParams) -> Result<()> {
+        ctx.accounts.store.load()?.validate_not_restarted()?;
+        ctx.accounts.delegation.load_init()?.init(
+            ctx.bumps.delegation,
+            &ctx.accounts.store.key(),
+            ctx.accounts.owner.key,
+            ctx.accounts.delegate.key,
+            params,
+        )?;
+        msg!(
+            "[Delegation] {} is delegated by {}",
+            ctx.accounts.delegate.key,
+            ctx.accounts.owner.key
+        );
+        Ok(())
+    }
+}
+
+/// The accounts definition for [`revoke_delegation`](crate::gmsol_store::revoke_delegation).
+#[derive(Accounts)]
+pub struct RevokeDelegation<'info> {
+    /// Owner.
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    /// Store.
+    pub store: AccountLoader<'info, Store>,
+    /// The delegation account to revoke.
+    #[account(
+        mut,
+        close = owner,
+        has_one = store,
+        has_one = owner,
+    )]
+    pub delegation: AccountLoader<'info, Delegation>,
+}
+
+impl RevokeDelegation<'_> {
+    pub(crate) fn invoke(ctx: Context<Self>) -> Result<()> {
+        msg!(
+            "[Delegation] revoked the delegation of {}",
+            ctx.accounts.delegation.load()?.delegate
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,450 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+};
+use gmsol_utils::{action::ActionCallbackKind, InitSpace};
+
+use crate::{
+    events::{EventEmitter, OrderCreated, OrderUpdated},
+    ops::order::{CreateOrderArgs, CreateOrderOperation, CreateOrderParams},
+    states::{
+        delegation::Delegation,
+        feature::{ActionDisabledFlag, DomainDisabledFlag},
+        order::{Order, OrderKind},
+        user::UserHeader,
+        Market, NonceBytes, Position, Seed, Store, UpdateOrderParams,
+    },
+    utils::internal,
+    CoreError,
+};
+
+/// The accounts definitions for [`create_order_by_delegate`](crate::gmsol_store::create_order_by_delegate)
+/// instruction.
+///
+/// Only increase and decrease orders are supported, and the position must have been
+/// prepared by the owner. Initial collateral tokens are transferred with the
+/// `delegation` as the authority, so the owner is expected to have approved the
+/// `delegation` to spend from the source token account.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(nonce: [u8; 32], params: CreateOrderParams)]
+pub struct CreateOrderByDelegate<'info> {
+    /// The delegate, who pays for the order account.
+    #[account(mut)]
+    pub delegate: Signer<'info>,
+    /// The owner of the order to be created, who is also the receiver of the output funds.
+    /// CHECK: only the address is used, which is checked by the `delegation`.
+    pub owner: UncheckedAccount<'info>,
+    /// Store.
+    pub store: AccountLoader<'info, Store>,
+    /// Market.
+    #[account(mut, has_one = store)]
+    pub market: AccountLoader<'info, Market>,
+    /// Delegation.
+    #[account(
+        mut,
+        has_one = store,
+        has_one = owner,
+        has_one = delegate,
+        seeds = [
+            Delegation::SEED,
+            store.key().as_ref(),
+            owner.key().as_ref(),
+            delegate.key().as_ref(),
+        ],
+        bump = delegation.load()?.bump,
+    )]
+    pub delegation: AccountLoader<'info, Delegation>,
+    /// User Account of the owner.
+    #[account(
+        constraint = user.load()?.is_initialized() @ CoreError::InvalidUserAccount,
+        has_one = owner,
+        has_one = store,
+        seeds = [UserHeader::SEED, store.key().as_ref(), owner.key().as_ref()],
+        bump = user.load()?.bump,
+    )]
+    pub user: AccountLoader<'info, UserHeader>,
+    /// The order to be created.
+    #[account(
+        init,
+        space = 8 + Order::INIT_SPACE,
+        payer = delegate,
+        seeds = [Order::SEED, store.key().as_ref(), delegate.key().as_ref(), &nonce],
+        bump,
+    )]
+    pub order: AccountLoader<'info, Order>,
+    /// The related position.
+    #[account(
+        mut,
+        has_one = store,
+        has_one = owner,
+        constraint = position.load()?.market_token == market.load()?.meta().market_token_mint @ CoreError::MarketTokenMintMismatched,
+        constraint = position.load()?.collateral_token == *params.collateral_token(&*market.load()?) @ CoreError::InvalidPosition,
+        constraint = position.load()?.kind()? == params.to_position_kind()? @ CoreError::InvalidPosition,
+        seeds = [
+            Position::SEED,
+            store.key().as_ref(),
+            owner.key().as_ref(),
+            market.load()?.meta().market_token_mint.as_ref(),
+            params.collateral_token(market.load()?.meta()).as_ref(),
+            &[params.to_position_kind()? as u8],
+        ],
+        bump = position.load()?.bump,
+    )]
+    pub position: AccountLoader<'info, Position>,
+    /// Initial collateral token.
+    /// Only required by increase orders.
+    pub initial_collateral_token: Option<Box<Account<'info, Mint>>>,
+    /// Final output token.
+    pub final_output_token: Box<Account<'info, Mint>>,
+    /// Long token of the market.
+    #[account(constraint = market.load()?.meta().long_token_mint == long_token.key())]
+    pub long_token: Box<Account<'info, Mint>>,
+    /// Short token of the market.
+    #[account(constraint = market.load()?.meta().short_token_mint == short_token.key())]
+    pub short_token: Box<Account<'info, Mint>>,
+    /// Initial collateral token escrow account.
+    /// Only required by increase orders.
+    #[account(
+        mut,
+        associated_token::mint = initial_collateral_token,
+        associated_token::authority = order,
+    )]
+    pub initial_collateral_token_escrow: Option<Box<Account<'info, TokenAccount>>>,
+    /// Final output token escrow account.
+    /// Required by decrease orders and optional for increase orders.
+    #[account(
+        mut,
+        associated_token::mint = final_output_token,
+        associated_token::authority = order,
+    )]
+    pub final_output_token_escrow: Option<Box<Account<'info, TokenAccount>>>,
+    /// Long token escrow.
+    #[account(
+        mut,
+        associated_token::mint = long_token,
+        associated_token::authority = order,
+    )]
+    pub long_token_escrow: Box<Account<'info, TokenAccount>>,
+    /// Short token escrow.
+    #[account(
+        mut,
+        associated_token::mint = short_token,
+        associated_token::authority = order,
+    )]
+    pub short_token_escrow: Box<Account<'info, TokenAccount>>,
+    /// The source initial token account of the owner.
+    /// Only required by increase orders.
+    #[account(
+        mut,
+        token::mint = initial_collateral_token,
+        token::authority = owner,
+    )]
+    pub initial_collateral_token_source: Option<Box<Account<'info, TokenAccount>>>,
+    /// The system program.
+    pub system_program: Program<'info, System>,
+    /// The token program.
+    pub token_program: Program<'info, Token>,
+    /// The associated token program.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> internal::Create<'info, Order> for CreateOrderByDelegate<'info> {
+    type CreateParams = CreateOrderArgs;
+
+    fn action(&self) -> AccountInfo<'info> {
+        self.order.to_account_info()
+    }
+
+    fn payer(&self) -> AccountInfo<'info> {
+        self.delegate.to_account_info()
+    }
+
+    fn system_program(&self) -> AccountInfo<'info> {
+        self.system_program.to_account_info()
+    }
+
+    fn validate(&self, args: &Self::CreateParams) -> Result<()> {
+        let params = &args.params;
+        let domain: DomainDisabledFlag = params
+            .kind
+            .try_into()
+            .map_err(CoreError::from)
+            .map_err(|err| error!(err))?;
+        self.store
+            .load()?
+            .validate_not_restarted()?
+            .validate_feature_enabled(domain, ActionDisabledFlag::Create)?;
+        self.market
+            .load()?
+            .validate_feature_enabled(domain, ActionDisabledFlag::Create)?;
+        self.delegation.load()?.validate_order(
+            &self.market.load()?.meta().market_token_mint,
+            params.size_delta_value,
+            Clock::get()?.unix_timestamp,
+        )?;
+        Ok(())
+    }
+
+    fn create_impl(
+        &mut self,
+        args: &Self::CreateParams,
+        nonce: &NonceBytes,
+        bumps: &Self::Bumps,
+        remaining_accounts: &'info [AccountInfo<'info>],
+        callback_version: Option<u8>,
+    ) -> Result<()> {
+        let params = &args.params;
+        require!(callback_version.is_none(), CoreError::InvalidArgument);
+
+        self.transfer_tokens(params)?;
+
+        let kind = params.kind;
+        let ops = CreateOrderOperation::builder()
+            .order(self.order.clone())
+            .market(self.market.clone())
+            .store(self.store.clone())
+            .owner(self.owner.to_account_info())
+            .receiver(self.owner.to_account_info())
+            .creator(self.delegate.to_account_info())
+            .nonce(nonce)
+            .bump(bumps.order)
+            .params(params)
+            .ext(&args.ext)
+            .swap_path(remaining_accounts)
+            .callback_version(None)
+            .callback_authority(None)
+            .callback_program(None)
+            .callback_shared_data_account(None)
+            .callback_partitioned_data_account(None)
+            .event_emitter(Some(EventEmitter::new(
+                &self.event_authority,
+                bumps.event_authority,
+            )))
+            .build();
+
+        match kind {
+            OrderKind::MarketIncrease | OrderKind::LimitIncrease | OrderKind::StopIncrease => {
+                require_keys_eq!(
+                    self.final_output_token.key(),
+                    *params.collateral_token(&*self.market.load()?),
+                    CoreError::TokenMintMismatched
+                );
+                let initial_collateral = self
+                    .initial_collateral_token_escrow
+                    .as_ref()
+                    .ok_or_else(|| error!(CoreError::TokenAccountNotProvided))?;
+                ops.increase()
+                    .position(&self.position)
+                    .initial_collateral_token(initial_collateral.as_ref())
+                    .long_token(self.long_token_escrow.as_ref())
+                    .short_token(self.short_token_escrow.as_ref())
+                    .final_output_token(self.final_output_token_escrow.as_deref())
+                    .build()
+                    .execute()?;
+            }
+            OrderKind::MarketDecrease
+            | OrderKind::LimitDecrease
+            | OrderKind::StopLossDecrease
+            | OrderKind::TrailingStopDecrease => {
+                let final_output = self
+                    .final_output_token_escrow
+                    .as_ref()
+                    .ok_or_else(|| error!(CoreError::TokenAccountNotProvided))?;
+                ops.decrease()
+                    .position(&self.position)
+                    .final_output_token(final_output.as_ref())
+                    .long_token(self.long_token_escrow.as_ref())
+                    .short_token(self.short_token_escrow.as_ref())
+                    .build()
+                    .execute()?;
+            }
+            _ => {
+                return err!(CoreError::OrderKindNotAllowed);
+            }
+        }
+
+        // The markets of the swap path must be allowed by the delegation too.
+        self.delegation
+            .load()?
+            .validate_swap_path(self.order.load()?.swap().iter())?;
+
+        emit!(OrderCreated::new(
+            self.store.key(),
+            self.order.key(),
+            Some(self.position.key()),
+        )?);
+
+        Ok(())
+    }
+}
+
+impl CreateOrderByDelegate<'_> {
+    fn transfer_tokens(&mut self, params: &CreateOrderParams) -> Result<()> {
+        if !params.kind.is_increase_position() {
+            return Ok(());
+        }
+        let amount = params.initial_collateral_delta_amount;
+        if amount != 0 {
+            self.delegation.load_mut()?.spend_collateral(amount)?;
+
+            let token = self
+                .initial_collateral_token
+                .as_ref()
+                .ok_or_else(|| error!(CoreError::MissingInitialCollateralToken))?;
+            let from = self
+                .initial_collateral_token_source
+                .as_ref()
+                .ok_or_else(|| error!(CoreError::TokenAccountNotProvided))?;
+            let to = self
+                .initial_collateral_token_escrow
+                .as_mut()
+                .ok_or_else(|| error!(CoreError::TokenAccountNotProvided))?;
+
+            let signer_seeds = self.delegation.load()?.signer_seeds();
+            let signer_seeds = signer_seeds
+                .iter()
+                .map(|seed| seed.as_slice())
+                .collect::<Vec<_>>();
+
+            transfer_checked(
+                CpiContext::new(
+                    self.token_program.to_account_info(),
+                    TransferChecked {
+                        from: from.to_account_info(),
+                        mint: token.to_account_info(),
+                        to: to.to_account_info(),
+                        authority: self.delegation.to_account_info(),
+                    },
+                )
+                .with_signer(&[&signer_seeds]),
+                amount,
+                token.decimals,
+            )?;
+
+            to.reload()?;
+        }
+        Ok(())
+    }
+}
+
+/// The accounts definitions for [`update_order_by_delegate`](crate::gmsol_store::update_order_by_delegate).
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateOrderByDelegate<'info> {
+    /// The delegate.
+    pub delegate: Signer<'info>,
+    /// Store.
+    pub store: AccountLoader<'info, Store>,
+    /// Market.
+    #[account(mut, has_one = store)]
+    pub market: AccountLoader<'info, Market>,
+    /// Delegation.
+    #[account(has_one = store, has_one = delegate)]
+    pub delegation: AccountLoader<'info, Delegation>,
+    /// Order to update.
+    #[account(
+        mut,
+        constraint = order.load()?.header.store == store.key() @ CoreError::StoreMismatched,
+        constraint = order.load()?.header.market == market.key() @ CoreError::MarketMismatched,
+        constraint = order.load()?.header.owner == delegation.load()?.owner @ CoreError::OwnerMismatched,
+        constraint = order.load()?.header.action_state()?.is_pending() @ CoreError::PreconditionsAreNotMet,
+        // Callbacks require the owner account, so orders with callbacks can only be updated by the owner.
+        constraint = order.load()?.header.callback_kind()? == ActionCallbackKind::Disabled @ CoreError::InvalidArgument,
+    )]
+    pub order: AccountLoader<'info, Order>,
+}
+
+impl UpdateOrderByDelegate<'_> {
+    pub(crate) fn invoke(ctx: Context<Self>, params: &UpdateOrderParams) -> Result<()> {
+        {
+            let order = ctx.accounts.order.load()?;
+            let domain: DomainDisabledFlag = order
+                .params()
+                .kind()?
+                .try_into()
+                .map_err(CoreError::from)
+                .map_err(|err| error!(err))?;
+            ctx.accounts
+                .store
+                .load()?
+                .validate_not_restarted()?
+                .validate_feature_enabled(domain, ActionDisabledFlag::Update)?;
+            ctx.accounts
+                .market
+                .load()?
+                .validate_feature_enabled(domain, ActionDisabledFlag::Update)?;
+            ctx.accounts.delegation.load()?.validate_order(
+                order.market_token(),
+                params
+                    .size_delta_value
+                    .unwrap_or_else(|| order.params().size()),
+                Clock::get()?.unix_timestamp,
+            )?;
+        }
+
+        let id = ctx
+            .accounts
+            .market
+            .load_mut()?
+            .indexer_mut()
+            .next_order_id()?;
+        ctx.accounts.order.load_mut()?.update(id, params)?;
+
+        let event_emitter =
+            EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+        let order = ctx.accounts.order.load()?;
+        event_emitter.emit_cpi(&OrderUpdated::new(
+            false,
+            &ctx.accounts.order.key(),
+            &order,
+        )?)?;
+        Ok(())
+    }
+}
+
+/// The accounts definitions for [`cancel_order_by_delegate`](crate::gmsol_store::cancel_order_by_delegate).
+#[derive(Accounts)]
+pub struct CancelOrderByDelegate<'info> {
+    /// The delegate.
+    pub delegate: Signer<'info>,
+    /// Store.
+    pub store: AccountLoader<'info, Store>,
+    /// Delegation.
+    #[account(has_one = store, has_one = delegate)]
+    pub delegation: AccountLoader<'info, Delegation>,
+    /// Order to cancel.
+    #[account(
+        mut,
+        constraint = order.load()?.header.store == store.key() @ CoreError::StoreMismatched,
+        constraint = order.load()?.header.owner == delegation.load()?.owner @ CoreError::OwnerMismatched,
+    )]
+    pub order: AccountLoader<'info, Order>,
+}
+
+impl CancelOrderByDelegate<'_> {
+    pub(crate) fn invoke(ctx: Context<Self>) -> Result<()> {
+        let mut order = ctx.accounts.order.load_mut()?;
+        ctx.accounts
+            .store
+            .load()?
+            .validate_not_restarted()?
+            .validate_feature_enabled(
+                order
+                    .params()
+                    .kind()?
+                    .try_into()
+                    .map_err(CoreError::from)
+                    .map_err(|err| error!(err))?,
+                ActionDisabledFlag::Cancel,
+            )?;
+        ctx.accounts.delegation.load()?.validate_order(
+            order.market_token(),
+            0,
+            Clock::get()?.unix_timestamp,
+        )?;
+        // Order must be in the pending state which is checked before the transition.
+        order.header.cancelled()
+    }
+}
@@ -122,6 +122,9 @@ impl<'info> internal::Create<'info, Deposit> for CreateDeposit<'info> {
             .load()?
             .validate_not_restarted()?
             .validate_feature_enabled(DomainDisabledFlag::Deposit, ActionDisabledFlag::Create)?;
+        self.market
+            .load()?
+            .validate_feature_enabled(DomainDisabledFlag::Deposit, ActionDisabledFlag::Create)?;
         Ok(())
     }
 
@@ -128,7 +128,8 @@ pub struct ExecuteDeposit<'info> {
     pub chainlink_program: Option<Program<'info, Chainlink>>,
 }
 
-/// CHECK: only ORDER_KEEPER can invoke this instruction.
+/// CHECK: only ORDER_KEEPER can invoke this instruction, unless the self-execution
+/// of the action is allowed.
 #[inline(never)]
 pub(crate) fn unchecked_execute_deposit<'info>(
     ctx: Context<'_, '_, 'info, 'info, ExecuteDeposit<'info>>,
@@ -143,6 +144,10 @@ pub(crate) fn unchecked_execute_deposit<'info>(
         .store
         .load()?
         .validate_feature_enabled(DomainDisabledFlag::Deposit, ActionDisabledFlag::Execute)?;
+    accounts
+        .market
+        .load()?
+        .validate_feature_enabled(DomainDisabledFlag::Deposit, ActionDisabledFlag::Execute)?;
 
     let signer = accounts.deposit.load()?.signer();
 
@@ -177,6 +182,12 @@ impl<'info> internal::Authentication<'info> for ExecuteDeposit<'info> {
     }
 }
 
+impl<'info> internal::SelfExecution<'info> for ExecuteDeposit<'info> {
+    fn pending_since(&self) -> Result<Option<i64>> {
+        Ok(Some(self.deposit.load()?.header.updated_at()))
+    }
+}
+
 impl<'info> ExecuteDeposit<'info> {
     #[inline(never)]
     fn pay_execution_fee(&self, execution_fee: u64) -> Result<()> {
@@ -0,0 +1,482 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{
+    constants,
+    events::EventEmitter,
+    ops::{
+        deposit::ExecuteDepositOperation,
+        execution_fee::PayExecutionFeeOperation,
+        market::{MarketTransferInOperation, MarketTransferOutOperation},
+    },
+    states::{
+        common::action::{ActionExt, ActionSigner},
+        feature::{ActionDisabledFlag, DomainDisabledFlag},
+        Chainlink, Deposit, HasMarketMeta, Market, Oracle, Store, TokenMapHeader,
+        ValidateOracleTime,
+    },
+    utils::{internal, token::validate_transferable_escrow},
+    CoreError, CoreResult,
+};
+
+/// The number of accounts required for each deposit in the remaining accounts.
+pub const NUM_ACCOUNTS_PER_DEPOSIT: usize = 4;
+
+/// The accounts definition for [`execute_deposits`](crate::gmsol_store::execute_deposits)
+/// instruction.
+///
+/// Remaining accounts expected by this instruction:
+///
+///   - 0..N. `[]` N feed accounts, where N represents the total number of unique tokens
+///     in the market.
+///   - N..N+4*D. `[writable]` D groups of deposit accounts, where D is the `count` argument.
+///     Each group consists of the deposit, its market token escrow, its initial long token
+///     escrow and its initial short token escrow. An escrow that is not recorded in the
+///     deposit must be replaced with the address of this program.
+///   - N+4*D..N+4*D+V. `[writable]` V virtual inventory accounts, where V represents the total
+///     number of unique virtual inventories required by the market.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteDeposits<'info> {
+    /// Authority.
+    pub authority: Signer<'info>,
+    /// Store.
+    #[account(has_one = token_map)]
+    pub store: AccountLoader<'info, Store>,
+    /// Token Map.
+    #[account(has_one = store)]
+    pub token_map: AccountLoader<'info, TokenMapHeader>,
+    /// Oracle buffer to use.
+    #[account(mut, has_one = store)]
+    pub oracle: AccountLoader<'info, Oracle>,
+    /// Market.
+    #[account(mut, has_one = store)]
+    pub market: AccountLoader<'info, Market>,
+    /// Market token mint.
+    #[account(mut, constraint = market.load()?.meta().market_token_mint == market_token.key() @ CoreError::MarketTokenMintMismatched)]
+    pub market_token: Box<Account<'info, Mint>>,
+    /// Long token.
+    #[account(constraint = market.load()?.meta().long_token_mint == long_token.key() @ CoreError::TokenMintMismatched)]
+    pub long_token: Box<Account<'info, Mint>>,
+    /// Short token.
+    #[account(constraint = market.load()?.meta().short_token_mint == short_token.key() @ CoreError::TokenMintMismatched)]
+    pub short_token: Box<Account<'info, Mint>>,
+    /// Long token vault.
+    #[account(
+        mut,
+        token::mint = long_token,
+        token::authority = store,
+        seeds = [
+            constants::MARKET_VAULT_SEED,
+            store.key().as_ref(),
+            long_token_vault.mint.as_ref(),
+        ],
+        bump,
+    )]
+    pub long_token_vault: Box<Account<'info, TokenAccount>>,
+    /// Short token vault.
+    #[account(
+        mut,
+        token::mint = short_token,
+        token::authority = store,
+        seeds = [
+            constants::MARKET_VAULT_SEED,
+            store.key().as_ref(),
+            short_token_vault.mint.as_ref(),
+        ],
+        bump,
+    )]
+    pub short_token_vault: Box<Account<'info, TokenAccount>>,
+    /// The token program.
+    pub token_program: Program<'info, Token>,
+    /// The system program.
+    pub system_program: Program<'info, System>,
+    /// Chainlink Program.
+    pub chainlink_program: Option<Program<'info, Chainlink>>,
+}
+
+/// CHECK: only ORDER_KEEPER can invoke this instruction.
+#[inline(never)]
+pub(crate) fn unchecked_execute_deposits<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteDeposits<'info>>,
+    execution_fee: u64,
+    count: u8,
+) -> Result<()> {
+    let accounts = ctx.accounts;
+
+    // Validate feature enabled.
+    accounts
+        .store
+        .load()?
+        .validate_feature_enabled(DomainDisabledFlag::Deposit, ActionDisabledFlag::Execute)?;
+    accounts
+        .market
+        .load()?
+        .validate_feature_enabled(DomainDisabledFlag::Deposit, ActionDisabledFlag::Execute)?;
+
+    require_neq!(count, 0, CoreError::InvalidArgument);
+
+    let tokens = accounts
+        .market
+        .load()?
+        .market_meta()
+        .ordered_tokens()
+        .into_iter()
+        .collect::<Vec<_>>();
+
+    let event_authority = accounts.event_authority.clone();
+    let event_emitter = EventEmitter::new(&event_authority, ctx.bumps.event_authority);
+
+    let oracle_loader = accounts.oracle.clone();
+    let store = accounts.store.clone();
+    let token_map = accounts.token_map.clone();
+    let processed = oracle_loader.load_mut()?.with_prices(
+        &store,
+        &token_map,
+        &tokens,
+        ctx.remaining_accounts,
+        |oracle, remaining_accounts| {
+            let len = usize::from(count) * NUM_ACCOUNTS_PER_DEPOSIT;
+            require_gte!(
+                remaining_accounts.len(),
+                len,
+                ErrorCode::AccountNotEnoughKeys
+            );
+            let (groups, virtual_inventories) = remaining_accounts.split_at(len);
+
+            let mut processed = Vec::with_capacity(usize::from(count));
+            for group in groups.chunks_exact(NUM_ACCOUNTS_PER_DEPOSIT) {
+                if let Some(deposit) =
+                    accounts.execute_one(oracle, group, virtual_inventories, &event_emitter)?
+                {
+                    processed.push(deposit);
+                }
+            }
+            Ok(processed)
+        },
+    )?;
+
+    // It must be placed at the end to be executed correctly.
+    for deposit in processed {
+        accounts.pay_execution_fee(&deposit, execution_fee)?;
+    }
+
+    Ok(())
+}
+
+impl<'info> internal::Authentication<'info> for ExecuteDeposits<'info> {
+    fn authority(&self) -> &Signer<'info> {
+        &self.authority
+    }
+
+    fn store(&self) -> &AccountLoader<'info, Store> {
+        &self.store
+    }
+}
+
+/// The escrow accounts of a deposit in a batch.
+struct DepositEscrows<'info> {
+    market_token: &'info AccountInfo<'info>,
+    initial_long_token: Option<&'info AccountInfo<'info>>,
+    initial_short_token: Option<&'info AccountInfo<'info>>,
+}
+
+impl<'info> DepositEscrows<'info> {
+    /// Validate the escrow accounts of the deposit, so that the transfers
+    /// of the execution cannot fail.
+    fn new(
+        accounts: &ExecuteDeposits<'info>,
+        deposit_key: &Pubkey,
+        deposit: &Deposit,
+        group: &'info [AccountInfo<'info>],
+    ) -> Result<Self> {
+        let [market_token, initial_long_token, initial_short_token] = group else {
+            return err!(ErrorCode::AccountNotEnoughKeys);
+        };
+        let optional = |info: &'info AccountInfo<'info>| (*info.key != crate::ID).then_some(info);
+        let escrows = Self {
+            market_token,
+            initial_long_token: optional(initial_long_token),
+            initial_short_token: optional(initial_short_token),
+        };
+
+        let tokens = &deposit.tokens;
+        require!(
+            tokens.market_token.account() == Some(escrows.market_token.key()),
+            CoreError::MarketTokenAccountMismatched
+        );
+        require!(
+            tokens.initial_long_token.account() == escrows.initial_long_token.map(|a| a.key()),
+            CoreError::TokenAccountMismatched
+        );
+        require!(
+            tokens.initial_short_token.account() == escrows.initial_short_token.map(|a| a.key()),
+            CoreError::TokenAccountMismatched
+        );
+
+        let token_program_id = accounts.token_program.key();
+        validate_transferable_escrow(
+            escrows.market_token,
+            deposit_key,
+            &accounts.market_token.key(),
+            &token_program_id,
+            0,
+        )?;
+        if let Some(escrow) = escrows.initial_long_token {
+            validate_transferable_escrow(
+                escrow,
+                deposit_key,
+                &accounts.long_token.key(),
+                &token_program_id,
+                deposit.params.initial_long_token_amount,
+            )?;
+        }
+        if let Some(escrow) = escrows.initial_short_token {
+            validate_transferable_escrow(
+                escrow,
+                deposit_key,
+                &accounts.short_token.key(),
+                &token_program_id,
+                deposit.params.initial_short_token_amount,
+            )?;
+        }
+        Ok(escrows)
+    }
+}
+
+/// Validates that the oracle prices are not older than the last update of a deposit.
+///
+/// The expiration is checked during the execution instead, so that an expired deposit
+/// can be cancelled in the batch.
+struct DepositOracleTime<'a> {
+    deposit: &'a Deposit,
+}
+
+impl ValidateOracleTime for DepositOracleTime<'_> {
+    fn oracle_updated_after(&self) -> CoreResult<Option<i64>> {
+        Ok(Some(self.deposit.header.updated_at))
+    }
+
+    fn oracle_updated_before(&self) -> CoreResult<Option<i64>> {
+        Ok(None)
+    }
+
+    fn oracle_updated_after_slot(&self) -> CoreResult<Option<u64>> {
+        Ok(Some(self.deposit.header.updated_at_slot))
+    }
+}
+
+impl<'info> ExecuteDeposits<'info> {
+    /// Execute one deposit of the batch.
+    ///
+    /// Returns the deposit if it has been processed (executed or cancelled),
+    /// or `None` if it is skipped and left pending.
+    #[inline(never)]
+    fn execute_one(
+        &mut self,
+        oracle: &Oracle,
+        group: &'info [AccountInfo<'info>],
+        virtual_inventories: &'info [AccountInfo<'info>],
+        event_emitter: &EventEmitter<'_, 'info>,
+    ) -> Result<Option<AccountLoader<'info, Deposit>>> {
+        let (info, group) = group
+            .split_first()
+            .ok_or_else(|| error!(ErrorCode::AccountNotEnoughKeys))?;
+
+        // A deposit that cannot be executed is skipped without affecting the others.
+        let (deposit, escrows, signer) = match self.prepare_one(oracle, info, group) {
+            Ok(Some(prepared)) => prepared,
+            Ok(None) => return Ok(None),
+            Err(err) => {
+                msg!("[Deposit] skipped deposit {}: {}", info.key, err);
+                return Ok(None);
+            }
+        };
+
+        self.transfer_tokens_in(&deposit, &escrows, &signer, event_emitter)?;
+
+        let executed = ExecuteDepositOperation::builder()
+            .store(&self.store)
+            .market(&self.market)
+            .deposit(&deposit)
+            .market_token_mint(&mut self.market_token)
+            .market_token_receiver(escrows.market_token.clone())
+            .token_program(self.token_program.to_account_info())
+            .throw_on_execution_error(false)
+            .event_emitter(*event_emitter)
+            .oracle(oracle)
+            .remaining_accounts(virtual_inventories)
+            .build()
+            .execute()?;
+
+        // The supply of the market token must be refreshed for the next deposit.
+        self.market_token.reload()?;
+
+        if executed {
+            deposit.load_mut()?.header.completed()?;
+        } else {
+            deposit.load_mut()?.header.cancelled()?;
+            self.transfer_tokens_out(&deposit, &escrows, event_emitter)?;
+        }
+
+        Ok(Some(deposit))
+    }
+
+    /// Validate the accounts of a deposit of the batch without modifying any state.
+    ///
+    /// Returns `None` if the deposit should be skipped.
+    #[allow(clippy::type_complexity)]
+    fn prepare_one(
+        &self,
+        oracle: &Oracle,
+        info: &'info AccountInfo<'info>,
+        group: &'info [AccountInfo<'info>],
+    ) -> Result<
+        Option<(
+            AccountLoader<'info, Deposit>,
+            DepositEscrows<'info>,
+            ActionSigner,
+        )>,
+    > {
+        let deposit = AccountLoader::<Deposit>::try_from(info)?;
+        let (escrows, signer) = {
+            let deposit = deposit.load()?;
+            let header = &deposit.header;
+            require_keys_eq!(header.store, self.store.key(), CoreError::StoreMismatched);
+            require_keys_eq!(
+                header.market,
+                self.market.key(),
+                CoreError::MarketMismatched
+            );
+
+            if !header.action_state()?.is_pending() {
+                msg!("[Deposit] skipped deposit {}: not pending", info.key);
+                return Ok(None);
+            }
+
+            let swap = deposit.swap();
+            if swap.primary_length() != 0 || swap.secondary_length() != 0 {
+                msg!(
+                    "[Deposit] skipped deposit {}: swap is not supported",
+                    info.key
+                );
+                return Ok(None);
+            }
+
+            match oracle.validate_time(&DepositOracleTime { deposit: &deposit }) {
+                Ok(()) => {}
+                Err(
+                    CoreError::OracleTimestampsAreSmallerThanRequired
+                    | CoreError::InvalidOracleSlot,
+                ) => {
+                    msg!(
+                        "[Deposit] skipped deposit {}: prices are older than the deposit",
+                        info.key
+                    );
+                    return Ok(None);
+                }
+                Err(err) => return Err(error!(err)),
+            }
+
+            let escrows = DepositEscrows::new(self, info.key, &deposit, group)?;
+            (escrows, deposit.signer())
+        };
+        Ok(Some((deposit, escrows, signer)))
+    }
+
+    #[inline(never)]
+    fn pay_execution_fee(
+        &self,
+        deposit: &AccountLoader<'info, Deposit>,
+        execution_fee: u64,
+    ) -> Result<()> {
+        let execution_lamports = deposit.load()?.execution_lamports(execution_fee);
+        PayExecutionFeeOperation::builder()
+            .payer(deposit.to_account_info())
+            .receiver(self.authority.to_account_info())
+            .execution_lamports(execution_lamports)
+            .build()
+            .execute()?;
+        Ok(())
+    }
+
+    #[inline(never)]
+    fn transfer_tokens_in(
+        &self,
+        deposit: &AccountLoader<'info, Deposit>,
+        escrows: &DepositEscrows<'info>,
+        signer: &ActionSigner,
+        event_emitter: &EventEmitter<'_, 'info>,
+    ) -> Result<()> {
+        let seeds = signer.as_seeds();
+
+        let builder = MarketTransferInOperation::builder()
+            .store(&self.store)
+            .market(&self.market)
+            .from_authority(deposit.to_account_info())
+            .token_program(self.token_program.to_account_info())
+            .signer_seeds(&seeds)
+            .event_emitter(*event_emitter);
+
+        if let Some(escrow) = escrows.initial_long_token {
+            builder
+                .clone()
+                .from(escrow.clone())
+                .vault(&self.long_token_vault)
+                .amount(deposit.load()?.params.initial_long_token_amount)
+                .build()
+                .execute()?;
+        }
+
+        if let Some(escrow) = escrows.initial_short_token {
+            builder
+                .from(escrow.clone())
+                .vault(&self.short_token_vault)
+                .amount(deposit.load()?.params.initial_short_token_amount)
+                .build()
+                .execute()?;
+        }
+
+        Ok(())
+    }
+
+    #[inline(never)]
+    fn transfer_tokens_out(
+        &self,
+        deposit: &AccountLoader<'info, Deposit>,
+        escrows: &DepositEscrows<'info>,
+        event_emitter: &EventEmitter<'_, 'info>,
+    ) -> Result<()> {
+        let builder = MarketTransferOutOperation::builder()
+            .store(&self.store)
+            .market(&self.market)
+            .token_program(self.token_program.to_account_info())
+            .allow_closed(false)
+            .event_emitter(*event_emitter);
+
+        if let Some(escrow) = escrows.initial_long_token {
+            builder
+                .clone()
+                .to(escrow.clone())
+                .vault(self.long_token_vault.to_account_info())
+                .amount(deposit.load()?.params.initial_long_token_amount)
+                .decimals(self.long_token.decimals)
+                .token_mint(self.long_token.to_account_info())
+                .build()
+                .execute()?;
+        }
+
+        if let Some(escrow) = escrows.initial_short_token {
+            builder
+                .to(escrow.clone())
+                .vault(self.short_token_vault.to_account_info())
+                .amount(deposit.load()?.params.initial_short_token_amount)
+                .decimals(self.short_token.decimals)
+                .token_mint(self.short_token.to_account_info())
+                .build()
+                .execute()?;
+        }
+
+        Ok(())
+    }
+}
@@ -18,20 +18,19 @@ use crate::{
     },
     states::{
         callback::CallbackAuthority,
-        common::{
-            action::{ActionExt, ActionSigner},
-            swap::SwapActionParamsExt,
-        },
-        feature::ActionDisabledFlag,
+        common::{action::ActionSigner, swap::SwapActionParamsExt},
+        feature::{ActionDisabledFlag, DomainDisabledFlag},
         order::{Order, TransferOut},
         position::Position,
-        user::UserHeader,
+        user::{UserHeader, UserStats},
         Market, Oracle, Seed, Store, TokenMapHeader, TokenMapLoader,
     },
     utils::{internal, pubkey::DEFAULT_PUBKEY},
     CoreError,
 };
 
+use super::linked_order::cancel_pending_linked_order;
+
 /// The accounts definition for [`prepare_trade_event_buffer`](crate::gmsol_store::prepare_trade_event_buffer).
 #[derive(Accounts)]
 #[instruction(index: u16)]
@@ -135,6 +134,11 @@ pub(crate) fn validated_recent_timestamp(config: &Store, timestamp: i64) -> Resu
 ///     markets excluding the current market in the swap params.
 ///   - M+N..M+N+V. `[writable]` V virtual inventory accounts, where V represents the total
 ///     number of unique virtual inventories required by the markets.
+///   - M+N+V. `[]` The position of the same owner and side with the other collateral token
+///     of the market, required only for increase orders in a market with a max position size
+///     configured for the side. It does not have to be initialized.
+///   - The last account. `[writable]` The user stats account of the owner, required only if
+///     the owner has prepared one.
 #[event_cpi]
 #[derive(Accounts)]
 #[instruction(recent_timestamp: i64)]
@@ -318,6 +322,18 @@ impl<'info> internal::Authentication<'info> for ExecuteIncreaseOrSwapOrderV2<'in
     }
 }
 
+impl<'info> internal::SelfExecution<'info> for ExecuteIncreaseOrSwapOrderV2<'info> {
+    fn pending_since(&self) -> Result<Option<i64>> {
+        let order = self.order.load()?;
+        // Only market orders can be self-executed.
+        Ok(order
+            .params()
+            .kind()?
+            .is_market()
+            .then(|| order.header.updated_at()))
+    }
+}
+
 impl<'info> ExecuteIncreaseOrSwapOrderV2<'info> {
     #[inline(never)]
     pub(crate) fn invoke(
@@ -337,14 +353,23 @@ impl<'info> ExecuteIncreaseOrSwapOrderV2<'info> {
         );
 
         // Validate feature enabled.
-        accounts.store.load()?.validate_feature_enabled(
-            kind.try_into()
-                .map_err(CoreError::from)
-                .map_err(|err| error!(err))?,
-            ActionDisabledFlag::Execute,
-        )?;
-
-        let remaining_accounts = ctx.remaining_accounts;
+        let domain: DomainDisabledFlag = kind
+            .try_into()
+            .map_err(CoreError::from)
+            .map_err(|err| error!(err))?;
+        accounts
+            .store
+            .load()?
+            .validate_feature_enabled(domain, ActionDisabledFlag::Execute)?;
+        accounts
+            .market
+            .load()?
+            .validate_feature_enabled(domain, ActionDisabledFlag::Execute)?;
+
+        let (remaining_accounts, user_stats) =
+            split_user_stats(&accounts.user, ctx.remaining_accounts)?;
+        let (remaining_accounts, sibling_position_size_in_usd) =
+            accounts.split_sibling_position(remaining_accounts)?;
         let signer = accounts.order.load()?.signer();
 
         let event_authority = accounts.event_authority.clone();
@@ -353,11 +378,17 @@ impl<'info> ExecuteIncreaseOrSwapOrderV2<'info> {
         accounts.transfer_tokens_in(&signer, remaining_accounts, &event_emitter)?;
 
         let (is_position_removed, transfer_out, should_send_trade_event) = accounts
-            .perform_execution(remaining_accounts, throw_on_execution_error, &event_emitter)?;
+            .perform_execution(
+                remaining_accounts,
+                throw_on_execution_error,
+                sibling_position_size_in_usd,
+                user_stats.as_ref(),
+                &event_emitter,
+            )?;
 
         let executed = transfer_out.executed();
         if executed {
-            accounts.order.load_mut()?.header.completed()?;
+            accounts.order.load_mut()?.complete_execution()?;
             accounts.process_transfer_out(&transfer_out, &event_emitter)?;
         } else {
             accounts.order.load_mut()?.header.cancelled()?;
@@ -384,6 +415,30 @@ impl<'info> ExecuteIncreaseOrSwapOrderV2<'info> {
         Ok(())
     }
 
+    /// Split the sibling position off the remaining accounts if it is required,
+    /// and return its size in USD.
+    fn split_sibling_position(
+        &self,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<(&'info [AccountInfo<'info>], u128)> {
+        let Some(position) = self.position.as_ref() else {
+            return Ok((remaining_accounts, 0));
+        };
+        let Some(expected) = required_sibling_position(
+            &self.store.key(),
+            &*self.market.load()?,
+            &*position.load()?,
+        )?
+        else {
+            return Ok((remaining_accounts, 0));
+        };
+        let (sibling, remaining_accounts) = remaining_accounts
+            .split_last()
+            .ok_or_else(|| error!(ErrorCode::AccountNotEnoughKeys))?;
+        require_keys_eq!(*sibling.key, expected, CoreError::PositionMismatched);
+        Ok((remaining_accounts, sibling_position_size_in_usd(sibling)?))
+    }
+
     #[inline(never)]
     fn transfer_tokens_in(
         &self,
@@ -403,7 +458,7 @@ impl<'info> ExecuteIncreaseOrSwapOrderV2<'info> {
                 .initial_collateral_token_vault
                 .as_ref()
                 .ok_or_else(|| error!(CoreError::TokenAccountNotProvided))?;
-            let amount = self.order.load()?.params.initial_collateral_delta_amount;
+            let amount = self.order.load()?.params.slice_amount();
             MarketTransferInOperation::builder()
                 .store(&self.store)
                 .from_authority(self.order.to_account_info())
@@ -442,7 +497,7 @@ impl<'info> ExecuteIncreaseOrSwapOrderV2<'info> {
                 .initial_collateral_token
                 .as_ref()
                 .ok_or_else(|| error!(CoreError::TokenMintNotProvided))?;
-            let amount = self.order.load()?.params.initial_collateral_delta_amount;
+            let amount = self.order.load()?.params.slice_amount();
             MarketTransferOutOperation::builder()
                 .store(&self.store)
                 .token_program(self.token_program.to_account_info())
@@ -470,6 +525,8 @@ impl<'info> ExecuteIncreaseOrSwapOrderV2<'info> {
         &mut self,
         remaining_accounts: &'info [AccountInfo<'info>],
         throw_on_execution_error: bool,
+        sibling_position_size_in_usd: u128,
+        user_stats: Option<&AccountLoader<'info, UserStats>>,
         event_emitter: &EventEmitter<'_, 'info>,
     ) -> Result<(RemovePosition, Box<TransferOut>, ShouldSendTradeEvent)> {
         let allow_closed = {
@@ -507,7 +564,9 @@ impl<'info> ExecuteIncreaseOrSwapOrderV2<'info> {
             .callback_authority(self.callback_authority.as_ref())
             .callback_program(self.callback_program.as_deref())
             .callback_shared_data_account(self.callback_shared_data_account.as_deref())
-            .callback_partitioned_data_account(self.callback_partitioned_data_account.as_deref());
+            .callback_partitioned_data_account(self.callback_partitioned_data_account.as_deref())
+            .user_stats(user_stats)
+            .sibling_position_size_in_usd(sibling_position_size_in_usd);
 
         self.oracle.load_mut()?.with_prices_opts(
             &self.store,
@@ -572,7 +631,10 @@ impl<'info> ExecuteIncreaseOrSwapOrderV2<'info> {
 
     #[inline(never)]
     fn pay_execution_fee(&self, execution_fee: u64) -> Result<()> {
-        let execution_lamports = self.order.load()?.execution_lamports(execution_fee);
+        let execution_lamports = self
+            .order
+            .load()?
+            .execution_lamports_per_execution(execution_fee);
         PayExecutionFeeOperation::builder()
             .payer(self.order.to_account_info())
             .receiver(self.authority.to_account_info())
@@ -583,6 +645,94 @@ impl<'info> ExecuteIncreaseOrSwapOrderV2<'info> {
     }
 }
 
+/// Returns the address of the sibling position if it is required to validate
+/// the max position size cap of the given position.
+///
+/// The sibling position is the position of the same owner, market and side
+/// with the other collateral token, which shares the max position size cap
+/// with the position to increase.
+pub(super) fn required_sibling_position(
+    store: &Pubkey,
+    market: &Market,
+    position: &Position,
+) -> Result<Option<Pubkey>> {
+    let meta = market.meta();
+    if meta.long_token_mint == meta.short_token_mint
+        || market
+            .max_position_size_usd(position.try_is_long()?)
+            .is_none()
+    {
+        return Ok(None);
+    }
+    let collateral_token = meta
+        .opposite_token(&position.collateral_token)
+        .map_err(CoreError::from)?;
+    let (address, _) = Pubkey::find_program_address(
+        &[
+            Position::SEED,
+            store.as_ref(),
+            position.owner.as_ref(),
+            position.market_token.as_ref(),
+            collateral_token.as_ref(),
+            &[position.kind],
+        ],
+        &crate::ID,
+    );
+    Ok(Some(address))
+}
+
+/// Returns the size in USD of the sibling position, which does not have to be initialized.
+///
+/// CHECK: the address of the sibling position must have been validated.
+pub(super) fn sibling_position_size_in_usd<'info>(
+    sibling: &'info AccountInfo<'info>,
+) -> Result<u128> {
+    if *sibling.owner != crate::ID || sibling.data_is_empty() {
+        return Ok(0);
+    }
+    let sibling = AccountLoader::<Position>::try_from(sibling)?;
+    let size_in_usd = sibling.load()?.state.size_in_usd;
+    Ok(size_in_usd)
+}
+
+/// Split the user stats account off the remaining accounts if the owner has one.
+///
+/// The user stats account is expected to be the last remaining account, and is
+/// required whenever the User Account is marked as having one.
+pub(super) fn split_user_stats<'info>(
+    user: &AccountLoader<'info, UserHeader>,
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<(
+    &'info [AccountInfo<'info>],
+    Option<AccountLoader<'info, UserStats>>,
+)> {
+    if !user.load()?.has_stats() {
+        return Ok((remaining_accounts, None));
+    }
+    let (user_stats, remaining_accounts) = remaining_accounts
+        .split_last()
+        .ok_or_else(|| error!(ErrorCode::AccountNotEnoughKeys))?;
+    Ok((remaining_accounts, Some(load_user_stats(user, user_stats)?)))
+}
+
+/// Load the user stats account of the owner of the given User Account.
+pub(super) fn load_user_stats<'info>(
+    user: &AccountLoader<'info, UserHeader>,
+    user_stats: &'info AccountInfo<'info>,
+) -> Result<AccountLoader<'info, UserStats>> {
+    require!(user_stats.is_writable, ErrorCode::ConstraintMut);
+    let user_stats = AccountLoader::<UserStats>::try_from(user_stats)?;
+    {
+        let user = user.load()?;
+        let stats = user_stats.load()?;
+        // CHECK: a user stats account can only be initialized at the address
+        // derived from its store and owner.
+        require_keys_eq!(stats.store, user.store, CoreError::StoreMismatched);
+        require_keys_eq!(stats.owner, user.owner, CoreError::OwnerMismatched);
+    }
+    Ok(user_stats)
+}
+
 /// The accounts definition for [`execute_decrease_order_v2`](crate::gmsol_store::execute_decrease_order_v2)
 /// instruction.
 ///
@@ -593,6 +743,10 @@ impl<'info> ExecuteIncreaseOrSwapOrderV2<'info> {
 ///     markets excluding the current market in the swap params.
 ///   - M+N..M+N+V. `[writable]` V virtual inventory accounts, where V represents the total
 ///     number of unique virtual inventories required by the markets.
+///   - M+N+V. `[writable]` The sibling order, required only if the order is a linked order.
+///     The sibling is cancelled once the order is executed, unless it has been closed.
+///   - The last account. `[writable]` The user stats account of the owner, required only if
+///     the owner has prepared one.
 #[event_cpi]
 #[derive(Accounts)]
 #[instruction(recent_timestamp: i64)]
@@ -807,7 +961,10 @@ impl<'info> ExecuteDecreaseOrderV2<'info> {
         throw_on_execution_error: bool,
     ) -> Result<()> {
         let accounts = &mut ctx.accounts;
-        let remaining_accounts = ctx.remaining_accounts;
+        let (remaining_accounts, user_stats) =
+            split_user_stats(&accounts.user, ctx.remaining_accounts)?;
+        let (remaining_accounts, linked_sibling) =
+            accounts.split_linked_sibling(remaining_accounts)?;
 
         let kind = accounts.order.load()?.params().kind()?;
 
@@ -815,22 +972,44 @@ impl<'info> ExecuteDecreaseOrderV2<'info> {
         require!(kind.is_decrease_position(), CoreError::InvalidArgument);
 
         // Validate feature enabled.
-        accounts.store.load()?.validate_feature_enabled(
-            kind.try_into()
-                .map_err(CoreError::from)
-                .map_err(|err| error!(err))?,
-            ActionDisabledFlag::Execute,
-        )?;
+        let domain: DomainDisabledFlag = kind
+            .try_into()
+            .map_err(CoreError::from)
+            .map_err(|err| error!(err))?;
+        accounts
+            .store
+            .load()?
+            .validate_feature_enabled(domain, ActionDisabledFlag::Execute)?;
+        accounts
+            .market
+            .load()?
+            .validate_feature_enabled(domain, ActionDisabledFlag::Execute)?;
 
         let event_authority = accounts.event_authority.clone();
         let event_emitter = EventEmitter::new(&event_authority, ctx.bumps.event_authority);
         let (is_position_removed, transfer_out, should_send_trade_event) = accounts
-            .perform_execution(remaining_accounts, throw_on_execution_error, &event_emitter)?;
+            .perform_execution(
+                remaining_accounts,
+                throw_on_execution_error,
+                user_stats.as_ref(),
+                &event_emitter,
+            )?;
 
         let executed = transfer_out.executed();
         if executed {
-            accounts.order.load_mut()?.header.completed()?;
+            {
+                let mut order = accounts.order.load_mut()?;
+                if is_position_removed {
+                    // No slice can be executed once the position is removed.
+                    order.header.completed()?;
+                } else {
+                    order.complete_execution()?;
+                }
+            }
             accounts.process_transfer_out(remaining_accounts, &transfer_out, &event_emitter)?;
+            if let Some(sibling) = linked_sibling {
+                accounts.cancel_linked_sibling(sibling, &event_emitter)?;
+            }
         } else {
             accounts.order.load_mut()?.header.cancelled()?;
         }
@@ -852,11 +1031,54 @@ impl<'info> ExecuteDecreaseOrderV2<'info> {
         Ok(())
     }
 
+    /// Split the sibling order off the remaining accounts if the order is a linked order.
+    fn split_linked_sibling(
+        &self,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<(
+        &'info [AccountInfo<'info>],
+        Option<&'info AccountInfo<'info>>,
+    )> {
+        let Some(expected) = self.order.load()?.linked_sibling().copied() else {
+            return Ok((remaining_accounts, None));
+        };
+        let (sibling, remaining_accounts) = remaining_accounts
+            .split_last()
+            .ok_or_else(|| error!(ErrorCode::AccountNotEnoughKeys))?;
+        require_keys_eq!(*sibling.key, expected, CoreError::InvalidLinkedOrder);
+        Ok((remaining_accounts, Some(sibling)))
+    }
+
+    /// Cancel the sibling of an executed linked order (one-cancels-other).
+    #[inline(never)]
+    fn cancel_linked_sibling(
+        &self,
+        sibling: &'info AccountInfo<'info>,
+        event_emitter: &EventEmitter<'_, 'info>,
+    ) -> Result<()> {
+        // A TWAP order is not a linked order, so the order must be completed here.
+        require!(
+            self.order.load()?.header.action_state()?.is_completed(),
+            CoreError::Internal
+        );
+        // The sibling closed by the owner is skipped.
+        if *sibling.owner != crate::ID {
+            return Ok(());
+        }
+        let sibling = AccountLoader::<Order>::try_from(sibling)?;
+        require!(
+            sibling.load()?.linked_sibling() == Some(&self.order.key()),
+            CoreError::InvalidLinkedOrder
+        );
+        cancel_pending_linked_order(&sibling, Some(&self.order.key()), event_emitter)
+    }
+
     #[inline(never)]
     fn perform_execution(
         &mut self,
         remaining_accounts: &'info [AccountInfo<'info>],
         throw_on_execution_error: bool,
+        user_stats: Option<&AccountLoader<'info, UserStats>>,
         event_emitter: &EventEmitter<'_, 'info>,
     ) -> Result<(RemovePosition, Box<TransferOut>, ShouldSendTradeEvent)> {
         // Note: We only need the tokens here, the feeds are not necessary.
@@ -880,7 +1102,8 @@ impl<'info> ExecuteDecreaseOrderV2<'info> {
             .callback_authority(self.callback_authority.as_ref())
             .callback_program(self.callback_program.as_deref())
             .callback_shared_data_account(self.callback_shared_data_account.as_deref())
-            .callback_partitioned_data_account(self.callback_partitioned_data_account.as_deref());
+            .callback_partitioned_data_account(self.callback_partitioned_data_account.as_deref())
+            .user_stats(user_stats);
 
         self.oracle.load_mut()?.with_prices(
             &self.store,
@@ -947,7 +1170,10 @@ impl<'info> ExecuteDecreaseOrderV2<'info> {
 
     #[inline(never)]
     fn pay_execution_fee(&self, execution_fee: u64) -> Result<()> {
-        let execution_lamports = self.order.load()?.execution_lamports(execution_fee);
+        let execution_lamports = self
+            .order
+            .load()?
+            .execution_lamports_per_execution(execution_fee);
         PayExecutionFeeOperation::builder()
             .payer(self.order.to_account_info())
             .receiver(self.authority.to_account_info())
@@ -0,0 +1,618 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use gmsol_utils::{action::ActionCallbackKind, order::OrderKind};
+
+use crate::{
+    constants,
+    events::{EventEmitter, TradeData, TradeEventRef},
+    ops::{
+        execution_fee::PayExecutionFeeOperation,
+        market::{MarketTransferInOperation, MarketTransferOutOperation},
+        order::{ExecuteOrderOperation, ProcessTransferOutOperation},
+    },
+    states::{
+        common::action::ActionSigner,
+        feature::{ActionDisabledFlag, DomainDisabledFlag},
+        order::{Order, TransferOut},
+        position::Position,
+        user::{UserHeader, UserStats},
+        HasMarketMeta, Market, Oracle, Store, TokenMapHeader, ValidateOracleTime,
+    },
+    utils::{internal, token::validate_transferable_escrow},
+    CoreError, CoreResult,
+};
+
+use super::execute_order::{
+    load_user_stats, required_sibling_position, sibling_position_size_in_usd,
+};
+
+/// The number of accounts required for each order in the remaining accounts.
+pub const NUM_ACCOUNTS_PER_ORDER: usize = 9;
+
+/// The accounts definition for [`execute_orders`](crate::gmsol_store::execute_orders)
+/// instruction.
+///
+/// Remaining accounts expected by this instruction:
+///
+///   - 0..N. `[]` N feed accounts, where N represents the total number of unique tokens
+///     in the market.
+///   - N..N+9*O. `[writable]` O groups of order accounts, where O is the `count` argument.
+///     Each group consists of the order, its owner, the User Account of the owner, the user
+///     stats account of the owner, its position, the position of the same owner and side with
+///     the other collateral token, its initial collateral token escrow, its long token escrow
+///     and its short token escrow. The user stats account is only required if the owner has
+///     prepared one. The position with the other collateral token is only required if the
+///     market has a max position size configured for the side. An optional account that is
+///     not provided must be replaced with the address of this program.
+///   - N+9*O..N+9*O+V. `[writable]` V virtual inventory accounts, where V represents the total
+///     number of unique virtual inventories required by the market.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteOrders<'info> {
+    /// Authority.
+    pub authority: Signer<'info>,
+    /// Store.
+    #[account(mut, has_one = token_map)]
+    pub store: AccountLoader<'info, Store>,
+    /// Token Map.
+    #[account(has_one = store)]
+    pub token_map: AccountLoader<'info, TokenMapHeader>,
+    /// Oracle buffer to use.
+    #[account(mut, has_one = store)]
+    pub oracle: AccountLoader<'info, Oracle>,
+    /// Market.
+    #[account(mut, has_one = store)]
+    pub market: AccountLoader<'info, Market>,
+    /// Trade event buffer.
+    #[account(mut, has_one = store, has_one = authority)]
+    pub event: AccountLoader<'info, TradeData>,
+    /// Long token.
+    #[account(constraint = market.load()?.meta().long_token_mint == long_token.key() @ CoreError::TokenMintMismatched)]
+    pub long_token: Box<Account<'info, Mint>>,
+    /// Short token.
+    #[account(constraint = market.load()?.meta().short_token_mint == short_token.key() @ CoreError::TokenMintMismatched)]
+    pub short_token: Box<Account<'info, Mint>>,
+    /// Long token vault.
+    #[account(
+        mut,
+        token::mint = long_token,
+        token::authority = store,
+        seeds = [
+            constants::MARKET_VAULT_SEED,
+            store.key().as_ref(),
+            long_token_vault.mint.as_ref(),
+        ],
+        bump,
+    )]
+    pub long_token_vault: Box<Account<'info, TokenAccount>>,
+    /// Short token vault.
+    #[account(
+        mut,
+        token::mint = short_token,
+        token::authority = store,
+        seeds = [
+            constants::MARKET_VAULT_SEED,
+            store.key().as_ref(),
+            short_token_vault.mint.as_ref(),
+        ],
+        bump,
+    )]
+    pub short_token_vault: Box<Account<'info, TokenAccount>>,
+    /// The token program.
+    pub token_program: Program<'info, Token>,
+    /// The system program.
+    pub system_program: Program<'info, System>,
+}
+
+/// CHECK: only ORDER_KEEPER can invoke this instruction.
+#[inline(never)]
+pub(crate) fn unchecked_execute_orders<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteOrders<'info>>,
+    execution_fee: u64,
+    count: u8,
+) -> Result<()> {
+    let accounts = ctx.accounts;
+
+    // Validate feature enabled.
+    accounts.store.load()?.validate_feature_enabled(
+        DomainDisabledFlag::MarketIncrease,
+        ActionDisabledFlag::Execute,
+    )?;
+    accounts.market.load()?.validate_feature_enabled(
+        DomainDisabledFlag::MarketIncrease,
+        ActionDisabledFlag::Execute,
+    )?;
+
+    require_neq!(count, 0, CoreError::InvalidArgument);
+
+    let tokens = accounts
+        .market
+        .load()?
+        .market_meta()
+        .ordered_tokens()
+        .into_iter()
+        .collect::<Vec<_>>();
+
+    let event_authority = accounts.event_authority.clone();
+    let event_emitter = EventEmitter::new(&event_authority, ctx.bumps.event_authority);
+
+    let oracle_loader = accounts.oracle.clone();
+    let store = accounts.store.clone();
+    let token_map = accounts.token_map.clone();
+    let processed = oracle_loader.load_mut()?.with_prices(
+        &store,
+        &token_map,
+        &tokens,
+        ctx.remaining_accounts,
+        |oracle, remaining_accounts| {
+            let len = usize::from(count) * NUM_ACCOUNTS_PER_ORDER;
+            require_gte!(
+                remaining_accounts.len(),
+                len,
+                ErrorCode::AccountNotEnoughKeys
+            );
+            let (groups, virtual_inventories) = remaining_accounts.split_at(len);
+
+            let mut processed = Vec::with_capacity(usize::from(count));
+            for group in groups.chunks_exact(NUM_ACCOUNTS_PER_ORDER) {
+                if let Some(order) =
+                    accounts.execute_one(oracle, group, virtual_inventories, &event_emitter)?
+                {
+                    processed.push(order);
+                }
+            }
+            Ok(processed)
+        },
+    )?;
+
+    // It must be placed at the end to be executed correctly.
+    for order in processed {
+        accounts.pay_execution_fee(&order, execution_fee)?;
+    }
+
+    Ok(())
+}
+
+impl<'info> internal::Authentication<'info> for ExecuteOrders<'info> {
+    fn authority(&self) -> &Signer<'info> {
+        &self.authority
+    }
+
+    fn store(&self) -> &AccountLoader<'info, Store> {
+        &self.store
+    }
+}
+
+/// The accounts of an order in a batch.
+struct OrderAccounts<'info> {
+    order: AccountLoader<'info, Order>,
+    owner: &'info AccountInfo<'info>,
+    user: AccountLoader<'info, UserHeader>,
+    user_stats: Option<AccountLoader<'info, UserStats>>,
+    position: AccountLoader<'info, Position>,
+    sibling_position_size_in_usd: u128,
+    initial_collateral_token: Option<OrderInitialCollateral<'info>>,
+    long_token_escrow: Option<&'info AccountInfo<'info>>,
+    short_token_escrow: Option<&'info AccountInfo<'info>>,
+    signer: ActionSigner,
+}
+
+/// The initial collateral token of an order in a batch.
+struct OrderInitialCollateral<'info> {
+    escrow: &'info AccountInfo<'info>,
+    is_long_token: bool,
+    amount: u64,
+}
+
+/// Validates that the oracle prices are not older than the last update of an order.
+///
+/// The expiration is checked during the execution instead, so that an expired order
+/// can be cancelled in the batch.
+struct OrderOracleTime<'a> {
+    order: &'a Order,
+}
+
+impl ValidateOracleTime for OrderOracleTime<'_> {
+    fn oracle_updated_after(&self) -> CoreResult<Option<i64>> {
+        // Note: `valid_from_ts` of a market order is only set for the slices of a TWAP order.
+        Ok(Some(
+            self.order
+                .header
+                .updated_at
+                .max(self.order.params.valid_from_ts),
+        ))
+    }
+
+    fn oracle_updated_before(&self) -> CoreResult<Option<i64>> {
+        Ok(None)
+    }
+
+    fn oracle_updated_after_slot(&self) -> CoreResult<Option<u64>> {
+        Ok(Some(self.order.header.updated_at_slot))
+    }
+}
+
+impl<'info> ExecuteOrders<'info> {
+    /// Execute one order of the batch.
+    ///
+    /// Returns the order if it has been processed (executed or cancelled),
+    /// or `None` if it is skipped and left pending.
+    #[inline(never)]
+    fn execute_one(
+        &mut self,
+        oracle: &Oracle,
+        group: &'info [AccountInfo<'info>],
+        virtual_inventories: &'info [AccountInfo<'info>],
+        event_emitter: &EventEmitter<'_, 'info>,
+    ) -> Result<Option<AccountLoader<'info, Order>>> {
+        let (info, group) = group
+            .split_first()
+            .ok_or_else(|| error!(ErrorCode::AccountNotEnoughKeys))?;
+
+        // An order that cannot be executed is skipped without affecting the others.
+        let accounts = match self.prepare_one(oracle, info, group) {
+            Ok(Some(accounts)) => accounts,
+            Ok(None) => return Ok(None),
+            Err(err) => {
+                msg!("[Order] skipped order {}: {}", info.key, err);
+                return Ok(None);
+            }
+        };
+
+        self.transfer_tokens_in(&accounts, event_emitter)?;
+
+        let (is_position_removed, transfer_out, should_send_trade_event) =
+            ExecuteOrderOperation::builder()
+                .store(&self.store)
+                .market(&self.market)
+                .owner(accounts.owner.clone())
+                .user(&accounts.user)
+                .order(&accounts.order)
+                .position(Some(&accounts.position))
+                .event(Some(&self.event))
+                .throw_on_execution_error(false)
+                .executor(self.authority.to_account_info())
+                .event_emitter(*event_emitter)
+                .callback_authority(None)
+                .callback_program(None)
+                .callback_shared_data_account(None)
+                .callback_partitioned_data_account(None)
+                .user_stats(accounts.user_stats.as_ref())
+                .sibling_position_size_in_usd(accounts.sibling_position_size_in_usd)
+                .oracle(oracle)
+                .remaining_accounts(virtual_inventories)
+                .build()
+                .execute()?;
+
+        if transfer_out.executed() {
+            accounts.order.load_mut()?.complete_execution()?;
+            self.process_transfer_out(&accounts, &transfer_out, event_emitter)?;
+        } else {
+            accounts.order.load_mut()?.header.cancelled()?;
+            self.transfer_tokens_out(&accounts, event_emitter)?;
+        }
+
+        if should_send_trade_event {
+            let event = self.event.load()?;
+            let event = TradeEventRef::from(&*event);
+            event_emitter.emit_cpi(&event)?;
+        }
+
+        if is_position_removed {
+            msg!("[Position] the position is removed");
+        }
+
+        Ok(Some(accounts.order))
+    }
+
+    /// Validate the accounts of an order of the batch without modifying any state.
+    ///
+    /// Returns `None` if the order should be skipped.
+    fn prepare_one(
+        &self,
+        oracle: &Oracle,
+        info: &'info AccountInfo<'info>,
+        group: &'info [AccountInfo<'info>],
+    ) -> Result<Option<OrderAccounts<'info>>> {
+        let [owner, user, user_stats, position, sibling_position, initial_collateral_token_escrow, long_token_escrow, short_token_escrow] =
+            group
+        else {
+            return err!(ErrorCode::AccountNotEnoughKeys);
+        };
+        let optional = |info: &'info AccountInfo<'info>| (*info.key != crate::ID).then_some(info);
+
+        require!(info.is_writable, ErrorCode::ConstraintMut);
+        let order_loader = AccountLoader::<Order>::try_from(info)?;
+        let order = order_loader.load()?;
+        let header = &order.header;
+        require_keys_eq!(header.store, self.store.key(), CoreError::StoreMismatched);
+        require_keys_eq!(
+            header.market,
+            self.market.key(),
+            CoreError::MarketMismatched
+        );
+
+        if !header.action_state()?.is_pending() {
+            msg!("[Order] skipped order {}: not pending", info.key);
+            return Ok(None);
+        }
+
+        let params = order.params();
+        if !matches!(params.kind()?, OrderKind::MarketIncrease) {
+            msg!(
+                "[Order] skipped order {}: only market increase orders are supported",
+                info.key
+            );
+            return Ok(None);
+        }
+
+        let swap = order.swap();
+        if swap.primary_length() != 0 || swap.secondary_length() != 0 {
+            msg!("[Order] skipped order {}: swap is not supported", info.key);
+            return Ok(None);
+        }
+
+        if !matches!(header.callback_kind()?, ActionCallbackKind::Disabled)
+            || order.is_linked()
+            || order.has_pending_linked_orders()
+        {
+            msg!(
+                "[Order] skipped order {}: callbacks and linked orders are not supported",
+                info.key
+            );
+            return Ok(None);
+        }
+
+        order.validate_valid_from_ts()?;
+        order.validate_valid_until_ts()?;
+        order.validate_activated()?;
+
+        match oracle.validate_time(&OrderOracleTime { order: &order }) {
+            Ok(()) => {}
+            Err(
+                CoreError::OracleTimestampsAreSmallerThanRequired | CoreError::InvalidOracleSlot,
+            ) => {
+                msg!(
+                    "[Order] skipped order {}: prices are older than the order",
+                    info.key
+                );
+                return Ok(None);
+            }
+            Err(err) => return Err(error!(err)),
+        }
+
+        // Validate the owner, the User Account and the user stats account.
+        require_keys_eq!(*owner.key, header.owner, CoreError::OwnerMismatched);
+        require!(owner.is_writable, ErrorCode::ConstraintMut);
+        require!(user.is_writable, ErrorCode::ConstraintMut);
+        let user = AccountLoader::<UserHeader>::try_from(user)?;
+        {
+            let user = user.load()?;
+            require!(user.is_initialized(), CoreError::InvalidUserAccount);
+            require_keys_eq!(user.owner, header.owner, CoreError::OwnerMismatched);
+            require_keys_eq!(user.store, header.store, CoreError::StoreMismatched);
+        }
+        let user_stats = if user.load()?.has_stats() {
+            Some(load_user_stats(&user, user_stats)?)
+        } else {
+            None
+        };
+
+        // Validate the positions.
+        require!(
+            params.position() == Some(position.key),
+            CoreError::PositionMismatched
+        );
+        require!(position.is_writable, ErrorCode::ConstraintMut);
+        let position = AccountLoader::<Position>::try_from(position)?;
+        let sibling_position_size_in_usd = {
+            let position = position.load()?;
+            require_keys_eq!(position.owner, header.owner, CoreError::OwnerMismatched);
+            require_keys_eq!(position.store, header.store, CoreError::StoreMismatched);
+            match required_sibling_position(&self.store.key(), &*self.market.load()?, &position)? {
+                Some(expected) => {
+                    require_keys_eq!(
+                        *sibling_position.key,
+                        expected,
+                        CoreError::PositionMismatched
+                    );
+                    sibling_position_size_in_usd(sibling_position)?
+                }
+                None => 0,
+            }
+        };
+
+        // Validate the escrows, so that the transfers of the execution cannot fail.
+        let tokens = order.tokens();
+        let token_program_id = self.token_program.key();
+        let initial_collateral_token_escrow = optional(initial_collateral_token_escrow);
+        require!(
+            tokens.initial_collateral().account()
+                == initial_collateral_token_escrow.map(|a| a.key()),
+            CoreError::TokenAccountMismatched
+        );
+        let initial_collateral_token = match initial_collateral_token_escrow {
+            Some(escrow) => {
+                let token = tokens
+                    .initial_collateral()
+                    .token()
+                    .ok_or_else(|| error!(CoreError::TokenMintNotProvided))?;
+                let is_long_token = if token == self.long_token.key() {
+                    true
+                } else {
+                    require_keys_eq!(
+                        token,
+                        self.short_token.key(),
+                        CoreError::TokenMintMismatched
+                    );
+                    false
+                };
+                let amount = params.slice_amount();
+                validate_transferable_escrow(escrow, info.key, &token, &token_program_id, amount)?;
+                Some(OrderInitialCollateral {
+                    escrow,
+                    is_long_token,
+                    amount,
+                })
+            }
+            None => None,
+        };
+
+        let long_token_escrow = optional(long_token_escrow);
+        require!(
+            tokens.long_token().account() == long_token_escrow.map(|a| a.key()),
+            CoreError::TokenAccountMismatched
+        );
+        if let Some(escrow) = long_token_escrow {
+            validate_transferable_escrow(
+                escrow,
+                info.key,
+                &self.long_token.key(),
+                &token_program_id,
+                0,
+            )?;
+        }
+
+        let short_token_escrow = optional(short_token_escrow);
+        require!(
+            tokens.short_token().account() == short_token_escrow.map(|a| a.key()),
+            CoreError::TokenAccountMismatched
+        );
+        if let Some(escrow) = short_token_escrow {
+            validate_transferable_escrow(
+                escrow,
+                info.key,
+                &self.short_token.key(),
+                &token_program_id,
+                0,
+            )?;
+        }
+
+        let signer = order.signer();
+        drop(order);
+
+        Ok(Some(OrderAccounts {
+            order: order_loader,
+            owner,
+            user,
+            user_stats,
+            position,
+            sibling_position_size_in_usd,
+            initial_collateral_token,
+            long_token_escrow,
+            short_token_escrow,
+            signer,
+        }))
+    }
+
+    #[inline(never)]
+    fn transfer_tokens_in(
+        &self,
+        accounts: &OrderAccounts<'info>,
+        event_emitter: &EventEmitter<'_, 'info>,
+    ) -> Result<()> {
+        let Some(collateral) = accounts.initial_collateral_token.as_ref() else {
+            return Ok(());
+        };
+        let vault = if collateral.is_long_token {
+            &self.long_token_vault
+        } else {
+            &self.short_token_vault
+        };
+        MarketTransferInOperation::builder()
+            .store(&self.store)
+            .from_authority(accounts.order.to_account_info())
+            .token_program(self.token_program.to_account_info())
+            .signer_seeds(&accounts.signer.as_seeds())
+            .market(&self.market)
+            .from(collateral.escrow.clone())
+            .vault(vault)
+            .amount(collateral.amount)
+            .event_emitter(*event_emitter)
+            .build()
+            .execute()?;
+        Ok(())
+    }
+
+    #[inline(never)]
+    fn transfer_tokens_out(
+        &self,
+        accounts: &OrderAccounts<'info>,
+        event_emitter: &EventEmitter<'_, 'info>,
+    ) -> Result<()> {
+        let Some(collateral) = accounts.initial_collateral_token.as_ref() else {
+            return Ok(());
+        };
+        let (vault, token) = if collateral.is_long_token {
+            (&self.long_token_vault, &self.long_token)
+        } else {
+            (&self.short_token_vault, &self.short_token)
+        };
+        MarketTransferOutOperation::builder()
+            .store(&self.store)
+            .token_program(self.token_program.to_account_info())
+            .market(&self.market)
+            .to(collateral.escrow.clone())
+            .vault(vault.to_account_info())
+            .amount(collateral.amount)
+            .decimals(token.decimals)
+            .token_mint(token.to_account_info())
+            .allow_closed(true)
+            .event_emitter(*event_emitter)
+            .build()
+            .execute()?;
+        Ok(())
+    }
+
+    #[inline(never)]
+    fn process_transfer_out(
+        &self,
+        accounts: &OrderAccounts<'info>,
+        transfer_out: &TransferOut,
+        event_emitter: &EventEmitter<'_, 'info>,
+    ) -> Result<()> {
+        let is_pnl_token_long_token = accounts.order.load()?.params.side()?.is_long();
+        ProcessTransferOutOperation::builder()
+            .token_program(self.token_program.to_account_info())
+            .store(&self.store)
+            .market(&self.market)
+            .is_pnl_token_long_token(is_pnl_token_long_token)
+            .final_output_token(None)
+            .final_output_market(&self.market)
+            .final_output_token_account(None)
+            .final_output_token_vault(None)
+            .long_token(Some(&self.long_token))
+            .long_token_account(accounts.long_token_escrow.cloned())
+            .long_token_vault(Some(&self.long_token_vault))
+            .short_token(Some(&self.short_token))
+            .short_token_account(accounts.short_token_escrow.cloned())
+            .short_token_vault(Some(&self.short_token_vault))
+            .claimable_long_token_account_for_user(None)
+            .claimable_short_token_account_for_user(None)
+            .claimable_pnl_token_account_for_holding(None)
+            .transfer_out(transfer_out)
+            .allow_closed(true)
+            .event_emitter(*event_emitter)
+            .build()
+            .execute()?;
+        Ok(())
+    }
+
+    #[inline(never)]
+    fn pay_execution_fee(
+        &self,
+        order: &AccountLoader<'info, Order>,
+        execution_fee: u64,
+    ) -> Result<()> {
+        let execution_lamports = order
+            .load()?
+            .execution_lamports_per_execution(execution_fee);
+        PayExecutionFeeOperation::builder()
+            .payer(order.to_account_info())
+            .receiver(self.authority.to_account_info())
+            .execution_lamports(execution_lamports)
+            .build()
+            .execute()?;
+        Ok(())
+    }
+}
@@ -121,6 +121,11 @@ pub fn unchecked_execute_shift<'info>(
         .store
         .load()?
         .validate_feature_enabled(DomainDisabledFlag::Shift, ActionDisabledFlag::Execute)?;
+    for market in [&accounts.from_market, &accounts.to_market] {
+        market
+            .load()?
+            .validate_feature_enabled(DomainDisabledFlag::Shift, ActionDisabledFlag::Execute)?;
+    }
 
     let signer = accounts.shift.load()?.signer();
 
@@ -138,7 +138,8 @@ pub struct ExecuteWithdrawal<'info> {
     pub chainlink_program: Option<Program<'info, Chainlink>>,
 }
 
-/// CHECK only ORDER_KEEPER can invoke this instruction.
+/// CHECK: only ORDER_KEEPER can invoke this instruction, unless the self-execution
+/// of the action is allowed.
 pub(crate) fn unchecked_execute_withdrawal<'info>(
     ctx: Context<'_, '_, 'info, 'info, ExecuteWithdrawal<'info>>,
     execution_fee: u64,
@@ -152,6 +153,10 @@ pub(crate) fn unchecked_execute_withdrawal<'info>(
         .store
         .load()?
         .validate_feature_enabled(DomainDisabledFlag::Withdrawal, ActionDisabledFlag::Execute)?;
+    accounts
+        .market
+        .load()?
+        .validate_feature_enabled(DomainDisabledFlag::Withdrawal, ActionDisabledFlag::Execute)?;
 
     let signer = accounts.withdrawal.load()?.signer();
 
@@ -195,6 +200,12 @@ impl<'info> internal::Authentication<'info> for ExecuteWithdrawal<'info> {
     }
 }
 
+impl<'info> internal::SelfExecution<'info> for ExecuteWithdrawal<'info> {
+    fn pending_since(&self) -> Result<Option<i64>> {
+        Ok(Some(self.withdrawal.load()?.header.updated_at()))
+    }
+}
+
 impl<'info> ExecuteWithdrawal<'info> {
     fn perform_execution(
         &mut self,
@@ -0,0 +1,510 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked};
+
+use crate::{
+    constants,
+    events::EventEmitter,
+    ops::{
+        execution_fee::PayExecutionFeeOperation, market::MarketTransferOutOperation,
+        withdrawal::ExecuteWithdrawalOperation,
+    },
+    states::{
+        common::action::{ActionExt, ActionSigner},
+        feature::{ActionDisabledFlag, DomainDisabledFlag},
+        withdrawal::Withdrawal,
+        Chainlink, HasMarketMeta, Market, Oracle, Store, TokenMapHeader, ValidateOracleTime,
+    },
+    utils::{internal, token::validate_transferable_escrow},
+    CoreError, CoreResult,
+};
+
+/// The number of accounts required for each withdrawal in the remaining accounts.
+pub const NUM_ACCOUNTS_PER_WITHDRAWAL: usize = 4;
+
+/// The accounts definition for [`execute_withdrawals`](crate::gmsol_store::execute_withdrawals)
+/// instruction.
+///
+/// Remaining accounts expected by this instruction:
+///
+///   - 0..N. `[]` N feed accounts, where N represents the total number of unique tokens
+///     in the market.
+///   - N..N+4*W. `[writable]` W groups of withdrawal accounts, where W is the `count` argument.
+///     Each group consists of the withdrawal, its market token escrow, its final long token
+///     escrow and its final short token escrow.
+///   - N+4*W..N+4*W+V. `[writable]` V virtual inventory accounts, where V represents the total
+///     number of unique virtual inventories required by the market.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteWithdrawals<'info> {
+    /// Authority.
+    pub authority: Signer<'info>,
+    /// Store.
+    #[account(has_one = token_map)]
+    pub store: AccountLoader<'info, Store>,
+    /// Token Map.
+    #[account(has_one = store)]
+    pub token_map: AccountLoader<'info, TokenMapHeader>,
+    /// Oracle buffer to use.
+    #[account(mut, has_one = store)]
+    pub oracle: AccountLoader<'info, Oracle>,
+    /// Market.
+    #[account(mut, has_one = store)]
+    pub market: AccountLoader<'info, Market>,
+    /// Market token mint.
+    #[account(mut, constraint = market.load()?.meta().market_token_mint == market_token.key() @ CoreError::MarketTokenMintMismatched)]
+    pub market_token: Box<Account<'info, Mint>>,
+    /// Long token.
+    #[account(constraint = market.load()?.meta().long_token_mint == long_token.key() @ CoreError::TokenMintMismatched)]
+    pub long_token: Box<Account<'info, Mint>>,
+    /// Short token.
+    #[account(constraint = market.load()?.meta().short_token_mint == short_token.key() @ CoreError::TokenMintMismatched)]
+    pub short_token: Box<Account<'info, Mint>>,
+    /// Market token vault.
+    #[account(
+        mut,
+        token::mint = market_token,
+        seeds = [
+            constants::MARKET_VAULT_SEED,
+            store.key().as_ref(),
+            market_token_vault.mint.as_ref(),
+        ],
+        bump,
+    )]
+    pub market_token_vault: Box<Account<'info, TokenAccount>>,
+    /// Long token vault.
+    #[account(
+        mut,
+        token::mint = long_token,
+        token::authority = store,
+        seeds = [
+            constants::MARKET_VAULT_SEED,
+            store.key().as_ref(),
+            long_token_vault.mint.as_ref(),
+        ],
+        bump,
+    )]
+    pub long_token_vault: Box<Account<'info, TokenAccount>>,
+    /// Short token vault.
+    #[account(
+        mut,
+        token::mint = short_token,
+        token::authority = store,
+        seeds = [
+            constants::MARKET_VAULT_SEED,
+            store.key().as_ref(),
+            short_token_vault.mint.as_ref(),
+        ],
+        bump,
+    )]
+    pub short_token_vault: Box<Account<'info, TokenAccount>>,
+    /// The token program.
+    pub token_program: Program<'info, Token>,
+    /// The system program.
+    pub system_program: Program<'info, System>,
+    /// Chainlink Program.
+    pub chainlink_program: Option<Program<'info, Chainlink>>,
+}
+
+/// CHECK: only ORDER_KEEPER can invoke this instruction.
+#[inline(never)]
+pub(crate) fn unchecked_execute_withdrawals<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteWithdrawals<'info>>,
+    execution_fee: u64,
+    count: u8,
+) -> Result<()> {
+    let accounts = ctx.accounts;
+
+    // Validate feature enabled.
+    accounts
+        .store
+        .load()?
+        .validate_feature_enabled(DomainDisabledFlag::Withdrawal, ActionDisabledFlag::Execute)?;
+    accounts
+        .market
+        .load()?
+        .validate_feature_enabled(DomainDisabledFlag::Withdrawal, ActionDisabledFlag::Execute)?;
+
+    require_neq!(count, 0, CoreError::InvalidArgument);
+
+    let tokens = accounts
+        .market
+        .load()?
+        .market_meta()
+        .ordered_tokens()
+        .into_iter()
+        .collect::<Vec<_>>();
+
+    let event_authority = accounts.event_authority.clone();
+    let event_emitter = EventEmitter::new(&event_authority, ctx.bumps.event_authority);
+
+    let oracle_loader = accounts.oracle.clone();
+    let store = accounts.store.clone();
+    let token_map = accounts.token_map.clone();
+    let processed = oracle_loader.load_mut()?.with_prices(
+        &store,
+        &token_map,
+        &tokens,
+        ctx.remaining_accounts,
+        |oracle, remaining_accounts| {
+            let len = usize::from(count) * NUM_ACCOUNTS_PER_WITHDRAWAL;
+            require_gte!(
+                remaining_accounts.len(),
+                len,
+                ErrorCode::AccountNotEnoughKeys
+            );
+            let (groups, virtual_inventories) = remaining_accounts.split_at(len);
+
+            let mut processed = Vec::with_capacity(usize::from(count));
+            for group in groups.chunks_exact(NUM_ACCOUNTS_PER_WITHDRAWAL) {
+                if let Some(withdrawal) =
+                    accounts.execute_one(oracle, group, virtual_inventories, &event_emitter)?
+                {
+                    processed.push(withdrawal);
+                }
+            }
+            Ok(processed)
+        },
+    )?;
+
+    // It must be placed at the end to be executed correctly.
+    for withdrawal in processed {
+        accounts.pay_execution_fee(&withdrawal, execution_fee)?;
+    }
+
+    Ok(())
+}
+
+impl<'info> internal::Authentication<'info> for ExecuteWithdrawals<'info> {
+    fn authority(&self) -> &Signer<'info> {
+        &self.authority
+    }
+
+    fn store(&self) -> &AccountLoader<'info, Store> {
+        &self.store
+    }
+}
+
+/// The escrow accounts of a withdrawal in a batch.
+struct WithdrawalEscrows<'info> {
+    market_token: &'info AccountInfo<'info>,
+    final_long_token: &'info AccountInfo<'info>,
+    final_short_token: &'info AccountInfo<'info>,
+}
+
+impl<'info> WithdrawalEscrows<'info> {
+    /// Validate the escrow accounts of the withdrawal, so that the transfers
+    /// of the execution cannot fail.
+    fn new(
+        accounts: &ExecuteWithdrawals<'info>,
+        withdrawal_key: &Pubkey,
+        withdrawal: &Withdrawal,
+        group: &'info [AccountInfo<'info>],
+    ) -> Result<Self> {
+        let [market_token, final_long_token, final_short_token] = group else {
+            return err!(ErrorCode::AccountNotEnoughKeys);
+        };
+        let escrows = Self {
+            market_token,
+            final_long_token,
+            final_short_token,
+        };
+
+        let tokens = &withdrawal.tokens;
+        require_keys_eq!(
+            tokens.market_token_account(),
+            escrows.market_token.key(),
+            CoreError::MarketTokenAccountMismatched
+        );
+        require_keys_eq!(
+            tokens.final_long_token_account(),
+            escrows.final_long_token.key(),
+            CoreError::TokenAccountMismatched
+        );
+        require_keys_eq!(
+            tokens.final_short_token_account(),
+            escrows.final_short_token.key(),
+            CoreError::TokenAccountMismatched
+        );
+
+        let token_program_id = accounts.token_program.key();
+        validate_transferable_escrow(
+            escrows.market_token,
+            withdrawal_key,
+            &accounts.market_token.key(),
+            &token_program_id,
+            withdrawal.params.market_token_amount,
+        )?;
+        validate_transferable_escrow(
+            escrows.final_long_token,
+            withdrawal_key,
+            &accounts.long_token.key(),
+            &token_program_id,
+            0,
+        )?;
+        validate_transferable_escrow(
+            escrows.final_short_token,
+            withdrawal_key,
+            &accounts.short_token.key(),
+            &token_program_id,
+            0,
+        )?;
+        Ok(escrows)
+    }
+}
+
+/// Validates that the oracle prices are not older than the last update of a withdrawal.
+///
+/// The expiration is checked during the execution instead, so that an expired withdrawal
+/// can be cancelled in the batch.
+struct WithdrawalOracleTime<'a> {
+    withdrawal: &'a Withdrawal,
+}
+
+impl ValidateOracleTime for WithdrawalOracleTime<'_> {
+    fn oracle_updated_after(&self) -> CoreResult<Option<i64>> {
+        Ok(Some(self.withdrawal.header.updated_at))
+    }
+
+    fn oracle_updated_before(&self) -> CoreResult<Option<i64>> {
+        Ok(None)
+    }
+
+    fn oracle_updated_after_slot(&self) -> CoreResult<Option<u64>> {
+        Ok(Some(self.withdrawal.header.updated_at_slot))
+    }
+}
+
+impl<'info> ExecuteWithdrawals<'info> {
+    /// Execute one withdrawal of the batch.
+    ///
+    /// Returns the withdrawal if it has been processed (executed or cancelled),
+    /// or `None` if it is skipped and left pending.
+    #[inline(never)]
+    fn execute_one(
+        &mut self,
+        oracle: &Oracle,
+        group: &'info [AccountInfo<'info>],
+        virtual_inventories: &'info [AccountInfo<'info>],
+        event_emitter: &EventEmitter<'_, 'info>,
+    ) -> Result<Option<AccountLoader<'info, Withdrawal>>> {
+        let (info, group) = group
+            .split_first()
+            .ok_or_else(|| error!(ErrorCode::AccountNotEnoughKeys))?;
+
+        // A withdrawal that cannot be executed is skipped without affecting the others.
+        let (withdrawal, escrows, signer) = match self.prepare_one(oracle, info, group) {
+            Ok(Some(prepared)) => prepared,
+            Ok(None) => return Ok(None),
+            Err(err) => {
+                msg!("[Withdrawal] skipped withdrawal {}: {}", info.key, err);
+                return Ok(None);
+            }
+        };
+
+        self.transfer_market_tokens_in(&withdrawal, &escrows, &signer)?;
+
+        let executed = ExecuteWithdrawalOperation::builder()
+            .store(&self.store)
+            .market(&self.market)
+            .withdrawal(&withdrawal)
+            .market_token_mint(&mut self.market_token)
+            .market_token_vault(self.market_token_vault.to_account_info())
+            .token_program(self.token_program.to_account_info())
+            .throw_on_execution_error(false)
+            .event_emitter(*event_emitter)
+            .oracle(oracle)
+            .remaining_accounts(virtual_inventories)
+            .build()
+            .execute()?;
+
+        // The supply of the market token must be refreshed for the next withdrawal.
+        self.market_token.reload()?;
+
+        match executed {
+            Some((final_long_token_amount, final_short_token_amount)) => {
+                withdrawal.load_mut()?.header.completed()?;
+                self.transfer_tokens_out(
+                    &escrows,
+                    final_long_token_amount,
+                    final_short_token_amount,
+                    event_emitter,
+                )?;
+            }
+            None => {
+                withdrawal.load_mut()?.header.cancelled()?;
+                self.transfer_market_tokens_out(&withdrawal, &escrows)?;
+            }
+        }
+
+        Ok(Some(withdrawal))
+    }
+
+    /// Validate the accounts of a withdrawal of the batch without modifying any state.
+    ///
+    /// Returns `None` if the withdrawal should be skipped.
+    #[allow(clippy::type_complexity)]
+    fn prepare_one(
+        &self,
+        oracle: &Oracle,
+        info: &'info AccountInfo<'info>,
+        group: &'info [AccountInfo<'info>],
+    ) -> Result<
+        Option<(
+            AccountLoader<'info, Withdrawal>,
+            WithdrawalEscrows<'info>,
+            ActionSigner,
+        )>,
+    > {
+        let withdrawal = AccountLoader::<Withdrawal>::try_from(info)?;
+        let (escrows, signer) = {
+            let withdrawal = withdrawal.load()?;
+            let header = &withdrawal.header;
+            require_keys_eq!(header.store, self.store.key(), CoreError::StoreMismatched);
+            require_keys_eq!(
+                header.market,
+                self.market.key(),
+                CoreError::MarketMismatched
+            );
+
+            if !header.action_state()?.is_pending() {
+                msg!("[Withdrawal] skipped withdrawal {}: not pending", info.key);
+                return Ok(None);
+            }
+
+            let swap = withdrawal.swap();
+            if swap.primary_length() != 0 || swap.secondary_length() != 0 {
+                msg!(
+                    "[Withdrawal] skipped withdrawal {}: swap is not supported",
+                    info.key
+                );
+                return Ok(None);
+            }
+
+            match oracle.validate_time(&WithdrawalOracleTime {
+                withdrawal: &withdrawal,
+            }) {
+                Ok(()) => {}
+                Err(
+                    CoreError::OracleTimestampsAreSmallerThanRequired
+                    | CoreError::InvalidOracleSlot,
+                ) => {
+                    msg!(
+                        "[Withdrawal] skipped withdrawal {}: prices are older than the withdrawal",
+                        info.key
+                    );
+                    return Ok(None);
+                }
+                Err(err) => return Err(error!(err)),
+            }
+
+            let escrows = WithdrawalEscrows::new(self, info.key, &withdrawal, group)?;
+            (escrows, withdrawal.signer())
+        };
+        Ok(Some((withdrawal, escrows, signer)))
+    }
+
+    #[inline(never)]
+    fn pay_execution_fee(
+        &self,
+        withdrawal: &AccountLoader<'info, Withdrawal>,
+        execution_fee: u64,
+    ) -> Result<()> {
+        let execution_lamports = withdrawal.load()?.execution_lamports(execution_fee);
+        PayExecutionFeeOperation::builder()
+            .payer(withdrawal.to_account_info())
+            .receiver(self.authority.to_account_info())
+            .execution_lamports(execution_lamports)
+            .build()
+            .execute()?;
+        Ok(())
+    }
+
+    fn transfer_market_tokens_in(
+        &self,
+        withdrawal: &AccountLoader<'info, Withdrawal>,
+        escrows: &WithdrawalEscrows<'info>,
+        signer: &ActionSigner,
+    ) -> Result<()> {
+        let seeds = signer.as_seeds();
+
+        transfer_checked(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: escrows.market_token.clone(),
+                    mint: self.market_token.to_account_info(),
+                    to: self.market_token_vault.to_account_info(),
+                    authority: withdrawal.to_account_info(),
+                },
+            )
+            .with_signer(&[&seeds]),
+            withdrawal.load()?.params.market_token_amount,
+            self.market_token.decimals,
+        )?;
+
+        Ok(())
+    }
+
+    fn transfer_market_tokens_out(
+        &self,
+        withdrawal: &AccountLoader<'info, Withdrawal>,
+        escrows: &WithdrawalEscrows<'info>,
+    ) -> Result<()> {
+        use crate::internal::TransferUtils;
+
+        let amount = withdrawal.load()?.params.market_token_amount;
+        TransferUtils::new(
+            self.token_program.to_account_info(),
+            &self.store,
+            self.market_token.to_account_info(),
+        )
+        .transfer_out(
+            self.market_token_vault.to_account_info(),
+            escrows.market_token.clone(),
+            amount,
+            self.market_token.decimals,
+        )?;
+
+        Ok(())
+    }
+
+    fn transfer_tokens_out(
+        &self,
+        escrows: &WithdrawalEscrows<'info>,
+        final_long_token_amount: u64,
+        final_short_token_amount: u64,
+        event_emitter: &EventEmitter<'_, 'info>,
+    ) -> Result<()> {
+        let builder = MarketTransferOutOperation::builder()
+            .store(&self.store)
+            .market(&self.market)
+            .token_program(self.token_program.to_account_info())
+            .allow_closed(false)
+            .event_emitter(*event_emitter);
+
+        if final_long_token_amount != 0 {
+            builder
+                .clone()
+                .to(escrows.final_long_token.clone())
+                .vault(self.long_token_vault.to_account_info())
+                .amount(final_long_token_amount)
+                .decimals(self.long_token.decimals)
+                .token_mint(self.long_token.to_account_info())
+                .build()
+                .execute()?;
+        }
+
+        if final_short_token_amount != 0 {
+            builder
+                .to(escrows.final_short_token.clone())
+                .vault(self.short_token_vault.to_account_info())
+                .amount(final_short_token_amount)
+                .decimals(self.short_token.decimals)
+                .token_mint(self.short_token.to_account_info())
+                .build()
+                .execute()?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,258 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    events::{EventEmitter, LinkedOrderCancelled, OrderUpdated},
+    states::{order::Order, Store},
+    utils::internal,
+    CoreError,
+};
+
+/// The accounts definitions for [`link_orders`](crate::gmsol_store::link_orders).
+#[event_cpi]
+#[derive(Accounts)]
+pub struct LinkOrders<'info> {
+    /// Owner.
+    pub owner: Signer<'info>,
+    /// Store.
+    pub store: AccountLoader<'info, Store>,
+    /// The parent (increase) order.
+    #[account(
+        mut,
+        constraint = parent.load()?.header.store == store.key() @ CoreError::StoreMismatched,
+        constraint = parent.load()?.header.owner == owner.key() @ CoreError::OwnerMismatched,
+        constraint = parent.load()?.header.action_state()?.is_pending() @ CoreError::PreconditionsAreNotMet,
+    )]
+    pub parent: AccountLoader<'info, Order>,
+    /// The first linked (decrease) order.
+    #[account(
+        mut,
+        constraint = first.key() != parent.key() @ CoreError::InvalidLinkedOrder,
+        constraint = first.load()?.header.store == store.key() @ CoreError::StoreMismatched,
+        constraint = first.load()?.header.owner == owner.key() @ CoreError::OwnerMismatched,
+        constraint = first.load()?.header.action_state()?.is_pending() @ CoreError::PreconditionsAreNotMet,
+    )]
+    pub first: AccountLoader<'info, Order>,
+    /// The second linked (decrease) order.
+    #[account(
+        mut,
+        constraint = second.key() != parent.key() @ CoreError::InvalidLinkedOrder,
+        constraint = second.key() != first.key() @ CoreError::InvalidLinkedOrder,
+        constraint = second.load()?.header.store == store.key() @ CoreError::StoreMismatched,
+        constraint = second.load()?.header.owner == owner.key() @ CoreError::OwnerMismatched,
+        constraint = second.load()?.header.action_state()?.is_pending() @ CoreError::PreconditionsAreNotMet,
+    )]
+    pub second: AccountLoader<'info, Order>,
+}
+
+impl LinkOrders<'_> {
+    pub(crate) fn invoke(ctx: Context<Self>) -> Result<()> {
+        ctx.accounts.validate()?;
+
+        let parent = ctx.accounts.parent.key();
+        let first = ctx.accounts.first.key();
+        let second = ctx.accounts.second.key();
+
+        ctx.accounts
+            .parent
+            .load_mut()?
+            .link_as_parent([first, second])?;
+        ctx.accounts
+            .first
+            .load_mut()?
+            .link_as_child(&parent, &second)?;
+        ctx.accounts
+            .second
+            .load_mut()?
+            .link_as_child(&parent, &first)?;
+
+        let event_emitter =
+            EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+        for order in [
+            &ctx.accounts.parent,
+            &ctx.accounts.first,
+            &ctx.accounts.second,
+        ] {
+            event_emitter.emit_cpi(&OrderUpdated::new(false, &order.key(), &*order.load()?)?)?;
+        }
+
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<()> {
+        self.store.load()?.validate_not_restarted()?;
+
+        let parent = self.parent.load()?;
+        for child in [&self.first, &self.second] {
+            let child = child.load()?;
+            require_keys_eq!(
+                child.header.market,
+                parent.header.market,
+                CoreError::MarketMismatched
+            );
+            require!(
+                child.params.position().is_some()
+                    && child.params.position() == parent.params.position(),
+                CoreError::PositionMismatched
+            );
+        }
+        Ok(())
+    }
+}
+
+/// The accounts definitions for [`activate_linked_orders`](crate::gmsol_store::activate_linked_orders).
+///
+/// Remaining accounts expected by this instruction:
+///
+///   - 0..2. `[writable]` The two linked orders recorded in the parent order, in the
+///     recorded order. A linked order that has been closed is skipped.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ActivateLinkedOrders<'info> {
+    /// Authority.
+    pub authority: Signer<'info>,
+    /// Store.
+    pub store: AccountLoader<'info, Store>,
+    /// The parent order.
+    #[account(
+        mut,
+        constraint = parent.load()?.header.store == store.key() @ CoreError::StoreMismatched,
+        constraint = parent.load()?.header.action_state()?.is_completed_or_cancelled() @ CoreError::PreconditionsAreNotMet,
+        constraint = parent.load()?.has_pending_linked_orders() @ CoreError::PreconditionsAreNotMet,
+    )]
+    pub parent: AccountLoader<'info, Order>,
+}
+
+/// Activate the linked orders if the parent has been executed, or cancel them otherwise.
+/// # CHECK
+/// Only [`ORDER_KEEPER`](crate::states::roles::RoleKey::ORDER_KEEPER) can use.
+pub(crate) fn unchecked_activate_linked_orders<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ActivateLinkedOrders<'info>>,
+) -> Result<()> {
+    let accounts = &ctx.accounts;
+    let parent = accounts.parent.key();
+    let (is_executed, linked_orders) = {
+        let parent = accounts.parent.load()?;
+        (
+            parent.header.action_state()?.is_completed(),
+            *parent.linked_orders(),
+        )
+    };
+
+    require_gte!(
+        ctx.remaining_accounts.len(),
+        linked_orders.len(),
+        ErrorCode::AccountNotEnoughKeys
+    );
+
+    let event_emitter = EventEmitter::new(&accounts.event_authority, ctx.bumps.event_authority);
+    for (info, expected) in ctx.remaining_accounts.iter().zip(linked_orders.iter()) {
+        require_keys_eq!(*info.key, *expected, CoreError::InvalidLinkedOrder);
+        // Linked orders closed by the owner are skipped.
+        if *info.owner != crate::ID {
+            continue;
+        }
+        let child = AccountLoader::<Order>::try_from(info)?;
+        if child.load()?.linked_parent() != Some(&parent) {
+            continue;
+        }
+        if is_executed {
+            {
+                let mut order = child.load_mut()?;
+                if !order.header.action_state()?.is_pending() {
+                    continue;
+                }
+                order.activate()?;
+            }
+            event_emitter.emit_cpi(&OrderUpdated::new(false, info.key, &*child.load()?)?)?;
+        } else {
+            cancel_pending_linked_order(&child, None, &event_emitter)?;
+        }
+    }
+
+    accounts.parent.load_mut()?.resolve_linked_orders()
+}
+
+impl<'info> internal::Authentication<'info> for ActivateLinkedOrders<'info> {
+    fn authority(&self) -> &Signer<'info> {
+        &self.authority
+    }
+
+    fn store(&self) -> &AccountLoader<'info, Store> {
+        &self.store
+    }
+}
+
+/// The accounts definitions for [`cancel_linked_order`](crate::gmsol_store::cancel_linked_order).
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelLinkedOrder<'info> {
+    /// Authority.
+    pub authority: Signer<'info>,
+    /// Store.
+    pub store: AccountLoader<'info, Store>,
+    /// The linked order to cancel.
+    #[account(
+        mut,
+        constraint = order.load()?.header.store == store.key() @ CoreError::StoreMismatched,
+        constraint = order.load()?.header.action_state()?.is_pending() @ CoreError::PreconditionsAreNotMet,
+        constraint = order.load()?.linked_sibling() == Some(&sibling.key()) @ CoreError::InvalidLinkedOrder,
+    )]
+    pub order: AccountLoader<'info, Order>,
+    /// The sibling of the order, which must have been executed.
+    #[account(
+        constraint = sibling.load()?.header.store == store.key() @ CoreError::StoreMismatched,
+        constraint = sibling.load()?.linked_sibling() == Some(&order.key()) @ CoreError::InvalidLinkedOrder,
+        constraint = sibling.load()?.header.action_state()?.is_completed() @ CoreError::PreconditionsAreNotMet,
+    )]
+    pub sibling: AccountLoader<'info, Order>,
+}
+
+/// Cancel the linked order since its sibling has been executed.
+/// # CHECK
+/// Only [`ORDER_KEEPER`](crate::states::roles::RoleKey::ORDER_KEEPER) can use.
+pub(crate) fn unchecked_cancel_linked_order(ctx: Context<CancelLinkedOrder>) -> Result<()> {
+    let accounts = &ctx.accounts;
+    let event_emitter = EventEmitter::new(&accounts.event_authority, ctx.bumps.event_authority);
+    cancel_pending_linked_order(
+        &accounts.order,
+        Some(&accounts.sibling.key()),
+        &event_emitter,
+    )
+}
+
+impl<'info> internal::Authentication<'info> for CancelLinkedOrder<'info> {
+    fn authority(&self) -> &Signer<'info> {
+        &self.authority
+    }
+
+    fn store(&self) -> &AccountLoader<'info, Store> {
+        &self.store
+    }
+}
+
+/// Cancel a pending linked order and emit a [`LinkedOrderCancelled`] event.
+///
+/// The order is left unchanged if it is no longer pending.
+pub(crate) fn cancel_pending_linked_order<'info>(
+    order: &AccountLoader<'info, Order>,
+    executed_sibling: Option<&Pubkey>,
+    event_emitter: &EventEmitter<'_, 'info>,
+) -> Result<()> {
+    let event = {
+        let mut linked_order = order.load_mut()?;
+        if !linked_order.header.action_state()?.is_pending() {
+            return Ok(());
+        }
+        let parent = *linked_order
+            .linked_parent()
+            .ok_or_else(|| error!(CoreError::InvalidLinkedOrder))?;
+        linked_order.header.cancelled()?;
+        LinkedOrderCancelled::new(
+            &linked_order.header.store,
+            &order.key(),
+            &parent,
+            executed_sibling,
+        )?
+    };
+    event_emitter.emit_cpi(&event)
+}
@@ -10,12 +10,21 @@ pub mod order;
 /// Execute Deposit.
 pub mod execute_deposit;
 
+/// Execute Deposits in batch.
+pub mod execute_deposits;
+
 /// Execute Withdrawal.
 pub mod execute_withdrawal;
 
+/// Execute Withdrawals in batch.
+pub mod execute_withdrawals;
+
 /// Execute Order.
 pub mod execute_order;
 
+/// Execute Orders in batch.
+pub mod execute_orders;
+
 /// Update ADL state.
 pub mod update_adl;
 
@@ -34,17 +43,44 @@ pub mod update_closed;
 /// Update fees state.
 pub mod update_fees;
 
+/// Update trailing-stop orders.
+pub mod update_trailing_stop;
+
+/// Linked orders.
+pub mod linked_order;
+
+/// Orders created by delegates.
+pub mod delegated_order;
+
+/// Orders created from signed intents.
+pub mod order_intent;
+
+/// Position ownership transfer.
+pub mod position_transfer;
+
+/// Position collateral switch.
+pub mod position_collateral_switch;
+
+pub use delegated_order::*;
 pub use deposit::*;
 pub use execute_deposit::*;
+pub use execute_deposits::*;
 pub use execute_order::*;
+pub use execute_orders::*;
 pub use execute_shift::*;
 pub use execute_withdrawal::*;
+pub use execute_withdrawals::*;
+pub use linked_order::*;
 pub use order::*;
+pub use order_intent::*;
+pub use position_collateral_switch::*;
 pub use position_cut::*;
+pub use position_transfer::*;
 pub use shift::*;
 pub use update_adl::*;
 pub use update_closed::*;
 pub use update_fees::*;
+pub use update_trailing_stop::*;
 pub use withdrawal::*;
 
 use crate::CoreError;
@@ -17,13 +17,13 @@ use crate::{
     events::{EventEmitter, GtUpdated, OrderCreated, OrderUpdated},
     ops::{
         execution_fee::TransferExecutionFeeOperation,
-        order::{CreateOrderOperation, CreateOrderParams},
+        order::{CreateOrderArgs, CreateOrderOperation, CreateOrderParams},
     },
     order::internal::Close,
     states::{
         callback::CallbackAuthority,
         common::action::{Action, On},
-        feature::ActionDisabledFlag,
+        feature::{ActionDisabledFlag, DomainDisabledFlag},
         order::{Order, OrderKind},
         position::PositionKind,
         user::UserHeader,
@@ -34,6 +34,8 @@ use crate::{
     CoreError,
 };
 
+use super::linked_order::cancel_pending_linked_order;
+
 /// The accounts definition for the [`prepare_position`](crate::gmsol_store::prepare_position)
 /// instruction.
 #[derive(Accounts)]
@@ -79,7 +81,8 @@ pub(crate) fn prepare_position(
         &ctx.accounts.position,
         ctx.bumps.position,
         params.to_position_kind()?,
-        &ctx.accounts.owner,
+        ctx.accounts.owner.key,
+        ctx.accounts.owner.to_account_info(),
         collateral_token,
         &market_token,
         meta.is_pure(),
@@ -90,11 +93,12 @@ pub(crate) fn prepare_position(
 }
 
 #[allow(clippy::too_many_arguments)]
-fn validate_and_initialize_position_if_needed<'info>(
+pub(super) fn validate_and_initialize_position_if_needed<'info>(
     position_loader: &AccountLoader<'info, Position>,
     bump: u8,
     kind: PositionKind,
-    owner: &AccountInfo<'info>,
+    owner_key: &Pubkey,
+    payer: AccountInfo<'info>,
     collateral_token: &Pubkey,
     market_token: &Pubkey,
     is_pure_market: bool,
@@ -103,7 +107,6 @@ fn validate_and_initialize_position_if_needed<'info>(
 ) -> Result<()> {
     let mut should_transfer_in = false;
 
-    let owner_key = owner.key;
     match position_loader.load_init() {
         Ok(mut position) => {
             position.try_init(
@@ -140,7 +143,7 @@ fn validate_and_initialize_position_if_needed<'info>(
     if should_transfer_in {
         TransferExecutionFeeOperation::builder()
             .payment(position_loader.to_account_info())
-            .payer(owner.clone())
+            .payer(payer)
             .execution_lamports(Order::position_cut_rent(is_pure_market, true)?)
             .system_program(system_program)
             .build()
@@ -318,7 +321,7 @@ pub struct CreateOrderV2<'info> {
 }
 
 impl<'info> internal::Create<'info, Order> for CreateOrderV2<'info> {
-    type CreateParams = CreateOrderParams;
+    type CreateParams = CreateOrderArgs;
 
     fn action(&self) -> AccountInfo<'info> {
         self.order.to_account_info()
@@ -332,29 +335,32 @@ impl<'info> internal::Create<'info, Order> for CreateOrderV2<'info> {
         self.system_program.to_account_info()
     }
 
-    fn validate(&self, params: &Self::CreateParams) -> Result<()> {
+    fn validate(&self, args: &Self::CreateParams) -> Result<()> {
+        let params = &args.params;
+        let domain: DomainDisabledFlag = params
+            .kind
+            .try_into()
+            .map_err(CoreError::from)
+            .map_err(|err| error!(err))?;
         self.store
             .load()?
             .validate_not_restarted()?
-            .validate_feature_enabled(
-                params
-                    .kind
-                    .try_into()
-                    .map_err(CoreError::from)
-                    .map_err(|err| error!(err))?,
-                ActionDisabledFlag::Create,
-            )?;
+            .validate_feature_enabled(domain, ActionDisabledFlag::Create)?;
+        self.market
+            .load()?
+            .validate_feature_enabled(domain, ActionDisabledFlag::Create)?;
         Ok(())
     }
 
     fn create_impl(
         &mut self,
-        params: &Self::CreateParams,
+        args: &Self::CreateParams,
         nonce: &NonceBytes,
         bumps: &Self::Bumps,
         remaining_accounts: &'info [AccountInfo<'info>],
         callback_version: Option<u8>,
     ) -> Result<()> {
+        let params = &args.params;
         self.transfer_tokens(params)?;
 
         let kind = params.kind;
@@ -375,6 +381,7 @@ impl<'info> internal::Create<'info, Order> for CreateOrderV2<'info> {
             .nonce(nonce)
             .bump(bumps.order)
             .params(params)
+            .ext(&args.ext)
             .swap_path(remaining_accounts)
             .callback_version(callback_version)
             .callback_authority(self.callback_authority.as_ref())
@@ -404,7 +411,7 @@ impl<'info> internal::Create<'info, Order> for CreateOrderV2<'info> {
                     .build()
                     .execute()?;
             }
-            OrderKind::MarketIncrease | OrderKind::LimitIncrease => {
+            OrderKind::MarketIncrease | OrderKind::LimitIncrease | OrderKind::StopIncrease => {
                 // The final output token is where a builder fee would be charged, so for an
                 // increase order it must be the position's collateral token. The check lives here
                 // rather than in the operation because the escrow is optional: without it the ops
@@ -446,7 +453,10 @@ impl<'info> internal::Create<'info, Order> for CreateOrderV2<'info> {
                     .build()
                     .execute()?;
             }
-            OrderKind::MarketDecrease | OrderKind::LimitDecrease | OrderKind::StopLossDecrease => {
+            OrderKind::MarketDecrease
+            | OrderKind::LimitDecrease
+            | OrderKind::StopLossDecrease
+            | OrderKind::TrailingStopDecrease => {
                 let final_output = self
                     .final_output_token_escrow
                     .as_ref()
@@ -494,6 +504,7 @@ impl CreateOrderV2<'_> {
                 | OrderKind::LimitSwap
                 | OrderKind::MarketIncrease
                 | OrderKind::LimitIncrease
+                | OrderKind::StopIncrease
         ) {
             return Ok(());
         }
@@ -533,6 +544,12 @@ impl CreateOrderV2<'_> {
 }
 
 /// The accounts definition for the [`close_order_v2`](crate::gmsol_store::close_order_v2) instruction.
+///
+/// Remaining accounts expected by this instruction, in any order:
+///   - `[]` The linked orders recorded in the order, required only if the order is the
+///     parent of linked orders that have not been resolved.
+///   - `[writable]` The position referenced by the order, required if the order is counted
+///     by the position, even if the position account has been closed.
 #[event_cpi]
 #[derive(Accounts)]
 pub struct CloseOrderV2<'info> {
@@ -749,6 +766,13 @@ impl<'info> internal::Close<'info, Order> for CloseOrderV2<'info> {
         Ok(success)
     }
 
+    fn process_remaining_accounts(
+        &self,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        self.release_position(remaining_accounts)
+    }
+
     fn event_authority(&self, bumps: &Self::Bumps) -> (AccountInfo<'info>, u8) {
         (
             self.event_authority.to_account_info(),
@@ -762,6 +786,94 @@ impl<'info> internal::Close<'info, Order> for CloseOrderV2<'info> {
 }
 
 impl<'info> CloseOrderV2<'info> {
+    /// Cancel and close the order if it has expired.
+    pub(crate) fn cancel_expired(ctx: &Context<'_, '_, 'info, 'info, Self>) -> Result<()> {
+        let accounts = &ctx.accounts;
+        accounts.store.load()?.validate_not_restarted()?;
+        {
+            let mut order = accounts.order.load_mut()?;
+            require!(
+                order.params.is_expired(Clock::get()?.unix_timestamp),
+                CoreError::PreconditionsAreNotMet
+            );
+            // Order must be in the pending state which is checked before the transition.
+            order.header.cancelled()?;
+        }
+        let event_emitter = EventEmitter::new(&accounts.event_authority, ctx.bumps.event_authority);
+        accounts.cancel_linked_orders(ctx.remaining_accounts, &event_emitter)?;
+        internal::Close::close(ctx, "expired")
+    }
+
+    /// Cancel the linked orders that are still waiting for the order to be executed.
+    ///
+    /// The linked orders must be provided in the remaining accounts if the order
+    /// has linked orders that have not been resolved.
+    fn cancel_linked_orders(
+        &self,
+        remaining_accounts: &'info [AccountInfo<'info>],
+        event_emitter: &EventEmitter<'_, 'info>,
+    ) -> Result<()> {
+        let linked_orders = {
+            let order = self.order.load()?;
+            if !order.has_pending_linked_orders() {
+                return Ok(());
+            }
+            *order.linked_orders()
+        };
+        let parent = self.order.key();
+        for address in linked_orders.iter() {
+            let info = remaining_accounts
+                .iter()
+                .find(|info| info.key == address)
+                .ok_or_else(|| error!(CoreError::LinkedOrdersNotResolved))?;
+            // Linked orders closed by the owner are skipped.
+            if *info.owner != crate::ID {
+                continue;
+            }
+            let child = AccountLoader::<Order>::try_from(info)?;
+            if child.load()?.linked_parent() != Some(&parent) {
+                continue;
+            }
+            cancel_pending_linked_order(&child, None, event_emitter)?;
+        }
+        self.order.load_mut()?.resolve_linked_orders()
+    }
+
+    /// Validate that the order is not the parent of linked orders that are still
+    /// waiting for activation, otherwise they could never be activated or cancelled.
+    ///
+    /// The linked orders must be provided in the remaining accounts if the order
+    /// has linked orders that have not been resolved.
+    pub(crate) fn validate_linked_orders_resolved(
+        &self,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        let order = self.order.load()?;
+        if !order.has_pending_linked_orders() {
+            return Ok(());
+        }
+        let parent = self.order.key();
+        for address in order.linked_orders() {
+            let info = remaining_accounts
+                .iter()
+                .find(|info| info.key == address)
+                .ok_or_else(|| error!(CoreError::LinkedOrdersNotResolved))?;
+            // Linked orders closed by the owner are resolved.
+            if *info.owner != crate::ID {
+                continue;
+            }
+            let child = AccountLoader::<Order>::try_from(info)?;
+            let child = child.load()?;
+            require!(
+                child.linked_parent() != Some(&parent)
+                    || !child.is_pending_activation()
+                    || !child.header.action_state()?.is_pending(),
+                CoreError::LinkedOrdersNotResolved
+            );
+        }
+        Ok(())
+    }
+
     #[inline(never)]
     fn transfer_to_atas(
         &self,
@@ -955,6 +1067,39 @@ impl<'info> CloseOrderV2<'info> {
         Ok(())
     }
 
+    /// Decrease the order count of the position referenced by the order.
+    ///
+    /// The position account must be provided if the order is counted by it.
+    #[inline(never)]
+    fn release_position(&self, remaining_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        let order = self.order.load()?;
+        if !order.is_counted_by_position() {
+            return Ok(());
+        }
+        let Some(address) = order.params().position() else {
+            return Ok(());
+        };
+        let info = remaining_accounts
+            .iter()
+            .find(|info| info.key == address)
+            .ok_or_else(|| error!(CoreError::PositionIsRequired))?;
+        // The position account may have been closed.
+        if *info.owner != crate::ID || info.data_is_empty() {
+            return Ok(());
+        }
+        let position = AccountLoader::<Position>::try_from(info)?;
+        let mut position = position.load_mut()?;
+        if matches!(position.kind_unchecked()?, PositionKind::Uninitialized) {
+            return Ok(());
+        }
+        // The position may have been closed and re-initialized while the order was pending,
+        // in which case the order is not counted by it.
+        if order.is_counted_by(&position) {
+            position.on_order_closed();
+        }
+        Ok(())
+    }
+
     #[inline(never)]
     fn handle_closed(&self, is_caller_owner: bool) -> Result<()> {
         match self.order.load()?.header.callback_kind()? {
@@ -1040,19 +1185,21 @@ impl UpdateOrderV2<'_> {
         // Validate feature enabled.
         {
             let order = ctx.accounts.order.load()?;
+            let domain: DomainDisabledFlag = order
+                .params()
+                .kind()?
+                .try_into()
+                .map_err(CoreError::from)
+                .map_err(|err| error!(err))?;
             ctx.accounts
                 .store
                 .load()?
                 .validate_not_restarted()?
-                .validate_feature_enabled(
-                    order
-                        .params()
-                        .kind()?
-                        .try_into()
-                        .map_err(CoreError::from)
-                        .map_err(|err| error!(err))?,
-                    ActionDisabledFlag::Update,
-                )?;
+                .validate_feature_enabled(domain, ActionDisabledFlag::Update)?;
+            ctx.accounts
+                .market
+                .load()?
+                .validate_feature_enabled(domain, ActionDisabledFlag::Update)?;
         }
 
         let id = ctx
@@ -0,0 +1,467 @@
+use anchor_lang::{prelude::*, solana_program::sysvar};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+};
+use gmsol_utils::InitSpace;
+
+use crate::{
+    constants,
+    events::{EventEmitter, OrderCreated},
+    ops::order::{CreateOrderArgs, CreateOrderOperation, CreateOrderParams, OrderIntent},
+    states::{
+        feature::{ActionDisabledFlag, DomainDisabledFlag},
+        order::{Order, OrderKind},
+        user::UserHeader,
+        HasMarketMeta, Market, NonceBytes, Position, Seed, Store,
+    },
+    utils::{ed25519::verify_ed25519_signature_ix, internal},
+    CoreError,
+};
+
+use super::order::validate_and_initialize_position_if_needed;
+
+/// The accounts definitions for [`create_order_with_intent`](crate::gmsol_store::create_order_with_intent)
+/// instruction.
+///
+/// Only increase and decrease orders without swaps are supported. Tokens of the owner are
+/// transferred with the `intent_authority` as the authority, so the owner is expected to have
+/// approved the `intent_authority` to spend from the source token accounts.
+///
+/// The relayer fee is deducted from the initial collateral of the order and paid to the
+/// [`relayer_fee_receiver`](Self::relayer_fee_receiver) of the relayer.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(nonce: [u8; 32], intent: OrderIntent)]
+pub struct CreateOrderWithIntent<'info> {
+    /// The relayer, who pays for the accounts and the execution fee.
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    /// The owner of the order to be created, who is also the receiver of the output funds.
+    /// CHECK: the owner is checked to be the signer of the intent.
+    pub owner: UncheckedAccount<'info>,
+    /// Store.
+    pub store: AccountLoader<'info, Store>,
+    /// Market.
+    #[account(mut, has_one = store)]
+    pub market: AccountLoader<'info, Market>,
+    /// User Account of the owner, which will be initialized if needed.
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + UserHeader::space(0),
+        seeds = [UserHeader::SEED, store.key().as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    pub user: AccountLoader<'info, UserHeader>,
+    /// The order to be created.
+    #[account(
+        init,
+        space = 8 + Order::INIT_SPACE,
+        payer = relayer,
+        seeds = [Order::SEED, store.key().as_ref(), relayer.key().as_ref(), &nonce],
+        bump,
+    )]
+    pub order: AccountLoader<'info, Order>,
+    /// The related position, which will be initialized if needed.
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + Position::INIT_SPACE,
+        seeds = [
+            Position::SEED,
+            store.key().as_ref(),
+            owner.key().as_ref(),
+            market.load()?.meta().market_token_mint.as_ref(),
+            intent.params.collateral_token(market.load()?.meta()).as_ref(),
+            &[intent.params.to_position_kind()? as u8],
+        ],
+        bump,
+    )]
+    pub position: AccountLoader<'info, Position>,
+    /// The authority of the owner's token accounts approved for order intents.
+    /// CHECK: only used as a signing PDA.
+    #[account(
+        seeds = [
+            constants::INTENT_AUTHORITY_SEED,
+            store.key().as_ref(),
+            owner.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub intent_authority: UncheckedAccount<'info>,
+    /// Initial collateral token.
+    /// Only required by increase orders.
+    pub initial_collateral_token: Option<Box<Account<'info, Mint>>>,
+    /// Final output token.
+    pub final_output_token: Box<Account<'info, Mint>>,
+    /// Long token of the market.
+    #[account(constraint = market.load()?.meta().long_token_mint == long_token.key())]
+    pub long_token: Box<Account<'info, Mint>>,
+    /// Short token of the market.
+    #[account(constraint = market.load()?.meta().short_token_mint == short_token.key())]
+    pub short_token: Box<Account<'info, Mint>>,
+    /// Initial collateral token escrow account.
+    /// Only required by increase orders.
+    #[account(
+        mut,
+        associated_token::mint = initial_collateral_token,
+        associated_token::authority = order,
+    )]
+    pub initial_collateral_token_escrow: Option<Box<Account<'info, TokenAccount>>>,
+    /// Final output token escrow account.
+    /// Required by decrease orders and optional for increase orders.
+    #[account(
+        mut,
+        associated_token::mint = final_output_token,
+        associated_token::authority = order,
+    )]
+    pub final_output_token_escrow: Option<Box<Account<'info, TokenAccount>>>,
+    /// Long token escrow.
+    #[account(
+        mut,
+        associated_token::mint = long_token,
+        associated_token::authority = order,
+    )]
+    pub long_token_escrow: Box<Account<'info, TokenAccount>>,
+    /// Short token escrow.
+    #[account(
+        mut,
+        associated_token::mint = short_token,
+        associated_token::authority = order,
+    )]
+    pub short_token_escrow: Box<Account<'info, TokenAccount>>,
+    /// The source initial token account of the owner.
+    /// Only required by increase orders.
+    #[account(
+        mut,
+        token::mint = initial_collateral_token,
+        token::authority = owner,
+    )]
+    pub initial_collateral_token_source: Option<Box<Account<'info, TokenAccount>>>,
+    /// The initial collateral token account of the relayer to receive the relayer fee.
+    /// Only required if the relayer fee is not zero.
+    #[account(
+        mut,
+        token::mint = initial_collateral_token,
+        token::authority = relayer,
+    )]
+    pub relayer_fee_receiver: Option<Box<Account<'info, TokenAccount>>>,
+    /// The instructions sysvar.
+    /// CHECK: only used to load the ed25519 program instruction.
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+    /// The system program.
+    pub system_program: Program<'info, System>,
+    /// The token program.
+    pub token_program: Program<'info, Token>,
+    /// The associated token program.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> CreateOrderWithIntent<'info> {
+    pub(crate) fn invoke(
+        mut ctx: Context<'_, '_, 'info, 'info, Self>,
+        nonce: &NonceBytes,
+        intent: &OrderIntent,
+    ) -> Result<()> {
+        let accounts = &mut ctx.accounts;
+
+        let message = intent.to_message()?;
+        verify_ed25519_signature_ix(&accounts.instructions_sysvar, accounts.owner.key, &message)?;
+
+        accounts.validate_intent(intent)?;
+        accounts.use_intent_nonce(ctx.bumps.user, intent.nonce)?;
+        accounts.initialize_position_if_needed(ctx.bumps.position, &intent.params)?;
+        accounts.transfer_relayer_fee(ctx.bumps.intent_authority, intent)?;
+
+        let mut params = intent.params.clone();
+        // The relayer fee is deducted from the initial collateral.
+        params.initial_collateral_delta_amount = params
+            .initial_collateral_delta_amount
+            .checked_sub(intent.relayer_fee)
+            .ok_or_else(|| error!(CoreError::InvalidOrderIntent))?;
+        let args = CreateOrderArgs {
+            params,
+            ext: intent.ext.clone(),
+        };
+        internal::Create::create(&mut ctx, nonce, &args, None)
+    }
+
+    fn validate_intent(&self, intent: &OrderIntent) -> Result<()> {
+        require_keys_eq!(
+            intent.store,
+            self.store.key(),
+            CoreError::InvalidOrderIntent
+        );
+        require_keys_eq!(
+            intent.market_token,
+            self.market.load()?.meta().market_token_mint,
+            CoreError::InvalidOrderIntent
+        );
+        require_keys_eq!(
+            intent.relayer,
+            self.relayer.key(),
+            CoreError::InvalidOrderIntent
+        );
+        require_gte!(
+            intent.deadline,
+            Clock::get()?.unix_timestamp,
+            CoreError::OrderIntentExpired
+        );
+        Ok(())
+    }
+
+    fn use_intent_nonce(&self, bump: u8, intent_nonce: u64) -> Result<()> {
+        let store = self.store.key();
+        let owner = self.owner.key;
+        {
+            match self.user.load_init() {
+                Ok(mut user) => {
+                    user.init(&store, owner, bump)?;
+                }
+                Err(Error::AnchorError(err)) => {
+                    if err.error_code_number != ErrorCode::AccountDiscriminatorAlreadySet as u32 {
+                        return Err(Error::AnchorError(err));
+                    }
+                }
+                Err(err) => {
+                    return Err(err);
+                }
+            }
+        }
+        self.user.exit(&crate::ID)?;
+        let mut user = self.user.load_mut()?;
+        require!(user.is_initialized(), CoreError::InvalidUserAccount);
+        require_keys_eq!(user.store, store, CoreError::InvalidUserAccount);
+        require_keys_eq!(user.owner, *owner, CoreError::InvalidUserAccount);
+        require_eq!(user.bump, bump, CoreError::InvalidUserAccount);
+        user.use_intent_nonce(intent_nonce)
+    }
+
+    fn initialize_position_if_needed(&self, bump: u8, params: &CreateOrderParams) -> Result<()> {
+        let meta = *self.market.load()?.meta();
+        validate_and_initialize_position_if_needed(
+            &self.position,
+            bump,
+            params.to_position_kind()?,
+            self.owner.key,
+            self.relayer.to_account_info(),
+            params.collateral_token(&meta),
+            &meta.market_token_mint,
+            meta.is_pure(),
+            &self.store.key(),
+            self.system_program.to_account_info(),
+        )
+    }
+
+    fn transfer_relayer_fee(&self, bump: u8, intent: &OrderIntent) -> Result<()> {
+        let amount = intent.relayer_fee;
+        if amount == 0 {
+            return Ok(());
+        }
+        // The relayer fee can only be paid from the initial collateral of increase orders.
+        require!(
+            intent.params.kind.is_increase_position(),
+            CoreError::InvalidOrderIntent
+        );
+        let token = self
+            .initial_collateral_token
+            .as_ref()
+            .ok_or_else(|| error!(CoreError::MissingInitialCollateralToken))?;
+        let from = self
+            .initial_collateral_token_source
+            .as_ref()
+            .ok_or_else(|| error!(CoreError::TokenAccountNotProvided))?;
+        let to = self
+            .relayer_fee_receiver
+            .as_ref()
+            .ok_or_else(|| error!(CoreError::TokenAccountNotProvided))?;
+        self.transfer_from_owner(bump, token, from, to, amount)
+    }
+
+    fn transfer_collateral(&mut self, bump: u8, params: &CreateOrderParams) -> Result<()> {
+        if !params.kind.is_increase_position() {
+            return Ok(());
+        }
+        let amount = params.initial_collateral_delta_amount;
+        if amount != 0 {
+            let token = self
+                .initial_collateral_token
+                .as_ref()
+                .ok_or_else(|| error!(CoreError::MissingInitialCollateralToken))?;
+            let from = self
+                .initial_collateral_token_source
+                .as_ref()
+                .ok_or_else(|| error!(CoreError::TokenAccountNotProvided))?;
+            let to = self
+                .initial_collateral_token_escrow
+                .as_ref()
+                .ok_or_else(|| error!(CoreError::TokenAccountNotProvided))?;
+            self.transfer_from_owner(bump, token, from, to, amount)?;
+            self.initial_collateral_token_escrow
+                .as_mut()
+                .ok_or_else(|| error!(CoreError::TokenAccountNotProvided))?
+                .reload()?;
+        }
+        Ok(())
+    }
+
+    fn transfer_from_owner(
+        &self,
+        bump: u8,
+        token: &Account<'info, Mint>,
+        from: &Account<'info, TokenAccount>,
+        to: &Account<'info, TokenAccount>,
+        amount: u64,
+    ) -> Result<()> {
+        let store = self.store.key();
+        let signer_seeds: &[&[u8]] = &[
+            constants::INTENT_AUTHORITY_SEED,
+            store.as_ref(),
+            self.owner.key.as_ref(),
+            &[bump],
+        ];
+        transfer_checked(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: from.to_account_info(),
+                    mint: token.to_account_info(),
+                    to: to.to_account_info(),
+                    authority: self.intent_authority.to_account_info(),
+                },
+            )
+            .with_signer(&[signer_seeds]),
+            amount,
+            token.decimals,
+        )
+    }
+}
+
+impl<'info> internal::Create<'info, Order> for CreateOrderWithIntent<'info> {
+    type CreateParams = CreateOrderArgs;
+
+    fn action(&self) -> AccountInfo<'info> {
+        self.order.to_account_info()
+    }
+
+    fn payer(&self) -> AccountInfo<'info> {
+        self.relayer.to_account_info()
+    }
+
+    fn system_program(&self) -> AccountInfo<'info> {
+        self.system_program.to_account_info()
+    }
+
+    fn validate(&self, args: &Self::CreateParams) -> Result<()> {
+        let params = &args.params;
+        let domain: DomainDisabledFlag = params
+            .kind
+            .try_into()
+            .map_err(CoreError::from)
+            .map_err(|err| error!(err))?;
+        self.store
+            .load()?
+            .validate_not_restarted()?
+            .validate_feature_enabled(domain, ActionDisabledFlag::Create)?;
+        self.market
+            .load()?
+            .validate_feature_enabled(domain, ActionDisabledFlag::Create)?;
+        require_eq!(params.swap_path_length, 0, CoreError::InvalidOrderIntent);
+        Ok(())
+    }
+
+    fn create_impl(
+        &mut self,
+        args: &Self::CreateParams,
+        nonce: &NonceBytes,
+        bumps: &Self::Bumps,
+        remaining_accounts: &'info [AccountInfo<'info>],
+        callback_version: Option<u8>,
+    ) -> Result<()> {
+        let params = &args.params;
+        require!(callback_version.is_none(), CoreError::InvalidArgument);
+
+        self.transfer_collateral(bumps.intent_authority, params)?;
+
+        let kind = params.kind;
+        let ops = CreateOrderOperation::builder()
+            .order(self.order.clone())
+            .market(self.market.clone())
+            .store(self.store.clone())
+            .owner(self.owner.to_account_info())
+            .receiver(self.owner.to_account_info())
+            .creator(self.relayer.to_account_info())
+            .nonce(nonce)
+            .bump(bumps.order)
+            .params(params)
+            .ext(&args.ext)
+            .swap_path(remaining_accounts)
+            .callback_version(None)
+            .callback_authority(None)
+            .callback_program(None)
+            .callback_shared_data_account(None)
+            .callback_partitioned_data_account(None)
+            .event_emitter(Some(EventEmitter::new(
+                &self.event_authority,
+                bumps.event_authority,
+            )))
+            .build();
+
+        match kind {
+            OrderKind::MarketIncrease | OrderKind::LimitIncrease | OrderKind::StopIncrease => {
+                require_keys_eq!(
+                    self.final_output_token.key(),
+                    *params.collateral_token(&*self.market.load()?),
+                    CoreError::TokenMintMismatched
+                );
+                let initial_collateral = self
+                    .initial_collateral_token_escrow
+                    .as_ref()
+                    .ok_or_else(|| error!(CoreError::TokenAccountNotProvided))?;
+                ops.increase()
+                    .position(&self.position)
+                    .initial_collateral_token(initial_collateral.as_ref())
+                    .long_token(self.long_token_escrow.as_ref())
+                    .short_token(self.short_token_escrow.as_ref())
+                    .final_output_token(self.final_output_token_escrow.as_deref())
+                    .build()
+                    .execute()?;
+            }
+            OrderKind::MarketDecrease
+            | OrderKind::LimitDecrease
+            | OrderKind::StopLossDecrease
+            | OrderKind::TrailingStopDecrease => {
+                let final_output = self
+                    .final_output_token_escrow
+                    .as_ref()
+                    .ok_or_else(|| error!(CoreError::TokenAccountNotProvided))?;
+                ops.decrease()
+                    .position(&self.position)
+                    .final_output_token(final_output.as_ref())
+                    .long_token(self.long_token_escrow.as_ref())
+                    .short_token(self.short_token_escrow.as_ref())
+                    .build()
+                    .execute()?;
+            }
+            _ => {
+                return err!(CoreError::OrderKindNotAllowed);
+            }
+        }
+
+        // The rent is paid by the relayer.
+        self.order
+            .load_mut()?
+            .header
+            .set_rent_receiver(self.relayer.key());
+
+        emit!(OrderCreated::new(
+            self.store.key(),
+            self.order.key(),
+            Some(self.position.key()),
+        )?);
+
+        Ok(())
+    }
+}
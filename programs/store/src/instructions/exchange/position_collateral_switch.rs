@@ -0,0 +1,253 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    events::EventEmitter,
+    ops::position::SwitchPositionCollateralOperation,
+    states::{
+        Chainlink, HasMarketMeta, Market, Oracle, Position, Seed, Store, TokenMapHeader,
+        ValidateOracleTime,
+    },
+    utils::internal,
+    CoreError, CoreResult,
+};
+
+use super::order::validate_and_initialize_position_if_needed;
+
+/// The accounts definitions for
+/// [`request_position_collateral_switch`](crate::gmsol_store::request_position_collateral_switch).
+#[derive(Accounts)]
+pub struct RequestPositionCollateralSwitch<'info> {
+    /// The owner of the position, who pays for the target position account if needed.
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    /// Store.
+    pub store: AccountLoader<'info, Store>,
+    /// The market of the position.
+    #[account(
+        has_one = store,
+        constraint = market.load()?.meta().market_token_mint == position.load()?.market_token @ CoreError::MarketTokenMintMismatched,
+    )]
+    pub market: AccountLoader<'info, Market>,
+    /// The position to switch the collateral token of.
+    #[account(
+        mut,
+        has_one = store,
+        has_one = owner,
+    )]
+    pub position: AccountLoader<'info, Position>,
+    /// The collateral token to switch to.
+    /// CHECK: only the address is used, which is checked to be the opposite collateral token
+    /// of the position.
+    pub collateral_token: UncheckedAccount<'info>,
+    /// The position with the opposite collateral token, which will be initialized if needed.
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + Position::INIT_SPACE,
+        seeds = [
+            Position::SEED,
+            store.key().as_ref(),
+            owner.key().as_ref(),
+            position.load()?.market_token.as_ref(),
+            collateral_token.key().as_ref(),
+            &[position.load()?.kind],
+        ],
+        bump,
+    )]
+    pub target: AccountLoader<'info, Position>,
+    /// The system program.
+    pub system_program: Program<'info, System>,
+}
+
+impl RequestPositionCollateralSwitch<'_> {
+    pub(crate) fn invoke(ctx: Context<Self>, min_collateral_amount: u64) -> Result<()> {
+        let accounts = ctx.accounts;
+        let store = accounts.store.key();
+        accounts.store.load()?.validate_not_restarted()?;
+
+        let (kind, collateral_token) = {
+            let position = accounts.position.load()?;
+            (position.kind()?, position.collateral_token)
+        };
+        let meta = *accounts.market.load()?.meta();
+        require_keys_eq!(
+            *meta
+                .opposite_token(&collateral_token)
+                .map_err(CoreError::from)?,
+            accounts.collateral_token.key(),
+            CoreError::TokenMintMismatched
+        );
+
+        validate_and_initialize_position_if_needed(
+            &accounts.target,
+            ctx.bumps.target,
+            kind,
+            accounts.owner.key,
+            accounts.owner.to_account_info(),
+            accounts.collateral_token.key,
+            &meta.market_token_mint,
+            meta.is_pure(),
+            &store,
+            accounts.system_program.to_account_info(),
+        )?;
+
+        let mut position = accounts.position.load_mut()?;
+        position.validate_collateral_switch_target(
+            &*accounts.target.load()?,
+            accounts.collateral_token.key,
+        )?;
+        position.request_collateral_switch(min_collateral_amount)?;
+
+        msg!(
+            "[Position] requested to switch the collateral token of position {} to {}",
+            accounts.position.key(),
+            accounts.collateral_token.key,
+        );
+        Ok(())
+    }
+}
+
+/// The accounts definitions for
+/// [`cancel_position_collateral_switch`](crate::gmsol_store::cancel_position_collateral_switch).
+#[derive(Accounts)]
+pub struct CancelPositionCollateralSwitch<'info> {
+    /// Owner.
+    pub owner: Signer<'info>,
+    /// Store.
+    pub store: AccountLoader<'info, Store>,
+    /// Position.
+    #[account(
+        mut,
+        has_one = store,
+        has_one = owner,
+    )]
+    pub position: AccountLoader<'info, Position>,
+}
+
+impl CancelPositionCollateralSwitch<'_> {
+    pub(crate) fn invoke(ctx: Context<Self>) -> Result<()> {
+        ctx.accounts.position.load_mut()?.cancel_collateral_switch()
+    }
+}
+
+/// The accounts definitions for
+/// [`switch_position_collateral`](crate::gmsol_store::switch_position_collateral).
+///
+/// *[See also the documentation for the instruction.](crate::gmsol_store::switch_position_collateral)*
+///
+/// Remaining accounts expected by this instruction:
+///
+///   - 0..N. `[]` N feed accounts, where N represents the total number of unique tokens
+///     in the market.
+///   - N..N+V. `[writable]` V virtual inventory accounts, where V represents the total
+///     number of unique virtual inventories required by the market.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SwitchPositionCollateral<'info> {
+    /// The address authorized to execute this instruction.
+    pub authority: Signer<'info>,
+    /// The owner of the position, who receives the rent of the closed position account.
+    /// CHECK: only used to receive the rent, and is checked by the `position`.
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+    /// Store.
+    #[account(has_one = token_map)]
+    pub store: AccountLoader<'info, Store>,
+    /// Token map.
+    #[account(has_one = store)]
+    pub token_map: AccountLoader<'info, TokenMapHeader>,
+    /// The oracle buffer to use.
+    #[account(mut, has_one = store)]
+    pub oracle: AccountLoader<'info, Oracle>,
+    /// The market of the position.
+    #[account(
+        mut,
+        has_one = store,
+        constraint = market.load()?.meta().market_token_mint == position.load()?.market_token @ CoreError::MarketTokenMintMismatched,
+    )]
+    pub market: AccountLoader<'info, Market>,
+    /// The position to switch the collateral token of, which will be closed.
+    #[account(
+        mut,
+        close = owner,
+        has_one = store,
+        has_one = owner,
+    )]
+    pub position: AccountLoader<'info, Position>,
+    /// The position with the opposite collateral token to move the state into.
+    #[account(mut, has_one = store, has_one = owner)]
+    pub target: AccountLoader<'info, Position>,
+    /// Chainlink Program.
+    pub chainlink_program: Option<Program<'info, Chainlink>>,
+}
+
+/// CHECK: only ORDER_KEEPER is authorized to perform this action.
+pub(crate) fn unchecked_switch_position_collateral<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SwitchPositionCollateral<'info>>,
+) -> Result<()> {
+    let accounts = &ctx.accounts;
+    accounts.store.load()?.validate_not_restarted()?;
+
+    let tokens = accounts
+        .market
+        .load()?
+        .market_meta()
+        .ordered_tokens()
+        .into_iter()
+        .collect::<Vec<_>>();
+    let event_emitter = EventEmitter::new(&accounts.event_authority, ctx.bumps.event_authority);
+
+    accounts.oracle.load_mut()?.with_prices(
+        &accounts.store,
+        &accounts.token_map,
+        &tokens,
+        ctx.remaining_accounts,
+        |oracle, remaining_accounts| {
+            oracle.validate_time(&**accounts)?;
+            SwitchPositionCollateralOperation::builder()
+                .market(&accounts.market)
+                .position(&accounts.position)
+                .target(&accounts.target)
+                .oracle(oracle)
+                .remaining_accounts(remaining_accounts)
+                .event_emitter(event_emitter)
+                .build()
+                .execute()
+        },
+    )
+}
+
+impl<'info> internal::Authentication<'info> for SwitchPositionCollateral<'info> {
+    fn authority(&self) -> &Signer<'info> {
+        &self.authority
+    }
+
+    fn store(&self) -> &AccountLoader<'info, Store> {
+        &self.store
+    }
+}
+
+impl ValidateOracleTime for SwitchPositionCollateral<'_> {
+    fn oracle_updated_after(&self) -> CoreResult<Option<i64>> {
+        // Prices older than the request must not be used.
+        Ok(self
+            .position
+            .load()
+            .map_err(|_| CoreError::LoadAccountError)?
+            .collateral_switch_requested_at()
+            .map(|(ts, _)| ts))
+    }
+
+    fn oracle_updated_before(&self) -> CoreResult<Option<i64>> {
+        Ok(None)
+    }
+
+    fn oracle_updated_after_slot(&self) -> CoreResult<Option<u64>> {
+        Ok(self
+            .position
+            .load()
+            .map_err(|_| CoreError::LoadAccountError)?
+            .collateral_switch_requested_at()
+            .map(|(_, slot)| slot))
+    }
+}
@@ -27,6 +27,8 @@ use crate::{
     validated_recent_timestamp, CoreError,
 };
 
+use super::execute_order::split_user_stats;
+
 /// The accounts definitions for the [`liquidate`](crate::gmsol_store::liquidate) and
 /// [`auto_deleverage`](crate::gmsol_store::auto_deleverage) instructions.
 ///
@@ -36,6 +38,8 @@ use crate::{
 ///     in the market.
 ///   - N..N+V. `[writable]` V virtual inventory accounts, where V represents the total
 ///     number of unique virtual inventories required by the markets.
+///   - N+V. `[writable]` The user stats account of the owner, required only if the owner
+///     has prepared one.
 ///
 /// # Warnings
 /// Because token accounts can be frozen by token's
@@ -241,14 +245,25 @@ pub(crate) fn unchecked_process_position_cut<'info>(
         let (domain, allow_closed) = match kind {
             PositionCutKind::Liquidate => (DomainDisabledFlag::Liquidation, true),
             PositionCutKind::AutoDeleverage(_) => (DomainDisabledFlag::AutoDeleveraging, false),
+            PositionCutKind::Settle => {
+                require!(
+                    accounts.market.load()?.settlement().is_settled(),
+                    CoreError::MarketNotSettled
+                );
+                (DomainDisabledFlag::Settlement, true)
+            }
         };
         store.validate_feature_enabled(domain, ActionDisabledFlag::Create)?;
         store.validate_feature_enabled(domain, ActionDisabledFlag::Execute)?;
+        let market = accounts.market.load()?;
+        market.validate_feature_enabled(domain, ActionDisabledFlag::Create)?;
+        market.validate_feature_enabled(domain, ActionDisabledFlag::Execute)?;
 
         allow_closed
     };
 
-    let remaining_accounts = ctx.remaining_accounts;
+    let (remaining_accounts, user_stats) =
+        split_user_stats(&accounts.user, ctx.remaining_accounts)?;
 
     let (tokens, is_pure_market) = {
         let market = accounts.market.load()?;
@@ -263,6 +278,7 @@ pub(crate) fn unchecked_process_position_cut<'info>(
         PositionCutKind::Liquidate => Order::position_cut_rent(is_pure_market, true)?,
         // For fairness, the keeper will not be refunded the execution fee for ADL.
         PositionCutKind::AutoDeleverage(_) => Order::position_cut_rent(is_pure_market, false)?,
+        PositionCutKind::Settle => Order::position_cut_rent(is_pure_market, true)?,
     };
 
     let event_emitter = EventEmitter::new(&accounts.event_authority, ctx.bumps.event_authority);
@@ -306,6 +322,7 @@ pub(crate) fn unchecked_process_position_cut<'info>(
         .refund(refund)
         .should_unwrap_native_token(should_unwrap_native_token)
         .allow_closed(allow_closed)
+        .user_stats(user_stats.as_ref())
         .event_emitter(event_emitter);
 
     let should_send_trade_event = accounts.oracle.load_mut()?.with_prices_opts(
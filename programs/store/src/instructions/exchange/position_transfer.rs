@@ -0,0 +1,218 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    states::{HasMarketMeta, Market, Order, Position, Seed, Store},
+    utils::internal,
+    CoreError,
+};
+
+use super::order::validate_and_initialize_position_if_needed;
+
+/// The accounts definitions for [`transfer_position`](crate::gmsol_store::transfer_position).
+#[derive(Accounts)]
+pub struct TransferPosition<'info> {
+    /// Owner.
+    pub owner: Signer<'info>,
+    /// Store.
+    pub store: AccountLoader<'info, Store>,
+    /// Position to transfer.
+    #[account(
+        mut,
+        has_one = store,
+        has_one = owner,
+    )]
+    pub position: AccountLoader<'info, Position>,
+    /// The next owner.
+    /// CHECK: only the address is used.
+    pub next_owner: UncheckedAccount<'info>,
+}
+
+impl TransferPosition<'_> {
+    pub(crate) fn invoke(ctx: Context<Self>) -> Result<()> {
+        ctx.accounts.store.load()?.validate_not_restarted()?;
+        ctx.accounts
+            .position
+            .load_mut()?
+            .set_next_owner(ctx.accounts.next_owner.key)?;
+        msg!(
+            "[Position] the next owner of position {} is set to {}",
+            ctx.accounts.position.key(),
+            ctx.accounts.next_owner.key,
+        );
+        Ok(())
+    }
+}
+
+/// The accounts definitions for [`cancel_position_transfer`](crate::gmsol_store::cancel_position_transfer).
+#[derive(Accounts)]
+pub struct CancelPositionTransfer<'info> {
+    /// Owner.
+    pub owner: Signer<'info>,
+    /// Store.
+    pub store: AccountLoader<'info, Store>,
+    /// Position.
+    #[account(
+        mut,
+        has_one = store,
+        has_one = owner,
+    )]
+    pub position: AccountLoader<'info, Position>,
+}
+
+impl CancelPositionTransfer<'_> {
+    pub(crate) fn invoke(ctx: Context<Self>) -> Result<()> {
+        ctx.accounts.position.load_mut()?.cancel_transfer()
+    }
+}
+
+/// The accounts definitions for [`accept_position`](crate::gmsol_store::accept_position).
+#[derive(Accounts)]
+pub struct AcceptPosition<'info> {
+    /// The next owner, who pays for the target position account if needed.
+    #[account(mut)]
+    pub next_owner: Signer<'info>,
+    /// The current owner of the position, who receives the rent of the closed position account.
+    /// CHECK: only used to receive the rent, and is checked by the `position`.
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+    /// Store.
+    pub store: AccountLoader<'info, Store>,
+    /// The market of the position.
+    #[account(
+        has_one = store,
+        constraint = market.load()?.meta().market_token_mint == position.load()?.market_token @ CoreError::MarketTokenMintMismatched,
+    )]
+    pub market: AccountLoader<'info, Market>,
+    /// Position to accept, which will be closed.
+    #[account(
+        mut,
+        close = owner,
+        has_one = store,
+        has_one = owner,
+        constraint = position.load()?.next_owner() == Some(next_owner.key) @ CoreError::OwnerMismatched,
+    )]
+    pub position: AccountLoader<'info, Position>,
+    /// The position of the next owner to move the state into, which will be initialized if needed.
+    #[account(
+        init_if_needed,
+        payer = next_owner,
+        space = 8 + Position::INIT_SPACE,
+        seeds = [
+            Position::SEED,
+            store.key().as_ref(),
+            next_owner.key().as_ref(),
+            position.load()?.market_token.as_ref(),
+            position.load()?.collateral_token.as_ref(),
+            &[position.load()?.kind],
+        ],
+        bump,
+    )]
+    pub target: AccountLoader<'info, Position>,
+    /// The system program.
+    pub system_program: Program<'info, System>,
+}
+
+impl AcceptPosition<'_> {
+    pub(crate) fn invoke(ctx: Context<Self>) -> Result<()> {
+        let accounts = ctx.accounts;
+        let store = accounts.store.key();
+        accounts.store.load()?.validate_not_restarted()?;
+
+        let (kind, collateral_token) = {
+            let position = accounts.position.load()?;
+            (position.kind()?, position.collateral_token)
+        };
+        let meta = *accounts.market.load()?.meta();
+
+        validate_and_initialize_position_if_needed(
+            &accounts.target,
+            ctx.bumps.target,
+            kind,
+            accounts.next_owner.key,
+            accounts.next_owner.to_account_info(),
+            &collateral_token,
+            &meta.market_token_mint,
+            meta.is_pure(),
+            &store,
+            accounts.system_program.to_account_info(),
+        )?;
+
+        accounts
+            .position
+            .load_mut()?
+            .move_to(&mut *accounts.target.load_mut()?)?;
+
+        msg!(
+            "[Position] position {} has been moved to {}",
+            accounts.position.key(),
+            accounts.target.key(),
+        );
+        Ok(())
+    }
+}
+
+/// The accounts definitions for [`sync_position_order_count`](crate::gmsol_store::sync_position_order_count).
+///
+/// Remaining accounts expected by this instruction:
+///   - 0..N. `[writable]` The orders referencing the position that have not been counted.
+///     All such orders must be provided, which is the responsibility of the keeper.
+#[derive(Accounts)]
+pub struct SyncPositionOrderCount<'info> {
+    /// Authority.
+    pub authority: Signer<'info>,
+    /// Store.
+    pub store: AccountLoader<'info, Store>,
+    /// Position.
+    #[account(mut, has_one = store)]
+    pub position: AccountLoader<'info, Position>,
+}
+
+/// Count the orders that were created before the order count was introduced.
+/// # CHECK
+/// Only [`ORDER_KEEPER`](crate::states::roles::RoleKey::ORDER_KEEPER) can use.
+pub(crate) fn unchecked_sync_position_order_count<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SyncPositionOrderCount<'info>>,
+) -> Result<()> {
+    let accounts = &ctx.accounts;
+    let store = accounts.store.key();
+    let address = accounts.position.key();
+    let mut position = accounts.position.load_mut()?;
+
+    let mut uncounted_orders = 0u32;
+    for info in ctx.remaining_accounts {
+        let order = AccountLoader::<Order>::try_from(info)?;
+        let mut order = order.load_mut()?;
+        require_keys_eq!(order.header.store, store, CoreError::StoreMismatched);
+        require!(
+            order.params.position() == Some(&address),
+            CoreError::PositionMismatched
+        );
+        // Orders that have been counted are skipped.
+        if order.is_counted_by_position() {
+            continue;
+        }
+        order.set_counted_by_position(&position);
+        uncounted_orders = uncounted_orders
+            .checked_add(1)
+            .ok_or_else(|| error!(CoreError::ValueOverflow))?;
+    }
+
+    position.sync_order_count(uncounted_orders)?;
+
+    msg!(
+        "[Position] the order count of position {} is synced with {} orders",
+        address,
+        uncounted_orders,
+    );
+    Ok(())
+}
+
+impl<'info> internal::Authentication<'info> for SyncPositionOrderCount<'info> {
+    fn authority(&self) -> &Signer<'info> {
+        &self.authority
+    }
+
+    fn store(&self) -> &AccountLoader<'info, Store> {
+        &self.store
+    }
+}
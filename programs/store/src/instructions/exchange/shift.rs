@@ -107,6 +107,11 @@ impl<'info> internal::Create<'info, Shift> for CreateShift<'info> {
             .load()?
             .validate_not_restarted()?
             .validate_feature_enabled(DomainDisabledFlag::Shift, ActionDisabledFlag::Create)?;
+        for market in [&self.from_market, &self.to_market] {
+            market
+                .load()?
+                .validate_feature_enabled(DomainDisabledFlag::Shift, ActionDisabledFlag::Create)?;
+        }
         Ok(())
     }
 
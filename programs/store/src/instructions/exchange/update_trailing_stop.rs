@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    events::{EventEmitter, OrderUpdated},
+    states::{
+        order::Order, Chainlink, HasMarketMeta, Market, Oracle, Store, TokenMapHeader,
+        ValidateOracleTime,
+    },
+    utils::internal,
+    CoreError, CoreResult,
+};
+
+/// The accounts definition for [`update_trailing_stop`](crate::gmsol_store::update_trailing_stop).
+///
+/// *[See also the documentation for the instruction.](crate::gmsol_store::update_trailing_stop)*
+///
+/// Remaining accounts expected by this instruction:
+///
+///   - 0..N. `[]` N feed accounts, where N represents the total number of unique tokens
+///     in the market.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateTrailingStop<'info> {
+    /// The address authorized to execute this instruction.
+    pub authority: Signer<'info>,
+    /// The store that owns the market.
+    #[account(has_one = token_map)]
+    pub store: AccountLoader<'info, Store>,
+    /// Token map.
+    #[account(has_one = store)]
+    pub token_map: AccountLoader<'info, TokenMapHeader>,
+    /// The oracle buffer to use.
+    #[account(mut, has_one = store)]
+    pub oracle: AccountLoader<'info, Oracle>,
+    /// The market of the order.
+    #[account(has_one = store)]
+    pub market: AccountLoader<'info, Market>,
+    /// The trailing-stop order to update.
+    #[account(
+        mut,
+        constraint = order.load()?.header.store == store.key() @ CoreError::StoreMismatched,
+        constraint = order.load()?.header.market == market.key() @ CoreError::MarketMismatched,
+        constraint = order.load()?.header.action_state()?.is_pending() @ CoreError::PreconditionsAreNotMet,
+    )]
+    pub order: AccountLoader<'info, Order>,
+    /// Chainlink Program.
+    pub chainlink_program: Option<Program<'info, Chainlink>>,
+}
+
+/// CHECK: only ORDER_KEEPER is authorized to perform this action.
+pub(crate) fn unchecked_update_trailing_stop<'info>(
+    ctx: Context<'_, '_, 'info, 'info, UpdateTrailingStop<'info>>,
+) -> Result<()> {
+    let accounts = &ctx.accounts;
+    let tokens = accounts
+        .market
+        .load()?
+        .market_meta()
+        .ordered_tokens()
+        .into_iter()
+        .collect::<Vec<_>>();
+
+    let updated = accounts.oracle.load_mut()?.with_prices(
+        &accounts.store,
+        &accounts.token_map,
+        &tokens,
+        ctx.remaining_accounts,
+        |oracle, _remaining_accounts| {
+            oracle.validate_time(&**accounts)?;
+            let prices = accounts.market.load()?.prices(oracle)?;
+            accounts
+                .order
+                .load_mut()?
+                .update_trailing_stop_mark(&prices.index_token_price)
+        },
+    )?;
+
+    if updated {
+        let event_emitter = EventEmitter::new(&accounts.event_authority, ctx.bumps.event_authority);
+        let order = accounts.order.load()?;
+        event_emitter.emit_cpi(&OrderUpdated::new(false, &accounts.order.key(), &order)?)?;
+    } else {
+        msg!("[Trailing Stop] the mark price is not improved");
+    }
+
+    Ok(())
+}
+
+impl<'info> internal::Authentication<'info> for UpdateTrailingStop<'info> {
+    fn authority(&self) -> &Signer<'info> {
+        &self.authority
+    }
+
+    fn store(&self) -> &AccountLoader<'info, Store> {
+        &self.store
+    }
+}
+
+impl ValidateOracleTime for UpdateTrailingStop<'_> {
+    fn oracle_updated_after(&self) -> CoreResult<Option<i64>> {
+        // Prices older than the last update of the order must not move the mark.
+        Ok(Some(
+            self.order
+                .load()
+                .map_err(|_| CoreError::LoadAccountError)?
+                .header
+                .updated_at,
+        ))
+    }
+
+    fn oracle_updated_before(&self) -> CoreResult<Option<i64>> {
+        Ok(None)
+    }
+
+    fn oracle_updated_after_slot(&self) -> CoreResult<Option<u64>> {
+        Ok(Some(
+            self.order
+                .load()
+                .map_err(|_| CoreError::LoadAccountError)?
+                .header
+                .updated_at_slot,
+        ))
+    }
+}
@@ -115,6 +115,9 @@ impl<'info> internal::Create<'info, Withdrawal> for CreateWithdrawal<'info> {
             .load()?
             .validate_not_restarted()?
             .validate_feature_enabled(DomainDisabledFlag::Withdrawal, ActionDisabledFlag::Create)?;
+        self.market
+            .load()?
+            .validate_feature_enabled(DomainDisabledFlag::Withdrawal, ActionDisabledFlag::Create)?;
         Ok(())
     }
 
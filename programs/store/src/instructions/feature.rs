@@ -3,9 +3,10 @@ use anchor_lang::prelude::*;
 use crate::{
     states::{
         feature::{ActionDisabledFlag, DomainDisabledFlag},
-        Store,
+        Glv, Market, Store,
     },
     utils::internal,
+    CoreError,
 };
 
 /// The accounts definition for [`toggle_feature`](crate::gmsol_store::toggle_feature).
@@ -42,3 +43,89 @@ impl<'info> internal::Authentication<'info> for ToggleFeature<'info> {
         &self.store
     }
 }
+
+/// The accounts definition for [`toggle_market_feature`](crate::gmsol_store::toggle_market_feature).
+#[derive(Accounts)]
+pub struct ToggleMarketFeature<'info> {
+    /// Authority.
+    pub authority: Signer<'info>,
+    /// Store.
+    pub store: AccountLoader<'info, Store>,
+    /// Market.
+    #[account(mut, has_one = store)]
+    pub market: AccountLoader<'info, Market>,
+}
+
+/// Enable or disable the given feature for the market.
+/// CHECK: only `FEATURE_KEEPER` can use this instruction.
+pub(crate) fn unchecked_toggle_market_feature(
+    ctx: Context<ToggleMarketFeature>,
+    domain: DomainDisabledFlag,
+    action: ActionDisabledFlag,
+    enable: bool,
+) -> Result<()> {
+    validate_toggleable_per_account(action)?;
+    ctx.accounts
+        .market
+        .load_mut()?
+        .set_feature_disabled(domain, action, !enable);
+    Ok(())
+}
+
+impl<'info> internal::Authentication<'info> for ToggleMarketFeature<'info> {
+    fn authority(&self) -> &Signer<'info> {
+        &self.authority
+    }
+
+    fn store(&self) -> &AccountLoader<'info, Store> {
+        &self.store
+    }
+}
+
+/// The accounts definition for [`toggle_glv_feature`](crate::gmsol_store::toggle_glv_feature).
+#[derive(Accounts)]
+pub struct ToggleGlvFeature<'info> {
+    /// Authority.
+    pub authority: Signer<'info>,
+    /// Store.
+    pub store: AccountLoader<'info, Store>,
+    /// GLV.
+    #[account(mut, has_one = store)]
+    pub glv: AccountLoader<'info, Glv>,
+}
+
+/// Enable or disable the given feature for the GLV.
+/// CHECK: only `FEATURE_KEEPER` can use this instruction.
+pub(crate) fn unchecked_toggle_glv_feature(
+    ctx: Context<ToggleGlvFeature>,
+    domain: DomainDisabledFlag,
+    action: ActionDisabledFlag,
+    enable: bool,
+) -> Result<()> {
+    validate_toggleable_per_account(action)?;
+    ctx.accounts
+        .glv
+        .load_mut()?
+        .set_feature_disabled(domain, action, !enable);
+    Ok(())
+}
+
+impl<'info> internal::Authentication<'info> for ToggleGlvFeature<'info> {
+    fn authority(&self) -> &Signer<'info> {
+        &self.authority
+    }
+
+    fn store(&self) -> &AccountLoader<'info, Store> {
+        &self.store
+    }
+}
+
+/// Cancellations do not load the market (or GLV) account,
+/// so they can only be toggled store-wide.
+fn validate_toggleable_per_account(action: ActionDisabledFlag) -> Result<()> {
+    require!(
+        !matches!(action, ActionDisabledFlag::Cancel),
+        CoreError::InvalidArgument
+    );
+    Ok(())
+}
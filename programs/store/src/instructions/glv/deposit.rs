@@ -146,6 +146,9 @@ impl<'info> internal::Create<'info, GlvDeposit> for CreateGlvDeposit<'info> {
             .load()?
             .validate_not_restarted()?
             .validate_feature_enabled(DomainDisabledFlag::GlvDeposit, ActionDisabledFlag::Create)?;
+        self.glv
+            .load()?
+            .validate_feature_enabled(DomainDisabledFlag::GlvDeposit, ActionDisabledFlag::Create)?;
         let market_token = self.market_token.key();
         let is_deposit_allowed = self
             .glv
@@ -733,6 +736,10 @@ pub(crate) fn unchecked_execute_glv_deposit<'info>(
         .store
         .load()?
         .validate_feature_enabled(DomainDisabledFlag::GlvDeposit, ActionDisabledFlag::Execute)?;
+    accounts
+        .glv
+        .load()?
+        .validate_feature_enabled(DomainDisabledFlag::GlvDeposit, ActionDisabledFlag::Execute)?;
 
     let SplitAccountsForGlv {
         markets,
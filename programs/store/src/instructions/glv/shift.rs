@@ -115,6 +115,9 @@ impl<'info> internal::Create<'info, GlvShift> for CreateGlvShift<'info> {
             .load()?
             .validate_not_restarted()?
             .validate_feature_enabled(DomainDisabledFlag::GlvShift, ActionDisabledFlag::Create)?;
+        self.glv
+            .load()?
+            .validate_feature_enabled(DomainDisabledFlag::GlvShift, ActionDisabledFlag::Create)?;
         let glv = self.glv.load()?;
         let market_token = self.to_market_token.key();
         let is_deposit_allowed = glv
@@ -403,6 +406,10 @@ pub fn unchecked_execute_glv_shift<'info>(
         .store
         .load()?
         .validate_feature_enabled(DomainDisabledFlag::GlvShift, ActionDisabledFlag::Execute)?;
+    accounts
+        .glv
+        .load()?
+        .validate_feature_enabled(DomainDisabledFlag::GlvShift, ActionDisabledFlag::Execute)?;
 
     let executed = accounts.perform_execution(
         remaining_accounts,
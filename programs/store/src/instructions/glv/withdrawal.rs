@@ -145,6 +145,10 @@ impl<'info> internal::Create<'info, GlvWithdrawal> for CreateGlvWithdrawal<'info
                 DomainDisabledFlag::GlvWithdrawal,
                 ActionDisabledFlag::Create,
             )?;
+        self.glv.load()?.validate_feature_enabled(
+            DomainDisabledFlag::GlvWithdrawal,
+            ActionDisabledFlag::Create,
+        )?;
         Ok(())
     }
 
@@ -668,6 +672,10 @@ pub(crate) fn unchecked_execute_glv_withdrawal<'info>(
         DomainDisabledFlag::GlvWithdrawal,
         ActionDisabledFlag::Execute,
     )?;
+    accounts.glv.load()?.validate_feature_enabled(
+        DomainDisabledFlag::GlvWithdrawal,
+        ActionDisabledFlag::Execute,
+    )?;
 
     let splitted = {
         let glv_withdrawal = accounts.glv_withdrawal.load()?;
@@ -23,7 +23,10 @@ use gmsol_utils::InitSpace;
 use crate::{
     constants,
     states::{
-        market::config::{EntryArgs, MarketConfigBuffer},
+        market::{
+            config::{EntryArgs, MarketConfigBuffer},
+            MarketScheduleParams,
+        },
         Market, Seed, Store, TokenMapAccess, TokenMapHeader, TokenMapLoader,
     },
     utils::internal,
@@ -730,6 +733,195 @@ impl<'info> internal::Authentication<'info> for ToggleGTMinting<'info> {
     }
 }
 
+/// The accounts definition for [`reset_market_circuit_breaker`](crate::gmsol_store::reset_market_circuit_breaker).
+///
+/// *[See also the documentation for the instruction.](crate::gmsol_store::reset_market_circuit_breaker)*
+#[derive(Accounts)]
+pub struct ResetMarketCircuitBreaker<'info> {
+    /// The caller.
+    pub authority: Signer<'info>,
+    /// Store.
+    pub store: AccountLoader<'info, Store>,
+    /// Market.
+    #[account(mut, has_one = store)]
+    pub market: AccountLoader<'info, Market>,
+}
+
+/// Reset Market Circuit Breaker.
+///
+/// ## CHECK
+/// - Only MARKET_KEEPER can use this instruction.
+pub(crate) fn unchecked_reset_market_circuit_breaker(
+    ctx: Context<ResetMarketCircuitBreaker>,
+) -> Result<()> {
+    let was_tripped = ctx.accounts.market.load_mut()?.reset_circuit_breaker();
+    msg!(
+        "[Market] the circuit breaker has been reset, was tripped: {}",
+        was_tripped
+    );
+    Ok(())
+}
+
+impl<'info> internal::Authentication<'info> for ResetMarketCircuitBreaker<'info> {
+    fn authority(&self) -> &Signer<'info> {
+        &self.authority
+    }
+
+    fn store(&self) -> &AccountLoader<'info, Store> {
+        &self.store
+    }
+}
+
+/// The accounts definition for [`set_market_schedule`](crate::gmsol_store::set_market_schedule).
+///
+/// *[See also the documentation for the instruction.](crate::gmsol_store::set_market_schedule)*
+#[derive(Accounts)]
+pub struct SetMarketSchedule<'info> {
+    /// The caller.
+    pub authority: Signer<'info>,
+    /// Store.
+    pub store: AccountLoader<'info, Store>,
+    /// Market.
+    #[account(mut, has_one = store)]
+    pub market: AccountLoader<'info, Market>,
+}
+
+/// Set Market Schedule.
+///
+/// ## CHECK
+/// - Only MARKET_KEEPER can use this instruction.
+pub(crate) fn unchecked_set_market_schedule(
+    ctx: Context<SetMarketSchedule>,
+    params: &MarketScheduleParams,
+) -> Result<()> {
+    ctx.accounts.market.load_mut()?.set_schedule(params)?;
+    msg!(
+        "[Market] the schedule has been set, enabled: {}",
+        params.duration != 0
+    );
+    Ok(())
+}
+
+impl<'info> internal::Authentication<'info> for SetMarketSchedule<'info> {
+    fn authority(&self) -> &Signer<'info> {
+        &self.authority
+    }
+
+    fn store(&self) -> &AccountLoader<'info, Store> {
+        &self.store
+    }
+}
+
+/// The accounts definition for [`start_market_settlement`](crate::gmsol_store::start_market_settlement).
+///
+/// *[See also the documentation for the instruction.](crate::gmsol_store::start_market_settlement)*
+///
+/// Remaining accounts expected by this instruction:
+///
+///   - 0..N. `[]` N feed accounts, where N represents the total number of unique tokens
+///     associated to the market.
+#[derive(Accounts)]
+pub struct StartMarketSettlement<'info> {
+    /// The caller.
+    pub authority: Signer<'info>,
+    /// Store.
+    #[account(has_one = token_map)]
+    pub store: AccountLoader<'info, Store>,
+    /// Token map.
+    #[account(has_one = store)]
+    pub token_map: AccountLoader<'info, TokenMapHeader>,
+    /// The oracle buffer to use.
+    #[account(mut, has_one = store)]
+    pub oracle: AccountLoader<'info, Oracle>,
+    /// Market.
+    #[account(mut, has_one = store)]
+    pub market: AccountLoader<'info, Market>,
+}
+
+/// Start Market Settlement.
+///
+/// ## CHECK
+/// - Only MARKET_KEEPER can use this instruction.
+pub(crate) fn unchecked_start_market_settlement<'info>(
+    ctx: Context<'_, '_, 'info, 'info, StartMarketSettlement<'info>>,
+) -> Result<()> {
+    let accounts = ctx.accounts;
+    let mut market = accounts.market.load_mut()?;
+    market.validate(&accounts.store.key())?;
+    let tokens = market
+        .meta()
+        .ordered_tokens()
+        .into_iter()
+        .collect::<Vec<_>>();
+    let index_token = market.meta().index_token_mint;
+    let (price, price_ts) = accounts.oracle.load_mut()?.with_prices(
+        &accounts.store,
+        &accounts.token_map,
+        &tokens,
+        ctx.remaining_accounts,
+        |oracle, _remaining_accounts| {
+            Ok((
+                oracle.get_primary_price(&index_token, true)?,
+                oracle.max_oracle_ts(),
+            ))
+        },
+    )?;
+    market.start_settlement(&price, price_ts)?;
+    msg!(
+        "[Market] settlement started, price: {}-{}, price ts: {}",
+        price.min,
+        price.max,
+        price_ts
+    );
+    Ok(())
+}
+
+impl<'info> internal::Authentication<'info> for StartMarketSettlement<'info> {
+    fn authority(&self) -> &Signer<'info> {
+        &self.authority
+    }
+
+    fn store(&self) -> &AccountLoader<'info, Store> {
+        &self.store
+    }
+}
+
+/// The accounts definition for [`complete_market_settlement`](crate::gmsol_store::complete_market_settlement).
+///
+/// *[See also the documentation for the instruction.](crate::gmsol_store::complete_market_settlement)*
+#[derive(Accounts)]
+pub struct CompleteMarketSettlement<'info> {
+    /// The caller.
+    pub authority: Signer<'info>,
+    /// Store.
+    pub store: AccountLoader<'info, Store>,
+    /// Market.
+    #[account(mut, has_one = store)]
+    pub market: AccountLoader<'info, Market>,
+}
+
+/// Complete Market Settlement.
+///
+/// ## CHECK
+/// - Only MARKET_KEEPER can use this instruction.
+pub(crate) fn unchecked_complete_market_settlement(
+    ctx: Context<CompleteMarketSettlement>,
+) -> Result<()> {
+    ctx.accounts.market.load_mut()?.complete_settlement()?;
+    msg!("[Market] settlement completed");
+    Ok(())
+}
+
+impl<'info> internal::Authentication<'info> for CompleteMarketSettlement<'info> {
+    fn authority(&self) -> &Signer<'info> {
+        &self.authority
+    }
+
+    fn store(&self) -> &AccountLoader<'info, Store> {
+        &self.store
+    }
+}
+
 /// The accounts definition for [`claim_fees_from_market`](crate::gmsol_store::claim_fees_from_market).
 ///
 /// *[See also the documentation for the instruction.](crate::gmsol_store::claim_fees_from_market)*
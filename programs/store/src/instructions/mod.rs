@@ -46,9 +46,13 @@ pub mod virtual_inventory;
 /// Instructions for builder fees.
 pub mod builder_fee;
 
+/// Instructions for delegations.
+pub mod delegation;
+
 pub use builder_fee::*;
 pub use callback::*;
 pub use config::*;
+pub use delegation::*;
 pub use exchange::*;
 pub use feature::*;
 pub use glv::*;
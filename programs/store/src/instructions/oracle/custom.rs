@@ -1,10 +1,12 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, solana_program::sysvar};
 use gmsol_chainlink_datastreams::interface::ChainlinkDataStreamsInterface;
-use gmsol_utils::InitSpace;
+use gmsol_utils::{price::PublisherPrice, InitSpace};
 
 use crate::{
-    states::{AmountKey, PriceFeed, PriceFeedPrice, PriceProviderKind, Seed, Store},
-    utils::internal,
+    states::{
+        AmountKey, PriceFeed, PriceFeedPrice, PriceProviderKind, PricePublishers, Seed, Store,
+    },
+    utils::{ed25519::load_ed25519_signatures_ix, internal},
     CoreError,
 };
 
@@ -46,9 +48,21 @@ pub(crate) fn unchecked_initialize_price_feed(
     feed_id: &Pubkey,
 ) -> Result<()> {
     require!(
-        matches!(provider, PriceProviderKind::ChainlinkDataStreams),
+        matches!(
+            provider,
+            PriceProviderKind::ChainlinkDataStreams | PriceProviderKind::SignedPublisher
+        ),
         CoreError::NotSupportedCustomPriceProvider
     );
+    // The feed id of a signed publisher price feed is the address of the price feed itself,
+    // so that it can be used directly as the feed account.
+    if matches!(provider, PriceProviderKind::SignedPublisher) {
+        require_keys_eq!(
+            *feed_id,
+            ctx.accounts.price_feed.key(),
+            CoreError::InvalidArgument
+        );
+    }
     let mut feed = ctx.accounts.price_feed.load_init()?;
     feed.init(
         ctx.bumps.price_feed,
@@ -191,3 +205,191 @@ impl UpdatePriceFeedWithChainlink<'_> {
         Ok(())
     }
 }
+
+/// The accounts definition for [`set_price_feed_history_interval`](crate::set_price_feed_history_interval) instruction.
+#[derive(Accounts)]
+pub struct SetPriceFeedHistoryInterval<'info> {
+    /// Authority.
+    pub authority: Signer<'info>,
+    /// Store.
+    pub store: AccountLoader<'info, Store>,
+    /// Price Feed Account.
+    #[account(mut, has_one = store)]
+    pub price_feed: AccountLoader<'info, PriceFeed>,
+}
+
+/// CHECK: only MARKET_KEEPER can set the history interval of a custom price feed.
+pub(crate) fn unchecked_set_price_feed_history_interval(
+    ctx: Context<SetPriceFeedHistoryInterval>,
+    interval: u32,
+) -> Result<()> {
+    ctx.accounts
+        .price_feed
+        .load_mut()?
+        .set_history_interval(interval);
+    Ok(())
+}
+
+impl<'info> internal::Authentication<'info> for SetPriceFeedHistoryInterval<'info> {
+    fn authority(&self) -> &Signer<'info> {
+        &self.authority
+    }
+
+    fn store(&self) -> &AccountLoader<'info, Store> {
+        &self.store
+    }
+}
+
+/// The accounts definition for [`initialize_price_publishers`](crate::initialize_price_publishers) instruction.
+#[derive(Accounts)]
+pub struct InitializePricePublishers<'info> {
+    /// Authority.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// Store.
+    pub store: AccountLoader<'info, Store>,
+    /// Price Publishers.
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PricePublishers::INIT_SPACE,
+        seeds = [PricePublishers::SEED, store.key().as_ref()],
+        bump,
+    )]
+    pub price_publishers: AccountLoader<'info, PricePublishers>,
+    /// The system program.
+    pub system_program: Program<'info, System>,
+}
+
+/// CHECK: only MARKET_KEEPER can initialize the price publishers.
+pub(crate) fn unchecked_initialize_price_publishers(
+    ctx: Context<InitializePricePublishers>,
+) -> Result<()> {
+    ctx.accounts
+        .price_publishers
+        .load_init()?
+        .init(ctx.bumps.price_publishers, &ctx.accounts.store.key());
+    Ok(())
+}
+
+impl<'info> internal::Authentication<'info> for InitializePricePublishers<'info> {
+    fn authority(&self) -> &Signer<'info> {
+        &self.authority
+    }
+
+    fn store(&self) -> &AccountLoader<'info, Store> {
+        &self.store
+    }
+}
+
+/// The accounts definition for [`set_price_publishers`](crate::set_price_publishers) instruction.
+#[derive(Accounts)]
+pub struct SetPricePublishers<'info> {
+    /// Authority.
+    pub authority: Signer<'info>,
+    /// Store.
+    pub store: AccountLoader<'info, Store>,
+    /// Price Publishers.
+    #[account(mut, has_one = store)]
+    pub price_publishers: AccountLoader<'info, PricePublishers>,
+}
+
+/// CHECK: only MARKET_KEEPER can set the price publishers.
+pub(crate) fn unchecked_set_price_publishers(
+    ctx: Context<SetPricePublishers>,
+    publishers: &[Pubkey],
+    quorum: u8,
+    max_age: u32,
+) -> Result<()> {
+    ctx.accounts
+        .price_publishers
+        .load_mut()?
+        .set(publishers, quorum, max_age)
+}
+
+impl<'info> internal::Authentication<'info> for SetPricePublishers<'info> {
+    fn authority(&self) -> &Signer<'info> {
+        &self.authority
+    }
+
+    fn store(&self) -> &AccountLoader<'info, Store> {
+        &self.store
+    }
+}
+
+/// The accounts definition for [`update_price_feed_with_publishers`](crate::update_price_feed_with_publishers) instruction.
+///
+/// The instruction right before this one must be an ed25519 program instruction verifying
+/// the signatures of the publishers over their [`PublisherPrice`] messages.
+#[derive(Accounts)]
+pub struct UpdatePriceFeedWithPublishers<'info> {
+    /// Authority.
+    pub authority: Signer<'info>,
+    /// Store.
+    pub store: AccountLoader<'info, Store>,
+    /// Price Publishers.
+    #[account(
+        has_one = store,
+        seeds = [PricePublishers::SEED, store.key().as_ref()],
+        bump = price_publishers.load()?.bump,
+    )]
+    pub price_publishers: AccountLoader<'info, PricePublishers>,
+    /// Price Feed Account.
+    #[account(mut, has_one = store, has_one = authority)]
+    pub price_feed: AccountLoader<'info, PriceFeed>,
+    /// The instructions sysvar.
+    /// CHECK: only used to load the ed25519 program instruction.
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+/// CHECK: only PRICE_KEEPER can update custom price feed.
+pub(crate) fn unchecked_update_price_feed_with_publishers(
+    ctx: Context<UpdatePriceFeedWithPublishers>,
+    idempotent: bool,
+) -> Result<bool> {
+    let accounts = ctx.accounts;
+
+    require_eq!(
+        accounts.price_feed.load()?.provider()?,
+        PriceProviderKind::SignedPublisher,
+        CoreError::InvalidArgument
+    );
+
+    let signed = load_ed25519_signatures_ix(&accounts.instructions_sysvar)?
+        .into_iter()
+        .map(|(signer, message)| {
+            let price = PublisherPrice::from_message(&message)
+                .ok_or_else(|| error!(CoreError::InvalidPublisherPrice))?;
+            Ok((signer, price))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let price = accounts.price_publishers.load()?.aggregate(
+        &accounts.price_feed.key(),
+        Clock::get()?.unix_timestamp,
+        signed,
+    )?;
+
+    let updated = accounts.price_feed.load_mut()?.update(
+        &price,
+        *accounts
+            .store
+            .load()?
+            .get_amount_by_key(AmountKey::OracleMaxFutureTimestampExcess)
+            .ok_or_else(|| error!(CoreError::Unimplemented))?,
+        idempotent,
+    )?;
+
+    Ok(updated)
+}
+
+impl<'info> internal::Authentication<'info> for UpdatePriceFeedWithPublishers<'info> {
+    fn authority(&self) -> &Signer<'info> {
+        &self.authority
+    }
+
+    fn store(&self) -> &AccountLoader<'info, Store> {
+        &self.store
+    }
+}
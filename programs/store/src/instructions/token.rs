@@ -9,6 +9,7 @@ use crate::{
     constants,
     states::Store,
     utils::{internal, token::must_be_uninitialized},
+    CoreError,
 };
 
 /// The accounts definition for [`initialize_market_vault`](crate::gmsol_store::initialize_market_vault).
@@ -109,9 +110,22 @@ pub struct UseClaimableAccount<'info> {
 /// - Only ORDER_KEEPER can use claimable account.
 pub(crate) fn unchecked_use_claimable_account(
     ctx: Context<UseClaimableAccount>,
-    _timestamp: i64,
+    timestamp: i64,
     amount: u64,
 ) -> Result<()> {
+    {
+        let store = ctx.accounts.store.load()?;
+        // The claimable accounts of users can only be unlocked after the delay.
+        if amount != 0 && ctx.accounts.owner.key != store.holding() {
+            if let Some(unlocked_at) = store.claimable_account_unlocked_at(timestamp)? {
+                require_gte!(
+                    Clock::get()?.unix_timestamp,
+                    unlocked_at,
+                    CoreError::ClaimableAccountLocked
+                );
+            }
+        }
+    }
     if ctx.accounts.account.delegate.is_none() || ctx.accounts.account.delegated_amount != amount {
         anchor_spl::token::approve(
             CpiContext::new_with_signer(
@@ -4,6 +4,7 @@ use gmsol_utils::price::market_status::MarketStatusFlag;
 use gmsol_utils::token_config::TokenConfigFlag;
 
 use crate::{
+    constants,
     states::{
         PriceProviderKind, Store, TokenConfigExt, TokenMapAccess, TokenMapAccessMut,
         TokenMapHeader, TokenMapLoader, UpdateTokenConfigParams,
@@ -295,6 +296,9 @@ pub(crate) fn unchecked_set_expected_provider(
     );
 
     config.set_expected_provider(provider);
+    config
+        .validate_shared_feed_slot()
+        .map_err(CoreError::from)?;
     Ok(())
 }
 
@@ -397,6 +401,104 @@ impl<'info> internal::Authentication<'info> for SetFeedConfig<'info> {
     }
 }
 
+/// The accounts definition for [`set_price_aggregation`](crate::gmsol_store::set_price_aggregation).
+///
+/// [*See also the documentation for the instruction.*](crate::gmsol_store::set_price_aggregation)
+#[derive(Accounts)]
+pub struct SetPriceAggregation<'info> {
+    /// The authority of the instruction.
+    pub authority: Signer<'info>,
+    /// The store that owns the token map.
+    pub store: AccountLoader<'info, Store>,
+    /// The token map to update.
+    #[account(mut, has_one = store)]
+    pub token_map: AccountLoader<'info, TokenMapHeader>,
+}
+
+/// Set the price aggregation config for the given token.
+///
+/// ## CHECK
+/// - Only [`MARKET_KEEPER`](crate::states::RoleKey::MARKET_KEEPER) can perform this action.
+pub(crate) fn unchecked_set_price_aggregation(
+    ctx: Context<SetPriceAggregation>,
+    token: Pubkey,
+    providers: &[PriceProviderKind],
+    max_spread_factor: u128,
+) -> Result<()> {
+    ctx.accounts
+        .token_map
+        .load_token_map_mut()?
+        .get_mut(&token)
+        .ok_or_else(|| error!(CoreError::NotFound))?
+        .set_price_aggregation(providers, max_spread_factor)
+        .map_err(CoreError::from)?;
+    Ok(())
+}
+
+impl<'info> internal::Authentication<'info> for SetPriceAggregation<'info> {
+    fn authority(&self) -> &Signer<'info> {
+        &self.authority
+    }
+
+    fn store(&self) -> &AccountLoader<'info, Store> {
+        &self.store
+    }
+}
+
+/// The accounts definition for [`set_price_spread`](crate::gmsol_store::set_price_spread).
+///
+/// [*See also the documentation for the instruction.*](crate::gmsol_store::set_price_spread)
+#[derive(Accounts)]
+pub struct SetPriceSpread<'info> {
+    /// The authority of the instruction.
+    pub authority: Signer<'info>,
+    /// The store that owns the token map.
+    pub store: AccountLoader<'info, Store>,
+    /// The token map to update.
+    #[account(mut, has_one = store)]
+    pub token_map: AccountLoader<'info, TokenMapHeader>,
+}
+
+/// Set the price spread config for the given token.
+///
+/// ## CHECK
+/// - Only [`MARKET_KEEPER`](crate::states::RoleKey::MARKET_KEEPER) can perform this action.
+pub(crate) fn unchecked_set_price_spread(
+    ctx: Context<SetPriceSpread>,
+    token: Pubkey,
+    min_spread_factor: u128,
+    spread_multiplier_factor: u128,
+) -> Result<()> {
+    require!(
+        spread_multiplier_factor == 0 || spread_multiplier_factor >= constants::MARKET_USD_UNIT,
+        CoreError::InvalidArgument
+    );
+    // A min spread of the whole mid price or more would make the min price non-positive.
+    require_gt!(
+        constants::MARKET_USD_UNIT,
+        min_spread_factor,
+        CoreError::InvalidArgument
+    );
+    ctx.accounts
+        .token_map
+        .load_token_map_mut()?
+        .get_mut(&token)
+        .ok_or_else(|| error!(CoreError::NotFound))?
+        .set_price_spread(min_spread_factor, spread_multiplier_factor)
+        .map_err(CoreError::from)?;
+    Ok(())
+}
+
+impl<'info> internal::Authentication<'info> for SetPriceSpread<'info> {
+    fn authority(&self) -> &Signer<'info> {
+        &self.authority
+    }
+
+    fn store(&self) -> &AccountLoader<'info, Store> {
+        &self.store
+    }
+}
+
 /// The accounts definition of the instructions to read token map.
 #[derive(Accounts)]
 pub struct ReadTokenMap<'info> {
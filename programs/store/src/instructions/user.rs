@@ -4,7 +4,7 @@ use gmsol_utils::InitSpace;
 use crate::{
     events::{BuilderFeeFactorSet, EventEmitter},
     states::{
-        user::{ReferralCodeBytes, ReferralCodeV2, UserHeader},
+        user::{ReferralCodeBytes, ReferralCodeV2, UserHeader, UserStats},
         FactorKey, Seed, Store,
     },
     CoreError,
@@ -59,6 +59,70 @@ pub(crate) fn prepare_user(ctx: Context<PrepareUser>) -> Result<()> {
     Ok(())
 }
 
+/// The accounts definitions for [`prepare_user_stats`](crate::gmsol_store::prepare_user_stats)
+/// instruction.
+#[derive(Accounts)]
+pub struct PrepareUserStats<'info> {
+    /// Owner.
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    /// Store.
+    pub store: AccountLoader<'info, Store>,
+    /// User Account, marked as having a user stats account.
+    #[account(
+        mut,
+        constraint = user.load()?.is_initialized() @ CoreError::InvalidUserAccount,
+        has_one = owner,
+        has_one = store,
+        seeds = [UserHeader::SEED, store.key().as_ref(), owner.key().as_ref()],
+        bump = user.load()?.bump,
+    )]
+    pub user: AccountLoader<'info, UserHeader>,
+    /// User Stats Account.
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + UserStats::INIT_SPACE,
+        seeds = [UserStats::SEED, store.key().as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    pub user_stats: AccountLoader<'info, UserStats>,
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn prepare_user_stats(ctx: Context<PrepareUserStats>) -> Result<()> {
+    let store = ctx.accounts.store.key();
+    let owner = ctx.accounts.owner.key;
+    match ctx.accounts.user_stats.load_init() {
+        Ok(mut user_stats) => {
+            user_stats.init(ctx.bumps.user_stats, &store, owner);
+        }
+        Err(Error::AnchorError(err)) => {
+            if err.error_code_number != ErrorCode::AccountDiscriminatorAlreadySet as u32 {
+                return Err(Error::AnchorError(err));
+            }
+        }
+        Err(err) => {
+            return Err(err);
+        }
+    }
+    ctx.accounts.user_stats.exit(&crate::ID)?;
+    {
+        let user_stats = ctx.accounts.user_stats.load()?;
+        require!(user_stats.is_initialized(), CoreError::InvalidUserAccount);
+        require_keys_eq!(user_stats.store, store, CoreError::InvalidUserAccount);
+        require_keys_eq!(user_stats.owner, *owner, CoreError::InvalidUserAccount);
+        require_eq!(
+            user_stats.bump,
+            ctx.bumps.user_stats,
+            CoreError::InvalidUserAccount
+        );
+    }
+    // Once marked, the user stats account must be provided to every execution.
+    ctx.accounts.user.load_mut()?.set_has_stats();
+    Ok(())
+}
+
 /// The accounts definition for [`initialize_referral_code`](crate::gmsol_store::initialize_referral_code)
 /// instruction.
 #[derive(Accounts)]
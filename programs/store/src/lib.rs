@@ -46,9 +46,12 @@
 //! - [`insert_address`]: Insert an address to the global config.
 //! - [`insert_order_fee_discount_for_referred_user`]:
 //!   Insert order fee discount for referred user factor to the global config.
+//! - [`set_volume_tiers`]: Set the order fee discount tiers by trading volume.
 //!
 //! #### Instructions for Feature Management
 //! - [`toggle_feature`]: Enable or disable the given feature.
+//! - [`toggle_market_feature`]: Enable or disable the given feature for a market.
+//! - [`toggle_glv_feature`]: Enable or disable the given feature for a GLV.
 //!
 //! ## Role-based Permission Management
 //!
@@ -85,6 +88,8 @@
 //! - [`set_expected_provider`]: Set the expected provider for the given token.
 //! - [`set_feed_config_v2`]: Set the feed config of the given provider for the given token.
 //! - [`set_feed_config_market_status_flag`]: Set a market-status flag on the feed config of the given provider for the given token.
+//! - [`set_price_aggregation`]: Set the providers whose prices are aggregated for the given token.
+//! - [`set_price_spread`]: Set the min spread and the spread multiplier of the prices of the given token.
 //! - [`is_token_config_enabled`](gmsol_store::is_token_config_enabled): Check if the config for the given token is enabled.
 //! - [`token_expected_provider`](gmsol_store::token_expected_provider): Get the expected provider set for the given token.
 //! - [`token_feed`](gmsol_store::token_feed): Get the feed address of the given provider set for the given token.
@@ -101,6 +106,10 @@
 //!   provided price feed accounts.
 //! - [`initialize_price_feed`]: Initialize a custom price feed.
 //! - [`update_price_feed_with_chainlink`]: Update a custom Chainlink price feed with Chainlink Data Streams report.
+//! - [`set_price_feed_history_interval`]: Set the sampling interval of the price history of a custom price feed.
+//! - [`initialize_price_publishers`]: Initialize the [`PricePublishers`](states::PricePublishers) account of the store.
+//! - [`set_price_publishers`]: Set the allow-listed price publishers, the quorum and the max age of their prices.
+//! - [`update_price_feed_with_publishers`]: Update a custom signed publisher price feed with prices signed by the publishers.
 //!
 //! ## Market Management
 //!
@@ -116,6 +125,10 @@
 //! - [`get_market_status`](gmsol_store::get_market_status): Calculate the market status with the given prices.
 //! - [`get_market_token_price`](gmsol_store::get_market_token_price): Calculate the market token price the given prices.
 //! - [`toggle_gt_minting`]: Enable or disable GT minting for the given market.
+//! - [`reset_market_circuit_breaker`]: Reset the circuit breaker of the given market.
+//! - [`set_market_schedule`]: Set the trading-hours schedule of the given market.
+//! - [`start_market_settlement`]: Start the settlement of the given market.
+//! - [`complete_market_settlement`]: Complete the settlement of the given market.
 //!
 //! #### Instructions for [`MarketConfigBuffer`](states::market::config::MarketConfigBuffer) accounts
 //! - [`initialize_market_config_buffer`](gmsol_store::initialize_market_config_buffer): Initialize a market config buffer account.
@@ -137,11 +150,13 @@
 //! #### Instructions for [`Deposit`](states::Deposit)
 //! - [`create_deposit`]: Create a deposit by the owner.
 //! - [`execute_deposit`](gmsol_store::execute_deposit()): Execute a deposit by keepers.
+//! - [`execute_deposits`](gmsol_store::execute_deposits()): Execute deposits of a market in batch by keepers.
 //! - [`close_deposit`]: Close a deposit, either by the owner or by keepers.
 //!
 //! #### Instructions for [`Withdrawal`](states::Withdrawal)
 //! - [`create_withdrawal`]: Create a withdrawal by the owner.
 //! - [`execute_withdrawal`](gmsol_store::execute_withdrawal()): Execute a withdrawal by keepers.
+//! - [`execute_withdrawals`](gmsol_store::execute_withdrawals()): Execute withdrawals of a market in batch by keepers.
 //! - [`close_withdrawal`]: Close a withdrawal, either by the owner or by keepers.
 //!
 //! #### Instructions for [`Shift`](states::Shift)
@@ -156,10 +171,33 @@
 //! - [`update_order_v2`]: Update an order by the owner.
 //! - [`execute_increase_or_swap_order_v2`]: Execute an order by keepers.
 //! - [`execute_decrease_order_v2`]: Execute a decrease order by keepers.
+//! - [`execute_orders`](gmsol_store::execute_orders()): Execute market increase orders of a market in batch by keepers.
+//! - [`update_trailing_stop`]: Move the mark of a trailing-stop order by keepers.
 //! - [`close_order_v2`]: Close an order, either by the owner or by keepers.
 //! - [`cancel_order_if_no_position`]: Cancel an order if the position does not exist.
+//! - [`cancel_expired_order`]: Cancel and close an expired order by keepers.
+//! - [`link_orders`]: Link two decrease orders to an increase order by the owner.
+//! - [`activate_linked_orders`]: Activate linked orders after the parent order is executed.
+//! - [`cancel_linked_order`]: Cancel a linked order after its sibling is executed.
+//! - [`create_delegation`]: Authorize a delegate to trade on behalf of the owner.
+//! - [`revoke_delegation`]: Revoke a delegation by the owner.
+//! - [`create_order_by_delegate`]: Create an order by a delegate of the owner.
+//! - [`update_order_by_delegate`]: Update an order by a delegate of the owner.
+//! - [`cancel_order_by_delegate`]: Cancel an order by a delegate of the owner.
+//! - [`create_order_with_intent`]: Create an order from an intent signed by the owner, relayed by
+//!   anyone.
+//! - [`transfer_position`]: Initiate a transfer of a position to another owner.
+//! - [`cancel_position_transfer`]: Cancel a position transfer by the owner.
+//! - [`accept_position`]: Complete a position transfer by the next owner.
+//! - [`sync_position_order_count`]: Count the orders of a position created before the order
+//!   count was introduced by keepers.
+//! - [`request_position_collateral_switch`]: Request to switch the collateral token of a position
+//!   to the opposite token of the market.
+//! - [`cancel_position_collateral_switch`]: Cancel a collateral switch request by the owner.
+//! - [`switch_position_collateral`]: Execute a collateral switch request by keepers.
 //! - [`liquidate`]: Perform a liquidation by keepers.
 //! - [`auto_deleverage`]: Perform an ADL by keepers.
+//! - [`settle_position`]: Force-close a position of a settled market by keepers.
 //! - [`update_adl_state`]: Update the ADL state of the market.
 //!
 //! ## GLV (GMX Liquidity Vault) Pools
@@ -191,6 +229,7 @@
 //! ## User Accounts and Referrals
 //! The instructions for user accounts and referrals are as follows:
 //! - [`prepare_user`](gmsol_store::prepare_user): Prepare a user account.
+//! - [`prepare_user_stats`](gmsol_store::prepare_user_stats): Prepare a user stats account.
 //! - [`initialize_referral_code`](gmsol_store::initialize_referral_code): Initialize and set a referral code.
 //! - [`set_referrer`](gmsol_store::set_referrer): Set the referrer.
 //! - [`transfer_referral_code`](gmsol_store::transfer_referral_code): Transfer the referral code to others.
@@ -235,13 +274,16 @@ use self::{
     ops::{
         deposit::CreateDepositParams,
         glv::{CreateGlvDepositParams, CreateGlvWithdrawalParams},
-        order::{CreateOrderParams, PositionCutKind},
+        order::{
+            CreateOrderArgs, CreateOrderParams, CreateOrderParamsExt, OrderIntent, PositionCutKind,
+        },
         shift::CreateShiftParams,
         withdrawal::CreateWithdrawalParams,
     },
     states::{
+        delegation::DelegationParams,
         glv::UpdateGlvParams,
-        market::{config::EntryArgs, status::MarketStatus},
+        market::{config::EntryArgs, status::MarketStatus, MarketScheduleParams},
         order::UpdateOrderParams,
         token_config::UpdateTokenConfigParams,
         FactorKey, PriceProviderKind,
@@ -591,6 +633,8 @@ pub mod gmsol_store {
     ///   in the store.
     /// - The provided `key` must be defined in [`AmountKey`](crate::states::AmountKey).
     /// - The store must be initialized and owned by this program.
+    /// - The `SelfExecutionGracePeriod`, if non-zero, must remain less than the
+    ///   `RequestExpiration`.
     #[access_control(internal::Authenticate::only_config_keeper(&ctx))]
     pub fn insert_amount(ctx: Context<InsertConfig>, key: String, amount: u64) -> Result<()> {
         instructions::unchecked_insert_amount(ctx, &key, amount)
@@ -671,6 +715,37 @@ pub mod gmsol_store {
         instructions::unchecked_insert_factor(ctx, &key.to_string(), factor)
     }
 
+    /// Set the order fee discount tiers by trading volume.
+    ///
+    /// A user whose rolling 30-day trading volume reaches the threshold of a tier receives
+    /// the order fee discount of the highest such tier, which is composed with the GT rank
+    /// discount and the referral discount.
+    ///
+    /// # Accounts
+    /// *[See the documentation for the accounts.](InsertConfig).*
+    ///
+    /// # Arguments
+    /// - `thresholds`: The min trading volume (in USD) of each tier.
+    /// - `factors`: The order fee discount factor of each tier.
+    ///
+    /// # Errors
+    /// - The [`authority`](InsertConfig::authority) must be a signer and have the
+    ///   MARKET_KEEPER role in the store.
+    /// - The store must be initialized and owned by this program.
+    /// - The lengths of `thresholds` and `factors` must match and must not exceed
+    ///   [`MAX_VOLUME_TIERS`](states::store::MAX_VOLUME_TIERS).
+    /// - The `thresholds` must be strictly increasing.
+    /// - The `factors` must be sorted and each of them must be less than or equal to
+    ///   [`MARKET_USD_UNIT`](crate::constants::MARKET_USD_UNIT)(i.e., 100%).
+    #[access_control(internal::Authenticate::only_market_keeper(&ctx))]
+    pub fn set_volume_tiers(
+        ctx: Context<InsertConfig>,
+        thresholds: Vec<u128>,
+        factors: Vec<u128>,
+    ) -> Result<()> {
+        instructions::unchecked_set_volume_tiers(ctx, &thresholds, &factors)
+    }
+
     // ===========================================
     //             Feature Management
     // ===========================================
@@ -725,6 +800,82 @@ pub mod gmsol_store {
         instructions::unchecked_toggle_feature(ctx, domain, action, enable)
     }
 
+    /// Enable or disable a feature for the given market.
+    ///
+    /// A feature is disabled for a market if it is disabled either store-wide
+    /// (see [`toggle_feature`]) or for the market.
+    ///
+    /// # Accounts
+    /// *[See the documentation for the accounts.](ToggleMarketFeature).*
+    ///
+    /// # Arguments
+    /// - `domain`: The domain part of the feature flag, must be a valid domain defined in
+    ///   [`DomainDisabledFlag`](crate::states::feature::DomainDisabledFlag).
+    /// - `action`: The action part of the feature flag, must be a valid action defined in
+    ///   [`ActionDisabledFlag`](crate::states::feature::ActionDisabledFlag).
+    /// - `enable`: If true, enables the feature. If false, disables it.
+    ///
+    /// # Errors
+    /// - The [`authority`](ToggleMarketFeature::authority) must be a signer and have the
+    ///   FEATURE_KEEPER role in the store.
+    /// - The [`market`](ToggleMarketFeature::market) must be owned by the store.
+    /// - The `domain` must be a valid domain defined in [`DomainDisabledFlag`](crate::states::feature::DomainDisabledFlag).
+    /// - The `action` must be a valid action defined in [`ActionDisabledFlag`](crate::states::feature::ActionDisabledFlag),
+    ///   other than `Cancel`, which can only be toggled store-wide.
+    #[access_control(internal::Authenticate::only_feature_keeper(&ctx))]
+    pub fn toggle_market_feature(
+        ctx: Context<ToggleMarketFeature>,
+        domain: String,
+        action: String,
+        enable: bool,
+    ) -> Result<()> {
+        let domain = domain
+            .parse()
+            .map_err(|_| error!(CoreError::InvalidArgument))?;
+        let action = action
+            .parse()
+            .map_err(|_| error!(CoreError::InvalidArgument))?;
+        instructions::unchecked_toggle_market_feature(ctx, domain, action, enable)
+    }
+
+    /// Enable or disable a feature for the given GLV.
+    ///
+    /// A feature is disabled for a GLV if it is disabled either store-wide
+    /// (see [`toggle_feature`]) or for the GLV.
+    ///
+    /// # Accounts
+    /// *[See the documentation for the accounts.](ToggleGlvFeature).*
+    ///
+    /// # Arguments
+    /// - `domain`: The domain part of the feature flag, must be a valid domain defined in
+    ///   [`DomainDisabledFlag`](crate::states::feature::DomainDisabledFlag).
+    /// - `action`: The action part of the feature flag, must be a valid action defined in
+    ///   [`ActionDisabledFlag`](crate::states::feature::ActionDisabledFlag).
+    /// - `enable`: If true, enables the feature. If false, disables it.
+    ///
+    /// # Errors
+    /// - The [`authority`](ToggleGlvFeature::authority) must be a signer and have the
+    ///   FEATURE_KEEPER role in the store.
+    /// - The [`glv`](ToggleGlvFeature::glv) must be owned by the store.
+    /// - The `domain` must be a valid domain defined in [`DomainDisabledFlag`](crate::states::feature::DomainDisabledFlag).
+    /// - The `action` must be a valid action defined in [`ActionDisabledFlag`](crate::states::feature::ActionDisabledFlag),
+    ///   other than `Cancel`, which can only be toggled store-wide.
+    #[access_control(internal::Authenticate::only_feature_keeper(&ctx))]
+    pub fn toggle_glv_feature(
+        ctx: Context<ToggleGlvFeature>,
+        domain: String,
+        action: String,
+        enable: bool,
+    ) -> Result<()> {
+        let domain = domain
+            .parse()
+            .map_err(|_| error!(CoreError::InvalidArgument))?;
+        let action = action
+            .parse()
+            .map_err(|_| error!(CoreError::InvalidArgument))?;
+        instructions::unchecked_toggle_glv_feature(ctx, domain, action, enable)
+    }
+
     // ===========================================
     //           Token Config Management
     // ===========================================
@@ -959,6 +1110,8 @@ pub mod gmsol_store {
     ///   owned by the `store`.
     /// - The given `token` must exist in the token map.
     /// - The `provider` index must correspond to a valid [`PriceProviderKind`].
+    /// - `Chainlink` and `SignedPublisher`, which share a feed slot, cannot both be used
+    ///   by the token.
     #[access_control(internal::Authenticate::only_market_keeper(&ctx))]
     pub fn set_expected_provider(
         ctx: Context<SetExpectedProvider>,
@@ -994,6 +1147,8 @@ pub mod gmsol_store {
     ///   owned by the `store`.
     /// - The given `token` must exist in the token map.
     /// - The `provider` index must correspond to a valid [`PriceProviderKind`].
+    /// - The feed slot of the `provider` must not be shared with another provider used by the
+    ///   token (`Chainlink` and `SignedPublisher` share a feed slot).
     #[access_control(internal::Authenticate::only_market_keeper(&ctx))]
     pub fn set_feed_config_v2(
         ctx: Context<SetFeedConfig>,
@@ -1014,6 +1169,98 @@ pub mod gmsol_store {
         )
     }
 
+    /// Set the providers whose prices are aggregated with the price of the expected provider
+    /// for the given token.
+    ///
+    /// When enabled, the oracle reads the prices of the expected provider and the given
+    /// providers in the same instruction and uses their median as the price of the token.
+    /// The feed accounts of the aggregated providers must be appended after the feed accounts
+    /// of all the tokens, ordered by token and then by provider index.
+    ///
+    /// # Accounts
+    /// [*See the documentation for the accounts*](SetPriceAggregation).
+    ///
+    /// # Arguments
+    /// - `token`: The token whose config will be updated.
+    /// - `providers`: The indexes of the providers to aggregate, excluding the expected provider.
+    ///   Passing an empty list disables price aggregation.
+    /// - `max_spread_factor`: The max allowed spread between the mid prices of the providers,
+    ///   as a factor of their median. Ignored when `providers` is empty.
+    ///
+    /// # Errors
+    /// - The [`authority`](SetPriceAggregation::authority) must be a signer
+    ///   and a MARKET_KEEPER in the given store.
+    /// - The [`store`](SetPriceAggregation::store) must be an initialized [`Store`](states::Store)
+    ///   account owned by the store program.
+    /// - The [`token_map`](SetPriceAggregation::token_map) must be an initialized token map account
+    ///   owned by the `store`.
+    /// - The given `token` must exist in the token map.
+    /// - Each of the `providers` must correspond to a valid [`PriceProviderKind`] with an
+    ///   initialized feed config, must not be the expected provider, and must not be duplicated.
+    /// - At most two providers can be aggregated with the expected provider.
+    /// - `max_spread_factor` must be non-zero when `providers` is not empty.
+    /// - `Chainlink` and `SignedPublisher`, which share a feed slot, cannot both be used
+    ///   by the token.
+    #[access_control(internal::Authenticate::only_market_keeper(&ctx))]
+    pub fn set_price_aggregation(
+        ctx: Context<SetPriceAggregation>,
+        token: Pubkey,
+        providers: Vec<u8>,
+        max_spread_factor: u128,
+    ) -> Result<()> {
+        let providers = providers
+            .into_iter()
+            .map(PriceProviderKind::try_from)
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|_| CoreError::InvalidProviderKindIndex)?;
+        instructions::unchecked_set_price_aggregation(ctx, token, &providers, max_spread_factor)
+    }
+
+    /// Set the price spread config for the given token.
+    ///
+    /// When enabled, the oracle widens the spread between the max and min prices of the token,
+    /// regardless of the provider. The spread reported by the providers (e.g. the confidence
+    /// interval of Pyth or the bid/ask spread of Chainlink Data Streams) is multiplied by the
+    /// spread multiplier factor and then raised to at least the min spread factor of the mid
+    /// price. The widened spread is applied symmetrically around the mid price, after price
+    /// aggregation and price adjustment, and never narrows the original spread.
+    ///
+    /// # Accounts
+    /// [*See the documentation for the accounts*](SetPriceSpread).
+    ///
+    /// # Arguments
+    /// - `token`: The token whose config will be updated.
+    /// - `min_spread_factor`: The min spread between the max and min prices, as a factor of
+    ///   the mid price. `0` means no min spread.
+    /// - `spread_multiplier_factor`: The factor by which the spread reported by the providers
+    ///   is multiplied. `0` means the spread is not multiplied.
+    ///
+    /// # Errors
+    /// - The [`authority`](SetPriceSpread::authority) must be a signer
+    ///   and a MARKET_KEEPER in the given store.
+    /// - The [`store`](SetPriceSpread::store) must be an initialized [`Store`](states::Store)
+    ///   account owned by the store program.
+    /// - The [`token_map`](SetPriceSpread::token_map) must be an initialized token map account
+    ///   owned by the `store`.
+    /// - The given `token` must exist in the token map.
+    /// - `min_spread_factor` must be less than `1`.
+    /// - `spread_multiplier_factor` must be either `0` or not less than `1`.
+    /// - Each non-zero factor must be at least `10^12`, and must not exceed `u32::MAX` times `10^12`.
+    #[access_control(internal::Authenticate::only_market_keeper(&ctx))]
+    pub fn set_price_spread(
+        ctx: Context<SetPriceSpread>,
+        token: Pubkey,
+        min_spread_factor: u128,
+        spread_multiplier_factor: u128,
+    ) -> Result<()> {
+        instructions::unchecked_set_price_spread(
+            ctx,
+            token,
+            min_spread_factor,
+            spread_multiplier_factor,
+        )
+    }
+
     /// Return whether the token config is enabled.
     ///
     /// # Accounts
@@ -1235,6 +1482,8 @@ pub mod gmsol_store {
     /// - The number of tokens provided cannot exceed [`MAX_TOKENS`](crate::states::oracle::price_map::PriceMap::MAX_TOKENS).
     /// - Each token in `tokens` must be configured and enabled in the token map.
     /// - For each token, there must be a valid corresponding price feed account included in the remaining accounts.
+    /// - The feed accounts of the aggregated providers of the tokens must follow, ordered by token and
+    ///   then by provider index.
     #[access_control(internal::Authenticate::only_oracle_controller(&ctx))]
     pub fn set_prices_from_price_feed<'info>(
         ctx: Context<'_, '_, 'info, 'info, SetPricesFromPriceFeed<'info>>,
@@ -1267,6 +1516,8 @@ pub mod gmsol_store {
     ///   must match the PDA derived from the `store`, `index`, `feed_id`, and other expected seeds.
     /// - The `provider` index must correspond to a valid [`PriceProviderKind`] that supports
     ///   custom price feeds.
+    /// - For the [`SignedPublisher`](PriceProviderKind::SignedPublisher) provider, the `feed_id`
+    ///   must be the address of the [`price_feed`](InitializePriceFeed::price_feed) itself.
     #[access_control(internal::Authenticate::only_price_keeper(&ctx))]
     pub fn initialize_price_feed(
         ctx: Context<InitializePriceFeed>,
@@ -1357,6 +1608,123 @@ pub mod gmsol_store {
         instructions::unchecked_update_price_feed_with_chainlink(ctx, compressed_report, true)
     }
 
+    /// Set the sampling interval of the price history of a custom price feed.
+    ///
+    /// When the interval is non-zero, a price sample is recorded on each update of the price feed
+    /// if at least `interval` seconds have passed since the last sample, and the TWAP of the
+    /// recorded samples is used as the reference price for the max deviation checks. Setting the
+    /// interval to `0` disables the price history. The recorded samples are always cleared.
+    ///
+    /// # Accounts
+    /// *[See the documentation for the accounts.](SetPriceFeedHistoryInterval)*
+    ///
+    /// # Arguments
+    /// - `interval`: The sampling interval in seconds.
+    ///
+    /// # Errors
+    /// - The [`authority`](SetPriceFeedHistoryInterval::authority) must be a signer and have the
+    ///   MARKET_KEEPER role in the store.
+    /// - The [`store`](SetPriceFeedHistoryInterval::store) must be an initialized store account.
+    /// - The [`price_feed`](SetPriceFeedHistoryInterval::price_feed) must be initialized and owned
+    ///   by the store.
+    #[access_control(internal::Authenticate::only_market_keeper(&ctx))]
+    pub fn set_price_feed_history_interval(
+        ctx: Context<SetPriceFeedHistoryInterval>,
+        interval: u32,
+    ) -> Result<()> {
+        instructions::unchecked_set_price_feed_history_interval(ctx, interval)
+    }
+
+    /// Initialize the [`PricePublishers`](states::PricePublishers) account of the store.
+    ///
+    /// # Accounts
+    /// *[See the documentation for the accounts.](InitializePricePublishers)*
+    ///
+    /// # Errors
+    /// - The [`authority`](InitializePricePublishers::authority) must be a signer and have the
+    ///   MARKET_KEEPER role in the store.
+    /// - The [`store`](InitializePricePublishers::store) must be an initialized store account.
+    /// - The [`price_publishers`](InitializePricePublishers::price_publishers) must be uninitialized
+    ///   and its address must match the PDA derived from the `store`.
+    #[access_control(internal::Authenticate::only_market_keeper(&ctx))]
+    pub fn initialize_price_publishers(ctx: Context<InitializePricePublishers>) -> Result<()> {
+        instructions::unchecked_initialize_price_publishers(ctx)
+    }
+
+    /// Set the allow-listed publishers of signed publisher price feeds.
+    ///
+    /// # Accounts
+    /// *[See the documentation for the accounts.](SetPricePublishers)*
+    ///
+    /// # Arguments
+    /// - `publishers`: The ed25519 public keys of the publishers, replacing the current ones.
+    /// - `quorum`: The min number of distinct publishers whose prices are required for an update.
+    /// - `max_age`: The max age in seconds of the price of each publisher.
+    ///
+    /// # Errors
+    /// - The [`authority`](SetPricePublishers::authority) must be a signer and have the
+    ///   MARKET_KEEPER role in the store.
+    /// - The [`store`](SetPricePublishers::store) must be an initialized store account.
+    /// - The [`price_publishers`](SetPricePublishers::price_publishers) must be initialized and
+    ///   owned by the store.
+    /// - The number of `publishers` must not exceed [`MAX_PRICE_PUBLISHERS`](states::MAX_PRICE_PUBLISHERS),
+    ///   and the `publishers` must not be duplicated.
+    /// - `quorum` must be non-zero and not greater than the number of `publishers`.
+    /// - `max_age` must be non-zero.
+    #[access_control(internal::Authenticate::only_market_keeper(&ctx))]
+    pub fn set_price_publishers(
+        ctx: Context<SetPricePublishers>,
+        publishers: Vec<Pubkey>,
+        quorum: u8,
+        max_age: u32,
+    ) -> Result<()> {
+        instructions::unchecked_set_price_publishers(ctx, &publishers, quorum, max_age)
+    }
+
+    /// Updates the price data in a custom price feed account using the prices signed by the
+    /// allow-listed publishers. The price feed must be configured to use the
+    /// [`SignedPublisher`](PriceProviderKind::SignedPublisher) provider.
+    ///
+    /// The median of the signed prices is used as the price, and the oldest timestamp as the
+    /// price timestamp. The min and max prices are the `k`-th lowest and highest prices, where
+    /// `k = (quorum - 1) / 2`, so that outliers within a quorum cannot widen the band.
+    ///
+    /// - Returns `Ok(true)` if the price feed is updated.
+    /// - Returns `Ok(false)` if `idempotent` is `true` and the prices are valid but older than
+    ///   the last update, provided no other errors occur.
+    ///
+    /// # Accounts
+    /// *[See the documentation for the accounts.](UpdatePriceFeedWithPublishers)*
+    ///
+    /// # Arguments
+    /// - `idempotent`: Whether to skip the update instead of failing if the prices are older
+    ///   than the last update.
+    ///
+    /// # Errors
+    /// - The [`authority`](UpdatePriceFeedWithPublishers::authority) must be a signer and have the
+    ///   PRICE_KEEPER role in the store.
+    /// - The [`store`](UpdatePriceFeedWithPublishers::store) must be an initialized store account.
+    /// - The [`price_publishers`](UpdatePriceFeedWithPublishers::price_publishers) must be the
+    ///   initialized price publishers account of the store.
+    /// - The [`price_feed`](UpdatePriceFeedWithPublishers::price_feed) must be initialized, owned by
+    ///   the store, authorized for the `authority`, and configured to use the
+    ///   [`SignedPublisher`](PriceProviderKind::SignedPublisher) provider.
+    /// - The instruction right before this one must be an ed25519 program instruction verifying
+    ///   the signatures over [`PublisherPrice`](gmsol_utils::price::PublisherPrice) messages, with
+    ///   all data contained in the instruction itself.
+    /// - Each price must be signed by a distinct allow-listed publisher, be for the given
+    ///   `price_feed`, and must not be older than the max age.
+    /// - The number of prices must reach the quorum.
+    /// - The aggregated price must meet all validity requirements (see the `update` method of
+    ///   [`PriceFeed`](states::oracle::PriceFeed)).
+    #[access_control(internal::Authenticate::only_price_keeper(&ctx))]
+    pub fn update_price_feed_with_publishers(
+        ctx: Context<UpdatePriceFeedWithPublishers>,
+        idempotent: bool,
+    ) -> Result<bool> {
+        instructions::unchecked_update_price_feed_with_publishers(ctx, idempotent)
+    }
+
     // ===========================================
     //              Market Management
     // ===========================================
@@ -1789,6 +2157,112 @@ pub mod gmsol_store {
         instructions::unchecked_toggle_gt_minting(ctx, enable)
     }
 
+    /// Reset the circuit breaker of the given market.
+    ///
+    /// This instruction allows a MARKET_KEEPER to re-enable increase orders and deposits for a
+    /// market whose circuit breaker has been tripped. The reference price is also cleared, so the
+    /// next validated price will become the new reference.
+    ///
+    /// # Accounts
+    /// [*See the documentation for the accounts.*](ResetMarketCircuitBreaker)
+    ///
+    /// # Errors
+    /// - The [`authority`](ResetMarketCircuitBreaker::authority) must be a signer and be a
+    ///   MARKET_KEEPER in the store.
+    /// - The [`store`](ResetMarketCircuitBreaker::store) must be an initialized store account.
+    /// - The [`market`](ResetMarketCircuitBreaker::market) must be an initialized market account
+    ///   and owned by the store.
+    #[access_control(internal::Authenticate::only_market_keeper(&ctx))]
+    pub fn reset_market_circuit_breaker(ctx: Context<ResetMarketCircuitBreaker>) -> Result<()> {
+        instructions::unchecked_reset_market_circuit_breaker(ctx)
+    }
+
+    /// Set the trading-hours schedule of the given market.
+    ///
+    /// Once a schedule is set, orders of the market are subject to its trading sessions:
+    /// - Increase orders, except for those only increasing collateral, can only be executed
+    ///   during a trading session and outside of its pre-close window (configured with
+    ///   the [`PreCloseWindow`](states::market::config::MarketConfigKey::PreCloseWindow)
+    ///   market config).
+    /// - Limit and stop orders can only be executed during a trading session, with prices
+    ///   updated after the session opens.
+    /// - Limit and stop increase orders that have been pending across the close are
+    ///   cancelled if the index price has gapped from their trigger prices by more
+    ///   than the [`GapToleranceFactor`](states::market::config::MarketConfigKey::GapToleranceFactor)
+    ///   market config, if set.
+    ///
+    /// # Accounts
+    /// [*See the documentation for the accounts.*](SetMarketSchedule)
+    ///
+    /// # Arguments
+    /// - `params`: The schedule params. A zero `duration` disables the schedule.
+    ///
+    /// # Errors
+    /// - The [`authority`](SetMarketSchedule::authority) must be a signer and be a
+    ///   MARKET_KEEPER in the store.
+    /// - The [`store`](SetMarketSchedule::store) must be an initialized store account.
+    /// - The [`market`](SetMarketSchedule::market) must be an initialized market account
+    ///   and owned by the store.
+    /// - Unless the schedule is disabled, `trading_days` must be a non-empty bitmap of the
+    ///   seven weekdays, `open_time` must be less than one day, and `duration` must not
+    ///   exceed one day.
+    /// - `holidays` must not contain duplicates or more than
+    ///   [`MAX_MARKET_HOLIDAYS`](states::market::schedule::MAX_MARKET_HOLIDAYS) days.
+    #[access_control(internal::Authenticate::only_market_keeper(&ctx))]
+    pub fn set_market_schedule(
+        ctx: Context<SetMarketSchedule>,
+        params: MarketScheduleParams,
+    ) -> Result<()> {
+        instructions::unchecked_set_market_schedule(ctx, &params)
+    }
+
+    /// Start the settlement of the given market.
+    ///
+    /// This instruction allows a MARKET_KEEPER to start winding down a market before delisting.
+    /// The index token price is frozen from the validated oracle prices and is used as the
+    /// settlement price for the market from then on. Increase orders, deposits and shifts into
+    /// the market are disabled, while decreases and withdrawals remain available.
+    ///
+    /// # Accounts
+    /// [*See the documentation for the accounts.*](StartMarketSettlement)
+    ///
+    /// # Errors
+    /// - The [`authority`](StartMarketSettlement::authority) must be a signer and be a
+    ///   MARKET_KEEPER in the store.
+    /// - The [`store`](StartMarketSettlement::store) must be an initialized store account.
+    /// - The [`token_map`](StartMarketSettlement::token_map) must be initialized and authorized
+    ///   by the store.
+    /// - The [`oracle`](StartMarketSettlement::oracle) must be initialized, cleared and
+    ///   store-owned.
+    /// - The [`market`](StartMarketSettlement::market) must be enabled, owned by the store and
+    ///   active.
+    /// - Price feed accounts must be valid and provided in the market's sorted token list order.
+    #[access_control(internal::Authenticate::only_market_keeper(&ctx))]
+    pub fn start_market_settlement<'info>(
+        ctx: Context<'_, '_, 'info, 'info, StartMarketSettlement<'info>>,
+    ) -> Result<()> {
+        instructions::unchecked_start_market_settlement(ctx)
+    }
+
+    /// Complete the settlement of the given market.
+    ///
+    /// Once completed, ORDER_KEEPERs can force-close the remaining positions of the market at
+    /// the settlement price with [`settle_position`](crate::gmsol_store::settle_position).
+    ///
+    /// # Accounts
+    /// [*See the documentation for the accounts.*](CompleteMarketSettlement)
+    ///
+    /// # Errors
+    /// - The [`authority`](CompleteMarketSettlement::authority) must be a signer and be a
+    ///   MARKET_KEEPER in the store.
+    /// - The [`store`](CompleteMarketSettlement::store) must be an initialized store account.
+    /// - The [`market`](CompleteMarketSettlement::market) must be owned by the store and its
+    ///   settlement must have been started.
+    #[access_control(internal::Authenticate::only_market_keeper(&ctx))]
+    pub fn complete_market_settlement(ctx: Context<CompleteMarketSettlement>) -> Result<()> {
+        instructions::unchecked_complete_market_settlement(ctx)
+    }
+
     /// Claim fees from the given market.
     ///
     /// # Accounts
@@ -1851,6 +2325,8 @@ pub mod gmsol_store {
     /// - The [`account`](UseClaimableAccount::account) must be a PDA derived from
     ///   the time window of the `timestamp` and other expected seeds. It can be uninitialized.
     /// - If the `account` is initialized, it must be owned by the store.
+    /// - If the `owner` is not the holding address and the `amount` is not zero, the claimable
+    ///   collateral delay (if set) must have passed since the end of the time window.
     #[access_control(internal::Authenticate::only_order_keeper(&ctx))]
     pub fn use_claimable_account(
         ctx: Context<UseClaimableAccount>,
@@ -1996,6 +2472,9 @@ pub mod gmsol_store {
 
     /// Execute a deposit by keepers.
     ///
+    /// The deposit can also be executed by anyone once it has been pending for longer than the
+    /// self-execution grace period, in which case the execution fee claimed is capped.
+    ///
     /// # Accounts
     /// *[See the documentation for the accounts.](ExecuteDeposit)*
     ///
@@ -2006,8 +2485,8 @@ pub mod gmsol_store {
     ///
     /// # Errors
     /// This instruction will fail if:
-    /// - The [`authority`](ExecuteDeposit::authority) is not a signer or is not an ORDER_KEEPER
-    ///   in the store.
+    /// - The [`authority`](ExecuteDeposit::authority) is not a signer, or is not an ORDER_KEEPER
+    ///   in the store while the self-execution of the deposit is not allowed.
     /// - The [`store`](ExecuteDeposit::store) is not properly initialized.
     /// - The [`token_map`](ExecuteDeposit::token_map) is not initialized or not authorized by
     ///   the store.
@@ -2025,7 +2504,7 @@ pub mod gmsol_store {
     ///   or do not match the swap parameters.
     /// - Any oracle prices from the feed accounts are incomplete or invalid.
     /// - The execution fails and `throw_on_execution_error` is set to `true`.
-    #[access_control(internal::Authenticate::only_order_keeper(&ctx))]
+    #[access_control(internal::Authenticate::only_order_keeper_or_self_executor(&ctx, execution_fee))]
     pub fn execute_deposit<'info>(
         ctx: Context<'_, '_, 'info, 'info, ExecuteDeposit<'info>>,
         execution_fee: u64,
@@ -2034,6 +2513,44 @@ pub mod gmsol_store {
         instructions::unchecked_execute_deposit(ctx, execution_fee, throw_on_execution_error)
     }
 
+    /// Execute deposits of the same market in batch by keepers, sharing one set of oracle prices.
+    ///
+    /// Each deposit is processed independently: a deposit that fails to execute is cancelled
+    /// and its tokens are returned to the escrows, without affecting the other deposits.
+    /// Deposits that are not pending, have a swap path, were updated after the oracle prices,
+    /// or are provided with invalid accounts are skipped and left untouched.
+    ///
+    /// # Accounts
+    /// *[See the documentation for the accounts.](ExecuteDeposits)*
+    ///
+    /// # Arguments
+    /// - `execution_fee`: The execution fee claimed to be used by the keeper for each deposit.
+    /// - `count`: The number of deposits to execute.
+    ///
+    /// # Errors
+    /// This instruction will fail if:
+    /// - The [`authority`](ExecuteDeposits::authority) is not a signer or is not an ORDER_KEEPER
+    ///   in the store.
+    /// - The [`store`](ExecuteDeposits::store) is not properly initialized.
+    /// - The [`token_map`](ExecuteDeposits::token_map) is not initialized or not authorized by
+    ///   the store.
+    /// - The [`oracle`](ExecuteDeposits::oracle) is not initialized, cleared and owned by the
+    ///   store.
+    /// - The [`market`](ExecuteDeposits::market) is not initialized, is disabled or not owned by
+    ///   the store.
+    /// - Any token mint or vault accounts do not match those of the market.
+    /// - `count` is zero or the remaining accounts are not enough for `count` deposits.
+    /// - Any feed accounts in the remaining accounts are invalid or do not match the market tokens.
+    /// - Any oracle prices from the feed accounts are incomplete or invalid.
+    #[access_control(internal::Authenticate::only_order_keeper(&ctx))]
+    pub fn execute_deposits<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteDeposits<'info>>,
+        execution_fee: u64,
+        count: u8,
+    ) -> Result<()> {
+        instructions::unchecked_execute_deposits(ctx, execution_fee, count)
+    }
+
     // ===========================================
     //                 Withdrawal
     // ===========================================
@@ -2100,6 +2617,9 @@ pub mod gmsol_store {
 
     /// Execute a withdrawal by keepers.
     ///
+    /// The withdrawal can also be executed by anyone once it has been pending for longer than the
+    /// self-execution grace period, in which case the execution fee claimed is capped.
+    ///
     /// # Accounts
     /// *[See the documentation for the accounts.](ExecuteWithdrawal)*
     ///
@@ -2110,8 +2630,8 @@ pub mod gmsol_store {
     ///
     /// # Errors
     /// This instruction will fail if:
-    /// - The [`authority`](ExecuteWithdrawal::authority) is not a signer or is not an ORDER_KEEPER
-    ///   in the store.
+    /// - The [`authority`](ExecuteWithdrawal::authority) is not a signer, or is not an ORDER_KEEPER
+    ///   in the store while the self-execution of the withdrawal is not allowed.
     /// - The [`store`](ExecuteWithdrawal::store) is not properly initialized.
     /// - The [`token_map`](ExecuteWithdrawal::token_map) is not initialized or not authorized by
     ///   the store.
@@ -2129,7 +2649,7 @@ pub mod gmsol_store {
     ///   not match the swap parameters.
     /// - Any oracle prices from the feed accounts are incomplete or invalid.
     /// - The execution fails and `throw_on_execution_error` is set to true.
-    #[access_control(internal::Authenticate::only_order_keeper(&ctx))]
+    #[access_control(internal::Authenticate::only_order_keeper_or_self_executor(&ctx, execution_fee))]
     pub fn execute_withdrawal<'info>(
         ctx: Context<'_, '_, 'info, 'info, ExecuteWithdrawal<'info>>,
         execution_fee: u64,
@@ -2138,6 +2658,44 @@ pub mod gmsol_store {
         instructions::unchecked_execute_withdrawal(ctx, execution_fee, throw_on_execution_error)
     }
 
+    /// Execute withdrawals of the same market in batch by keepers, sharing one set of oracle prices.
+    ///
+    /// Each withdrawal is processed independently: a withdrawal that fails to execute is cancelled
+    /// and its market tokens are returned to the escrow, without affecting the other withdrawals.
+    /// Withdrawals that are not pending, have a swap path, were updated after the oracle prices,
+    /// or are provided with invalid accounts are skipped and left untouched.
+    ///
+    /// # Accounts
+    /// *[See the documentation for the accounts.](ExecuteWithdrawals)*
+    ///
+    /// # Arguments
+    /// - `execution_fee`: The execution fee claimed to be used by the keeper for each withdrawal.
+    /// - `count`: The number of withdrawals to execute.
+    ///
+    /// # Errors
+    /// This instruction will fail if:
+    /// - The [`authority`](ExecuteWithdrawals::authority) is not a signer or is not an ORDER_KEEPER
+    ///   in the store.
+    /// - The [`store`](ExecuteWithdrawals::store) is not properly initialized.
+    /// - The [`token_map`](ExecuteWithdrawals::token_map) is not initialized or not authorized by
+    ///   the store.
+    /// - The [`oracle`](ExecuteWithdrawals::oracle) is not initialized, cleared and owned by the
+    ///   store.
+    /// - The [`market`](ExecuteWithdrawals::market) is not initialized, is disabled or not owned by
+    ///   the store.
+    /// - Any token mint or vault accounts do not match those of the market.
+    /// - `count` is zero or the remaining accounts are not enough for `count` withdrawals.
+    /// - Any feed accounts in the remaining accounts are invalid or do not match the market tokens.
+    /// - Any oracle prices from the feed accounts are incomplete or invalid.
+    #[access_control(internal::Authenticate::only_order_keeper(&ctx))]
+    pub fn execute_withdrawals<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteWithdrawals<'info>>,
+        execution_fee: u64,
+        count: u8,
+    ) -> Result<()> {
+        instructions::unchecked_execute_withdrawals(ctx, execution_fee, count)
+    }
+
     // ===========================================
     //             Order and Position
     // ===========================================
@@ -2178,119 +2736,392 @@ pub mod gmsol_store {
     /// - `params`: Order Parameters specifying the market, order kind, and other details.
     ///
     /// # Errors
-    /// This instruction will fail if:
-    /// - The [`owner`](CreateOrderV2::owner) is not a signer or has insufficient balance for the
-    ///   execution fee and rent.
-    /// - The [`store`](CreateOrderV2::store) is not properly initialized.
-    /// - The [`market`](CreateOrderV2::market) is not initialized, is disabled, or not owned by
-    ///   the `store`.
-    /// - The [`user`](CreateOrderV2::user) is not initialized or does not correspond to the owner.
-    ///   The address must be a valid PDA derived from the `owner` and expected seeds.
-    /// - The [`order`](CreateOrderV2::order) is not uninitialized or the address is not a valid
-    ///   PDA derived from the `owner`, `nonce` and expected seeds.
-    /// - For increase/decrease orders:
-    ///   - The [`position`](CreateOrderV2::position) is missing, not validly initialized, or not
-    ///     owned by both the `owner` and `store`.
-    ///   - The [`long_token`](CreateOrderV2::long_token) or [`short_token`](CreateOrderV2::short_token)
-    ///     are missing, or do not match the those defined in the [`market`](CreateOrderV2::market).
-    ///   - The [`long_token_escrow`](CreateOrderV2::long_token_escrow) or
-    ///     [`short_token_escrow`](CreateOrderV2::short_token_escrow) are missing, not valid
-    ///     escrow accounts for `long_token` or `short_token` respectively, or not owned by the `order`.
-    /// - For increase/swap orders:
-    ///   - The [`initial_collateral_token`](CreateOrderV2::initial_collateral_token) is missing
-    ///     or invalid.
-    ///   - The [`initial_collateral_token_escrow`](CreateOrderV2::initial_collateral_token_escrow)
-    ///     is missing, not a valid escrow account for `initial_collateral_token`, or not owned by
-    ///     the `order`.
-    ///   - The [`initial_collateral_token_source`](CreateOrderV2::initial_collateral_token_source)
-    ///     is missing or not a valid source account with `owner` as the authority.
-    /// - For decrease/swap orders:
-    ///   - The [`final_output_token`](CreateOrderV2::final_output_token) is invalid.
-    ///   - The [`final_output_token_escrow`](CreateOrderV2::final_output_token_escrow) is missing,
-    ///     not a valid escrow account for `final_output_token`, or not owned by the `order`.
-    /// - The feature for creating this kind of order is not enabled.
-    /// - The remaining market accounts do not match the swap parameters, not all enabled or owned
-    ///   by the `store`.
-    /// - The accounts related to callback must be provided if
-    ///   [`callback_authority`](CreateOrderV2::callback_authority) is provided.
-    pub fn create_order_v2<'info>(
-        mut ctx: Context<'_, '_, 'info, 'info, CreateOrderV2<'info>>,
+    /// This instruction will fail if:
+    /// - The [`owner`](CreateOrderV2::owner) is not a signer or has insufficient balance for the
+    ///   execution fee and rent.
+    /// - The [`store`](CreateOrderV2::store) is not properly initialized.
+    /// - The [`market`](CreateOrderV2::market) is not initialized, is disabled, or not owned by
+    ///   the `store`.
+    /// - The [`user`](CreateOrderV2::user) is not initialized or does not correspond to the owner.
+    ///   The address must be a valid PDA derived from the `owner` and expected seeds.
+    /// - The [`order`](CreateOrderV2::order) is not uninitialized or the address is not a valid
+    ///   PDA derived from the `owner`, `nonce` and expected seeds.
+    /// - For increase/decrease orders:
+    ///   - The [`position`](CreateOrderV2::position) is missing, not validly initialized, or not
+    ///     owned by both the `owner` and `store`.
+    ///   - The [`long_token`](CreateOrderV2::long_token) or [`short_token`](CreateOrderV2::short_token)
+    ///     are missing, or do not match the those defined in the [`market`](CreateOrderV2::market).
+    ///   - The [`long_token_escrow`](CreateOrderV2::long_token_escrow) or
+    ///     [`short_token_escrow`](CreateOrderV2::short_token_escrow) are missing, not valid
+    ///     escrow accounts for `long_token` or `short_token` respectively, or not owned by the `order`.
+    /// - For increase/swap orders:
+    ///   - The [`initial_collateral_token`](CreateOrderV2::initial_collateral_token) is missing
+    ///     or invalid.
+    ///   - The [`initial_collateral_token_escrow`](CreateOrderV2::initial_collateral_token_escrow)
+    ///     is missing, not a valid escrow account for `initial_collateral_token`, or not owned by
+    ///     the `order`.
+    ///   - The [`initial_collateral_token_source`](CreateOrderV2::initial_collateral_token_source)
+    ///     is missing or not a valid source account with `owner` as the authority.
+    /// - For decrease/swap orders:
+    ///   - The [`final_output_token`](CreateOrderV2::final_output_token) is invalid.
+    ///   - The [`final_output_token_escrow`](CreateOrderV2::final_output_token_escrow) is missing,
+    ///     not a valid escrow account for `final_output_token`, or not owned by the `order`.
+    /// - The feature for creating this kind of order is not enabled.
+    /// - The remaining market accounts do not match the swap parameters, not all enabled or owned
+    ///   by the `store`.
+    /// - The accounts related to callback must be provided if
+    ///   [`callback_authority`](CreateOrderV2::callback_authority) is provided.
+    pub fn create_order_v2<'info>(
+        mut ctx: Context<'_, '_, 'info, 'info, CreateOrderV2<'info>>,
+        nonce: [u8; 32],
+        params: CreateOrderParams,
+        callback_version: Option<u8>,
+    ) -> Result<()> {
+        let args = CreateOrderArgs {
+            params,
+            ext: Default::default(),
+        };
+        internal::Create::create(&mut ctx, &nonce, &args, callback_version)
+    }
+
+    /// Create an order with the extended parameters.
+    ///
+    /// # Accounts
+    /// *[See the documentation for the accounts.](CreateOrderV2)*
+    ///
+    /// # Arguments
+    /// - `nonce`: Nonce bytes used to derive the address for the order.
+    /// - `params`: Order Parameters specifying the market, order kind, and other details.
+    /// - `ext`: Extended Order Parameters, such as the trail distance of a trailing-stop order.
+    ///
+    /// # Errors
+    /// - See [`create_order_v2`] for the requirements.
+    pub fn create_order_v3<'info>(
+        mut ctx: Context<'_, '_, 'info, 'info, CreateOrderV2<'info>>,
+        nonce: [u8; 32],
+        params: CreateOrderParams,
+        ext: CreateOrderParamsExt,
+        callback_version: Option<u8>,
+    ) -> Result<()> {
+        let args = CreateOrderArgs { params, ext };
+        internal::Create::create(&mut ctx, &nonce, &args, callback_version)
+    }
+
+    /// Close an order, either by the owner or by keepers.
+    ///
+    /// # Accounts
+    /// *[See the documentation for the accounts.](CloseOrderV2)*
+    ///
+    /// # Arguments
+    /// - `reason`: The reason for the close.
+    ///
+    /// # Errors
+    /// - The [`executor`](CloseOrderV2::executor) must be a signer and either the owner
+    ///   of the `order` or a ORDER_KEEPER in the store.
+    /// - The [`store`](CloseOrderV2::store) must be initialized.
+    /// - The [`owner`](CloseOrderV2::owner) must be the owner of the `order`.
+    /// - The [`user`](CloseOrderV2::user) must be initialized and correspond to the `owner`.
+    /// - The [`referrer_user`](CloseOrderV2::referrer_user) must be present if the `owner` has a
+    ///   referrer, and it must be initialized and correspond to the referrer of the `owner`.
+    /// - The [`order`](CloseOrderV2::order) must be initialized and owned by the `store` and the
+    ///   `owner`.
+    /// - The tokens must be those recorded in the `order`.
+    /// - The escrow accounts must be owned and recorded in the `order`.
+    /// - The addresses of the ATAs must be valid.
+    /// - The `order` must be cancelled or completed if the `executor` is not the owner.
+    /// - The feature must be enabled for closing the given kind of `order`.
+    /// - The accounts related to callback must be provided if
+    ///   [`callback_authority`](CloseOrderV2::callback_authority) is provided.
+    /// - If the `order` is the parent of linked orders that have not been resolved, the linked
+    ///   orders must be provided as remaining accounts, and none of them can still be waiting
+    ///   for activation.
+    pub fn close_order_v2<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CloseOrderV2<'info>>,
+        reason: String,
+    ) -> Result<()> {
+        ctx.accounts
+            .validate_linked_orders_resolved(ctx.remaining_accounts)?;
+        internal::Close::close(&ctx, &reason)
+    }
+
+    /// Settle the builder fee of an order.
+    ///
+    /// Permissionless: any signer may invoke it, no role is required, and
+    /// no specific address can block it. Idempotent: a recorded builder
+    /// fee amount of zero, including orders that never had a builder set,
+    /// is an explicit no-op, so this may be called in any order state,
+    /// before or after terminal.
+    ///
+    /// # Accounts
+    /// *[See the documentation for the accounts.](SettleBuilderFee)*
+    ///
+    /// # Errors
+    /// - The [`order`](SettleBuilderFee::order) must be initialized and owned by the `store`.
+    /// - If the order's recorded builder fee amount is non-zero, [`builder_user`](SettleBuilderFee::builder_user)
+    ///   must be provided and must match the builder recorded on the order, and
+    ///   [`claim_vault`](SettleBuilderFee::claim_vault) must be provided and must be the
+    ///   associated token account of the final output token owned by `builder_user`.
+    pub fn settle_builder_fee(ctx: Context<SettleBuilderFee>) -> Result<()> {
+        SettleBuilderFee::invoke(ctx)
+    }
+
+    /// Cancel order if the corresponding position does not exist.
+    ///
+    /// # Accounts
+    /// *[See the documentation for the accounts.](CancelOrderIfNoPosition)*
+    ///
+    /// # Errors
+    /// - The [`authority`](CancelOrderIfNoPosition::authority) must be a signed ORDER_KEEPER
+    ///   in the store.
+    /// - The [`store`](CancelOrderIfNoPosition::authority) must be initialized.
+    /// - The [`order`](CancelOrderIfNoPosition::order) must be initialized and owned by the
+    ///   `store`. It must be in the pending state.
+    /// - The [`position`](CancelOrderIfNoPosition::position) must be recorded in the order.
+    ///   It must be owned by the system program (i.e., considered to be missing).
+    #[access_control(internal::Authenticate::only_order_keeper(&ctx))]
+    pub fn cancel_order_if_no_position(ctx: Context<CancelOrderIfNoPosition>) -> Result<()> {
+        instructions::unchecked_cancel_order_if_no_position(ctx)
+    }
+
+    /// Cancel and close an expired order by keepers.
+    ///
+    /// The funds are returned to the owner and the receiver, and the rent is returned to the
+    /// rent receiver, just like [`close_order_v2`]. An [`OrderRemoved`](events::OrderRemoved)
+    /// event is emitted with the reason `expired`.
+    ///
+    /// # Accounts
+    /// *[See the documentation for the accounts.](CloseOrderV2)*
+    ///
+    /// # Errors
+    /// - The [`executor`](CloseOrderV2::executor) must be a signed ORDER_KEEPER in the store.
+    /// - The [`store`](CloseOrderV2::store) must be initialized and not restarted.
+    /// - The [`order`](CloseOrderV2::order) must be initialized and owned by the `store` and
+    ///   the `owner`. It must be in the pending state and must have expired.
+    /// - The other accounts must be valid as required by [`close_order_v2`].
+    /// - If the `order` is the parent of linked orders that have not been resolved, the linked
+    ///   orders must be provided as writable remaining accounts. They will be cancelled.
+    ///
+    /// # Notes
+    /// - If some of the ATAs are not initialized, the order is cancelled but kept open, and
+    ///   it can be closed later with [`close_order_v2`].
+    #[access_control(internal::Authenticate::only_order_keeper(&ctx))]
+    pub fn cancel_expired_order<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CloseOrderV2<'info>>,
+    ) -> Result<()> {
+        CloseOrderV2::cancel_expired(&ctx)
+    }
+
+    /// Link a take-profit and a stop-loss order to an increase order by the owner.
+    ///
+    /// The linked orders stay inactive until the parent order is executed, after which
+    /// executing either of them cancels the other (one-cancels-other).
+    ///
+    /// # Accounts
+    /// *[See the documentation for the accounts.](LinkOrders)*
+    ///
+    /// # Errors
+    /// - The [`owner`](LinkOrders::owner) must be a signer and the owner of all the orders.
+    /// - The [`store`](LinkOrders::store) must be initialized.
+    /// - The [`parent`](LinkOrders::parent) must be a pending increase order owned by
+    ///   the `store`. It must not be linked.
+    /// - The [`first`](LinkOrders::first) and [`second`](LinkOrders::second) must be pending
+    ///   limit-decrease, stop-loss-decrease or trailing-stop-decrease orders owned by the
+    ///   `store`, for the same position as the `parent`. They must not be linked.
+    pub fn link_orders(ctx: Context<LinkOrders>) -> Result<()> {
+        LinkOrders::invoke(ctx)
+    }
+
+    /// Resolve the linked orders of a parent order by keepers.
+    ///
+    /// The linked orders are activated if the parent order has been executed,
+    /// or cancelled otherwise. Linked orders that have been closed are skipped.
+    ///
+    /// # Accounts
+    /// *[See the documentation for the accounts.](ActivateLinkedOrders)*
+    ///
+    /// # Errors
+    /// - The [`authority`](ActivateLinkedOrders::authority) must be a signed ORDER_KEEPER
+    ///   in the store.
+    /// - The [`store`](ActivateLinkedOrders::store) must be initialized.
+    /// - The [`parent`](ActivateLinkedOrders::parent) must be initialized and owned by the
+    ///   `store`. It must be completed or cancelled, with its linked orders not yet resolved.
+    /// - The linked orders must be provided as remaining accounts in the recorded order.
+    #[access_control(internal::Authenticate::only_order_keeper(&ctx))]
+    pub fn activate_linked_orders<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ActivateLinkedOrders<'info>>,
+    ) -> Result<()> {
+        instructions::unchecked_activate_linked_orders(ctx)
+    }
+
+    /// Cancel a linked order by keepers after its sibling has been executed.
+    ///
+    /// # Accounts
+    /// *[See the documentation for the accounts.](CancelLinkedOrder)*
+    ///
+    /// # Errors
+    /// - The [`authority`](CancelLinkedOrder::authority) must be a signed ORDER_KEEPER
+    ///   in the store.
+    /// - The [`store`](CancelLinkedOrder::store) must be initialized.
+    /// - The [`order`](CancelLinkedOrder::order) must be a pending linked order owned by the
+    ///   `store`.
+    /// - The [`sibling`](CancelLinkedOrder::sibling) must be the sibling of the `order`.
+    ///   It must be in the completed state.
+    #[access_control(internal::Authenticate::only_order_keeper(&ctx))]
+    pub fn cancel_linked_order(ctx: Context<CancelLinkedOrder>) -> Result<()> {
+        instructions::unchecked_cancel_linked_order(ctx)
+    }
+
+    /// Authorize a delegate to create, update and cancel position orders on behalf of the owner.
+    ///
+    /// The delegation is bounded by the allowed markets, the max size delta value per order,
+    /// the max cumulative collateral spend and the expiry. To let the delegate create increase
+    /// orders, the owner is expected to approve the `delegation` account to spend from the
+    /// source token accounts.
+    ///
+    /// # Accounts
+    /// *[See the documentation for the accounts.](CreateDelegation)*
+    ///
+    /// # Arguments
+    /// - `params`: Delegation Parameters.
+    ///
+    /// # Errors
+    /// - The [`owner`](CreateDelegation::owner) must be a signer and must not be the `delegate`.
+    /// - The [`store`](CreateDelegation::store) must be initialized.
+    /// - The [`delegation`](CreateDelegation::delegation) must be uninitialized and a PDA derived
+    ///   from the `store`, `owner`, `delegate` and expected seeds.
+    /// - The `params` must contain between 1 and
+    ///   [`MAX_DELEGATION_MARKETS`](states::delegation::MAX_DELEGATION_MARKETS) markets,
+    ///   and must not have expired.
+    pub fn create_delegation(
+        ctx: Context<CreateDelegation>,
+        params: DelegationParams,
+    ) -> Result<()> {
+        CreateDelegation::invoke(ctx, &params)
+    }
+
+    /// Revoke a delegation by the owner.
+    ///
+    /// # Accounts
+    /// *[See the documentation for the accounts.](RevokeDelegation)*
+    ///
+    /// # Errors
+    /// - The [`owner`](RevokeDelegation::owner) must be a signer and the owner of the `delegation`.
+    /// - The [`delegation`](RevokeDelegation::delegation) must be owned by the `store`.
+    pub fn revoke_delegation(ctx: Context<RevokeDelegation>) -> Result<()> {
+        RevokeDelegation::invoke(ctx)
+    }
+
+    /// Create an increase or decrease order by a delegate of the owner.
+    ///
+    /// The order is derived from the `delegate`, while its owner and receiver are the owner
+    /// of the `delegation`.
+    ///
+    /// # Accounts
+    /// *[See the documentation for the accounts.](CreateOrderByDelegate)*
+    ///
+    /// # Arguments
+    /// - `nonce`: Nonce bytes used to derive the address for the order.
+    /// - `params`: Order Parameters.
+    /// - `ext`: Extended Order Parameters.
+    ///
+    /// # Errors
+    /// - The [`delegate`](CreateOrderByDelegate::delegate) must be a signer.
+    /// - The [`delegation`](CreateOrderByDelegate::delegation) must be a valid delegation of the
+    ///   `owner` to the `delegate`. It must not have expired, the market and the markets of the
+    ///   swap path must be allowed, and the size delta value and the cumulative collateral spend
+    ///   must be within its limits.
+    /// - The [`position`](CreateOrderByDelegate::position) must have been prepared by the owner.
+    /// - The order kind must be an increase or decrease order kind, and no callback is supported.
+    /// - See [`create_order_v2`] for the other requirements.
+    pub fn create_order_by_delegate<'info>(
+        mut ctx: Context<'_, '_, 'info, 'info, CreateOrderByDelegate<'info>>,
         nonce: [u8; 32],
         params: CreateOrderParams,
-        callback_version: Option<u8>,
+        ext: CreateOrderParamsExt,
     ) -> Result<()> {
-        internal::Create::create(&mut ctx, &nonce, &params, callback_version)
+        let args = CreateOrderArgs { params, ext };
+        internal::Create::create(&mut ctx, &nonce, &args, None)
     }
 
-    /// Close an order, either by the owner or by keepers.
+    /// Update an order by a delegate of the owner.
     ///
     /// # Accounts
-    /// *[See the documentation for the accounts.](CloseOrderV2)*
+    /// *[See the documentation for the accounts.](UpdateOrderByDelegate)*
     ///
     /// # Arguments
-    /// - `reason`: The reason for the close.
+    /// - `params`: Update Order Parameters.
     ///
     /// # Errors
-    /// - The [`executor`](CloseOrderV2::executor) must be a signer and either the owner
-    ///   of the `order` or a ORDER_KEEPER in the store.
-    /// - The [`store`](CloseOrderV2::store) must be initialized.
-    /// - The [`owner`](CloseOrderV2::owner) must be the owner of the `order`.
-    /// - The [`user`](CloseOrderV2::user) must be initialized and correspond to the `owner`.
-    /// - The [`referrer_user`](CloseOrderV2::referrer_user) must be present if the `owner` has a
-    ///   referrer, and it must be initialized and correspond to the referrer of the `owner`.
-    /// - The [`order`](CloseOrderV2::order) must be initialized and owned by the `store` and the
-    ///   `owner`.
-    /// - The tokens must be those recorded in the `order`.
-    /// - The escrow accounts must be owned and recorded in the `order`.
-    /// - The addresses of the ATAs must be valid.
-    /// - The `order` must be cancelled or completed if the `executor` is not the owner.
-    /// - The feature must be enabled for closing the given kind of `order`.
-    /// - The accounts related to callback must be provided if
-    ///   [`callback_authority`](CloseOrderV2::callback_authority) is provided.
-    pub fn close_order_v2<'info>(
-        ctx: Context<'_, '_, 'info, 'info, CloseOrderV2<'info>>,
-        reason: String,
+    /// - The [`delegate`](UpdateOrderByDelegate::delegate) must be a signer.
+    /// - The [`delegation`](UpdateOrderByDelegate::delegation) must be a delegation to the
+    ///   `delegate` from the owner of the `order`. It must not have expired, the market must be
+    ///   allowed and the updated size delta value must be within its limit.
+    /// - The [`order`](UpdateOrderByDelegate::order) must not have a callback.
+    /// - See [`update_order_v2`] for the other requirements.
+    pub fn update_order_by_delegate(
+        ctx: Context<UpdateOrderByDelegate>,
+        params: UpdateOrderParams,
     ) -> Result<()> {
-        internal::Close::close(&ctx, &reason)
+        UpdateOrderByDelegate::invoke(ctx, &params)
     }
 
-    /// Settle the builder fee of an order.
+    /// Cancel an order by a delegate of the owner.
     ///
-    /// Permissionless: any signer may invoke it, no role is required, and
-    /// no specific address can block it. Idempotent: a recorded builder
-    /// fee amount of zero, including orders that never had a builder set,
-    /// is an explicit no-op, so this may be called in any order state,
-    /// before or after terminal.
+    /// The cancelled order can then be closed by keepers or the owner.
     ///
     /// # Accounts
-    /// *[See the documentation for the accounts.](SettleBuilderFee)*
+    /// *[See the documentation for the accounts.](CancelOrderByDelegate)*
     ///
     /// # Errors
-    /// - The [`order`](SettleBuilderFee::order) must be initialized and owned by the `store`.
-    /// - If the order's recorded builder fee amount is non-zero, [`builder_user`](SettleBuilderFee::builder_user)
-    ///   must be provided and must match the builder recorded on the order, and
-    ///   [`claim_vault`](SettleBuilderFee::claim_vault) must be provided and must be the
-    ///   associated token account of the final output token owned by `builder_user`.
-    pub fn settle_builder_fee(ctx: Context<SettleBuilderFee>) -> Result<()> {
-        SettleBuilderFee::invoke(ctx)
+    /// - The [`delegate`](CancelOrderByDelegate::delegate) must be a signer.
+    /// - The [`delegation`](CancelOrderByDelegate::delegation) must be a delegation to the
+    ///   `delegate` from the owner of the `order`. It must not have expired and the market
+    ///   must be allowed.
+    /// - The [`order`](CancelOrderByDelegate::order) must be in the pending state.
+    /// - The feature for cancelling this kind of order must be enabled.
+    pub fn cancel_order_by_delegate(ctx: Context<CancelOrderByDelegate>) -> Result<()> {
+        CancelOrderByDelegate::invoke(ctx)
     }
 
-    /// Cancel order if the corresponding position does not exist.
+    /// Create an increase or decrease order from an intent signed off-chain by the owner.
+    ///
+    /// The relayer pays for the rent and the execution fee, and is reimbursed with the relayer
+    /// fee specified in the intent. The order is derived from the `relayer`, while its owner and
+    /// receiver are the signer of the intent.
+    ///
+    /// The relayer fee is deducted from the initial collateral delta amount of the intent and
+    /// paid to the relayer, so it is only supported by increase orders.
     ///
     /// # Accounts
-    /// *[See the documentation for the accounts.](CancelOrderIfNoPosition)*
+    /// *[See the documentation for the accounts.](CreateOrderWithIntent)*
+    ///
+    /// # Arguments
+    /// - `nonce`: Nonce bytes used to derive the address for the order.
+    /// - `intent`: The signed order intent.
     ///
     /// # Errors
-    /// - The [`authority`](CancelOrderIfNoPosition::authority) must be a signed ORDER_KEEPER
-    ///   in the store.
-    /// - The [`store`](CancelOrderIfNoPosition::authority) must be initialized.
-    /// - The [`order`](CancelOrderIfNoPosition::order) must be initialized and owned by the
-    ///   `store`. It must be in the pending state.
-    /// - The [`position`](CancelOrderIfNoPosition::position) must be recorded in the order.
-    ///   It must be owned by the system program (i.e., considered to be missing).
-    #[access_control(internal::Authenticate::only_order_keeper(&ctx))]
-    pub fn cancel_order_if_no_position(ctx: Context<CancelOrderIfNoPosition>) -> Result<()> {
-        instructions::unchecked_cancel_order_if_no_position(ctx)
+    /// - The [`relayer`](CreateOrderWithIntent::relayer) must be a signer.
+    /// - The instruction right before this one must be an ed25519 program instruction verifying
+    ///   the signature of the [`owner`](CreateOrderWithIntent::owner) over the intent message
+    ///   (see [`OrderIntent::to_message`]).
+    /// - The intent must be for the `store`, the `market` and the `relayer`, must not have passed
+    ///   its deadline, and its nonce must match the one recorded in the
+    ///   [`user`](CreateOrderWithIntent::user) account.
+    /// - The [`intent_authority`](CreateOrderWithIntent::intent_authority) must have been
+    ///   approved by the owner to spend the initial collateral.
+    /// - If the relayer fee is not zero, the order must be an increase order, the relayer fee must
+    ///   not exceed the initial collateral delta amount, and the
+    ///   [`relayer_fee_receiver`](CreateOrderWithIntent::relayer_fee_receiver) must be an initial
+    ///   collateral token account of the `relayer`.
+    /// - The order kind must be an increase or decrease order kind without swaps, and no callback
+    ///   is supported.
+    /// - See [`create_order_v2`] for the other requirements.
+    pub fn create_order_with_intent<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateOrderWithIntent<'info>>,
+        nonce: [u8; 32],
+        intent: OrderIntent,
+    ) -> Result<()> {
+        CreateOrderWithIntent::invoke(ctx, &nonce, &intent)
     }
 
     /// Close an empty position account.
@@ -2307,6 +3138,166 @@ pub mod gmsol_store {
         CloseEmptyPosition::invoke(ctx)
     }
 
+    /// Initiate a transfer of a position to another owner.
+    ///
+    /// The transfer is completed once the next owner accepts it with [`accept_position`].
+    /// No orders can be created for the position while the transfer is in progress, except for
+    /// liquidations and ADLs.
+    ///
+    /// # Accounts
+    /// *[See the documentation for the accounts.](TransferPosition)*
+    ///
+    /// # Errors
+    /// - The [`owner`](TransferPosition::owner) must sign the transaction and own the `position`.
+    /// - The [`store`](TransferPosition::store) must be initialized.
+    /// - The [`next_owner`](TransferPosition::next_owner) must not be the current owner.
+    /// - The [`position`](TransferPosition::position) must not be referenced by any order that
+    ///   has not been closed, and its order count must have been synced (see
+    ///   [`sync_position_order_count`]).
+    pub fn transfer_position(ctx: Context<TransferPosition>) -> Result<()> {
+        TransferPosition::invoke(ctx)
+    }
+
+    /// Cancel a position transfer.
+    ///
+    /// # Accounts
+    /// *[See the documentation for the accounts.](CancelPositionTransfer)*
+    ///
+    /// # Errors
+    /// - The [`owner`](CancelPositionTransfer::owner) must sign the transaction and own the
+    ///   `position`.
+    /// - The [`position`](CancelPositionTransfer::position) must have a transfer in progress.
+    pub fn cancel_position_transfer(ctx: Context<CancelPositionTransfer>) -> Result<()> {
+        CancelPositionTransfer::invoke(ctx)
+    }
+
+    /// Accept a position transfer.
+    ///
+    /// Moves the state of the position, including the claimable funding state, into the position
+    /// account of the next owner, and closes the original position account.
+    ///
+    /// # Accounts
+    /// *[See the documentation for the accounts.](AcceptPosition)*
+    ///
+    /// # Errors
+    /// - The [`next_owner`](AcceptPosition::next_owner) must sign the transaction and be the
+    ///   next owner of the `position`.
+    /// - The [`store`](AcceptPosition::store) must be initialized.
+    /// - The [`position`](AcceptPosition::position) must not be referenced by any order that
+    ///   has not been closed.
+    /// - The [`target`](AcceptPosition::target) must be either uninitialized, or an empty
+    ///   position of the next owner not referenced by any order, whose order count has been
+    ///   synced.
+    pub fn accept_position(ctx: Context<AcceptPosition>) -> Result<()> {
+        AcceptPosition::invoke(ctx)
+    }
+
+    /// Count the orders of a position that were created before the order count was introduced,
+    /// and mark the order count of the position as synced.
+    ///
+    /// Positions initialized before the order count was introduced cannot be transferred or
+    /// switched to the other collateral token until their order count is synced.
+    ///
+    /// # Accounts
+    /// *[See the documentation for the accounts.](SyncPositionOrderCount)*
+    ///
+    /// # Errors
+    /// - The [`authority`](SyncPositionOrderCount::authority) must be a signed ORDER_KEEPER in
+    ///   the store.
+    /// - The [`store`](SyncPositionOrderCount::store) must be initialized.
+    /// - The [`position`](SyncPositionOrderCount::position) must be initialized and owned by the
+    ///   `store`.
+    /// - The remaining accounts must be orders of the `store` referencing the `position`.
+    #[access_control(internal::Authenticate::only_order_keeper(&ctx))]
+    pub fn sync_position_order_count<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SyncPositionOrderCount<'info>>,
+    ) -> Result<()> {
+        instructions::unchecked_sync_position_order_count(ctx)
+    }
+
+    /// Request to switch the collateral token of a position to the opposite token of the market.
+    ///
+    /// The switch is executed by keepers with [`switch_position_collateral`]. The position with
+    /// the opposite collateral token is initialized if needed, and must be empty.
+    ///
+    /// # Accounts
+    /// *[See the documentation for the accounts.](RequestPositionCollateralSwitch)*
+    ///
+    /// # Arguments
+    /// - `min_collateral_amount`: The min collateral amount after the switch.
+    ///
+    /// # Errors
+    /// - The [`owner`](RequestPositionCollateralSwitch::owner) must sign the transaction and own
+    ///   the `position`.
+    /// - The [`store`](RequestPositionCollateralSwitch::store) must be initialized.
+    /// - The [`market`](RequestPositionCollateralSwitch::market) must be the market of the
+    ///   `position`, and must not be a pure market.
+    /// - The [`position`](RequestPositionCollateralSwitch::position) must not be empty, must not
+    ///   have a pending collateral switch or transfer, and must not be referenced by any order
+    ///   that has not been closed.
+    /// - The [`collateral_token`](RequestPositionCollateralSwitch::collateral_token) must be the
+    ///   opposite collateral token of the `position`.
+    /// - The [`target`](RequestPositionCollateralSwitch::target) must be either uninitialized, or
+    ///   an empty position of the owner not referenced by any order.
+    pub fn request_position_collateral_switch(
+        ctx: Context<RequestPositionCollateralSwitch>,
+        min_collateral_amount: u64,
+    ) -> Result<()> {
+        RequestPositionCollateralSwitch::invoke(ctx, min_collateral_amount)
+    }
+
+    /// Cancel a collateral switch request.
+    ///
+    /// # Accounts
+    /// *[See the documentation for the accounts.](CancelPositionCollateralSwitch)*
+    ///
+    /// # Errors
+    /// - The [`owner`](CancelPositionCollateralSwitch::owner) must sign the transaction and own
+    ///   the `position`.
+    /// - The [`position`](CancelPositionCollateralSwitch::position) must have a pending
+    ///   collateral switch.
+    pub fn cancel_position_collateral_switch(
+        ctx: Context<CancelPositionCollateralSwitch>,
+    ) -> Result<()> {
+        CancelPositionCollateralSwitch::invoke(ctx)
+    }
+
+    /// Switch the collateral token of a position to the opposite token of the market.
+    ///
+    /// The pending funding fee is paid with the current collateral, and the rest is swapped
+    /// through the market's own swap pool without realizing the PnL or paying position fees.
+    /// The state of the position is then moved to the `target` position, and the `position`
+    /// account is closed.
+    ///
+    /// # Accounts
+    /// *[See the documentation for the accounts.](SwitchPositionCollateral)*
+    ///
+    /// # Errors
+    /// - The [`authority`](SwitchPositionCollateral::authority) must be a signer with the
+    ///   ORDER_KEEPER role in the `store`.
+    /// - The [`store`](SwitchPositionCollateral::store) must be initialized.
+    /// - The [`token_map`](SwitchPositionCollateral::token_map) must be initialized and
+    ///   authorized by the `store`.
+    /// - The [`oracle`](SwitchPositionCollateral::oracle) must be initialized, cleared and owned
+    ///   by the `store`.
+    /// - The [`market`](SwitchPositionCollateral::market) must be the enabled market of the
+    ///   `position`.
+    /// - The [`position`](SwitchPositionCollateral::position) must have a pending collateral
+    ///   switch, must not have a pending transfer, and must not be referenced by any order that
+    ///   has not been closed.
+    /// - The [`target`](SwitchPositionCollateral::target) must be an empty position of the
+    ///   owner with the opposite collateral token, not referenced by any order.
+    /// - Price feed accounts must be valid and provided in the market's sorted token list order.
+    /// - Oracle prices must be updated after the collateral switch was requested.
+    /// - The collateral amount after the switch must not be less than the requested min
+    ///   collateral amount, and the position must remain valid.
+    #[access_control(internal::Authenticate::only_order_keeper(&ctx))]
+    pub fn switch_position_collateral<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SwitchPositionCollateral<'info>>,
+    ) -> Result<()> {
+        instructions::unchecked_switch_position_collateral(ctx)
+    }
+
     /// Prepare a trade event buffer.
     ///
     /// # Accounts
@@ -2376,6 +3367,9 @@ pub mod gmsol_store {
 
     /// Execute an increase/swap order by keepers.
     ///
+    /// A market order can also be executed by anyone once it has been pending for longer than
+    /// the self-execution grace period, in which case the execution fee claimed is capped.
+    ///
     /// # Accounts
     /// *[See the documentation for the accounts.](ExecuteIncreaseOrSwapOrderV2)*
     ///
@@ -2387,7 +3381,7 @@ pub mod gmsol_store {
     ///
     /// # Errors
     /// - The [`authority`](ExecuteIncreaseOrSwapOrderV2::authority) must be a signer and have the
-    ///   ORDER_KEEPER role in the `store`.
+    ///   ORDER_KEEPER role in the `store`, unless the self-execution of the order is allowed.
     /// - The [`store`](ExecuteIncreaseOrSwapOrderV2::store) must be initialized.
     /// - The [`token_map`](ExecuteIncreaseOrSwapOrderV2::token_map) must be initialized and authorized
     ///   by the `store`.
@@ -2425,7 +3419,7 @@ pub mod gmsol_store {
     /// - If `throw_on_execution_error` is true, any execution failure will throw an error
     // Note: There is a false positive lint for the doc link of `event`.
     #[allow(rustdoc::broken_intra_doc_links)]
-    #[access_control(internal::Authenticate::only_order_keeper(&ctx))]
+    #[access_control(internal::Authenticate::only_order_keeper_or_self_executor(&ctx, execution_fee))]
     pub fn execute_increase_or_swap_order_v2<'info>(
         ctx: Context<'_, '_, 'info, 'info, ExecuteIncreaseOrSwapOrderV2<'info>>,
         recent_timestamp: i64,
@@ -2497,6 +3491,79 @@ pub mod gmsol_store {
         )
     }
 
+    /// Execute market increase orders of the same market in batch by keepers, sharing one set of
+    /// oracle prices.
+    ///
+    /// Each order is processed independently: an order that fails to execute is cancelled and its
+    /// initial collateral is returned to the escrow, without affecting the other orders. Orders
+    /// that are not pending, are not market increase orders, have a swap path, callbacks or linked
+    /// orders, were updated after the oracle prices, or are provided with invalid accounts are
+    /// skipped and left untouched.
+    ///
+    /// # Accounts
+    /// *[See the documentation for the accounts.](ExecuteOrders)*
+    ///
+    /// # Arguments
+    /// - `execution_fee`: The execution fee claimed to be used by the keeper for each order.
+    /// - `count`: The number of orders to execute.
+    ///
+    /// # Errors
+    /// This instruction will fail if:
+    /// - The [`authority`](ExecuteOrders::authority) is not a signer or is not an ORDER_KEEPER
+    ///   in the store.
+    /// - The [`store`](ExecuteOrders::store) is not properly initialized.
+    /// - The [`token_map`](ExecuteOrders::token_map) is not initialized or not authorized by
+    ///   the store.
+    /// - The [`oracle`](ExecuteOrders::oracle) is not initialized, cleared and owned by the
+    ///   store.
+    /// - The [`market`](ExecuteOrders::market) is not initialized, is disabled or not owned by
+    ///   the store.
+    /// - The [`event`](ExecuteOrders::event) is not a valid trade event buffer owned by the
+    ///   `authority`.
+    /// - Any token mint or vault accounts do not match those of the market.
+    /// - `count` is zero or the remaining accounts are not enough for `count` orders.
+    /// - Any feed accounts in the remaining accounts are invalid or do not match the market tokens.
+    /// - Any oracle prices from the feed accounts are incomplete or invalid.
+    /// - The feature for executing market increase orders is not enabled in the `store` or the
+    ///   `market`.
+    #[access_control(internal::Authenticate::only_order_keeper(&ctx))]
+    pub fn execute_orders<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteOrders<'info>>,
+        execution_fee: u64,
+        count: u8,
+    ) -> Result<()> {
+        instructions::unchecked_execute_orders(ctx, execution_fee, count)
+    }
+
+    /// Move the mark price of a trailing-stop order with validated oracle prices.
+    ///
+    /// The mark only ever moves in the favorable direction: up for long positions and down
+    /// for short positions. The trigger price is recomputed from the new mark and the trail
+    /// distance. This is a no-op if the index price does not improve the mark.
+    ///
+    /// # Accounts
+    /// *[See the documentation for the accounts.](UpdateTrailingStop)*
+    ///
+    /// # Errors
+    /// - The [`authority`](UpdateTrailingStop::authority) must be a signer with the ORDER_KEEPER
+    ///   role in the `store`.
+    /// - The [`store`](UpdateTrailingStop::store) must be initialized.
+    /// - The [`token_map`](UpdateTrailingStop::token_map) must be initialized and authorized
+    ///   by the `store`.
+    /// - The [`oracle`](UpdateTrailingStop::oracle) must be initialized, cleared and owned
+    ///   by the `store`.
+    /// - The [`market`](UpdateTrailingStop::market) must be initialized and owned by the `store`.
+    /// - The [`order`](UpdateTrailingStop::order) must be a pending trailing-stop order of the
+    ///   `market` and owned by the `store`.
+    /// - Price feed accounts must be valid and provided in the market's sorted token list order.
+    /// - Oracle prices must be updated after the last update of the `order`.
+    #[access_control(internal::Authenticate::only_order_keeper(&ctx))]
+    pub fn update_trailing_stop<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UpdateTrailingStop<'info>>,
+    ) -> Result<()> {
+        instructions::unchecked_update_trailing_stop(ctx)
+    }
+
     /// Perform a liquidation by keepers.
     ///
     /// # Accounts
@@ -2656,6 +3723,68 @@ pub mod gmsol_store {
         )
     }
 
+    /// Force-close a position of a settled market at the settlement price by keepers.
+    ///
+    /// # Accounts
+    /// *[See the documentation for the accounts.](PositionCut)*
+    ///
+    /// # Arguments
+    /// - `nonce`: The nonce used to derive the `order` PDA address.
+    /// - `recent_timestamp`: A recent blockchain timestamp for validation.
+    /// - `execution_fee`: The execution fee claimed to be used by the keeper.
+    ///
+    /// # Errors
+    /// - The [`authority`](PositionCut::authority) must be a signer with the ORDER_KEEPER role.
+    /// - The [`owner`](PositionCut::owner) must be the position owner.
+    /// - The [`user`](PositionCut::user) must be initialized and corresponding to the `owner`.
+    /// - The [`store`](PositionCut::store) must be initialized.
+    /// - The [`token_map`](PositionCut::token_map) must be initialized and authorized by the store.
+    /// - The [`oracle`](PositionCut::oracle) must be initialized, cleared and store-owned.
+    /// - The [`market`](PositionCut::market) must be initialized, enabled, store-owned and match
+    ///   the position's market. The market must have been settled.
+    /// - The [`order`](PositionCut::order) must be uninitialized with address matching PDA from
+    ///   the `store`, `owner`, `nonce` and other expected seeds.
+    /// - The [`position`](PositionCut::position) must be initialized and owned by the `owner`
+    ///   and `store`.
+    /// - The [`event`](PositionCut::event) must be a valid trade event buffer owned by the `store`
+    ///   and `authority`.
+    /// - The [`long_token`](PositionCut::long_token) and [`short_token`](PositionCut::short_token)
+    ///   must match those defined in the `market`.
+    /// - The [`long_token_escrow`](PositionCut::long_token_escrow) and
+    ///   [`short_token_escrow`](PositionCut::short_token_escrow) must be valid order-owned escrow
+    ///   accounts for their respective tokens.
+    /// - The [`long_token_vault`](PositionCut::long_token_vault) and
+    ///   [`short_token_vault`](PositionCut::short_token_vault) must be valid store-owned market
+    ///   vault accounts for their tokens.
+    /// - The [`claimable_long_token_account_for_user`](PositionCut::claimable_long_token_account_for_user)
+    ///   must be a store-owned, owner-delegated claimable account for long token.
+    /// - The [`claimable_short_token_account_for_user`](PositionCut::claimable_short_token_account_for_user)
+    ///   must be a store-owned, owner-delegated claimable account for short token.
+    /// - The [`claimable_pnl_token_account_for_holding`](PositionCut::claimable_pnl_token_account_for_holding)
+    ///   must be a store-owned, holding-delegated claimable account for PnL token.
+    /// - Price feed accounts must be valid and provided in the market's sorted token list order.
+    /// - The settlement feature must be enabled in the `store` and the `market`.
+    /// - Oracle prices must be valid and complete.
+    /// - Execution must complete successfully.
+    // Note: There is a false positive lint for the doc link of `event`.
+    #[allow(rustdoc::broken_intra_doc_links)]
+    #[access_control(internal::Authenticate::only_order_keeper(&ctx))]
+    pub fn settle_position<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PositionCut<'info>>,
+        nonce: [u8; 32],
+        recent_timestamp: i64,
+        execution_fee: u64,
+    ) -> Result<()> {
+        instructions::unchecked_process_position_cut(
+            ctx,
+            &nonce,
+            recent_timestamp,
+            PositionCutKind::Settle,
+            execution_fee,
+            true,
+        )
+    }
+
     /// Update the closed state for the market.
     ///
     /// # Accounts
@@ -3075,6 +4204,26 @@ pub mod gmsol_store {
         instructions::prepare_user(ctx)
     }
 
+    /// Initialize or validate a User Stats Account.
+    ///
+    /// Once initialized, the User Account of the owner is marked as having a user stats account,
+    /// which must then be provided to every execution of the owner's trades, and is updated with
+    /// each of them.
+    ///
+    /// # Accounts
+    /// *[See the documentation for the accounts.](PrepareUserStats)*
+    ///
+    /// # Errors
+    /// - The [`owner`](PrepareUserStats::owner) must be a signer.
+    /// - The [`store`](PrepareUserStats::store) must be properly initialized.
+    /// - The [`user`](PrepareUserStats::user) must be initialized and correspond to the `owner`.
+    /// - The [`user_stats`](PrepareUserStats::user_stats) must be either:
+    ///   - Uninitialized (for new account creation)
+    ///   - Or validly initialized and correspond to the `owner`
+    pub fn prepare_user_stats(ctx: Context<PrepareUserStats>) -> Result<()> {
+        instructions::prepare_user_stats(ctx)
+    }
+
     /// Initialize referral code.
     ///
     /// # Accounts
@@ -4452,6 +5601,93 @@ pub enum CoreError {
     /// builder fee trivially and reliably bypassable.
     #[msg("this decrease position swap type is not allowed together with a builder fee")]
     BuilderFeeSwapTypeNotAllowed,
+    // ===========================================
+    //              Order Errors (2)
+    // ===========================================
+    /// Invalid trail distance.
+    #[msg("invalid trail distance")]
+    InvalidTrailDistance,
+    /// Invalid linked order.
+    #[msg("invalid linked order")]
+    InvalidLinkedOrder,
+    /// Linked order is not activated.
+    #[msg("linked order is not activated")]
+    LinkedOrderNotActivated,
+    /// Invalid TWAP params.
+    #[msg("invalid TWAP params")]
+    InvalidTwapParams,
+    /// Invalid valid-until timestamp.
+    #[msg("invalid valid-until timestamp")]
+    InvalidValidUntilTs,
+    /// Order expired.
+    #[msg("order expired")]
+    OrderExpired,
+    /// Delegation expired.
+    #[msg("delegation expired")]
+    DelegationExpired,
+    /// Delegation limit exceeded.
+    #[msg("delegation limit exceeded")]
+    DelegationLimitExceeded,
+    /// Invalid signature.
+    #[msg("invalid signature")]
+    InvalidSignature,
+    /// Invalid order intent.
+    #[msg("invalid order intent")]
+    InvalidOrderIntent,
+    /// Order intent expired.
+    #[msg("order intent expired")]
+    OrderIntentExpired,
+    /// Position has pending orders.
+    #[msg("position has pending orders")]
+    PositionHasPendingOrders,
+    /// Position transfer in progress.
+    #[msg("position transfer in progress")]
+    PositionTransferInProgress,
+    /// Claimable account is locked.
+    #[msg("claimable account is locked")]
+    ClaimableAccountLocked,
+    /// Market circuit breaker tripped.
+    #[msg("market circuit breaker tripped")]
+    MarketCircuitBreakerTripped,
+    /// Max position size exceeded.
+    #[msg("max position size exceeded")]
+    MaxPositionSizeExceeded,
+    /// Self-execution not allowed.
+    #[msg("self-execution is not allowed")]
+    SelfExecutionNotAllowed,
+    /// Self-execution fee cap exceeded.
+    #[msg("execution fee exceeds the cap for self-execution")]
+    SelfExecutionFeeCapExceeded,
+    /// Market is not active.
+    #[msg("market is not active")]
+    MarketNotActive,
+    /// Market is not settled.
+    #[msg("market is not settled")]
+    MarketNotSettled,
+    /// Price spread between providers exceeded.
+    #[msg("price spread between providers exceeded")]
+    PriceProviderSpreadExceeded,
+    /// Invalid publisher price.
+    #[msg("invalid publisher price")]
+    InvalidPublisherPrice,
+    /// Price publisher quorum not reached.
+    #[msg("price publisher quorum not reached")]
+    PricePublisherQuorumNotReached,
+    /// Market is closed by schedule.
+    #[msg("market is closed by schedule")]
+    MarketClosedBySchedule,
+    /// Market is in the pre-close window.
+    #[msg("market is in the pre-close window")]
+    MarketInPreCloseWindow,
+    /// Market gap tolerance exceeded.
+    #[msg("market gap tolerance exceeded")]
+    MarketGapToleranceExceeded,
+    /// Linked orders are not resolved.
+    #[msg("linked orders are not resolved")]
+    LinkedOrdersNotResolved,
+    /// Position order count is not synced.
+    #[msg("position order count is not synced")]
+    PositionOrderCountNotSynced,
     // NOTE: New variants must be appended here to keep existing error codes stable.
 }
 
@@ -16,7 +16,7 @@ use crate::{
     CoreError, CoreResult,
 };
 
-use super::market::RemainingAccountsForMarket;
+use super::market::{RemainingAccountsForMarket, UpdateCircuitBreakerOperation};
 
 /// Create Deposit Params.
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
@@ -65,7 +65,12 @@ pub(crate) struct CreateDepositOperation<'a, 'info> {
 impl CreateDepositOperation<'_, '_> {
     /// Execute.
     pub(crate) fn execute(self) -> Result<()> {
-        self.market.load()?.validate(&self.store.key())?;
+        {
+            let market = self.market.load()?;
+            market.validate(&self.store.key())?;
+            market.validate_circuit_breaker_not_tripped()?;
+            market.validate_active()?;
+        }
         self.validate_params_excluding_swap()?;
 
         let Self {
@@ -239,11 +244,22 @@ impl ExecuteDepositOperation<'_, '_> {
     fn validate_before_execution(&self) -> Result<()> {
         let market = self.market.load()?;
         market.validate(&self.store.key())?;
+        market.validate_circuit_breaker_not_tripped()?;
+        market.validate_active()?;
         Ok(())
     }
 
     #[inline(never)]
     fn perform_deposit(self) -> Result<()> {
+        {
+            let prices = self.oracle.market_prices(&*self.market.load()?)?;
+            UpdateCircuitBreakerOperation::builder()
+                .market(self.market)
+                .prices(&prices)
+                .event_emitter(self.event_emitter)
+                .build()
+                .execute()?;
+        }
         self.validate_before_execution()?;
         {
             let deposit = self.deposit.load()?;
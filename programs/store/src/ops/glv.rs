@@ -20,7 +20,7 @@ use crate::{
         glv::{GlvShift, GlvWithdrawal},
         market::revertible::Revertible,
         withdrawal::WithdrawalActionParams,
-        Glv, GlvDeposit, HasMarketMeta, Market, NonceBytes, Oracle, Shift, Store,
+        Glv, GlvDeposit, HasMarketMeta, HasSettlement, Market, NonceBytes, Oracle, Shift, Store,
         ValidateOracleTime,
     },
     utils::internal::TransferUtils,
@@ -171,6 +171,7 @@ impl CreateGlvDepositOperation<'_, '_> {
     fn validate_market_and_get_tokens(&self) -> Result<(Pubkey, Pubkey)> {
         let market = self.market.load()?;
         let meta = market.validated_meta(&self.store.key())?;
+        market.validate_active()?;
         Ok((meta.long_token_mint, meta.short_token_mint))
     }
 
@@ -302,6 +303,7 @@ impl ExecuteGlvDepositOperation<'_, '_> {
     fn validate_before_execution(&self) -> Result<()> {
         let market = self.market.load()?;
         market.validate(&self.store.key())?;
+        market.validate_active()?;
 
         let glv = self.glv.load()?;
         let glv_deposit = self.glv_deposit.load()?;
@@ -1116,10 +1118,13 @@ pub(crate) fn get_glv_value_for_market_with_new_index_price<M>(
 ) -> Result<GlvValueForMarket<u128>>
 where
     M: gmsol_model::LiquidityMarket<{ constants::MARKET_DECIMALS }, Num = u128, Signed = i128>,
-    M: HasMarketMeta,
+    M: HasMarketMeta + HasSettlement,
 {
     let index_token_mint = market.market_meta().index_token_mint;
-    prices.index_token_price = oracle.get_primary_price(&index_token_mint, true)?;
+    prices.index_token_price = match market.settlement().price() {
+        Some(price) => price,
+        None => oracle.get_primary_price(&index_token_mint, true)?,
+    };
 
     get_glv_value_for_market(prices, market, balance, maximize)
 }
@@ -1159,7 +1164,7 @@ fn get_market_token_amount_for_glv_value<M>(
 ) -> Result<u128>
 where
     M: gmsol_model::LiquidityMarket<{ constants::MARKET_DECIMALS }, Num = u128, Signed = i128>,
-    M: HasMarketMeta,
+    M: HasMarketMeta + HasSettlement,
 {
     let prices = oracle.market_prices(market).expect("must exist");
 
@@ -1265,6 +1270,7 @@ impl ExecuteGlvShiftOperation<'_, '_> {
 
         from_market.validate(&self.store.key())?;
         to_market.validate(&self.store.key())?;
+        to_market.validate_active()?;
 
         from_market.validate_shiftable(&to_market)?;
 
@@ -6,7 +6,7 @@ use gmsol_model::{price::Prices, Bank, LiquidityMarketMutExt, MarketAction};
 use typed_builder::TypedBuilder;
 
 use crate::{
-    events::{DepositExecuted, EventEmitter, WithdrawalExecuted},
+    events::{DepositExecuted, EventEmitter, MarketCircuitBreakerTripped, WithdrawalExecuted},
     states::{
         common::swap::{SwapActionParams, SwapActionParamsExt},
         deposit::DepositActionParams,
@@ -122,6 +122,51 @@ impl MarketTransferInOperation<'_, '_> {
     }
 }
 
+/// Operation for feeding the validated prices to the circuit breaker of the market.
+///
+/// Increase orders and deposits are disabled once the circuit breaker is tripped,
+/// until it is reset by a MARKET_KEEPER.
+#[derive(TypedBuilder)]
+pub(crate) struct UpdateCircuitBreakerOperation<'a, 'info> {
+    market: &'a AccountLoader<'info, Market>,
+    prices: &'a Prices<u128>,
+    #[builder(setter(into))]
+    event_emitter: EventEmitter<'a, 'info>,
+}
+
+impl UpdateCircuitBreakerOperation<'_, '_> {
+    /// Execute.
+    ///
+    /// Returns whether the circuit breaker has just been tripped.
+    pub(crate) fn execute(self) -> Result<bool> {
+        let ts = Clock::get()?.unix_timestamp;
+        let price = self
+            .prices
+            .index_token_price
+            .checked_mid()
+            .ok_or_else(|| error!(CoreError::ValueOverflow))?;
+        let mut market = self.market.load_mut()?;
+        let Some(ref_price) = market.update_circuit_breaker(price, ts)? else {
+            return Ok(false);
+        };
+        let event = MarketCircuitBreakerTripped {
+            market_token: market.meta().market_token_mint,
+            ref_price,
+            ref_ts: market.circuit_breaker().ref_ts(),
+            price,
+            ts,
+        };
+        drop(market);
+        msg!(
+            "[Market] circuit breaker tripped, price: {}, ref price: {}",
+            event.price,
+            event.ref_price
+        );
+        self.event_emitter.emit_cpi(&event)?;
+        Ok(true)
+    }
+}
+
 /// Operation for transferring funds out of market vault.
 #[derive(TypedBuilder)]
 pub(crate) struct MarketTransferOutOperation<'a, 'info> {
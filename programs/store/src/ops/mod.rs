@@ -13,6 +13,9 @@ pub mod withdrawal;
 /// Order operations.
 pub mod order;
 
+/// Position operations.
+pub mod position;
+
 /// Shift operations.
 pub mod shift;
 
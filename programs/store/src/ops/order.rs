@@ -32,18 +32,23 @@ use crate::{
             },
             utils::{Adl, ClosableMarket, ValidateMarketBalances},
         },
-        order::{Order, OrderActionParams, OrderKind, OrderTokenAccounts, TransferOut},
+        order::{
+            Order, OrderActionParams, OrderKind, OrderTokenAccounts, TrailDistance, TransferOut,
+            TwapParams,
+        },
         position::PositionKind,
-        user::UserHeader,
-        AmountKey, HasMarketMeta, Market, MarketPriceOptions, NonceBytes, Oracle, Position, Store,
-        ValidateOracleTime,
+        user::{UserHeader, UserStats},
+        AmountKey, HasMarketMeta, HasSettlement, Market, MarketPriceOptions, NonceBytes, Oracle,
+        Position, Store, ValidateOracleTime,
     },
     CoreError, ModelError,
 };
 
 use super::{
     execution_fee::TransferExecutionFeeOperation,
-    market::{MarketTransferOutOperation, RemainingAccountsForMarket},
+    market::{
+        MarketTransferOutOperation, RemainingAccountsForMarket, UpdateCircuitBreakerOperation,
+    },
 };
 
 pub use gmsol_utils::order::PositionCutKind;
@@ -109,6 +114,68 @@ impl CreateOrderParams {
     }
 }
 
+/// Extended Create Order Arguments.
+///
+/// Holds the order params not supported by `create_order_v2`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, InitSpace)]
+pub struct CreateOrderParamsExt {
+    /// Trail distance. Required for trailing-stop orders only.
+    pub trail_distance: Option<TrailDistance>,
+    /// Split the order into slices executed at a fixed interval.
+    /// Only market increase and market decrease orders can be TWAP orders.
+    pub twap: Option<TwapParams>,
+    /// Valid until timestamp. The order never expires if not provided.
+    pub valid_until_ts: Option<i64>,
+}
+
+/// Create Order Arguments with the extended arguments.
+#[derive(Clone, Debug)]
+pub(crate) struct CreateOrderArgs {
+    pub(crate) params: CreateOrderParams,
+    pub(crate) ext: CreateOrderParamsExt,
+}
+
+impl ActionParams for CreateOrderArgs {
+    fn execution_lamports(&self) -> u64 {
+        self.params.execution_lamports
+    }
+}
+
+/// Order intent signed off-chain by the owner, for creating an order by a relayer.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct OrderIntent {
+    /// Store.
+    pub store: Pubkey,
+    /// Market token.
+    pub market_token: Pubkey,
+    /// The intent nonce of the owner, which must match the one recorded in the
+    /// owner's User Account.
+    pub nonce: u64,
+    /// The intent cannot be used after this timestamp.
+    pub deadline: i64,
+    /// The relayer allowed to submit the intent.
+    pub relayer: Pubkey,
+    /// The amount of the initial collateral paid to the relayer, which is deducted from
+    /// the initial collateral delta amount of the order. Must be zero for decrease orders.
+    pub relayer_fee: u64,
+    /// Order params.
+    pub params: CreateOrderParams,
+    /// Extended order params.
+    pub ext: CreateOrderParamsExt,
+}
+
+impl OrderIntent {
+    /// The domain prefix of the signed message.
+    pub const DOMAIN: &'static [u8] = b"gmsol-store:order-intent:v1";
+
+    /// Get the message to sign.
+    pub fn to_message(&self) -> Result<Vec<u8>> {
+        let mut message = Self::DOMAIN.to_vec();
+        self.serialize(&mut message)?;
+        Ok(message)
+    }
+}
+
 /// Operations for creating a new order.
 #[derive(TypedBuilder)]
 pub(crate) struct CreateOrderOperation<'a, 'info> {
@@ -128,6 +195,8 @@ pub(crate) struct CreateOrderOperation<'a, 'info> {
     nonce: &'a NonceBytes,
     bump: u8,
     params: &'a CreateOrderParams,
+    #[builder(default, setter(strip_option))]
+    ext: Option<&'a CreateOrderParamsExt>,
     swap_path: &'info [AccountInfo<'info>],
     callback_version: Option<u8>,
     callback_authority: Option<&'a Account<'info, CallbackAuthority>>,
@@ -173,6 +242,14 @@ impl<'a, 'info> CreateOrderOperation<'a, 'info> {
             .load()?
             .validate_with_options(&self.store.key(), self.allow_closed)?;
         ActionExt::validate_balance(&self.order, self.params.execution_lamports)?;
+        if let Some(twap) = self.ext.and_then(|ext| ext.twap.as_ref()) {
+            // Each slice must be able to pay for its own execution.
+            require_gte!(
+                self.params.execution_lamports,
+                Order::MIN_EXECUTION_LAMPORTS.saturating_mul(twap.slices.into()),
+                CoreError::NotEnoughExecutionFee
+            );
+        }
         Ok(())
     }
 
@@ -218,6 +295,14 @@ impl<'a, 'info> CreateOrderOperation<'a, 'info> {
 
             let (from, to) = (f)(self.params, tokens, params)?;
 
+            if let Some(twap) = self.ext.and_then(|ext| ext.twap.as_ref()) {
+                params.init_twap(twap)?;
+            }
+
+            if let Some(ts) = self.ext.and_then(|ext| ext.valid_until_ts) {
+                params.init_valid_until_ts(ts, Clock::get()?.unix_timestamp)?;
+            }
+
             let market = self.market.load()?;
             let meta = market.meta();
             let swap_path = self.swap_path;
@@ -373,6 +458,11 @@ pub(crate) struct CreateIncreaseOrderOperation<'a, 'info> {
 impl CreateIncreaseOrderOperation<'_, '_> {
     pub(crate) fn execute(self) -> Result<()> {
         self.common.validate()?;
+        {
+            let market = self.common.market.load()?;
+            market.validate_circuit_breaker_not_tripped()?;
+            market.validate_active()?;
+        }
         self.validate_params_excluding_swap()?;
 
         let collateral_token = if self.common.params.is_collateral_long {
@@ -417,6 +507,14 @@ impl CreateIncreaseOrderOperation<'_, '_> {
             },
             Some(self.position.as_ref()),
         )?;
+        {
+            let mut position = self.position.load_mut()?;
+            position.on_order_created()?;
+            self.common
+                .order
+                .load_mut()?
+                .set_counted_by_position(&position);
+        }
 
         Ok(())
     }
@@ -449,10 +547,11 @@ impl CreateIncreaseOrderOperation<'_, '_> {
                 self.short_token.mint,
                 CoreError::TokenMintMismatched
             );
-            self.position
-                .load()?
+            let position = self.position.load()?;
+            position
                 .validate_for_market(&market, self.common.allow_closed)
                 .map_err(ModelError::from)?;
+            position.validate_not_transferring()?;
         }
 
         Ok(())
@@ -497,11 +596,20 @@ impl CreateDecreaseOrderOperation<'_, '_> {
                     create.min_output,
                     create.decrease_position_swap_type.unwrap_or_default(),
                     create.valid_from_ts,
+                    self.common.ext.and_then(|ext| ext.trail_distance),
                 )?;
                 Ok((collateral_token, self.final_output_token.mint))
             },
             Some(self.position.as_ref()),
         )?;
+        {
+            let mut position = self.position.load_mut()?;
+            position.on_order_created()?;
+            self.common
+                .order
+                .load_mut()?
+                .set_counted_by_position(&position);
+        }
         Ok(())
     }
 
@@ -532,10 +640,17 @@ impl CreateDecreaseOrderOperation<'_, '_> {
                 self.short_token.mint,
                 CoreError::TokenMintMismatched
             );
-            self.position
-                .load()?
+            let position = self.position.load()?;
+            position
                 .validate_for_market(&market, self.common.allow_closed)
                 .map_err(ModelError::from)?;
+            // Position cuts are always allowed, even while a transfer is in progress.
+            if !matches!(
+                self.common.params.kind,
+                OrderKind::Liquidation | OrderKind::AutoDeleveraging
+            ) {
+                position.validate_not_transferring()?;
+            }
         }
         Ok(())
     }
@@ -896,6 +1011,12 @@ pub(crate) struct ExecuteOrderOperation<'a, 'info> {
     callback_partitioned_data_account: Option<&'a AccountInfo<'info>>,
     #[builder(default)]
     allow_closed: bool,
+    #[builder(default)]
+    user_stats: Option<&'a AccountLoader<'info, UserStats>>,
+    /// The size in USD of the position of the same owner and side with the
+    /// other collateral token, which counts towards the max position size cap.
+    #[builder(default)]
+    sibling_position_size_in_usd: u128,
 }
 
 pub(crate) type RemovePosition = bool;
@@ -927,6 +1048,8 @@ impl ExecuteOrderOperation<'_, '_> {
         let mut remove_position = false;
 
         self.order.load()?.validate_valid_from_ts()?;
+        self.order.load()?.validate_valid_until_ts()?;
+        self.order.load()?.validate_activated()?;
 
         match self.validate_oracle() {
             Ok(()) => {}
@@ -950,8 +1073,19 @@ impl ExecuteOrderOperation<'_, '_> {
 
         let mut should_throw_error = false;
         let prices = self.prices()?;
+        let is_circuit_breaker_just_tripped = UpdateCircuitBreakerOperation::builder()
+            .market(self.market)
+            .prices(&prices)
+            .event_emitter(self.event_emitter)
+            .build()
+            .execute()?;
         let discount = self.validate_and_get_order_fee_discount()?;
-        let res = match self.perform_execution(&mut should_throw_error, prices, discount) {
+        let res = match self.perform_execution(
+            &mut should_throw_error,
+            prices,
+            discount,
+            is_circuit_breaker_just_tripped,
+        ) {
             Ok((should_remove_position, mut transfer_out, should_send_trade_event)) => {
                 transfer_out.set_executed(true);
                 remove_position = should_remove_position;
@@ -992,16 +1126,21 @@ impl ExecuteOrderOperation<'_, '_> {
             self.user.load()?.is_initialized(),
             CoreError::InvalidUserAccount
         );
-        let (rank, is_referred) = {
+        let now = Clock::get()?.unix_timestamp;
+        let (rank, is_referred, volume) = {
             let user = self.user.load()?;
-            (user.gt.rank(), user.referral.referrer().is_some())
+            (
+                user.gt.rank(),
+                user.referral.referrer().is_some(),
+                user.volume().rolling_volume(now),
+            )
         };
-        let discount_factor = self
-            .store
-            .load()?
-            .order_fee_discount_factor(rank, is_referred)?;
+        let discount_factor =
+            self.store
+                .load()?
+                .order_fee_discount_factor(rank, is_referred, volume)?;
         msg!(
-            "[Order] apply a {} order fee discount (factor) for this {} rank {} user",
+            "[Order] apply a {} order fee discount (factor) for this {} rank {} user with 30-day volume {}",
             discount_factor,
             if is_referred {
                 "referred"
@@ -1009,19 +1148,46 @@ impl ExecuteOrderOperation<'_, '_> {
                 "non-referred"
             },
             rank,
+            volume,
         );
         Ok(discount_factor)
     }
 
+    /// Validate that the total size of the positions of the owner on the side of the
+    /// given position (with either collateral token) does not exceed the per-account
+    /// cap of the side, unless the owner is exempt from it.
+    fn validate_position_size_cap(&self, position: &RevertiblePosition<'_, '_>) -> Result<()> {
+        let max_size_in_usd = position
+            .market()
+            .as_ref()
+            .max_position_size_usd(position.is_long());
+        if !exceeds_position_size_cap(
+            *position.size_in_usd(),
+            self.sibling_position_size_in_usd,
+            max_size_in_usd,
+        ) {
+            return Ok(());
+        }
+        let owner = *self.order.load()?.header().owner();
+        require!(
+            self.store
+                .load()?
+                .is_exempt_from_position_size_cap(&owner)?,
+            CoreError::MaxPositionSizeExceeded
+        );
+        Ok(())
+    }
+
     #[inline(never)]
     fn perform_execution(
         &self,
         should_throw_error: &mut bool,
         prices: Prices<u128>,
         order_fee_discount_factor: u128,
+        is_circuit_breaker_just_tripped: bool,
     ) -> Result<(RemovePosition, Box<TransferOut>, ShouldSendTradeEvent)> {
         self.validate_market()?;
-        self.validate_order(should_throw_error, &prices)?;
+        self.validate_order(should_throw_error, &prices, is_circuit_breaker_just_tripped)?;
 
         // Prepare execution context.
         let gt_minting_enabled = self.market.load()?.is_gt_minting_enabled();
@@ -1068,8 +1234,10 @@ impl ExecuteOrderOperation<'_, '_> {
             | OrderKind::Liquidation
             | OrderKind::AutoDeleveraging
             | OrderKind::LimitIncrease
+            | OrderKind::StopIncrease
             | OrderKind::LimitDecrease
-            | OrderKind::StopLossDecrease => {
+            | OrderKind::StopLossDecrease
+            | OrderKind::TrailingStopDecrease => {
                 let position_loader = self.position.as_ref().ok_or_else(|| {
                     *should_throw_error = true;
                     error!(CoreError::PositionIsRequired)
@@ -1099,8 +1267,11 @@ impl ExecuteOrderOperation<'_, '_> {
 
                 position.on_validate().map_err(ModelError::from)?;
 
+                let size_in_usd_before = *position.size_in_usd();
                 let (should_remove_position, paid_fee_value) = match kind {
-                    OrderKind::MarketIncrease | OrderKind::LimitIncrease => {
+                    OrderKind::MarketIncrease
+                    | OrderKind::LimitIncrease
+                    | OrderKind::StopIncrease => {
                         let paid_fee_value = execute_increase_position(
                             self.oracle,
                             prices,
@@ -1113,6 +1284,7 @@ impl ExecuteOrderOperation<'_, '_> {
                             // the order once it is captured at order creation.
                             0,
                         )?;
+                        self.validate_position_size_cap(&position)?;
                         (false, paid_fee_value)
                     }
                     OrderKind::Liquidation => execute_decrease_position(
@@ -1145,7 +1317,8 @@ impl ExecuteOrderOperation<'_, '_> {
                     )?,
                     OrderKind::MarketDecrease
                     | OrderKind::LimitDecrease
-                    | OrderKind::StopLossDecrease => execute_decrease_position(
+                    | OrderKind::StopLossDecrease
+                    | OrderKind::TrailingStopDecrease => execute_decrease_position(
                         self.oracle,
                         prices,
                         &mut position,
@@ -1178,6 +1351,19 @@ impl ExecuteOrderOperation<'_, '_> {
                     msg!("[GT] GT minting is disabled for this market");
                 }
 
+                if let Some(user_stats) = self.user_stats {
+                    user_stats.load_mut()?.update_with_trade(
+                        &*event_loader.load()?,
+                        matches!(kind, OrderKind::Liquidation),
+                    );
+                }
+
+                let size_delta_usd = position.size_in_usd().abs_diff(size_in_usd_before);
+                self.user
+                    .load_mut()?
+                    .volume
+                    .record(Clock::get()?.unix_timestamp, size_delta_usd);
+
                 position.commit();
                 msg!(
                     "[Position] executed with trade_id={}",
@@ -1254,7 +1440,15 @@ impl ExecuteOrderOperation<'_, '_> {
             )
         };
         // Validate ADL state.
-        if let OrderKind::AutoDeleveraging = kind {
+        // Positions of a settled market are force-closed with ADL orders, which are not
+        // subject to the ADL state.
+        let is_settled = self
+            .market
+            .load()
+            .map_err(|_| CoreError::LoadAccountError)?
+            .settlement()
+            .is_settled();
+        if matches!(kind, OrderKind::AutoDeleveraging) && !is_settled {
             let max_staleness = *self
                 .store
                 .load()
@@ -1284,17 +1478,40 @@ impl ExecuteOrderOperation<'_, '_> {
         Ok(())
     }
 
-    fn validate_order(&self, should_throw_error: &mut bool, prices: &Prices<u128>) -> Result<()> {
+    fn validate_order(
+        &self,
+        should_throw_error: &mut bool,
+        prices: &Prices<u128>,
+        is_circuit_breaker_just_tripped: bool,
+    ) -> Result<()> {
         self.validate_non_empty_order()?;
+        // Increase orders are cancelled once the market is no longer active.
+        self.validate_market_active()?;
+        if let Err(err) = self.validate_circuit_breaker() {
+            let kind = self.order.load()?.params.kind()?;
+            if should_throw_on_tripped_circuit_breaker(&kind, is_circuit_breaker_just_tripped) {
+                *should_throw_error = true;
+            }
+            return Err(err);
+        }
+        // Pending limit and stop orders wait for the next trading session.
+        if let Err(err) = self.validate_market_schedule() {
+            if !self.order.load()?.params.kind()?.is_market() {
+                *should_throw_error = true;
+            }
+            return Err(err);
+        }
         match self.validate_trigger_price(prices) {
-            Ok(()) => Ok(()),
+            Ok(()) => {}
             Err(err) => {
                 if !self.order.load()?.params.kind()?.is_market() {
                     *should_throw_error = true;
                 }
-                Err(err)
+                return Err(err);
             }
         }
+        // Increase orders whose trigger price has been gapped through are cancelled.
+        self.validate_gap_tolerance(prices)
     }
 
     fn validate_non_empty_order(&self) -> Result<()> {
@@ -1326,6 +1543,83 @@ impl ExecuteOrderOperation<'_, '_> {
         Ok(())
     }
 
+    fn validate_circuit_breaker(&self) -> Result<()> {
+        if self.order.load()?.params.kind()?.is_increase_position() {
+            self.market.load()?.validate_circuit_breaker_not_tripped()?;
+        }
+        Ok(())
+    }
+
+    fn validate_market_schedule(&self) -> Result<()> {
+        let kind = self.order.load()?.params.kind()?;
+        let market = self.market.load()?;
+        if !market.schedule().is_enabled() {
+            return Ok(());
+        }
+        let now = Clock::get()?.unix_timestamp;
+        if kind.is_increase_position() {
+            // Increasing collateral is allowed while the market is closed.
+            if !self.allow_closed {
+                market.validate_schedule_for_increase(now)?;
+            }
+        } else if kind.is_trigger() {
+            market.validate_in_session(now)?;
+        }
+        Ok(())
+    }
+
+    fn validate_gap_tolerance(&self, prices: &Prices<u128>) -> Result<()> {
+        let Some(session_start) = self.session_start_for_trigger_order()? else {
+            return Ok(());
+        };
+        let order = self.order.load()?;
+        // Only increase orders that have been pending across the close are checked.
+        if !is_subject_to_gap_tolerance(&order.params.kind()?)
+            || order.header.updated_at >= session_start
+        {
+            return Ok(());
+        }
+        self.market
+            .load()?
+            .validate_gap_tolerance(order.params.trigger_price, &prices.index_token_price)
+    }
+
+    /// Returns the start of the current trading session if the order is a limit
+    /// or stop order of a market with a schedule.
+    fn session_start_for_trigger_order(&self) -> crate::CoreResult<Option<i64>> {
+        let kind = self
+            .order
+            .load()
+            .map_err(|_| CoreError::LoadAccountError)?
+            .params
+            .kind()
+            .map_err(|_| CoreError::InvalidArgument)?;
+        if !kind.is_trigger() {
+            return Ok(None);
+        }
+        let market = self
+            .market
+            .load()
+            .map_err(|_| CoreError::LoadAccountError)?;
+        if !market.schedule().is_enabled() {
+            return Ok(None);
+        }
+        let now = Clock::get()
+            .map_err(|err| {
+                msg!("Failed to get `Clock`. Error Message: {}", err);
+                CoreError::Internal
+            })?
+            .unix_timestamp;
+        Ok(market.schedule().session_at(now).map(|(start, _)| start))
+    }
+
+    fn validate_market_active(&self) -> Result<()> {
+        if self.order.load()?.params.kind()?.is_increase_position() {
+            self.market.load()?.validate_active()?;
+        }
+        Ok(())
+    }
+
     fn validate_trigger_price(&self, prices: &Prices<u128>) -> Result<()> {
         self.order
             .load()?
@@ -1398,7 +1692,10 @@ impl ValidateOracleTime for ExecuteOrderOperation<'_, '_> {
         };
 
         match kind {
-            OrderKind::MarketSwap | OrderKind::MarketIncrease => Ok(Some(updated_at)),
+            // Note: `valid_from_ts` of a market order is only set for the slices of a TWAP order.
+            OrderKind::MarketSwap | OrderKind::MarketIncrease => {
+                Ok(Some(updated_at.max(valid_from_ts)))
+            }
             OrderKind::MarketDecrease => {
                 let position = self
                     .position
@@ -1406,12 +1703,22 @@ impl ValidateOracleTime for ExecuteOrderOperation<'_, '_> {
                     .ok_or(CoreError::PositionIsRequired)?
                     .load()
                     .map_err(|_| CoreError::LoadAccountError)?;
-                Ok(Some(updated_at.max(position.state.increased_at)))
+                let last_updated = updated_at.max(position.state.increased_at);
+                Ok(Some(last_updated.max(valid_from_ts)))
             }
-            OrderKind::LimitSwap | OrderKind::LimitIncrease => {
-                Ok(Some(updated_at.max(valid_from_ts)))
+            // Note: For markets with a schedule, limit and stop orders can only be executed
+            // with prices updated after the current trading session opens.
+            OrderKind::LimitSwap | OrderKind::LimitIncrease | OrderKind::StopIncrease => {
+                let session_start = self.session_start_for_trigger_order()?;
+                Ok(Some(
+                    updated_at
+                        .max(valid_from_ts)
+                        .max(session_start.unwrap_or(i64::MIN)),
+                ))
             }
-            OrderKind::LimitDecrease | OrderKind::StopLossDecrease => {
+            OrderKind::LimitDecrease
+            | OrderKind::StopLossDecrease
+            | OrderKind::TrailingStopDecrease => {
                 let position = self
                     .position
                     .as_ref()
@@ -1419,7 +1726,12 @@ impl ValidateOracleTime for ExecuteOrderOperation<'_, '_> {
                     .load()
                     .map_err(|_| CoreError::LoadAccountError)?;
                 let last_updated = updated_at.max(position.state.increased_at);
-                Ok(Some(last_updated.max(valid_from_ts)))
+                let session_start = self.session_start_for_trigger_order()?;
+                Ok(Some(
+                    last_updated
+                        .max(valid_from_ts)
+                        .max(session_start.unwrap_or(i64::MIN)),
+                ))
             }
             OrderKind::Liquidation => {
                 let position = self
@@ -1439,7 +1751,7 @@ impl ValidateOracleTime for ExecuteOrderOperation<'_, '_> {
     }
 
     fn oracle_updated_before(&self) -> crate::CoreResult<Option<i64>> {
-        let (kind, updated_at) = {
+        let (kind, updated_at, valid_from_ts) = {
             let order = self.order.load().map_err(|_| CoreError::LoadAccountError)?;
             (
                 order
@@ -1447,11 +1759,13 @@ impl ValidateOracleTime for ExecuteOrderOperation<'_, '_> {
                     .kind()
                     .map_err(|_| CoreError::InvalidArgument)?,
                 order.header().updated_at,
+                order.params.valid_from_ts,
             )
         };
         let ts = match kind {
+            // The next slice of a TWAP order expires relative to the time it becomes valid.
             OrderKind::MarketSwap | OrderKind::MarketIncrease | OrderKind::MarketDecrease => {
-                Some(updated_at)
+                Some(updated_at.max(valid_from_ts))
             }
             _ => None,
         };
@@ -1483,6 +1797,33 @@ impl ValidateOracleTime for ExecuteOrderOperation<'_, '_> {
     }
 }
 
+/// Returns whether the execution should be aborted, instead of cancelling the order,
+/// when the circuit breaker of the market is tripped.
+///
+/// Pending limit and stop orders wait for the circuit breaker to be reset. However,
+/// if it has just been tripped by this execution, the order is cancelled instead,
+/// since aborting the execution would roll back the trip.
+fn should_throw_on_tripped_circuit_breaker(kind: &OrderKind, is_just_tripped: bool) -> bool {
+    !(kind.is_market() || is_just_tripped)
+}
+
+/// Returns whether the order is cancelled when its trigger price has been gapped through.
+///
+/// Decrease orders are never cancelled by the gap tolerance, so that protective
+/// orders such as stop-losses still close the position after a gap.
+fn is_subject_to_gap_tolerance(kind: &OrderKind) -> bool {
+    kind.is_trigger() && kind.is_increase_position()
+}
+
+/// Returns whether the total size of the positions on one side exceeds the cap.
+fn exceeds_position_size_cap(
+    size_in_usd: u128,
+    sibling_size_in_usd: u128,
+    max_size_in_usd: Option<u128>,
+) -> bool {
+    max_size_in_usd.is_some_and(|max| size_in_usd.saturating_add(sibling_size_in_usd) > max)
+}
+
 #[inline(never)]
 /// Computes a builder fee amount, denominated in the token of `price`,
 /// from `size_delta_usd` and the builder's fee `factor`.
@@ -1640,7 +1981,7 @@ fn execute_increase_position(
             swap,
             (collateral_token, collateral_token),
             (Some(initial_collateral_token), None),
-            (params.initial_collateral_delta_amount, 0),
+            (params.slice_amount(), 0),
         )?;
         collateral_increment_amount
     };
@@ -1680,7 +2021,7 @@ fn execute_increase_position(
         let (collateral_increment_amount, payable_amount) =
             charge_builder_fee_on_collateral_increment(
                 collateral_increment_amount,
-                params.size_delta_value,
+                params.slice_size(),
                 builder_fee_factor,
                 position.collateral_price(&prices),
             )?;
@@ -1711,7 +2052,7 @@ fn execute_increase_position(
 
     // Increase position.
     let (long_amount, short_amount, paid_order_fee_value) = {
-        let size_delta_usd = params.size_delta_value;
+        let size_delta_usd = params.slice_size();
         let acceptable_price = params.acceptable_price;
         let report = position
             .increase(
@@ -1826,44 +2167,57 @@ fn execute_decrease_position(
     let report = {
         let params = &order.params;
         let decrease_position_swap_type = params.decrease_position_swap_type()?;
-        let size_delta_usd = params.size_delta_value;
+        let mut size_delta_usd = params.slice_size();
         let acceptable_price = params.acceptable_price;
+        let is_liquidation_order =
+            matches!(secondary_order_type, Some(SecondaryOrderType::Liquidation));
+
+        if is_liquidation_order {
+            // Validate the liquidation is a fully close.
+            require_gte!(
+                size_delta_usd,
+                *position.size_in_usd(),
+                CoreError::InvalidArgument
+            );
+            // Only close enough size to restore the position to the target collateral factor
+            // if a partial liquidation is possible. Otherwise, fall back to a full liquidation.
+            if let Some(partial_size_delta_usd) = position
+                .partial_liquidation_size_delta_usd(&prices)
+                .map_err(ModelError::from)?
+            {
+                size_delta_usd = partial_size_delta_usd;
+            }
+        }
+
         // Fold an estimate of the builder fee into the collateral
         // withdrawal amount so it's still funded even if the position is
         // closed at a loss. This is only an estimate for sizing the
         // withdrawal; the amount actually charged is computed later, in
         // the final output token, once the receive-token swap has run.
         let collateral_withdrawal_amount = estimate_builder_fee_for_collateral_withdrawal(
-            u128::from(params.initial_collateral_delta_amount),
+            u128::from(params.slice_amount()),
             size_delta_usd,
             builder_fee_factor,
             position.collateral_price(&prices),
             decrease_position_swap_type,
         )?;
-        let is_liquidation_order =
-            matches!(secondary_order_type, Some(SecondaryOrderType::Liquidation));
+        // ADL orders of a settled market are used to force-close the positions,
+        // so the ADL requirements do not apply.
         let is_adl_order = matches!(
             secondary_order_type,
             Some(SecondaryOrderType::AutoDeleveraging)
-        );
+        ) && !position.market().settlement().is_settled();
 
         let is_cap_size_delta_usd_allowed = matches!(
             order.params().kind()?,
-            OrderKind::LimitDecrease | OrderKind::StopLossDecrease
+            OrderKind::LimitDecrease
+                | OrderKind::StopLossDecrease
+                | OrderKind::TrailingStopDecrease
         );
 
         // Only required when the order is an ADL order.
         let mut pnl_factor_before_execution = None;
 
-        // Validate the liquidation is a fully close.
-        if is_liquidation_order {
-            require_gte!(
-                size_delta_usd,
-                *position.size_in_usd(),
-                CoreError::InvalidArgument
-            );
-        }
-
         // Validate that ADL is required.
         if is_adl_order {
             let Some(pnl_factor) = position
@@ -2136,6 +2490,8 @@ pub struct PositionCutOperation<'a, 'info> {
     #[builder(setter(into))]
     event_emitter: EventEmitter<'a, 'info>,
     remaining_accounts: &'info [AccountInfo<'info>],
+    #[builder(default)]
+    user_stats: Option<&'a AccountLoader<'info, UserStats>>,
 }
 
 impl PositionCutOperation<'_, '_> {
@@ -2258,6 +2614,7 @@ impl PositionCutOperation<'_, '_> {
             .callback_shared_data_account(None)
             .callback_partitioned_data_account(None)
             .allow_closed(self.allow_closed)
+            .user_stats(self.user_stats)
             .build()
             .execute()
     }
@@ -2323,6 +2680,31 @@ mod tests {
         Price { min, max }
     }
 
+    #[test]
+    fn position_size_cap_counts_both_collateral_positions() {
+        let cap = Some(100);
+        assert!(!exceeds_position_size_cap(60, 0, cap));
+        assert!(!exceeds_position_size_cap(60, 40, cap));
+        assert!(exceeds_position_size_cap(60, 41, cap));
+        assert!(exceeds_position_size_cap(101, 0, cap));
+        assert!(!exceeds_position_size_cap(u128::MAX, u128::MAX, None));
+    }
+
+    #[test]
+    fn gap_tolerance_never_applies_to_decrease_orders() {
+        assert!(is_subject_to_gap_tolerance(&OrderKind::LimitIncrease));
+        assert!(is_subject_to_gap_tolerance(&OrderKind::StopIncrease));
+        assert!(!is_subject_to_gap_tolerance(&OrderKind::MarketIncrease));
+        assert!(!is_subject_to_gap_tolerance(&OrderKind::LimitSwap));
+        for kind in [
+            OrderKind::LimitDecrease,
+            OrderKind::StopLossDecrease,
+            OrderKind::TrailingStopDecrease,
+        ] {
+            assert!(!is_subject_to_gap_tolerance(&kind));
+        }
+    }
+
     #[test]
     fn zero_builder_fee_factor_yields_zero_fee_without_needing_a_price() {
         let fee = compute_builder_fee_amount(1_000_000, 0, &price(0, 0)).unwrap();
@@ -2548,4 +2930,44 @@ mod tests {
         );
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn limit_increase_order_tripping_the_circuit_breaker_is_cancelled() {
+        use gmsol_utils::market::MarketConfigKey;
+
+        let mut market = Market::default();
+        *market
+            .get_config_by_key_mut(MarketConfigKey::CircuitBreakerMaxPriceChangeFactor)
+            .unwrap() = constants::MARKET_USD_UNIT / 10;
+        *market
+            .get_config_by_key_mut(MarketConfigKey::CircuitBreakerWindow)
+            .unwrap() = 60;
+
+        assert_eq!(market.update_circuit_breaker(1_000, 100).unwrap(), None);
+        let is_just_tripped = market.update_circuit_breaker(1_200, 110).unwrap().is_some();
+        assert!(is_just_tripped);
+        assert!(market.validate_circuit_breaker_not_tripped().is_err());
+
+        // The order is cancelled so that the trip is kept.
+        assert!(!should_throw_on_tripped_circuit_breaker(
+            &OrderKind::LimitIncrease,
+            is_just_tripped
+        ));
+
+        // Once tripped, later executions report no new trip, and pending limit and stop
+        // increase orders keep waiting for the reset.
+        assert_eq!(market.update_circuit_breaker(1_000, 120).unwrap(), None);
+        assert!(should_throw_on_tripped_circuit_breaker(
+            &OrderKind::LimitIncrease,
+            false
+        ));
+        assert!(should_throw_on_tripped_circuit_breaker(
+            &OrderKind::StopIncrease,
+            false
+        ));
+        assert!(!should_throw_on_tripped_circuit_breaker(
+            &OrderKind::MarketIncrease,
+            false
+        ));
+    }
 }
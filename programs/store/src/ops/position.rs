@@ -0,0 +1,176 @@
+use anchor_lang::prelude::*;
+use gmsol_model::{
+    num::Unsigned, MarketAction, PerpMarketExt, PerpMarketMut, PoolExt, Position as _, PositionExt,
+    PositionMut, PositionMutExt, PositionState, PositionStateMut, SwapMarketMutExt,
+};
+use typed_builder::TypedBuilder;
+
+use crate::{
+    events::{EventEmitter, PositionCollateralSwitched, SwapExecuted},
+    states::{
+        market::{
+            revertible::{
+                market::RevertibleMarket, revertible_position::RevertiblePosition, Revertible,
+                Revision,
+            },
+            utils::ValidateMarketBalances,
+        },
+        Market, Oracle, Position,
+    },
+    CoreError, ModelError,
+};
+
+use super::market::RemainingAccountsForMarket;
+
+/// Operation for switching the collateral token of a position to the opposite token
+/// of the market.
+///
+/// The pending funding fee is paid with the current collateral, and the rest is swapped
+/// through the market's own swap pool. The state is then moved to the `target` position,
+/// while the borrowing and claimable funding states are carried over.
+#[derive(TypedBuilder)]
+pub(crate) struct SwitchPositionCollateralOperation<'a, 'info> {
+    market: &'a AccountLoader<'info, Market>,
+    position: &'a AccountLoader<'info, Position>,
+    target: &'a AccountLoader<'info, Position>,
+    oracle: &'a Oracle,
+    remaining_accounts: &'info [AccountInfo<'info>],
+    #[builder(setter(into))]
+    event_emitter: EventEmitter<'a, 'info>,
+}
+
+impl SwitchPositionCollateralOperation<'_, '_> {
+    /// Execute.
+    #[inline(never)]
+    pub(crate) fn execute(self) -> Result<()> {
+        let min_collateral_amount = {
+            let position = self.position.load()?;
+            require!(
+                position.has_collateral_switch_request(),
+                CoreError::PreconditionsAreNotMet
+            );
+            position.validate_not_transferring()?;
+            position.validate_no_orders()?;
+            position.collateral_switch_min_amount()
+        };
+
+        let prices = self.oracle.market_prices(&*self.market.load()?)?;
+        let market_token = self.market.load()?.meta().market_token_mint;
+        let remaining_accounts =
+            RemainingAccountsForMarket::new(self.remaining_accounts, market_token, None)?;
+        let virtual_inventories = remaining_accounts.load_virtual_inventories()?;
+
+        let mut market =
+            RevertibleMarket::new(self.market, Some(&virtual_inventories), self.event_emitter)?;
+        market.update_fees_state(&prices)?;
+
+        let mut position = RevertiblePosition::new(market, self.position, false)?;
+        position.on_validate().map_err(ModelError::from)?;
+
+        let is_long = position.is_long();
+        let is_collateral_long = position.is_collateral_token_long();
+        let collateral_amount = *position.collateral_amount();
+        let size_in_usd = *position.size_in_usd();
+        let size_in_tokens = *position.size_in_tokens();
+
+        // Pay the pending funding fee with the current collateral. The claimable funding
+        // amounts are not affected since they do not depend on the collateral token.
+        let funding_fee_amount = *position
+            .pending_funding_fees()
+            .map_err(ModelError::from)?
+            .amount();
+        let swap_in_amount = collateral_amount
+            .checked_sub(funding_fee_amount)
+            .filter(|amount| *amount != 0)
+            .ok_or_else(|| error!(CoreError::NotEnoughTokenAmount))?;
+
+        // Swap the rest of the collateral through the market's own swap pool.
+        let report = position
+            .market_mut()
+            .swap(is_collateral_long, swap_in_amount, prices)
+            .and_then(|swap| swap.execute())
+            .map_err(ModelError::from)?;
+        let next_collateral_amount = *report.token_out_amount();
+        position.event_emitter().emit_cpi(&SwapExecuted::new(
+            position.market().rev(),
+            market_token,
+            report,
+            None,
+        ))?;
+
+        // Remove the position from the current collateral side.
+        position
+            .update_open_interest(
+                &size_in_usd.to_opposite_signed().map_err(ModelError::from)?,
+                &size_in_tokens
+                    .to_opposite_signed()
+                    .map_err(ModelError::from)?,
+            )
+            .map_err(ModelError::from)?;
+        position
+            .market_mut()
+            .collateral_sum_pool_mut(is_long)
+            .and_then(|pool| {
+                pool.apply_delta_amount(
+                    is_collateral_long,
+                    &collateral_amount.to_opposite_signed()?,
+                )
+            })
+            .map_err(ModelError::from)?;
+
+        // Move the position to the opposite collateral side.
+        let mut position = position.into_opposite_collateral(self.target)?;
+        *position.collateral_amount_mut() = next_collateral_amount;
+        *position.funding_fee_amount_per_size_mut() = position
+            .market()
+            .funding_fee_amount_per_size(is_long, !is_collateral_long)
+            .map_err(ModelError::from)?;
+        position
+            .update_open_interest(
+                &size_in_usd.to_signed().map_err(ModelError::from)?,
+                &size_in_tokens.to_signed().map_err(ModelError::from)?,
+            )
+            .map_err(ModelError::from)?;
+        position
+            .market_mut()
+            .collateral_sum_pool_mut(is_long)
+            .and_then(|pool| {
+                pool.apply_delta_amount(!is_collateral_long, &next_collateral_amount.to_signed()?)
+            })
+            .map_err(ModelError::from)?;
+
+        require_gte!(
+            next_collateral_amount,
+            u128::from(min_collateral_amount),
+            CoreError::InsufficientOutputAmount
+        );
+        position
+            .validate(&prices, true, true)
+            .map_err(ModelError::from)?;
+        position.market().validate_market_balances(0, 0)?;
+
+        let event = PositionCollateralSwitched::new(
+            position.market().rev(),
+            market_token,
+            self.position.load()?.owner,
+            self.position.key(),
+            self.target.key(),
+            is_long,
+            is_collateral_long,
+            collateral_amount,
+            funding_fee_amount,
+            next_collateral_amount,
+        );
+        position.commit();
+        self.event_emitter.emit_cpi(&event)?;
+
+        self.position.load_mut()?.state = bytemuck::Zeroable::zeroed();
+
+        msg!(
+            "[Position] collateral switched, position {} has been moved to {}",
+            self.position.key(),
+            self.target.key(),
+        );
+        Ok(())
+    }
+}
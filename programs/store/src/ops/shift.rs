@@ -113,6 +113,7 @@ where
         let store = &self.store.key();
         from_market.validate(store)?;
         to_market.validate(store)?;
+        to_market.validate_active()?;
 
         from_market.validate_shiftable(&to_market)?;
 
@@ -209,6 +210,7 @@ impl ExecuteShiftOperation<'_, '_> {
 
         from_market.validate(&self.store.key())?;
         to_market.validate(&self.store.key())?;
+        to_market.validate_active()?;
 
         from_market.validate_shiftable(&to_market)?;
 
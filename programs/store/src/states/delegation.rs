@@ -0,0 +1,235 @@
+use anchor_lang::prelude::*;
+use bytemuck::Zeroable;
+use gmsol_utils::InitSpace;
+
+use crate::CoreError;
+
+use super::Seed;
+
+/// Max number of markets allowed in a delegation.
+pub const MAX_DELEGATION_MARKETS: usize = 8;
+
+/// Delegation Account.
+///
+/// Authorizes a delegate (e.g. a session key) to create, update and cancel
+/// position orders on behalf of the owner within the recorded limits.
+#[account(zero_copy)]
+#[cfg_attr(feature = "debug", derive(derive_more::Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Delegation {
+    version: u8,
+    /// The bump seed.
+    pub(crate) bump: u8,
+    num_markets: u8,
+    #[cfg_attr(feature = "debug", debug(skip))]
+    padding_0: [u8; 13],
+    /// Store.
+    pub store: Pubkey,
+    /// The owner who grants the delegation.
+    pub owner: Pubkey,
+    /// The delegate.
+    pub delegate: Pubkey,
+    /// The delegation cannot be used after this timestamp.
+    expires_at: i64,
+    /// Max cumulative collateral amount the delegate can spend.
+    max_collateral_spend: u64,
+    /// Collateral amount the delegate has spent.
+    collateral_spent: u64,
+    #[cfg_attr(feature = "debug", debug(skip))]
+    padding_1: [u8; 8],
+    /// Max size delta value per order.
+    max_size_delta_value: u128,
+    /// Allowed market tokens.
+    markets: [Pubkey; MAX_DELEGATION_MARKETS],
+    #[cfg_attr(feature = "debug", debug(skip))]
+    #[cfg_attr(feature = "serde", serde(with = "serde_bytes"))]
+    reserved: [u8; 128],
+}
+
+impl InitSpace for Delegation {
+    const INIT_SPACE: usize = std::mem::size_of::<Self>();
+}
+
+impl Seed for Delegation {
+    const SEED: &'static [u8] = b"delegation";
+}
+
+impl Default for Delegation {
+    fn default() -> Self {
+        Zeroable::zeroed()
+    }
+}
+
+/// Delegation Params.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct DelegationParams {
+    /// Allowed market tokens.
+    pub markets: Vec<Pubkey>,
+    /// Max size delta value per order.
+    pub max_size_delta_value: u128,
+    /// Max cumulative collateral amount the delegate can spend.
+    pub max_collateral_spend: u64,
+    /// The delegation cannot be used after this timestamp.
+    pub expires_at: i64,
+}
+
+impl Delegation {
+    pub(crate) fn init(
+        &mut self,
+        bump: u8,
+        store: &Pubkey,
+        owner: &Pubkey,
+        delegate: &Pubkey,
+        params: &DelegationParams,
+    ) -> Result<()> {
+        require_keys_neq!(*owner, *delegate, CoreError::InvalidArgument);
+        require!(
+            !params.markets.is_empty() && params.markets.len() <= MAX_DELEGATION_MARKETS,
+            CoreError::InvalidArgument
+        );
+        require_gt!(
+            params.expires_at,
+            Clock::get()?.unix_timestamp,
+            CoreError::InvalidArgument
+        );
+
+        self.bump = bump;
+        self.store = *store;
+        self.owner = *owner;
+        self.delegate = *delegate;
+        self.expires_at = params.expires_at;
+        self.max_collateral_spend = params.max_collateral_spend;
+        self.collateral_spent = 0;
+        self.max_size_delta_value = params.max_size_delta_value;
+        self.num_markets = params.markets.len() as u8;
+        self.markets[..params.markets.len()].copy_from_slice(&params.markets);
+
+        Ok(())
+    }
+
+    /// Get the expiry timestamp.
+    pub fn expires_at(&self) -> i64 {
+        self.expires_at
+    }
+
+    /// Returns whether the delegation has expired.
+    pub fn is_expired(&self, now: i64) -> bool {
+        now > self.expires_at
+    }
+
+    /// Get the allowed market tokens.
+    pub fn markets(&self) -> &[Pubkey] {
+        &self.markets[..usize::from(self.num_markets)]
+    }
+
+    /// Get the max size delta value per order.
+    pub fn max_size_delta_value(&self) -> u128 {
+        self.max_size_delta_value
+    }
+
+    /// Get the remaining collateral amount the delegate can spend.
+    pub fn remaining_collateral_spend(&self) -> u64 {
+        self.max_collateral_spend
+            .saturating_sub(self.collateral_spent)
+    }
+
+    /// Validate that the delegation can be used for an order of the given
+    /// market with the given size delta value.
+    pub(crate) fn validate_order(
+        &self,
+        market_token: &Pubkey,
+        size_delta_value: u128,
+        now: i64,
+    ) -> Result<()> {
+        require!(!self.is_expired(now), CoreError::DelegationExpired);
+        require!(
+            self.markets().contains(market_token),
+            CoreError::DelegationLimitExceeded
+        );
+        require_gte!(
+            self.max_size_delta_value,
+            size_delta_value,
+            CoreError::DelegationLimitExceeded
+        );
+        Ok(())
+    }
+
+    /// Validate that the markets of the given swap path are allowed by the delegation.
+    pub(crate) fn validate_swap_path<'a>(
+        &self,
+        market_tokens: impl IntoIterator<Item = &'a Pubkey>,
+    ) -> Result<()> {
+        for market_token in market_tokens {
+            require!(
+                self.markets().contains(market_token),
+                CoreError::DelegationLimitExceeded
+            );
+        }
+        Ok(())
+    }
+
+    /// Record the collateral spent by the delegate.
+    pub(crate) fn spend_collateral(&mut self, amount: u64) -> Result<()> {
+        let spent = self
+            .collateral_spent
+            .checked_add(amount)
+            .ok_or_else(|| error!(CoreError::TokenAmountOverflow))?;
+        require_gte!(
+            self.max_collateral_spend,
+            spent,
+            CoreError::DelegationLimitExceeded
+        );
+        self.collateral_spent = spent;
+        Ok(())
+    }
+
+    /// Get the signer seeds of this delegation.
+    pub(crate) fn signer_seeds(&self) -> [Vec<u8>; 5] {
+        [
+            Self::SEED.to_vec(),
+            self.store.to_bytes().to_vec(),
+            self.owner.to_bytes().to_vec(),
+            self.delegate.to_bytes().to_vec(),
+            vec![self.bump],
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn delegation(markets: &[Pubkey]) -> Delegation {
+        let mut delegation = Delegation::default();
+        delegation.expires_at = 100;
+        delegation.max_collateral_spend = 1_000;
+        delegation.max_size_delta_value = 10_000;
+        delegation.num_markets = markets.len() as u8;
+        delegation.markets[..markets.len()].copy_from_slice(markets);
+        delegation
+    }
+
+    #[test]
+    fn delegation_limits() {
+        let market = Pubkey::new_unique();
+        let mut delegation = delegation(&[market]);
+
+        assert!(delegation.validate_order(&market, 10_000, 100).is_ok());
+        assert!(delegation.validate_order(&market, 10_001, 100).is_err());
+        assert!(delegation.validate_order(&market, 1, 101).is_err());
+        assert!(delegation
+            .validate_order(&Pubkey::new_unique(), 1, 0)
+            .is_err());
+
+        assert!(delegation.validate_swap_path([&market]).is_ok());
+        assert!(delegation
+            .validate_swap_path([&market, &Pubkey::new_unique()])
+            .is_err());
+
+        delegation.spend_collateral(600).unwrap();
+        assert_eq!(delegation.remaining_collateral_spend(), 400);
+        assert!(delegation.spend_collateral(401).is_err());
+        delegation.spend_collateral(400).unwrap();
+        assert_eq!(delegation.remaining_collateral_spend(), 0);
+    }
+}
@@ -39,6 +39,68 @@ impl DisabledFeatures {
     }
 }
 
+/// Disabled Features State of a market or a GLV.
+///
+/// Each byte corresponds to a [`DomainDisabledFlag`], and each bit of it
+/// to an [`ActionDisabledFlag`].
+#[zero_copy]
+#[cfg_attr(feature = "debug", derive(Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DisabledFeaturesBitmap {
+    bitmap: [u8; 32],
+}
+
+impl DisabledFeaturesBitmap {
+    /// Returns the byte index and the bit mask of the given feature.
+    fn to_bit(domain: DomainDisabledFlag, action: ActionDisabledFlag) -> (usize, u8) {
+        (domain as usize, 1 << (action as u8))
+    }
+
+    /// Returns whether the given feature is disabled.
+    pub fn is_disabled(&self, domain: DomainDisabledFlag, action: ActionDisabledFlag) -> bool {
+        let (index, mask) = Self::to_bit(domain, action);
+        self.bitmap[index] & mask != 0
+    }
+
+    /// Set disabled.
+    ///
+    /// Return the previous value.
+    pub(crate) fn set_disabled(
+        &mut self,
+        domain: DomainDisabledFlag,
+        action: ActionDisabledFlag,
+        disabled: bool,
+    ) -> bool {
+        let previous = self.is_disabled(domain, action);
+        let (index, mask) = Self::to_bit(domain, action);
+        if disabled {
+            self.bitmap[index] |= mask;
+        } else {
+            self.bitmap[index] &= !mask;
+        }
+        previous
+    }
+
+    /// Validate that the given feature is not disabled for the given market (or GLV) token.
+    pub(crate) fn validate_enabled(
+        &self,
+        market_token: &Pubkey,
+        domain: DomainDisabledFlag,
+        action: ActionDisabledFlag,
+    ) -> Result<()> {
+        if self.is_disabled(domain, action) {
+            msg!(
+                "Feature `{}` is disabled for `{}`",
+                display_feature(domain, action),
+                market_token
+            );
+            err!(CoreError::FeatureDisabled)
+        } else {
+            Ok(())
+        }
+    }
+}
+
 fn to_key(key: &DisabledKey) -> [u8; 2] {
     [key.0 as u8, key.1 as u8]
 }
@@ -61,3 +123,27 @@ impl From<ConfigError> for CoreError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disabled_features_bitmap() {
+        let mut features: DisabledFeaturesBitmap = bytemuck::Zeroable::zeroed();
+        let (domain, action) = (DomainDisabledFlag::StopIncrease, ActionDisabledFlag::Cancel);
+        assert!(!features.set_disabled(domain, action, true));
+        assert!(features.is_disabled(domain, action));
+        assert!(!features.is_disabled(domain, ActionDisabledFlag::Execute));
+        assert!(!features.is_disabled(DomainDisabledFlag::GlvWithdrawal, action));
+        assert!(features
+            .validate_enabled(&Pubkey::default(), domain, action)
+            .is_err());
+
+        assert!(features.set_disabled(domain, action, false));
+        assert!(!features.is_disabled(domain, action));
+        assert!(features
+            .validate_enabled(&Pubkey::default(), domain, action)
+            .is_ok());
+    }
+}
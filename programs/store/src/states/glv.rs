@@ -21,6 +21,7 @@ use super::{
         token::{TokenAndAccount, TokensCollector},
     },
     deposit::DepositActionParams,
+    feature::{ActionDisabledFlag, DisabledFeaturesBitmap, DomainDisabledFlag},
     shift, Seed, Shift, TokenMapAccess,
 };
 
@@ -52,8 +53,9 @@ pub struct Glv {
     padding_1: [u8; 4],
     shift_max_price_impact_factor: u128,
     shift_min_value: u128,
+    disabled_features: DisabledFeaturesBitmap,
     #[cfg_attr(feature = "debug", debug(skip))]
-    reserved: [u8; 256],
+    reserved: [u8; 224],
     /// Market config map with market token addresses as keys.
     markets: GlvMarkets,
 }
@@ -542,6 +544,40 @@ impl Glv {
         self.shift_last_executed_at = clock.unix_timestamp;
         Ok(())
     }
+
+    /// Returns whether the given feature is disabled for this GLV.
+    ///
+    /// Only the GLV-level state is considered, regardless of the store-level one.
+    pub fn is_feature_disabled(
+        &self,
+        domain: DomainDisabledFlag,
+        action: ActionDisabledFlag,
+    ) -> bool {
+        self.disabled_features.is_disabled(domain, action)
+    }
+
+    /// Validate that the given feature is not disabled for this GLV.
+    pub(crate) fn validate_feature_enabled(
+        &self,
+        domain: DomainDisabledFlag,
+        action: ActionDisabledFlag,
+    ) -> Result<()> {
+        self.disabled_features
+            .validate_enabled(&self.glv_token, domain, action)
+    }
+
+    /// Set the given feature disabled for this GLV.
+    ///
+    /// Return the previous value.
+    pub(crate) fn set_feature_disabled(
+        &mut self,
+        domain: DomainDisabledFlag,
+        action: ActionDisabledFlag,
+        disabled: bool,
+    ) -> bool {
+        self.disabled_features
+            .set_disabled(domain, action, disabled)
+    }
 }
 
 #[cfg(feature = "utils")]
@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants, CoreError};
+
+/// The state of the price-move circuit breaker of a market.
+///
+/// The reference price is the first price observed in the current window,
+/// and it is reset once the window has passed.
+#[zero_copy]
+#[cfg_attr(feature = "debug", derive(derive_more::Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CircuitBreaker {
+    ref_price: u128,
+    ref_ts: i64,
+    #[cfg_attr(feature = "debug", debug(skip))]
+    padding: [u8; 8],
+}
+
+impl CircuitBreaker {
+    /// Get the reference price.
+    ///
+    /// Returns `None` if it has not been set.
+    pub fn ref_price(&self) -> Option<u128> {
+        (self.ref_price != 0).then_some(self.ref_price)
+    }
+
+    /// Get the timestamp at which the reference price was set.
+    pub fn ref_ts(&self) -> i64 {
+        self.ref_ts
+    }
+
+    /// Observe the given price.
+    ///
+    /// Returns the reference price if the given price has moved more than
+    /// `max_change_factor` from it within the window.
+    pub(crate) fn observe(
+        &mut self,
+        price: u128,
+        ts: i64,
+        max_change_factor: u128,
+        window: u128,
+    ) -> Result<Option<u128>> {
+        use gmsol_model::utils::apply_factor;
+
+        let is_window_passed = i128::from(ts)
+            .checked_sub(i128::from(self.ref_ts))
+            .and_then(|elapsed| u128::try_from(elapsed).ok())
+            .map(|elapsed| elapsed > window)
+            .unwrap_or(false);
+        let Some(ref_price) = self.ref_price().filter(|_| !is_window_passed) else {
+            self.ref_price = price;
+            self.ref_ts = ts;
+            return Ok(None);
+        };
+
+        let max_change =
+            apply_factor::<_, { constants::MARKET_DECIMALS }>(&ref_price, &max_change_factor)
+                .ok_or_else(|| error!(CoreError::ValueOverflow))?;
+        if price.abs_diff(ref_price) > max_change {
+            Ok(Some(ref_price))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Reset the reference price.
+    pub(crate) fn reset(&mut self) {
+        self.ref_price = 0;
+        self.ref_ts = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trip_within_window() {
+        let factor = constants::MARKET_USD_UNIT / 10;
+        let mut breaker: CircuitBreaker = bytemuck::Zeroable::zeroed();
+        assert_eq!(breaker.observe(1_000, 100, factor, 60).unwrap(), None);
+        assert_eq!(breaker.ref_price(), Some(1_000));
+        assert_eq!(breaker.observe(1_100, 120, factor, 60).unwrap(), None);
+        assert_eq!(breaker.observe(899, 160, factor, 60).unwrap(), Some(1_000));
+
+        // The reference price is reset once the window has passed.
+        assert_eq!(breaker.observe(899, 161, factor, 60).unwrap(), None);
+        assert_eq!(breaker.ref_price(), Some(899));
+        assert_eq!(breaker.ref_ts(), 161);
+    }
+}
@@ -91,7 +91,22 @@ pub struct MarketConfig {
     pub(super) market_closed_min_collateral_factor_for_liquidation: Factor,
     pub(super) market_closed_borrowing_fee_base_factor: Factor,
     pub(super) market_closed_borrowing_fee_above_optimal_usage_factor: Factor,
-    reserved: [Factor; 28],
+    pub(super) partial_liquidation_target_collateral_factor: Factor,
+    pub(super) circuit_breaker_max_price_change_factor: Factor,
+    pub(super) circuit_breaker_window: Factor,
+    pub(super) max_position_size_usd_for_long: Factor,
+    pub(super) max_position_size_usd_for_short: Factor,
+    pub(super) size_tier1_min_position_size_usd: Factor,
+    pub(super) size_tier1_min_collateral_factor: Factor,
+    pub(super) size_tier2_min_position_size_usd: Factor,
+    pub(super) size_tier2_min_collateral_factor: Factor,
+    pub(super) size_tier3_min_position_size_usd: Factor,
+    pub(super) size_tier3_min_collateral_factor: Factor,
+    pub(super) size_tier4_min_position_size_usd: Factor,
+    pub(super) size_tier4_min_collateral_factor: Factor,
+    pub(super) pre_close_window: Factor,
+    pub(super) gap_tolerance_factor: Factor,
+    reserved: [Factor; 13],
     // CHECK: at most `MAX_MARKET_CONFIG_FACTORS` factors.
 }
 
@@ -352,6 +367,25 @@ impl MarketConfig {
             MarketConfigKey::MarketClosedBorrowingFeeAboveOptimalUsageFactor => {
                 &self.market_closed_borrowing_fee_above_optimal_usage_factor
             }
+            MarketConfigKey::PartialLiquidationTargetCollateralFactor => {
+                &self.partial_liquidation_target_collateral_factor
+            }
+            MarketConfigKey::CircuitBreakerMaxPriceChangeFactor => {
+                &self.circuit_breaker_max_price_change_factor
+            }
+            MarketConfigKey::CircuitBreakerWindow => &self.circuit_breaker_window,
+            MarketConfigKey::MaxPositionSizeUsdForLong => &self.max_position_size_usd_for_long,
+            MarketConfigKey::MaxPositionSizeUsdForShort => &self.max_position_size_usd_for_short,
+            MarketConfigKey::SizeTier1MinPositionSizeUsd => &self.size_tier1_min_position_size_usd,
+            MarketConfigKey::SizeTier1MinCollateralFactor => &self.size_tier1_min_collateral_factor,
+            MarketConfigKey::SizeTier2MinPositionSizeUsd => &self.size_tier2_min_position_size_usd,
+            MarketConfigKey::SizeTier2MinCollateralFactor => &self.size_tier2_min_collateral_factor,
+            MarketConfigKey::SizeTier3MinPositionSizeUsd => &self.size_tier3_min_position_size_usd,
+            MarketConfigKey::SizeTier3MinCollateralFactor => &self.size_tier3_min_collateral_factor,
+            MarketConfigKey::SizeTier4MinPositionSizeUsd => &self.size_tier4_min_position_size_usd,
+            MarketConfigKey::SizeTier4MinCollateralFactor => &self.size_tier4_min_collateral_factor,
+            MarketConfigKey::PreCloseWindow => &self.pre_close_window,
+            MarketConfigKey::GapToleranceFactor => &self.gap_tolerance_factor,
             _ => return None,
         };
         Some(value)
@@ -505,6 +539,43 @@ impl MarketConfig {
             MarketConfigKey::MarketClosedBorrowingFeeAboveOptimalUsageFactor => {
                 &mut self.market_closed_borrowing_fee_above_optimal_usage_factor
             }
+            MarketConfigKey::PartialLiquidationTargetCollateralFactor => {
+                &mut self.partial_liquidation_target_collateral_factor
+            }
+            MarketConfigKey::CircuitBreakerMaxPriceChangeFactor => {
+                &mut self.circuit_breaker_max_price_change_factor
+            }
+            MarketConfigKey::CircuitBreakerWindow => &mut self.circuit_breaker_window,
+            MarketConfigKey::MaxPositionSizeUsdForLong => &mut self.max_position_size_usd_for_long,
+            MarketConfigKey::MaxPositionSizeUsdForShort => {
+                &mut self.max_position_size_usd_for_short
+            }
+            MarketConfigKey::SizeTier1MinPositionSizeUsd => {
+                &mut self.size_tier1_min_position_size_usd
+            }
+            MarketConfigKey::SizeTier1MinCollateralFactor => {
+                &mut self.size_tier1_min_collateral_factor
+            }
+            MarketConfigKey::SizeTier2MinPositionSizeUsd => {
+                &mut self.size_tier2_min_position_size_usd
+            }
+            MarketConfigKey::SizeTier2MinCollateralFactor => {
+                &mut self.size_tier2_min_collateral_factor
+            }
+            MarketConfigKey::SizeTier3MinPositionSizeUsd => {
+                &mut self.size_tier3_min_position_size_usd
+            }
+            MarketConfigKey::SizeTier3MinCollateralFactor => {
+                &mut self.size_tier3_min_collateral_factor
+            }
+            MarketConfigKey::SizeTier4MinPositionSizeUsd => {
+                &mut self.size_tier4_min_position_size_usd
+            }
+            MarketConfigKey::SizeTier4MinCollateralFactor => {
+                &mut self.size_tier4_min_collateral_factor
+            }
+            MarketConfigKey::PreCloseWindow => &mut self.pre_close_window,
+            MarketConfigKey::GapToleranceFactor => &mut self.gap_tolerance_factor,
             _ => return None,
         };
         Some(value)
@@ -543,6 +614,98 @@ impl MarketConfig {
         }
     }
 
+    /// Returns target collateral factor for partial liquidation.
+    pub(super) fn partial_liquidation_target_collateral_factor(&self) -> Option<Factor> {
+        let factor = self.partial_liquidation_target_collateral_factor;
+        if factor == 0 {
+            None
+        } else {
+            Some(factor)
+        }
+    }
+
+    /// Returns the max price change factor and the window of the circuit breaker.
+    ///
+    /// Returns `None` if the circuit breaker is disabled.
+    pub(super) fn circuit_breaker_params(&self) -> Option<(Factor, Factor)> {
+        let factor = self.circuit_breaker_max_price_change_factor;
+        if factor == 0 {
+            None
+        } else {
+            Some((factor, self.circuit_breaker_window))
+        }
+    }
+
+    /// Returns the pre-close window (in seconds) of the market schedule.
+    ///
+    /// Returns `None` if there is no pre-close window.
+    pub(super) fn pre_close_window(&self) -> Option<Factor> {
+        let window = self.pre_close_window;
+        if window == 0 {
+            None
+        } else {
+            Some(window)
+        }
+    }
+
+    /// Returns the gap tolerance factor of the market schedule.
+    ///
+    /// Returns `None` if the gap tolerance check is disabled.
+    pub(super) fn gap_tolerance_factor(&self) -> Option<Factor> {
+        let factor = self.gap_tolerance_factor;
+        if factor == 0 {
+            None
+        } else {
+            Some(factor)
+        }
+    }
+
+    /// Returns the max position size in USD per account for the given side.
+    ///
+    /// Returns `None` if no cap is applied.
+    pub(super) fn max_position_size_usd(&self, is_long: bool) -> Option<Factor> {
+        let size = if is_long {
+            self.max_position_size_usd_for_long
+        } else {
+            self.max_position_size_usd_for_short
+        };
+        if size == 0 {
+            None
+        } else {
+            Some(size)
+        }
+    }
+
+    /// Returns the min collateral factor for the given position size, i.e., the largest
+    /// min collateral factor of the enabled size tiers reached by the size.
+    ///
+    /// Returns zero if the size does not reach any of the size tiers.
+    pub(super) fn min_collateral_factor_for_position_size(&self, size_in_usd: &Factor) -> Factor {
+        [
+            (
+                self.size_tier1_min_position_size_usd,
+                self.size_tier1_min_collateral_factor,
+            ),
+            (
+                self.size_tier2_min_position_size_usd,
+                self.size_tier2_min_collateral_factor,
+            ),
+            (
+                self.size_tier3_min_position_size_usd,
+                self.size_tier3_min_collateral_factor,
+            ),
+            (
+                self.size_tier4_min_position_size_usd,
+                self.size_tier4_min_collateral_factor,
+            ),
+        ]
+        .into_iter()
+        .filter(|(min_size_in_usd, _)| *min_size_in_usd != 0 && size_in_usd >= min_size_in_usd)
+        .map(|(_, factor)| factor)
+        .max()
+        .unwrap_or(0)
+    }
+
     /// Returns whether to skip borrowing fee for smaller side.
     pub(super) fn skip_borrowing_fee_for_smaller_side(&self, is_market_closed: bool) -> bool {
         if self.use_market_closed_params(is_market_closed) {
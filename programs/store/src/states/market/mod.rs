@@ -30,6 +30,7 @@ use std::str::FromStr;
 use anchor_lang::{prelude::*, Bump};
 use anchor_spl::token::Mint;
 use borsh::{BorshDeserialize, BorshSerialize};
+use circuit_breaker::CircuitBreaker;
 use config::MarketConfigFlag;
 use gmsol_model::{
     num::Unsigned, price::Prices, Balance, BaseMarket, BaseMarketExt, ClockKind, Delta, PoolKind,
@@ -49,7 +50,10 @@ use crate::{
     CoreError, ModelError,
 };
 
-use super::{Factor, InitSpace, Oracle, Seed};
+use super::{
+    feature::{ActionDisabledFlag, DisabledFeaturesBitmap, DomainDisabledFlag},
+    Factor, InitSpace, Oracle, Seed,
+};
 
 use self::{
     config::{MarketConfig, MarketConfigBuffer, MarketConfigKey},
@@ -58,6 +62,8 @@ use self::{
 
 pub use gmsol_utils::market::{HasMarketMeta, MarketMeta};
 pub use model::AsLiquidityMarket;
+pub use schedule::{MarketSchedule, MarketScheduleParams};
+pub use settlement::{HasSettlement, Settlement};
 
 /// Market Utils.
 pub mod utils;
@@ -80,6 +86,15 @@ pub mod status;
 /// Virtual Inventory.
 pub mod virtual_inventory;
 
+/// Circuit Breaker.
+pub mod circuit_breaker;
+
+/// Settlement.
+pub mod settlement;
+
+/// Market Schedule.
+pub mod schedule;
+
 mod model;
 
 const MAX_NAME_LEN: usize = 64;
@@ -106,8 +121,12 @@ pub struct Market {
     buffer: RevertibleBuffer,
     virtual_inventory_for_swaps: Pubkey,
     virtual_inventory_for_positions: Pubkey,
+    circuit_breaker: CircuitBreaker,
+    settlement: Settlement,
+    schedule: MarketSchedule,
+    disabled_features: DisabledFeaturesBitmap,
     #[cfg_attr(feature = "serde", serde(with = "serde_bytes"))]
-    reserved: [u8; 192],
+    reserved: [u8; 32],
 }
 
 #[zero_copy]
@@ -303,6 +322,188 @@ impl Market {
         Ok(self.set_flag(MarketFlag::Closed, closed))
     }
 
+    /// Returns whether the circuit breaker has been tripped.
+    pub fn is_circuit_breaker_tripped(&self) -> bool {
+        self.flag(MarketFlag::CircuitBreakerTripped)
+    }
+
+    /// Get the circuit breaker state.
+    pub fn circuit_breaker(&self) -> &CircuitBreaker {
+        &self.circuit_breaker
+    }
+
+    /// Feed the index token price to the circuit breaker.
+    ///
+    /// Returns the reference price if the circuit breaker has just been tripped.
+    pub(crate) fn update_circuit_breaker(&mut self, price: u128, now: i64) -> Result<Option<u128>> {
+        if self.is_circuit_breaker_tripped() {
+            return Ok(None);
+        }
+        let Some((max_change_factor, window)) = self.config.circuit_breaker_params() else {
+            return Ok(None);
+        };
+        let ref_price = self
+            .circuit_breaker
+            .observe(price, now, max_change_factor, window)?;
+        if ref_price.is_some() {
+            self.set_flag(MarketFlag::CircuitBreakerTripped, true);
+        }
+        Ok(ref_price)
+    }
+
+    /// Reset the circuit breaker.
+    ///
+    /// Return whether it was tripped.
+    pub(crate) fn reset_circuit_breaker(&mut self) -> bool {
+        self.circuit_breaker.reset();
+        self.set_flag(MarketFlag::CircuitBreakerTripped, false)
+    }
+
+    /// Validate that the circuit breaker has not been tripped.
+    pub(crate) fn validate_circuit_breaker_not_tripped(&self) -> Result<()> {
+        require!(
+            !self.is_circuit_breaker_tripped(),
+            CoreError::MarketCircuitBreakerTripped
+        );
+        Ok(())
+    }
+
+    /// Returns whether the given feature is disabled for this market.
+    ///
+    /// Only the market-level state is considered, regardless of the store-level one.
+    pub fn is_feature_disabled(
+        &self,
+        domain: DomainDisabledFlag,
+        action: ActionDisabledFlag,
+    ) -> bool {
+        self.disabled_features.is_disabled(domain, action)
+    }
+
+    /// Validate that the given feature is not disabled for this market.
+    pub(crate) fn validate_feature_enabled(
+        &self,
+        domain: DomainDisabledFlag,
+        action: ActionDisabledFlag,
+    ) -> Result<()> {
+        self.disabled_features
+            .validate_enabled(&self.meta.market_token_mint, domain, action)
+    }
+
+    /// Set the given feature disabled for this market.
+    ///
+    /// Return the previous value.
+    pub(crate) fn set_feature_disabled(
+        &mut self,
+        domain: DomainDisabledFlag,
+        action: ActionDisabledFlag,
+        disabled: bool,
+    ) -> bool {
+        self.disabled_features
+            .set_disabled(domain, action, disabled)
+    }
+
+    /// Get the settlement state.
+    pub fn settlement(&self) -> &Settlement {
+        &self.settlement
+    }
+
+    /// Start the settlement of the market with the given settlement price.
+    pub(crate) fn start_settlement(
+        &mut self,
+        price: &gmsol_model::price::Price<u128>,
+        price_ts: i64,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        self.settlement.start(price, price_ts, clock.unix_timestamp)
+    }
+
+    /// Mark the settlement of the market as completed.
+    pub(crate) fn complete_settlement(&mut self) -> Result<()> {
+        let clock = Clock::get()?;
+        self.settlement.complete(clock.unix_timestamp)
+    }
+
+    /// Validate that the market is active, i.e., not being settled or settled.
+    pub(crate) fn validate_active(&self) -> Result<()> {
+        require!(self.settlement.is_active(), CoreError::MarketNotActive);
+        Ok(())
+    }
+
+    /// Get the trading-hours schedule.
+    pub fn schedule(&self) -> &MarketSchedule {
+        &self.schedule
+    }
+
+    /// Set the trading-hours schedule.
+    pub(crate) fn set_schedule(&mut self, params: &MarketScheduleParams) -> Result<()> {
+        self.schedule.set(params)
+    }
+
+    /// Get the trading session of the schedule containing `now`, as `(start, end)`.
+    ///
+    /// Returns `Ok(None)` if the schedule is disabled.
+    ///
+    /// # Errors
+    /// Returns [`CoreError::MarketClosedBySchedule`] if the market is closed at `now`.
+    pub(crate) fn validate_in_session(&self, now: i64) -> Result<Option<(i64, i64)>> {
+        if !self.schedule.is_enabled() {
+            return Ok(None);
+        }
+        let session = self
+            .schedule
+            .session_at(now)
+            .ok_or_else(|| error!(CoreError::MarketClosedBySchedule))?;
+        Ok(Some(session))
+    }
+
+    /// Validate that increase orders can be executed at `now` according to the schedule,
+    /// i.e., the market is in a trading session and not in its pre-close window.
+    pub(crate) fn validate_schedule_for_increase(&self, now: i64) -> Result<()> {
+        let Some((_, end)) = self.validate_in_session(now)? else {
+            return Ok(());
+        };
+        if let Some(window) = self.config.pre_close_window() {
+            let remaining = u128::try_from(end.saturating_sub(now)).unwrap_or(0);
+            require_gt!(remaining, window, CoreError::MarketInPreCloseWindow);
+        }
+        Ok(())
+    }
+
+    /// Validate that the given index price has not gapped too far from the `trigger_price`.
+    pub(crate) fn validate_gap_tolerance(
+        &self,
+        trigger_price: u128,
+        index_price: &gmsol_model::price::Price<u128>,
+    ) -> Result<()> {
+        use gmsol_model::utils::apply_factor;
+
+        let Some(factor) = self.config.gap_tolerance_factor() else {
+            return Ok(());
+        };
+        let max_gap = apply_factor::<_, MARKET_DECIMALS>(&trigger_price, &factor)
+            .ok_or_else(|| error!(CoreError::ValueOverflow))?;
+        let price = index_price
+            .checked_mid()
+            .ok_or_else(|| error!(CoreError::ValueOverflow))?;
+        if price.abs_diff(trigger_price) > max_gap {
+            msg!(
+                "[Market] gap tolerance exceeded: price = {}, trigger_price = {}, max_gap = {}",
+                price,
+                trigger_price,
+                max_gap
+            );
+            return err!(CoreError::MarketGapToleranceExceeded);
+        }
+        Ok(())
+    }
+
+    /// Get the max position size in USD per account for the given side.
+    ///
+    /// Returns `None` if no cap is applied.
+    pub fn max_position_size_usd(&self, is_long: bool) -> Option<u128> {
+        self.config.max_position_size_usd(is_long)
+    }
+
     /// Get pool of the given kind.
     #[inline]
     pub fn pool(&self, kind: PoolKind) -> Option<Pool> {
@@ -724,6 +925,12 @@ impl HasMarketMeta for Market {
     }
 }
 
+impl HasSettlement for Market {
+    fn settlement(&self) -> &Settlement {
+        &self.settlement
+    }
+}
+
 /// Market clocks.
 #[zero_copy]
 #[derive(BorshSerialize, BorshDeserialize, InitSpace)]
@@ -15,7 +15,10 @@ use gmsol_model::{
 
 use crate::constants;
 
-use super::{clock::AsClock, config::MarketConfigFlag, HasMarketMeta, Market, Pool};
+use super::{
+    clock::AsClock, config::MarketConfigFlag, HasMarketMeta, HasSettlement, Market, Pool,
+    Settlement,
+};
 
 impl gmsol_model::BaseMarket<{ constants::MARKET_DECIMALS }> for Market {
     type Num = u128;
@@ -312,6 +315,9 @@ impl gmsol_model::PerpMarket<{ constants::MARKET_DECIMALS }> for Market {
                 self.config
                     .min_collateral_factor_for_liquidation(self.is_closed()),
             )
+            .partial_liquidation_target_collateral_factor(
+                self.config.partial_liquidation_target_collateral_factor(),
+            )
             .build())
     }
 
@@ -338,6 +344,15 @@ impl gmsol_model::PerpMarket<{ constants::MARKET_DECIMALS }> for Market {
         }
     }
 
+    fn min_collateral_factor_for_position_size(
+        &self,
+        size_in_usd: &Self::Num,
+    ) -> gmsol_model::Result<Self::Num> {
+        Ok(self
+            .config
+            .min_collateral_factor_for_position_size(size_in_usd))
+    }
+
     fn liquidation_fee_params(&self) -> gmsol_model::Result<LiquidationFeeParams<Self::Num>> {
         Ok(LiquidationFeeParams::builder()
             .factor(self.config.liquidation_fee_factor)
@@ -375,6 +390,15 @@ where
     }
 }
 
+impl<M> HasSettlement for AsLiquidityMarket<'_, M>
+where
+    M: AsRef<Market>,
+{
+    fn settlement(&self) -> &Settlement {
+        self.market.as_ref().settlement()
+    }
+}
+
 impl<M> gmsol_model::BaseMarket<{ constants::MARKET_DECIMALS }> for AsLiquidityMarket<'_, M>
 where
     M: gmsol_model::BaseMarket<
@@ -6,7 +6,10 @@ use gmsol_model::params::{position::PositionImpactDistributionParams, PriceImpac
 
 use crate::{
     constants,
-    states::{HasMarketMeta, Store},
+    states::{
+        market::{HasSettlement, Settlement},
+        HasMarketMeta, Store,
+    },
     utils::internal::TransferUtils,
 };
 
@@ -100,6 +103,12 @@ impl HasMarketMeta for RevertibleLiquidityMarket<'_, '_> {
     }
 }
 
+impl HasSettlement for RevertibleLiquidityMarket<'_, '_> {
+    fn settlement(&self) -> &Settlement {
+        self.base.settlement()
+    }
+}
+
 impl Revertible for RevertibleLiquidityMarket<'_, '_> {
     fn commit(self) {
         if self.to_mint != 0 {
@@ -21,9 +21,10 @@ use crate::{
     states::{
         market::{
             clock::{AsClock, AsClockMut},
+            settlement::Settlement,
             Clocks, Pool,
         },
-        Factor, HasMarketMeta, Market, MarketMeta, OtherState,
+        Factor, HasMarketMeta, HasSettlement, Market, MarketMeta, OtherState,
     },
     CoreError, ModelError,
 };
@@ -339,6 +340,12 @@ impl HasMarketMeta for RevertibleMarket<'_, '_> {
     }
 }
 
+impl HasSettlement for RevertibleMarket<'_, '_> {
+    fn settlement(&self) -> &Settlement {
+        self.market.settlement()
+    }
+}
+
 impl gmsol_model::Bank<Pubkey> for RevertibleMarket<'_, '_> {
     type Num = u64;
 
@@ -636,6 +643,14 @@ impl gmsol_model::PerpMarket<{ constants::MARKET_DECIMALS }> for RevertibleMarke
             .min_collateral_factor_for_open_interest_multiplier(is_long)
     }
 
+    fn min_collateral_factor_for_position_size(
+        &self,
+        size_in_usd: &Self::Num,
+    ) -> gmsol_model::Result<Self::Num> {
+        self.market
+            .min_collateral_factor_for_position_size(size_in_usd)
+    }
+
     fn liquidation_fee_params(
         &self,
     ) -> gmsol_model::Result<gmsol_model::params::fee::LiquidationFeeParams<Self::Num>> {
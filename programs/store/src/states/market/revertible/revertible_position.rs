@@ -63,6 +63,44 @@ impl<'a, 'info> RevertiblePosition<'a, 'info> {
     pub(crate) fn event_emitter(&self) -> &EventEmitter<'a, 'info> {
         self.market.event_emitter()
     }
+
+    /// Move this position to `target`, which must be a position of the same owner
+    /// with the opposite collateral token.
+    ///
+    /// The state is carried over and will be written to `target` on commit,
+    /// while the state of this position is left unchanged.
+    pub(crate) fn into_opposite_collateral(
+        self,
+        target: &'a AccountLoader<'info, Position>,
+    ) -> Result<Self> {
+        let Self {
+            market,
+            storage,
+            mut state,
+            is_collateral_token_long,
+            is_long,
+            allow_market_closed,
+        } = self;
+
+        let collateral_token = market
+            .market_meta()
+            .opposite_token(&storage.collateral_token)
+            .map_err(CoreError::from)?;
+        let target_storage = target.load_mut()?;
+        storage.validate_collateral_switch_target(&target_storage, collateral_token)?;
+
+        let clock = Clock::get()?;
+        state.updated_at_slot = clock.slot;
+
+        Ok(Self {
+            market,
+            storage: target_storage,
+            state,
+            is_collateral_token_long: !is_collateral_token_long,
+            is_long,
+            allow_market_closed,
+        })
+    }
 }
 
 impl Revertible for RevertiblePosition<'_, '_> {
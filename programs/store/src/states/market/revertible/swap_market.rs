@@ -7,7 +7,7 @@ use crate::{
     events::{BorrowingFeesUpdated, EventEmitter, SwapExecuted},
     states::{
         common::swap::SwapActionParams, market::utils::ValidateMarketBalances, HasMarketMeta,
-        Market, Oracle,
+        HasSettlement, Market, Oracle,
     },
     CoreError, ModelError,
 };
@@ -76,6 +76,7 @@ impl<'a, 'info> SwapMarkets<'a, 'info> {
         M: Key
             + Revision
             + HasMarketMeta
+            + HasSettlement
             + gmsol_model::Bank<Pubkey, Num = u64>
             + gmsol_model::SwapMarketMut<{ constants::MARKET_DECIMALS }, Num = u128>,
     {
@@ -287,7 +288,8 @@ impl<'a, 'info> SwapMarkets<'a, 'info> {
             + Revision
             + gmsol_model::Bank<Pubkey, Num = u64>
             + gmsol_model::SwapMarketMut<{ constants::MARKET_DECIMALS }, Num = u128>
-            + HasMarketMeta,
+            + HasMarketMeta
+            + HasSettlement,
     {
         require!(
             self.get_mut(&direction.current()).is_none(),
@@ -466,6 +468,7 @@ where
     M: Key
         + Revision
         + HasMarketMeta
+        + HasSettlement
         + gmsol_model::SwapMarketMut<{ constants::MARKET_DECIMALS }, Num = u128>,
 {
     fn swap_with_current(
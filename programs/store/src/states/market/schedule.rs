@@ -0,0 +1,211 @@
+use anchor_lang::prelude::*;
+
+use crate::CoreError;
+
+/// Max number of holidays in the [`MarketSchedule`].
+pub const MAX_MARKET_HOLIDAYS: usize = 8;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// The trading-hours calendar of a market.
+///
+/// A trading session starts at `open_time` (seconds since 00:00 UTC) of each trading
+/// day and lasts for `duration` seconds, so it may end on the next day. The session
+/// starting on a holiday is skipped. The schedule is disabled when `duration` is `0`.
+#[zero_copy]
+#[cfg_attr(feature = "debug", derive(derive_more::Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MarketSchedule {
+    trading_days: u8,
+    num_holidays: u8,
+    #[cfg_attr(feature = "debug", debug(skip))]
+    padding_0: [u8; 2],
+    open_time: u32,
+    duration: u32,
+    holidays: [u16; MAX_MARKET_HOLIDAYS],
+    #[cfg_attr(feature = "debug", debug(skip))]
+    padding_1: [u8; 4],
+}
+
+/// Market schedule params.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+#[cfg_attr(feature = "debug", derive(Debug))]
+pub struct MarketScheduleParams {
+    /// The bitmap of the trading days, where bit `0` is Monday and bit `6` is Sunday.
+    pub trading_days: u8,
+    /// The time at which a trading session opens, in seconds since 00:00 UTC.
+    pub open_time: u32,
+    /// The duration of a trading session in seconds, at most one day.
+    /// Passing `0` disables the schedule.
+    pub duration: u32,
+    /// The days on which no trading session opens, in days since the Unix epoch.
+    pub holidays: Vec<u16>,
+}
+
+impl MarketSchedule {
+    /// Returns whether the schedule is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.duration != 0
+    }
+
+    /// Get the bitmap of the trading days, where bit `0` is Monday.
+    pub fn trading_days(&self) -> u8 {
+        self.trading_days
+    }
+
+    /// Get the open time of a trading session in seconds since 00:00 UTC.
+    pub fn open_time(&self) -> u32 {
+        self.open_time
+    }
+
+    /// Get the duration of a trading session in seconds.
+    pub fn duration(&self) -> u32 {
+        self.duration
+    }
+
+    /// Get the holidays in days since the Unix epoch.
+    pub fn holidays(&self) -> &[u16] {
+        &self.holidays[..usize::from(self.num_holidays)]
+    }
+
+    /// Set the schedule.
+    pub(crate) fn set(&mut self, params: &MarketScheduleParams) -> Result<()> {
+        if params.duration == 0 {
+            *self = bytemuck::Zeroable::zeroed();
+            return Ok(());
+        }
+        require!(
+            params.trading_days != 0 && params.trading_days >> 7 == 0,
+            CoreError::InvalidArgument
+        );
+        require_gt!(
+            SECONDS_PER_DAY,
+            i64::from(params.open_time),
+            CoreError::InvalidArgument
+        );
+        require_gte!(
+            SECONDS_PER_DAY,
+            i64::from(params.duration),
+            CoreError::InvalidArgument
+        );
+        require_gte!(
+            MAX_MARKET_HOLIDAYS,
+            params.holidays.len(),
+            CoreError::ExceedMaxLengthLimit
+        );
+        for (idx, holiday) in params.holidays.iter().enumerate() {
+            require!(
+                !params.holidays[..idx].contains(holiday),
+                CoreError::InvalidArgument
+            );
+        }
+
+        self.trading_days = params.trading_days;
+        self.open_time = params.open_time;
+        self.duration = params.duration;
+        self.holidays = Default::default();
+        self.holidays[..params.holidays.len()].copy_from_slice(&params.holidays);
+        self.num_holidays = params.holidays.len() as u8;
+        Ok(())
+    }
+
+    fn is_trading_day(&self, day: i64) -> bool {
+        // 1970-01-01 is a Thursday.
+        let weekday = (day + 3).rem_euclid(7);
+        if self.trading_days & (1 << weekday) == 0 {
+            return false;
+        }
+        match u16::try_from(day) {
+            Ok(day) => !self.holidays().contains(&day),
+            Err(_) => true,
+        }
+    }
+
+    /// Get the trading session containing the given timestamp, as `(start, end)`.
+    ///
+    /// Returns `None` if the schedule is disabled or the market is closed at `ts`.
+    pub fn session_at(&self, ts: i64) -> Option<(i64, i64)> {
+        if !self.is_enabled() {
+            return None;
+        }
+        let today = ts.div_euclid(SECONDS_PER_DAY);
+        // A session starting yesterday may end today.
+        [today, today - 1]
+            .into_iter()
+            .filter(|day| self.is_trading_day(*day))
+            .map(|day| {
+                let start = day * SECONDS_PER_DAY + i64::from(self.open_time);
+                (start, start + i64::from(self.duration))
+            })
+            .find(|(start, end)| *start <= ts && ts < *end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2024-01-01 00:00:00 UTC, a Monday.
+    const MONDAY: i64 = 1_704_067_200;
+
+    fn schedule(trading_days: u8, open_time: u32, duration: u32) -> MarketSchedule {
+        let mut schedule: MarketSchedule = bytemuck::Zeroable::zeroed();
+        schedule
+            .set(&MarketScheduleParams {
+                trading_days,
+                open_time,
+                duration,
+                holidays: vec![],
+            })
+            .unwrap();
+        schedule
+    }
+
+    #[test]
+    fn weekday_sessions() {
+        // Monday to Friday, 13:30 to 20:00 UTC.
+        let schedule = schedule(0b0011111, 48_600, 23_400);
+        assert_eq!(schedule.session_at(MONDAY), None);
+        assert_eq!(
+            schedule.session_at(MONDAY + 50_000),
+            Some((MONDAY + 48_600, MONDAY + 72_000))
+        );
+        assert_eq!(schedule.session_at(MONDAY + 72_000), None);
+
+        let saturday = MONDAY + 5 * SECONDS_PER_DAY;
+        assert_eq!(schedule.session_at(saturday + 50_000), None);
+    }
+
+    #[test]
+    fn overnight_sessions_and_holidays() {
+        // Sunday to Thursday, from 22:00 UTC to 22:00 UTC of the next day.
+        let mut schedule = schedule(0b1001111, 79_200, 86_400);
+        let friday = MONDAY + 4 * SECONDS_PER_DAY;
+        assert!(schedule.session_at(friday + 1_000).is_some());
+        assert_eq!(schedule.session_at(friday + 79_200), None);
+        assert_eq!(schedule.session_at(friday + 2 * SECONDS_PER_DAY), None);
+
+        let tuesday = MONDAY + SECONDS_PER_DAY;
+        schedule
+            .set(&MarketScheduleParams {
+                trading_days: 0b1001111,
+                open_time: 79_200,
+                duration: 86_400,
+                holidays: vec![(tuesday / SECONDS_PER_DAY) as u16],
+            })
+            .unwrap();
+        assert!(schedule.session_at(tuesday + 1_000).is_some());
+        assert_eq!(schedule.session_at(tuesday + 80_000), None);
+
+        assert!(schedule
+            .set(&MarketScheduleParams {
+                trading_days: 0,
+                open_time: 0,
+                duration: 3_600,
+                holidays: vec![],
+            })
+            .is_err());
+        schedule.set(&MarketScheduleParams::default()).unwrap();
+        assert!(!schedule.is_enabled());
+    }
+}
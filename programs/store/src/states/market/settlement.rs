@@ -0,0 +1,126 @@
+use anchor_lang::prelude::*;
+use gmsol_model::price::Price;
+use gmsol_utils::market::MarketLifecycle;
+
+use crate::CoreError;
+
+/// The settlement state of a market.
+///
+/// The settlement price is frozen from validated oracle prices when the
+/// settlement starts, and is used as the index token price from then on.
+#[zero_copy]
+#[cfg_attr(feature = "debug", derive(derive_more::Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Settlement {
+    min_price: u128,
+    max_price: u128,
+    price_ts: i64,
+    updated_at: i64,
+    lifecycle: u8,
+    #[cfg_attr(feature = "debug", debug(skip))]
+    padding: [u8; 15],
+}
+
+impl Settlement {
+    /// Get the lifecycle of the market.
+    pub fn lifecycle(&self) -> Result<MarketLifecycle> {
+        MarketLifecycle::try_from(self.lifecycle).map_err(|_| error!(CoreError::InvalidArgument))
+    }
+
+    /// Returns whether the market is active.
+    pub fn is_active(&self) -> bool {
+        matches!(self.lifecycle(), Ok(MarketLifecycle::Active))
+    }
+
+    /// Returns whether the market has been settled.
+    pub fn is_settled(&self) -> bool {
+        matches!(self.lifecycle(), Ok(MarketLifecycle::Settled))
+    }
+
+    /// Get the settlement price.
+    ///
+    /// Returns `None` if the settlement has not started.
+    pub fn price(&self) -> Option<Price<u128>> {
+        (!self.is_active()).then_some(Price {
+            min: self.min_price,
+            max: self.max_price,
+        })
+    }
+
+    /// Get the oracle timestamp of the settlement price.
+    pub fn price_ts(&self) -> i64 {
+        self.price_ts
+    }
+
+    /// Get the timestamp at which the lifecycle was last updated.
+    pub fn updated_at(&self) -> i64 {
+        self.updated_at
+    }
+
+    /// Start the settlement with the given price.
+    pub(crate) fn start(&mut self, price: &Price<u128>, price_ts: i64, now: i64) -> Result<()> {
+        require!(
+            self.lifecycle()? == MarketLifecycle::Active,
+            CoreError::PreconditionsAreNotMet
+        );
+        require!(
+            price.min != 0 && price.min <= price.max,
+            CoreError::InvalidArgument
+        );
+        self.min_price = price.min;
+        self.max_price = price.max;
+        self.price_ts = price_ts;
+        self.updated_at = now;
+        self.lifecycle = MarketLifecycle::Settling.into();
+        Ok(())
+    }
+
+    /// Mark the settlement as completed.
+    pub(crate) fn complete(&mut self, now: i64) -> Result<()> {
+        require!(
+            self.lifecycle()? == MarketLifecycle::Settling,
+            CoreError::PreconditionsAreNotMet
+        );
+        self.updated_at = now;
+        self.lifecycle = MarketLifecycle::Settled.into();
+        Ok(())
+    }
+}
+
+/// Has settlement state.
+pub trait HasSettlement {
+    /// Get the settlement state.
+    fn settlement(&self) -> &Settlement;
+}
+
+impl<T: HasSettlement> HasSettlement for &T {
+    fn settlement(&self) -> &Settlement {
+        (**self).settlement()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settlement_lifecycle() {
+        let price = Price { min: 99, max: 101 };
+        let mut settlement: Settlement = bytemuck::Zeroable::zeroed();
+        assert!(settlement.is_active());
+        assert!(settlement.price().is_none());
+        assert!(settlement.complete(10).is_err());
+
+        settlement.start(&price, 5, 10).unwrap();
+        assert!(settlement.lifecycle().unwrap() == MarketLifecycle::Settling);
+        let frozen = settlement.price().unwrap();
+        assert_eq!((frozen.min, frozen.max), (99, 101));
+        assert!(settlement.start(&price, 5, 10).is_err());
+
+        settlement.complete(20).unwrap();
+        assert!(settlement.is_settled());
+        assert!(settlement.price().is_some());
+        assert_eq!(settlement.updated_at(), 20);
+        assert_eq!(settlement.price_ts(), 5);
+    }
+}
@@ -49,10 +49,15 @@ pub mod callback;
 /// Permission stores and related definitions.
 pub mod permissions;
 
+/// Delegation.
+pub mod delegation;
+
+pub use delegation::Delegation;
 pub use deposit::Deposit;
 pub use glv::{Glv, GlvDeposit, GlvShift, GlvWithdrawal};
 pub use market::{
-    config::MarketConfigKey, pool::PoolStorage, HasMarketMeta, Market, MarketMeta, OtherState,
+    config::MarketConfigKey, pool::PoolStorage, HasMarketMeta, HasSettlement, Market, MarketMeta,
+    OtherState,
 };
 pub use oracle::*;
 pub use order::{Order, OrderActionParams, UpdateOrderParams};
@@ -61,7 +66,7 @@ pub use roles::*;
 pub use shift::*;
 pub use store::*;
 pub use token_config::*;
-pub use user::UserHeader;
+pub use user::{UserHeader, UserStats};
 pub use withdrawal::Withdrawal;
 
 pub type Amount = u64;
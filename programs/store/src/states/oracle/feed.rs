@@ -7,7 +7,7 @@ use crate::{
     CoreError,
 };
 
-use super::{OraclePriceParts, PriceProviderKind};
+use super::{OraclePriceParts, PriceHistory, PriceProviderKind};
 
 pub use gmsol_utils::price::feed_price::PriceFeedPrice;
 
@@ -27,7 +27,8 @@ pub struct PriceFeed {
     last_published_at_slot: u64,
     last_published_at: i64,
     price: PriceFeedPrice,
-    reserved: [u8; 256],
+    history: PriceHistory,
+    reserved: [u8; 16],
 }
 
 impl InitSpace for PriceFeed {
@@ -120,6 +121,7 @@ impl PriceFeed {
         self.last_published_at_slot = slot;
         self.last_published_at = current_ts;
         self.price = *price;
+        self.history.record(price);
 
         Ok(true)
     }
@@ -135,6 +137,16 @@ impl PriceFeed {
         &self.price
     }
 
+    /// Get price history.
+    pub fn history(&self) -> &PriceHistory {
+        &self.history
+    }
+
+    /// Set the sampling interval of the price history.
+    pub(crate) fn set_history_interval(&mut self, interval: u32) {
+        self.history.set_interval(interval);
+    }
+
     /// Get published slot.
     pub fn last_published_at_slot(&self) -> u64 {
         self.last_published_at_slot
@@ -152,9 +164,9 @@ impl PriceFeed {
         allow_closed: bool,
     ) -> Result<OraclePriceParts> {
         let provider = self.provider()?;
-        require_eq!(
-            token_config.expected_provider().map_err(CoreError::from)?,
-            provider
+        require!(
+            token_config.is_provider_used(&provider),
+            CoreError::InvalidPriceFeedAccount
         );
 
         let feed_config = token_config
@@ -201,9 +213,21 @@ impl PriceFeed {
             .map_err(|_| error!(CoreError::InvalidPriceFeedPrice))
     }
 
+    /// Use the TWAP of the price history as the reference price if available,
+    /// and fall back to the spot price otherwise.
     fn try_to_ref_price(&self, token_config: &TokenConfig) -> Result<Decimal> {
-        self.price()
-            .try_to_ref_price(token_config)
-            .map_err(|_| error!(CoreError::InvalidPriceFeedPrice))
+        let twap = (self.history.decimals() == self.price.decimals())
+            .then(|| self.history.twap(self.price.ts()))
+            .flatten();
+        match twap {
+            Some(twap) => Decimal::try_from_price(
+                twap,
+                self.price.decimals(),
+                token_config.token_decimals(),
+                token_config.precision(),
+            ),
+            None => self.price().try_to_ref_price(token_config),
+        }
+        .map_err(|_| error!(CoreError::InvalidPriceFeedPrice))
     }
 }
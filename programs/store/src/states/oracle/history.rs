@@ -0,0 +1,178 @@
+use anchor_lang::prelude::*;
+
+use super::PriceFeedPrice;
+
+/// Max number of samples in the [`PriceHistory`].
+pub const MAX_PRICE_HISTORY_SAMPLES: usize = 7;
+
+/// A ring buffer of recent price samples of a custom price feed.
+///
+/// A new sample is recorded only if at least `interval` seconds have passed
+/// since the last one. Recording is disabled when `interval` is `0`.
+#[zero_copy]
+#[cfg_attr(feature = "debug", derive(derive_more::Debug))]
+pub struct PriceHistory {
+    interval: u32,
+    head: u8,
+    len: u8,
+    decimals: u8,
+    #[cfg_attr(feature = "debug", debug(skip))]
+    padding: [u8; 9],
+    samples: [PriceSample; MAX_PRICE_HISTORY_SAMPLES],
+}
+
+/// Price sample.
+#[zero_copy]
+#[cfg_attr(feature = "debug", derive(derive_more::Debug))]
+pub struct PriceSample {
+    price: u128,
+    ts: i64,
+    #[cfg_attr(feature = "debug", debug(skip))]
+    padding: [u8; 8],
+}
+
+impl PriceSample {
+    /// Get the price.
+    pub fn price(&self) -> u128 {
+        self.price
+    }
+
+    /// Get the timestamp.
+    pub fn ts(&self) -> i64 {
+        self.ts
+    }
+}
+
+impl PriceHistory {
+    /// Get the sampling interval in seconds.
+    ///
+    /// Returns `None` if recording is disabled.
+    pub fn interval(&self) -> Option<u32> {
+        (self.interval != 0).then_some(self.interval)
+    }
+
+    /// Get the decimals of the recorded prices.
+    pub fn decimals(&self) -> u8 {
+        self.decimals
+    }
+
+    /// Returns the number of recorded samples.
+    pub fn len(&self) -> usize {
+        usize::from(self.len)
+    }
+
+    /// Returns whether there are no recorded samples.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns an iterator over the recorded samples, from oldest to newest.
+    pub fn samples(&self) -> impl Iterator<Item = &PriceSample> + '_ {
+        let start = (usize::from(self.head) + MAX_PRICE_HISTORY_SAMPLES - self.len())
+            % MAX_PRICE_HISTORY_SAMPLES;
+        (0..self.len()).map(move |idx| &self.samples[(start + idx) % MAX_PRICE_HISTORY_SAMPLES])
+    }
+
+    fn last(&self) -> Option<&PriceSample> {
+        self.samples().last()
+    }
+
+    /// Set the sampling interval. Recorded samples are cleared.
+    pub(crate) fn set_interval(&mut self, interval: u32) {
+        self.clear();
+        self.interval = interval;
+    }
+
+    fn clear(&mut self) {
+        self.head = 0;
+        self.len = 0;
+        self.decimals = 0;
+    }
+
+    /// Record the given price if the interval has passed since the last sample.
+    ///
+    /// Returns whether a new sample is recorded.
+    pub(crate) fn record(&mut self, price: &PriceFeedPrice) -> bool {
+        let Some(interval) = self.interval() else {
+            return false;
+        };
+        if !self.is_empty() && self.decimals != price.decimals() {
+            self.clear();
+        }
+        if let Some(last) = self.last() {
+            if price.ts() < last.ts.saturating_add_unsigned(interval.into()) {
+                return false;
+            }
+        }
+        let head = usize::from(self.head);
+        self.samples[head] = PriceSample {
+            price: *price.price(),
+            ts: price.ts(),
+            padding: [0; 8],
+        };
+        self.head = ((head + 1) % MAX_PRICE_HISTORY_SAMPLES) as u8;
+        self.len = (self.len() + 1).min(MAX_PRICE_HISTORY_SAMPLES) as u8;
+        self.decimals = price.decimals();
+        true
+    }
+
+    /// Calculate the time-weighted average price up to `end_ts`.
+    ///
+    /// Each sample is weighted by the time until the next sample, and the newest
+    /// one by the time until `end_ts`. Returns `None` if the total duration is zero.
+    pub fn twap(&self, end_ts: i64) -> Option<u128> {
+        let first = self.samples().next()?;
+        let total = u128::try_from(end_ts.checked_sub(first.ts)?).ok()?;
+        if total == 0 {
+            return None;
+        }
+        let mut sum = 0u128;
+        let mut samples = self.samples().peekable();
+        while let Some(sample) = samples.next() {
+            let next_ts = samples.peek().map(|next| next.ts).unwrap_or(end_ts);
+            let duration = u128::try_from(next_ts.checked_sub(sample.ts)?).ok()?;
+            sum = sum.checked_add(sample.price.checked_mul(duration)?)?;
+        }
+        Some(sum / total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn price(ts: i64, price: u128) -> PriceFeedPrice {
+        PriceFeedPrice::new(8, ts, price, price, price, 0)
+    }
+
+    #[test]
+    fn record_and_twap() {
+        let mut history: PriceHistory = bytemuck::Zeroable::zeroed();
+        assert!(!history.record(&price(0, 100)));
+
+        history.set_interval(10);
+        assert!(history.record(&price(0, 100)));
+        assert!(!history.record(&price(5, 1_000)));
+        assert!(history.record(&price(10, 200)));
+        assert_eq!(history.len(), 2);
+
+        // A print at `end_ts` has no weight.
+        assert_eq!(history.twap(20), Some(150));
+        assert_eq!(history.twap(0), None);
+    }
+
+    #[test]
+    fn ring_buffer_overwrites_oldest() {
+        let mut history: PriceHistory = bytemuck::Zeroable::zeroed();
+        history.set_interval(1);
+        for idx in 0..(MAX_PRICE_HISTORY_SAMPLES as i64 + 2) {
+            assert!(history.record(&price(idx, idx as u128)));
+        }
+        assert_eq!(history.len(), MAX_PRICE_HISTORY_SAMPLES);
+        assert_eq!(history.samples().next().map(|s| s.ts()), Some(2));
+        assert_eq!(
+            history.samples().last().map(|s| s.ts()),
+            Some(MAX_PRICE_HISTORY_SAMPLES as i64 + 1)
+        );
+    }
+}
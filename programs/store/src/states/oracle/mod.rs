@@ -4,6 +4,12 @@ pub mod price_map;
 /// Custom Price Feed.
 mod feed;
 
+/// Price History.
+mod history;
+
+/// Price Publishers.
+mod publisher;
+
 /// Switchboard.
 pub mod switchboard;
 
@@ -34,11 +40,13 @@ use gmsol_utils::{
 };
 
 use self::price_map::PriceMap;
-use super::{HasMarketMeta, Seed, Store, TokenConfig, TokenMapHeader, TokenMapRef};
+use super::{HasMarketMeta, HasSettlement, Seed, Store, TokenConfig, TokenMapHeader, TokenMapRef};
 
 pub use self::{
     chainlink::Chainlink,
     feed::{PriceFeed, PriceFeedPrice},
+    history::{PriceHistory, PriceSample, MAX_PRICE_HISTORY_SAMPLES},
+    publisher::{PricePublishers, MAX_PRICE_PUBLISHERS},
     pyth::Pyth,
     switchboard::Switchboard,
     time::{ValidateOracleTime, ValidateOracleTimeExt},
@@ -113,34 +121,58 @@ impl Oracle {
             ErrorCode::AccountNotEnoughKeys
         );
         // Assume the remaining accounts are arranged in the following way:
-        // [token_config, feed; tokens.len()] [..remaining]
+        // [feed; tokens.len()] [aggregated feeds] [..remaining]
+        // where the aggregated feeds are ordered by token and then by provider index.
+        let (feeds, mut aggregated_feeds) = remaining_accounts.split_at(tokens.len());
         for (idx, token) in tokens.iter().enumerate() {
-            let feed = &remaining_accounts[idx];
+            let feed = &feeds[idx];
             let token_config = map.get(token).ok_or_else(|| error!(CoreError::NotFound))?;
 
             require!(token_config.is_enabled(), CoreError::TokenConfigDisabled);
 
+            let expected_provider = token_config.expected_provider().map_err(CoreError::from)?;
             let oracle_price = OraclePrice::parse_from_feed_account(
                 validator.clock(),
                 token_config,
+                &expected_provider,
                 feed,
                 allow_closed,
             )?;
+            oracle_price.validate(&mut validator, token_config)?;
+
+            let OraclePriceParts {
+                mut price,
+                mut is_open,
+                ..
+            } = oracle_price.parts;
+
+            if token_config.is_price_aggregation_enabled() {
+                let mut prices = vec![price];
+                for provider in token_config.aggregated_providers() {
+                    let (feed, rest) = aggregated_feeds
+                        .split_first()
+                        .ok_or_else(|| error!(ErrorCode::AccountNotEnoughKeys))?;
+                    aggregated_feeds = rest;
+                    let oracle_price = OraclePrice::parse_from_feed_account(
+                        validator.clock(),
+                        token_config,
+                        &provider,
+                        feed,
+                        allow_closed,
+                    )?;
+                    oracle_price.validate(&mut validator, token_config)?;
+                    prices.push(oracle_price.parts.price);
+                    is_open &= oracle_price.parts.is_open;
+                }
+                price = aggregate_prices(token_config, &prices)?;
+            }
 
-            validator.validate_one(
-                token_config,
-                &oracle_price.provider,
-                oracle_price.parts.oracle_ts,
-                oracle_price.parts.oracle_slot,
-                &oracle_price.parts.price,
-                oracle_price.parts.ref_price.as_ref(),
-            )?;
-            self.primary.set(
-                token,
-                oracle_price.parts.price,
-                token_config.is_synthetic(),
-                oracle_price.parts.is_open,
-            )?;
+            if token_config.is_price_spread_enabled() {
+                price = widen_price_spread(token_config, &price)?;
+            }
+
+            self.primary
+                .set(token, price, token_config.is_synthetic(), is_open)?;
         }
         self.update_oracle_ts_and_slot(validator)?;
         Ok(())
@@ -200,15 +232,18 @@ impl Oracle {
         allow_closed: bool,
     ) -> Result<T> {
         let validator = PriceValidator::try_from(store.load()?.deref())?;
+        let token_map = token_map.load_token_map()?;
+        let num_feeds = token_map
+            .num_feed_accounts(tokens)
+            .ok_or_else(|| error!(CoreError::NotFound))?;
         require_gte!(
             remaining_accounts.len(),
-            tokens.len(),
+            num_feeds,
             CoreError::NotEnoughTokenFeeds,
         );
-        let feeds = &remaining_accounts[..tokens.len()];
-        let remaining_accounts = &remaining_accounts[tokens.len()..];
+        let feeds = &remaining_accounts[..num_feeds];
+        let remaining_accounts = &remaining_accounts[num_feeds..];
         let res = {
-            let token_map = token_map.load_token_map()?;
             self.set_prices_from_remaining_accounts(
                 validator,
                 &token_map,
@@ -217,6 +252,7 @@ impl Oracle {
                 allow_closed,
             )
         };
+        drop(token_map);
         match res {
             Ok(()) => {
                 let output = f(self, remaining_accounts);
@@ -301,18 +337,25 @@ impl Oracle {
     }
 
     /// Get prices for the market with options.
+    ///
+    /// The settlement price is used as the index token price once the
+    /// settlement of the market has started.
     pub(crate) fn market_prices_with_options(
         &self,
-        market: &impl HasMarketMeta,
+        market: &(impl HasMarketMeta + HasSettlement),
         options: MarketPriceOptions,
     ) -> Result<gmsol_model::price::Prices<u128>> {
         let meta = market.market_meta();
-        let prices = gmsol_model::price::Prices {
-            index_token_price: self.get_primary_price_with_options(
+        let index_token_price = match market.settlement().price() {
+            Some(price) => price,
+            None => self.get_primary_price_with_options(
                 &meta.index_token_mint,
                 true,
                 options.allow_index_closed,
             )?,
+        };
+        let prices = gmsol_model::price::Prices {
+            index_token_price,
             long_token_price: self.get_primary_price_with_options(
                 &meta.long_token_mint,
                 false,
@@ -330,7 +373,7 @@ impl Oracle {
     /// Get prices for the market
     pub(crate) fn market_prices(
         &self,
-        market: &impl HasMarketMeta,
+        market: &(impl HasMarketMeta + HasSettlement),
     ) -> Result<gmsol_model::price::Prices<u128>> {
         self.market_prices_with_options(market, Default::default())
     }
@@ -375,6 +418,7 @@ impl OraclePrice {
     fn parse_from_feed_account<'info>(
         clock: &Clock,
         token_config: &TokenConfig,
+        expected_provider: &PriceProviderKind,
         account: &'info AccountInfo<'info>,
         allow_closed: bool,
     ) -> Result<Self> {
@@ -392,10 +436,7 @@ impl OraclePrice {
             None => return Err(error!(CoreError::InvalidPriceFeedAccount)),
         };
 
-        require_eq!(
-            token_config.expected_provider().map_err(CoreError::from)?,
-            provider
-        );
+        require_eq!(*expected_provider, provider);
 
         let feed_config = token_config
             .get_feed_config(&provider)
@@ -403,7 +444,7 @@ impl OraclePrice {
         let feed_id = feed_config.feed();
 
         let mut parts = match provider {
-            PriceProviderKind::ChainlinkDataStreams => {
+            PriceProviderKind::ChainlinkDataStreams | PriceProviderKind::SignedPublisher => {
                 parsed.ok_or_else(|| error!(CoreError::Internal))?
             }
             PriceProviderKind::Pyth => {
@@ -438,6 +479,128 @@ impl OraclePrice {
 
         Ok(Self { provider, parts })
     }
+
+    fn validate(&self, validator: &mut PriceValidator, token_config: &TokenConfig) -> Result<()> {
+        validator.validate_one(
+            token_config,
+            &self.provider,
+            self.parts.oracle_ts,
+            self.parts.oracle_slot,
+            &self.parts.price,
+            self.parts.ref_price.as_ref(),
+        )
+    }
+}
+
+/// Aggregate the prices of multiple providers by taking the median of the
+/// min and max prices respectively.
+///
+/// The first price is used as the base for the decimals of the output.
+fn aggregate_prices(
+    token_config: &TokenConfig,
+    prices: &[gmsol_utils::Price],
+) -> Result<gmsol_utils::Price> {
+    use gmsol_model::utils::apply_factor;
+    use gmsol_utils::price::median;
+
+    let base = prices.first().ok_or_else(|| error!(CoreError::Internal))?;
+    let unit_prices = prices
+        .iter()
+        .map(gmsol_model::price::Price::<u128>::from)
+        .collect::<Vec<_>>();
+
+    let mut mids = unit_prices
+        .iter()
+        .map(|price| price.checked_mid())
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| error!(CoreError::ValueOverflow))?;
+    let median_mid = median(&mut mids).ok_or_else(|| error!(CoreError::Internal))?;
+
+    if let Some(factor) = token_config.max_provider_spread_factor() {
+        let max_spread = apply_factor::<_, { constants::MARKET_DECIMALS }>(&median_mid, &factor)
+            .ok_or_else(|| error!(CoreError::ValueOverflow))?;
+        // `mids` is sorted by `median`.
+        let spread = mids[mids.len() - 1] - mids[0];
+        if spread > max_spread {
+            msg!(
+                "[Oracle] price spread between providers exceeded: spread = {}, max = {}",
+                spread,
+                max_spread
+            );
+            return err!(CoreError::PriceProviderSpreadExceeded);
+        }
+    }
+
+    let mut mins = unit_prices
+        .iter()
+        .map(|price| price.min)
+        .collect::<Vec<_>>();
+    let mut maxs = unit_prices
+        .iter()
+        .map(|price| price.max)
+        .collect::<Vec<_>>();
+    let min = median(&mut mins).ok_or_else(|| error!(CoreError::Internal))?;
+    let max = median(&mut maxs).ok_or_else(|| error!(CoreError::Internal))?;
+
+    Ok(gmsol_utils::Price {
+        min: base
+            .min
+            .with_unit_price(min, false)
+            .ok_or_else(|| error!(CoreError::PriceOverflow))?,
+        max: base
+            .max
+            .with_unit_price(max, true)
+            .ok_or_else(|| error!(CoreError::PriceOverflow))?,
+    })
+}
+
+/// Widen the spread between the max and min prices with the price spread config
+/// of the token.
+///
+/// The spread reported by the providers (e.g. the confidence interval or the bid/ask
+/// spread) is multiplied by the spread multiplier factor and then raised to at least
+/// the min spread factor of the mid price. The widened spread is applied symmetrically
+/// around the mid price, and the original spread is never narrowed.
+fn widen_price_spread(
+    token_config: &TokenConfig,
+    price: &gmsol_utils::Price,
+) -> Result<gmsol_utils::Price> {
+    widen_price_spread_with_factors(
+        price,
+        token_config.min_spread_factor(),
+        token_config.spread_multiplier_factor(),
+    )
+    .ok_or_else(|| {
+        msg!("[Oracle] failed to widen the price spread");
+        error!(CoreError::InvalidPriceFeedPrice)
+    })
+}
+
+fn widen_price_spread_with_factors(
+    price: &gmsol_utils::Price,
+    min_spread_factor: Option<u128>,
+    spread_multiplier_factor: Option<u128>,
+) -> Option<gmsol_utils::Price> {
+    use gmsol_model::utils::apply_factor;
+
+    let unit_prices = gmsol_model::price::Price::<u128>::from(price);
+    let mid = unit_prices.checked_mid()?;
+    let mut spread = unit_prices.max.checked_sub(unit_prices.min)?;
+    if let Some(factor) = spread_multiplier_factor {
+        spread = apply_factor::<_, { constants::MARKET_DECIMALS }>(&spread, &factor)?;
+    }
+    if let Some(factor) = min_spread_factor {
+        spread = spread.max(apply_factor::<_, { constants::MARKET_DECIMALS }>(
+            &mid, &factor,
+        )?);
+    }
+    let half_spread = spread.div_ceil(2);
+    let min = mid.checked_sub(half_spread)?.min(unit_prices.min);
+    let max = mid.checked_add(half_spread)?.max(unit_prices.max);
+    Some(gmsol_utils::Price {
+        min: price.min.with_unit_price(min, false)?,
+        max: price.max.with_unit_price(max, true)?,
+    })
 }
 
 fn try_adjust_price(feed_config: &FeedConfig, parts: &mut OraclePriceParts) -> Result<bool> {
@@ -533,3 +696,54 @@ pub(crate) struct MarketPriceOptions {
     pub(crate) allow_long_closed: bool,
     pub(crate) allow_short_closed: bool,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn price(min: u32, max: u32) -> gmsol_utils::Price {
+        gmsol_utils::Price {
+            min: Decimal {
+                value: min,
+                decimal_multiplier: 16,
+            },
+            max: Decimal {
+                value: max,
+                decimal_multiplier: 16,
+            },
+        }
+    }
+
+    #[test]
+    fn widen_spread() {
+        let unit = constants::MARKET_USD_UNIT;
+
+        // The reported spread is multiplied.
+        let widened =
+            widen_price_spread_with_factors(&price(9_900, 10_100), None, Some(2 * unit)).unwrap();
+        assert_eq!((widened.min.value, widened.max.value), (9_800, 10_200));
+
+        // The min spread is applied when the reported spread is too narrow.
+        let widened = widen_price_spread_with_factors(
+            &price(10_000, 10_000),
+            Some(unit / 100),
+            Some(2 * unit),
+        )
+        .unwrap();
+        assert_eq!((widened.min.value, widened.max.value), (9_950, 10_050));
+
+        // The spread is never narrowed.
+        let widened = widen_price_spread_with_factors(
+            &price(9_900, 10_100),
+            Some(unit / 100),
+            Some(unit / 2),
+        )
+        .unwrap();
+        assert_eq!((widened.min.value, widened.max.value), (9_900, 10_100));
+
+        // The min price cannot be negative.
+        assert!(
+            widen_price_spread_with_factors(&price(10_000, 10_000), Some(3 * unit), None).is_none()
+        );
+    }
+}
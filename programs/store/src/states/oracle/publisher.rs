@@ -0,0 +1,214 @@
+use anchor_lang::prelude::*;
+use bytemuck::Zeroable;
+use gmsol_utils::{
+    price::{publisher::aggregate_publisher_prices, PublisherPrice},
+    InitSpace,
+};
+
+use crate::{states::Seed, CoreError};
+
+use super::PriceFeedPrice;
+
+/// Max number of publishers in the [`PricePublishers`].
+pub const MAX_PRICE_PUBLISHERS: usize = 16;
+
+/// The allow-listed publishers of the prices of
+/// [`SignedPublisher`](super::PriceProviderKind::SignedPublisher) price feeds.
+#[account(zero_copy)]
+#[cfg_attr(feature = "debug", derive(derive_more::Debug))]
+pub struct PricePublishers {
+    version: u8,
+    pub(crate) bump: u8,
+    quorum: u8,
+    num_publishers: u8,
+    max_age: u32,
+    #[cfg_attr(feature = "debug", debug(skip))]
+    padding: [u8; 8],
+    /// Store.
+    pub store: Pubkey,
+    publishers: [Pubkey; MAX_PRICE_PUBLISHERS],
+    #[cfg_attr(feature = "debug", debug(skip))]
+    reserved: [u8; 128],
+}
+
+impl InitSpace for PricePublishers {
+    const INIT_SPACE: usize = std::mem::size_of::<Self>();
+}
+
+impl Seed for PricePublishers {
+    const SEED: &'static [u8] = b"price_publishers";
+}
+
+impl Default for PricePublishers {
+    fn default() -> Self {
+        Zeroable::zeroed()
+    }
+}
+
+impl PricePublishers {
+    pub(crate) fn init(&mut self, bump: u8, store: &Pubkey) {
+        self.bump = bump;
+        self.store = *store;
+    }
+
+    /// Get the publishers.
+    pub fn publishers(&self) -> &[Pubkey] {
+        &self.publishers[..usize::from(self.num_publishers)]
+    }
+
+    /// Get the min number of publishers required for a price update.
+    pub fn quorum(&self) -> u8 {
+        self.quorum
+    }
+
+    /// Get the max age in seconds of the price of each publisher.
+    pub fn max_age(&self) -> u32 {
+        self.max_age
+    }
+
+    /// Returns whether the given address is an allow-listed publisher.
+    pub fn is_publisher(&self, address: &Pubkey) -> bool {
+        self.publishers().contains(address)
+    }
+
+    /// Set the publishers, the quorum and the max age.
+    pub(crate) fn set(&mut self, publishers: &[Pubkey], quorum: u8, max_age: u32) -> Result<()> {
+        require_gte!(
+            MAX_PRICE_PUBLISHERS,
+            publishers.len(),
+            CoreError::ExceedMaxLengthLimit
+        );
+        require!(
+            quorum != 0 && usize::from(quorum) <= publishers.len(),
+            CoreError::InvalidArgument
+        );
+        require_neq!(max_age, 0, CoreError::InvalidArgument);
+        for (idx, publisher) in publishers.iter().enumerate() {
+            require!(
+                !publishers[..idx].contains(publisher),
+                CoreError::InvalidArgument
+            );
+        }
+
+        self.publishers = Zeroable::zeroed();
+        self.publishers[..publishers.len()].copy_from_slice(publishers);
+        self.num_publishers = publishers.len() as u8;
+        self.quorum = quorum;
+        self.max_age = max_age;
+        Ok(())
+    }
+
+    /// Aggregate the signed prices of the publishers for the given price feed.
+    ///
+    /// Each price must be signed by a distinct publisher and must not be older
+    /// than the max age.
+    pub(crate) fn aggregate(
+        &self,
+        feed: &Pubkey,
+        current_ts: i64,
+        signed: impl IntoIterator<Item = (Pubkey, PublisherPrice)>,
+    ) -> Result<PriceFeedPrice> {
+        let mut signers = Vec::with_capacity(usize::from(self.quorum));
+        let mut prices = Vec::with_capacity(usize::from(self.quorum));
+        for (signer, price) in signed {
+            require!(self.is_publisher(&signer), CoreError::InvalidPublisherPrice);
+            require!(!signers.contains(&signer), CoreError::InvalidPublisherPrice);
+            require_keys_eq!(price.feed, *feed, CoreError::InvalidPublisherPrice);
+            require_gte!(
+                price.ts.saturating_add_unsigned(self.max_age.into()),
+                current_ts,
+                CoreError::InvalidPublisherPrice
+            );
+            signers.push(signer);
+            prices.push(price);
+        }
+        require_gte!(
+            prices.len(),
+            usize::from(self.quorum),
+            CoreError::PricePublisherQuorumNotReached
+        );
+        aggregate_publisher_prices(&prices, self.quorum)
+            .ok_or_else(|| error!(CoreError::InvalidPublisherPrice))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn price(feed: &Pubkey, ts: i64, price: u128) -> PublisherPrice {
+        PublisherPrice {
+            feed: *feed,
+            decimals: 8,
+            ts,
+            price,
+        }
+    }
+
+    #[test]
+    fn aggregate_with_quorum() {
+        let feed = Pubkey::new_unique();
+        let [a, b, c] = [
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+        let mut publishers = PricePublishers::default();
+        assert!(publishers.set(&[a, b, c], 4, 60).is_err());
+        assert!(publishers.set(&[a, b, a], 2, 60).is_err());
+        publishers.set(&[a, b, c], 2, 60).unwrap();
+
+        let aggregated = publishers
+            .aggregate(
+                &feed,
+                100,
+                [(a, price(&feed, 90, 100)), (c, price(&feed, 95, 104))],
+            )
+            .unwrap();
+        assert_eq!(aggregated.ts(), 90);
+        assert_eq!(*aggregated.price(), 102);
+
+        // Quorum not reached.
+        assert!(publishers
+            .aggregate(&feed, 100, [(a, price(&feed, 90, 100))])
+            .is_err());
+        // Duplicated publisher.
+        assert!(publishers
+            .aggregate(
+                &feed,
+                100,
+                [(a, price(&feed, 90, 100)), (a, price(&feed, 95, 104))],
+            )
+            .is_err());
+        // Unknown publisher.
+        assert!(publishers
+            .aggregate(
+                &feed,
+                100,
+                [
+                    (a, price(&feed, 90, 100)),
+                    (Pubkey::new_unique(), price(&feed, 95, 104))
+                ],
+            )
+            .is_err());
+        // Stale price.
+        assert!(publishers
+            .aggregate(
+                &feed,
+                100,
+                [(a, price(&feed, 39, 100)), (b, price(&feed, 95, 104))],
+            )
+            .is_err());
+        // Price of another feed.
+        assert!(publishers
+            .aggregate(
+                &feed,
+                100,
+                [
+                    (a, price(&feed, 90, 100)),
+                    (b, price(&Pubkey::new_unique(), 95, 104))
+                ],
+            )
+            .is_err());
+    }
+}
@@ -10,6 +10,7 @@ use gmsol_utils::{
 };
 
 use crate::{
+    constants,
     events::{EventEmitter, GtUpdated, OrderRemoved},
     utils::pubkey::optional_address,
     CoreError,
@@ -22,7 +23,7 @@ use super::{
         token::TokenAndAccount,
     },
     user::UserHeader,
-    Oracle, Seed, Store,
+    Oracle, Position, Seed, Store,
 };
 
 pub use gmsol_utils::order::{OrderKind, OrderSide};
@@ -56,6 +57,24 @@ impl UpdateOrderParams {
     }
 }
 
+/// Trail distance of a trailing-stop order.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace, Copy, Debug)]
+pub enum TrailDistance {
+    /// Absolute distance (in unit price).
+    Absolute(u128),
+    /// Distance as a factor of the mark price.
+    Factor(u128),
+}
+
+/// TWAP params of an order.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace, Copy, Debug)]
+pub struct TwapParams {
+    /// The number of slices.
+    pub slices: u16,
+    /// The interval between two slices in seconds.
+    pub interval: u32,
+}
+
 /// Transfer Out.
 #[zero_copy]
 #[cfg_attr(feature = "debug", derive(derive_more::Debug))]
@@ -330,9 +349,15 @@ pub struct Order {
     pub(crate) builder: Pubkey,
     /// The builder's fee factor, snapshotted when the builder fee is set.
     pub(crate) builder_fee_factor: u128,
+    /// Linked orders.
+    /// - For a parent order: the two linked (child) orders.
+    /// - For a linked order: the parent order and the sibling order.
+    ///
+    /// The default (zero) [`Pubkey`]s mean the order is not linked.
+    pub(crate) linked_orders: [Pubkey; 2],
     #[cfg_attr(feature = "debug", debug(skip))]
     #[cfg_attr(feature = "serde", serde(with = "serde_bytes"))]
-    reserved: [u8; 80],
+    reserved: [u8; 16],
 }
 
 impl Seed for Order {
@@ -393,13 +418,31 @@ impl Order {
 
     /// Validate that current timestamp >= `valid_from_ts`.
     pub fn validate_valid_from_ts(&self) -> Result<()> {
-        if self.params.kind()?.is_market() {
+        if self.params.kind()?.is_market() && !self.params.is_twap() {
             return Ok(());
         }
         require_gte!(Clock::get()?.unix_timestamp, self.params.valid_from_ts);
         Ok(())
     }
 
+    /// Validate that current timestamp <= `valid_until_ts`.
+    pub fn validate_valid_until_ts(&self) -> Result<()> {
+        require!(
+            !self.params.is_expired(Clock::get()?.unix_timestamp),
+            CoreError::OrderExpired
+        );
+        Ok(())
+    }
+
+    /// Validate that the order is not waiting for its parent to be executed.
+    pub fn validate_activated(&self) -> Result<()> {
+        require!(
+            !self.params.flags.get_flag(OrderFlag::IsPendingActivation),
+            CoreError::LinkedOrderNotActivated
+        );
+        Ok(())
+    }
+
     /// Validate trigger price.
     pub fn validate_trigger_price(&self, index_price: &Price<u128>) -> Result<()> {
         let params = &self.params;
@@ -422,6 +465,21 @@ impl Order {
                     );
                 }
             }
+            OrderKind::StopIncrease => {
+                if is_long {
+                    require_gte!(
+                        index_price.pick_price(true),
+                        trigger_price,
+                        CoreError::InvalidTriggerPrice
+                    );
+                } else {
+                    require_gte!(
+                        trigger_price,
+                        index_price.pick_price(false),
+                        CoreError::InvalidTriggerPrice
+                    );
+                }
+            }
             OrderKind::LimitDecrease => {
                 if is_long {
                     require_gte!(
@@ -437,7 +495,7 @@ impl Order {
                     );
                 }
             }
-            OrderKind::StopLossDecrease => {
+            OrderKind::StopLossDecrease | OrderKind::TrailingStopDecrease => {
                 if is_long {
                     require_gte!(
                         trigger_price,
@@ -640,6 +698,11 @@ impl Order {
         }
 
         if let Some(trigger_price) = params.trigger_price {
+            // The trigger price of a trailing-stop order is derived from its mark.
+            require!(
+                !matches!(current.kind()?, OrderKind::TrailingStopDecrease),
+                CoreError::InvalidTriggerPrice
+            );
             current.trigger_price = trigger_price;
         }
 
@@ -658,6 +721,180 @@ impl Order {
 
         Ok(())
     }
+
+    /// Returns whether the order is linked to other orders.
+    pub fn is_linked(&self) -> bool {
+        self.linked_orders
+            .iter()
+            .any(|address| *address != Pubkey::default())
+    }
+
+    /// Returns whether the order is the parent of linked orders that have not been resolved.
+    pub fn has_pending_linked_orders(&self) -> bool {
+        self.params
+            .flags
+            .get_flag(OrderFlag::HasPendingLinkedOrders)
+    }
+
+    /// Returns whether the order is a linked order waiting for its parent to be executed.
+    pub fn is_pending_activation(&self) -> bool {
+        self.params.flags.get_flag(OrderFlag::IsPendingActivation)
+    }
+
+    /// Returns whether the order is counted in the number of orders of its position.
+    ///
+    /// Orders created before the counter was introduced are not counted until
+    /// the order count of the position is synced.
+    pub fn is_counted_by_position(&self) -> bool {
+        self.params.flags.get_flag(OrderFlag::IsCountedByPosition)
+    }
+
+    /// Mark this order as counted in the number of orders of its position.
+    pub(crate) fn set_counted_by_position(&mut self, position: &Position) {
+        self.params
+            .flags
+            .set_flag(OrderFlag::IsCountedByPosition, true);
+        self.params.position_created_at = position.created_at;
+    }
+
+    /// Returns whether the order is counted in the number of orders of the given position.
+    ///
+    /// Returns `false` if the position account has been closed and re-initialized
+    /// since the order was counted.
+    pub fn is_counted_by(&self, position: &Position) -> bool {
+        self.is_counted_by_position() && self.params.position_created_at == position.created_at
+    }
+
+    /// Get the linked orders.
+    ///
+    /// See [`Order::linked_parent`] and [`Order::linked_sibling`] for the linked orders
+    /// of a child order.
+    pub fn linked_orders(&self) -> &[Pubkey; 2] {
+        &self.linked_orders
+    }
+
+    /// Get the parent order of a linked order.
+    ///
+    /// Returns `None` if the order is not a linked (child) order.
+    pub fn linked_parent(&self) -> Option<&Pubkey> {
+        if self.params.kind().ok()?.is_decrease_position() {
+            optional_address(&self.linked_orders[0])
+        } else {
+            None
+        }
+    }
+
+    /// Get the sibling order of a linked order.
+    ///
+    /// Returns `None` if the order is not a linked (child) order.
+    pub fn linked_sibling(&self) -> Option<&Pubkey> {
+        self.linked_parent()?;
+        optional_address(&self.linked_orders[1])
+    }
+
+    /// Link this order as the parent of the given linked orders.
+    pub(crate) fn link_as_parent(&mut self, linked_orders: [Pubkey; 2]) -> Result<()> {
+        require!(!self.is_linked(), CoreError::InvalidLinkedOrder);
+        require!(
+            self.params.kind()?.is_increase_position(),
+            CoreError::InvalidLinkedOrder
+        );
+        self.linked_orders = linked_orders;
+        self.params
+            .flags
+            .set_flag(OrderFlag::HasPendingLinkedOrders, true);
+        Ok(())
+    }
+
+    /// Link this order as a child of the given parent order.
+    pub(crate) fn link_as_child(&mut self, parent: &Pubkey, sibling: &Pubkey) -> Result<()> {
+        require!(!self.is_linked(), CoreError::InvalidLinkedOrder);
+        require!(
+            matches!(
+                self.params.kind()?,
+                OrderKind::LimitDecrease
+                    | OrderKind::StopLossDecrease
+                    | OrderKind::TrailingStopDecrease
+            ),
+            CoreError::InvalidLinkedOrder
+        );
+        self.linked_orders = [*parent, *sibling];
+        self.params
+            .flags
+            .set_flag(OrderFlag::IsPendingActivation, true);
+        Ok(())
+    }
+
+    /// Mark the linked orders of this parent order as resolved.
+    pub(crate) fn resolve_linked_orders(&mut self) -> Result<()> {
+        require!(
+            self.has_pending_linked_orders(),
+            CoreError::PreconditionsAreNotMet
+        );
+        self.params
+            .flags
+            .set_flag(OrderFlag::HasPendingLinkedOrders, false);
+        Ok(())
+    }
+
+    /// Activate this linked order.
+    pub(crate) fn activate(&mut self) -> Result<()> {
+        require!(
+            self.is_pending_activation(),
+            CoreError::PreconditionsAreNotMet
+        );
+        self.params
+            .flags
+            .set_flag(OrderFlag::IsPendingActivation, false);
+        self.header.updated()?;
+        Ok(())
+    }
+
+    /// Mark the current execution as completed.
+    ///
+    /// A TWAP order with slices left stays pending, and its next slice becomes
+    /// valid after the TWAP interval.
+    pub(crate) fn complete_execution(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        if self.params.advance_twap(now)? {
+            self.header.updated()
+        } else {
+            self.header.completed()
+        }
+    }
+
+    /// Get the execution lamports to pay for one execution.
+    ///
+    /// The max execution lamports of a TWAP order is shared by its slices.
+    pub(crate) fn execution_lamports_per_execution(&self, execution_lamports: u64) -> u64 {
+        let max = self.header.max_execution_lamports / u64::from(self.params.twap_slices.max(1));
+        execution_lamports.min(max)
+    }
+
+    /// Move the mark of a trailing-stop order towards the given index price.
+    ///
+    /// Returns whether the mark has been updated.
+    pub(crate) fn update_trailing_stop_mark(&mut self, index_price: &Price<u128>) -> Result<bool> {
+        let params = &mut self.params;
+        require!(
+            matches!(params.kind()?, OrderKind::TrailingStopDecrease),
+            CoreError::OrderKindNotAllowed
+        );
+        let is_long = params.side()?.is_long();
+        // Use the same side of the price that is compared against the trigger price.
+        let price = index_price.pick_price(!is_long);
+        let is_better = if is_long {
+            *price > params.trail_mark_price
+        } else {
+            *price < params.trail_mark_price
+        };
+        if !is_better {
+            return Ok(false);
+        }
+        params.set_trail_mark_price(*price)?;
+        self.header.updated()?;
+        Ok(true)
+    }
 }
 
 /// Token accounts for Order.
@@ -740,15 +977,31 @@ pub struct OrderActionParams {
     /// Acceptable price (in unit price).
     pub(crate) acceptable_price: u128,
     pub(crate) valid_from_ts: i64,
-    #[cfg_attr(feature = "debug", debug(skip))]
-    padding_2: [u8; 8],
+    /// The creation time of the position when the order was counted by it.
+    position_created_at: i64,
+    /// Trail distance (in unit price or as a factor) for trailing-stop orders.
+    trail_distance: u128,
+    /// The best index price seen by a trailing-stop order (in unit price).
+    /// - High-water mark for long positions.
+    /// - Low-water mark for short positions.
+    trail_mark_price: u128,
+    /// The interval between two TWAP slices in seconds.
+    twap_interval: u32,
+    /// The total number of TWAP slices. Zero means the order is not a TWAP order.
+    twap_slices: u16,
+    /// The number of executed TWAP slices.
+    twap_executed_slices: u16,
+    /// The order cannot be executed after this timestamp.
+    /// Zero means the order never expires.
+    valid_until_ts: i64,
     #[cfg_attr(feature = "debug", debug(skip))]
     #[cfg_attr(feature = "serde", serde(with = "serde_bytes"))]
-    reserved: [u8; 64],
+    reserved: [u8; 16],
 }
 
 impl OrderActionParams {
     const DEFAULT_VALID_FROM_TS: i64 = 0;
+    const MAX_TWAP_SLICES: u16 = 100;
 
     pub(crate) fn init_swap(
         &mut self,
@@ -825,7 +1078,7 @@ impl OrderActionParams {
                 require!(trigger_price.is_none(), CoreError::InvalidTriggerPrice);
                 self.valid_from_ts = Self::DEFAULT_VALID_FROM_TS;
             }
-            OrderKind::LimitIncrease => {
+            OrderKind::LimitIncrease | OrderKind::StopIncrease => {
                 let Some(price) = trigger_price else {
                     return err!(CoreError::InvalidTriggerPrice);
                 };
@@ -853,6 +1106,7 @@ impl OrderActionParams {
         min_output: Option<u128>,
         swap_type: DecreasePositionSwapType,
         valid_from_ts: Option<i64>,
+        trail_distance: Option<TrailDistance>,
     ) -> Result<()> {
         self.kind = kind.into();
         self.side = if is_long {
@@ -891,13 +1145,139 @@ impl OrderActionParams {
                 self.trigger_price = price;
                 self.valid_from_ts = valid_from_ts.unwrap_or(Self::DEFAULT_VALID_FROM_TS);
             }
+            OrderKind::TrailingStopDecrease => {
+                // For trailing-stop orders, the trigger price provided is the initial mark.
+                let Some(price) = trigger_price else {
+                    return err!(CoreError::InvalidTriggerPrice);
+                };
+                let Some(distance) = trail_distance else {
+                    return err!(CoreError::InvalidTrailDistance);
+                };
+                self.init_trail(distance, price)?;
+                self.valid_from_ts = valid_from_ts.unwrap_or(Self::DEFAULT_VALID_FROM_TS);
+            }
             _ => {
                 return err!(CoreError::Internal);
             }
         }
+        if !matches!(kind, OrderKind::TrailingStopDecrease) {
+            require!(trail_distance.is_none(), CoreError::InvalidTrailDistance);
+        }
         Ok(())
     }
 
+    fn init_trail(&mut self, distance: TrailDistance, mark_price: u128) -> Result<()> {
+        let (distance, is_factor) = match distance {
+            TrailDistance::Absolute(distance) => (distance, false),
+            TrailDistance::Factor(factor) => {
+                require_gt!(
+                    constants::MARKET_USD_UNIT,
+                    factor,
+                    CoreError::InvalidTrailDistance
+                );
+                (factor, true)
+            }
+        };
+        require_neq!(distance, 0, CoreError::InvalidTrailDistance);
+        self.trail_distance = distance;
+        self.flags
+            .set_flag(OrderFlag::IsTrailDistanceFactor, is_factor);
+        self.set_trail_mark_price(mark_price)
+    }
+
+    /// Set the mark price and recompute the trigger price of a trailing-stop order.
+    fn set_trail_mark_price(&mut self, mark_price: u128) -> Result<()> {
+        let distance = if self.flags.get_flag(OrderFlag::IsTrailDistanceFactor) {
+            gmsol_model::utils::apply_factor::<_, { constants::MARKET_DECIMALS }>(
+                &mark_price,
+                &self.trail_distance,
+            )
+            .ok_or_else(|| error!(CoreError::ValueOverflow))?
+        } else {
+            self.trail_distance
+        };
+        let trigger_price = if self.side()?.is_long() {
+            mark_price.saturating_sub(distance)
+        } else {
+            mark_price
+                .checked_add(distance)
+                .ok_or_else(|| error!(CoreError::ValueOverflow))?
+        };
+        self.trail_mark_price = mark_price;
+        self.trigger_price = trigger_price;
+        Ok(())
+    }
+
+    /// Set the expiration time of the order.
+    ///
+    /// Only limit, stop and trailing-stop orders can expire, since market orders
+    /// already expire with the request.
+    pub(crate) fn init_valid_until_ts(&mut self, valid_until_ts: i64, now: i64) -> Result<()> {
+        require!(
+            matches!(
+                self.kind()?,
+                OrderKind::LimitSwap
+                    | OrderKind::LimitIncrease
+                    | OrderKind::StopIncrease
+                    | OrderKind::LimitDecrease
+                    | OrderKind::StopLossDecrease
+                    | OrderKind::TrailingStopDecrease
+            ),
+            CoreError::InvalidValidUntilTs
+        );
+        require_gt!(valid_until_ts, now, CoreError::InvalidValidUntilTs);
+        require_gt!(
+            valid_until_ts,
+            self.valid_from_ts,
+            CoreError::InvalidValidUntilTs
+        );
+        self.valid_until_ts = valid_until_ts;
+        Ok(())
+    }
+
+    /// Turn the order into a TWAP order.
+    ///
+    /// Only market increase and market decrease orders can be split into slices,
+    /// and the acceptable price is applied to each slice.
+    pub(crate) fn init_twap(&mut self, twap: &TwapParams) -> Result<()> {
+        require!(
+            matches!(
+                self.kind()?,
+                OrderKind::MarketIncrease | OrderKind::MarketDecrease
+            ),
+            CoreError::InvalidTwapParams
+        );
+        require!(
+            (2..=Self::MAX_TWAP_SLICES).contains(&twap.slices),
+            CoreError::InvalidTwapParams
+        );
+        require_neq!(twap.interval, 0, CoreError::InvalidTwapParams);
+        // The min output is not well-defined for a single slice.
+        require_eq!(self.min_output, 0, CoreError::InvalidTwapParams);
+        self.twap_slices = twap.slices;
+        self.twap_interval = twap.interval;
+        self.twap_executed_slices = 0;
+        Ok(())
+    }
+
+    /// Deduct the executed slice and schedule the next one.
+    ///
+    /// Returns `false` if there is no slice left.
+    fn advance_twap(&mut self, now: i64) -> Result<bool> {
+        if self.twap_remaining_slices() <= 1 {
+            return Ok(false);
+        }
+        let amount = self.slice_amount();
+        let size = self.slice_size();
+        self.initial_collateral_delta_amount -= amount;
+        self.size_delta_value -= size;
+        self.twap_executed_slices += 1;
+        self.valid_from_ts = now
+            .checked_add(self.twap_interval.into())
+            .ok_or_else(|| error!(CoreError::ValueOverflow))?;
+        Ok(true)
+    }
+
     /// Get order kind.
     pub fn kind(&self) -> Result<OrderKind> {
         self.kind
@@ -921,8 +1301,10 @@ impl OrderActionParams {
             kind,
             OrderKind::LimitSwap
                 | OrderKind::LimitIncrease
+                | OrderKind::StopIncrease
                 | OrderKind::LimitDecrease
                 | OrderKind::StopLossDecrease
+                | OrderKind::TrailingStopDecrease
         ))
     }
 
@@ -968,6 +1350,76 @@ impl OrderActionParams {
         self.valid_from_ts
     }
 
+    /// Get valid until ts.
+    ///
+    /// Returns `None` if the order never expires.
+    pub fn valid_until_ts(&self) -> Option<i64> {
+        (self.valid_until_ts != 0).then_some(self.valid_until_ts)
+    }
+
+    /// Returns whether the order has expired at the given timestamp.
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.valid_until_ts().is_some_and(|ts| now > ts)
+    }
+
+    /// Get trail distance.
+    ///
+    /// Returns `None` if the order is not a trailing-stop order.
+    pub fn trail_distance(&self) -> Option<TrailDistance> {
+        if self.trail_distance == 0 {
+            return None;
+        }
+        if self.flags.get_flag(OrderFlag::IsTrailDistanceFactor) {
+            Some(TrailDistance::Factor(self.trail_distance))
+        } else {
+            Some(TrailDistance::Absolute(self.trail_distance))
+        }
+    }
+
+    /// Get the mark price of a trailing-stop order (unit price).
+    pub fn trail_mark_price(&self) -> u128 {
+        self.trail_mark_price
+    }
+
+    /// Returns whether the order is a TWAP order.
+    pub fn is_twap(&self) -> bool {
+        self.twap_slices != 0
+    }
+
+    /// Get the TWAP params.
+    ///
+    /// Returns `None` if the order is not a TWAP order.
+    pub fn twap(&self) -> Option<TwapParams> {
+        self.is_twap().then_some(TwapParams {
+            slices: self.twap_slices,
+            interval: self.twap_interval,
+        })
+    }
+
+    /// Get the number of remaining TWAP slices.
+    ///
+    /// Always returns `1` for non-TWAP orders.
+    pub fn twap_remaining_slices(&self) -> u16 {
+        self.twap_slices
+            .saturating_sub(self.twap_executed_slices)
+            .max(1)
+    }
+
+    /// Get the initial collateral delta amount to use in the next execution.
+    ///
+    /// For TWAP orders, the remaining amount is split evenly between the remaining
+    /// slices and the last slice takes the rest.
+    pub fn slice_amount(&self) -> u64 {
+        self.initial_collateral_delta_amount / u64::from(self.twap_remaining_slices())
+    }
+
+    /// Get the size delta value to use in the next execution.
+    ///
+    /// See [`slice_amount`](Self::slice_amount) for how TWAP orders are sliced.
+    pub fn slice_size(&self) -> u128 {
+        self.size_delta_value / u128::from(self.twap_remaining_slices())
+    }
+
     /// Returns whether the empty position account should be kept.
     pub(crate) fn should_keep_position_account(&self) -> bool {
         self.flags.get_flag(OrderFlag::ShouldKeepPositionAccount)
@@ -992,6 +1444,7 @@ mod tests {
     const BUILDER_FEE_AMOUNT_OFFSET: usize = 2328;
     const BUILDER_OFFSET: usize = 2336;
     const BUILDER_FEE_FACTOR_OFFSET: usize = 2368;
+    const LINKED_ORDERS_OFFSET: usize = 2384;
 
     #[test]
     fn order_account_layout() {
@@ -1008,6 +1461,10 @@ mod tests {
         );
         // u128 requires 16-byte alignment.
         assert_eq!(BUILDER_FEE_FACTOR_OFFSET % 16, 0);
+        assert_eq!(
+            std::mem::offset_of!(Order, linked_orders),
+            LINKED_ORDERS_OFFSET
+        );
     }
 
     #[test]
@@ -1041,4 +1498,279 @@ mod tests {
         // The rejected charge leaves the recorded amount untouched.
         assert_eq!(order.builder_fee_amount(), u64::MAX);
     }
+
+    fn trailing_stop_params(
+        is_long: bool,
+        distance: TrailDistance,
+        mark: u128,
+    ) -> OrderActionParams {
+        let mut params: OrderActionParams = bytemuck::Zeroable::zeroed();
+        params
+            .init_decrease(
+                is_long,
+                OrderKind::TrailingStopDecrease,
+                Pubkey::default(),
+                Pubkey::default(),
+                0,
+                1,
+                Some(mark),
+                None,
+                None,
+                DecreasePositionSwapType::NoSwap,
+                None,
+                Some(distance),
+            )
+            .unwrap();
+        params
+    }
+
+    #[test]
+    fn trailing_stop_trigger_price_follows_mark() {
+        let mut long = trailing_stop_params(true, TrailDistance::Absolute(10), 100);
+        assert_eq!(long.trigger_price(), 90);
+        long.set_trail_mark_price(120).unwrap();
+        assert_eq!(long.trail_mark_price(), 120);
+        assert_eq!(long.trigger_price(), 110);
+
+        let short = trailing_stop_params(
+            false,
+            TrailDistance::Factor(constants::MARKET_USD_UNIT / 10),
+            100,
+        );
+        assert_eq!(short.trigger_price(), 110);
+        assert!(matches!(
+            short.trail_distance(),
+            Some(TrailDistance::Factor(factor)) if factor == constants::MARKET_USD_UNIT / 10
+        ));
+    }
+
+    #[test]
+    fn trailing_stop_rejects_invalid_distance() {
+        let mut params: OrderActionParams = bytemuck::Zeroable::zeroed();
+        for distance in [
+            None,
+            Some(TrailDistance::Absolute(0)),
+            Some(TrailDistance::Factor(constants::MARKET_USD_UNIT)),
+        ] {
+            let err = params
+                .init_decrease(
+                    true,
+                    OrderKind::TrailingStopDecrease,
+                    Pubkey::default(),
+                    Pubkey::default(),
+                    0,
+                    1,
+                    Some(100),
+                    None,
+                    None,
+                    DecreasePositionSwapType::NoSwap,
+                    None,
+                    distance,
+                )
+                .unwrap_err();
+            assert_eq!(err, error!(CoreError::InvalidTrailDistance));
+        }
+    }
+
+    fn order_of_kind(kind: OrderKind) -> Order {
+        let mut order: Order = bytemuck::Zeroable::zeroed();
+        order.params.kind = kind.into();
+        order
+    }
+
+    #[test]
+    fn linked_orders_are_inactive_until_activated() {
+        let parent_address = Pubkey::new_unique();
+        let tp_address = Pubkey::new_unique();
+        let sl_address = Pubkey::new_unique();
+
+        let mut parent = order_of_kind(OrderKind::MarketIncrease);
+        let mut tp = order_of_kind(OrderKind::LimitDecrease);
+        let mut sl = order_of_kind(OrderKind::StopLossDecrease);
+
+        parent.link_as_parent([tp_address, sl_address]).unwrap();
+        tp.link_as_child(&parent_address, &sl_address).unwrap();
+        sl.link_as_child(&parent_address, &tp_address).unwrap();
+
+        assert!(parent.has_pending_linked_orders());
+        assert_eq!(parent.linked_parent(), None);
+        assert_eq!(tp.linked_parent(), Some(&parent_address));
+        assert_eq!(tp.linked_sibling(), Some(&sl_address));
+        assert_eq!(sl.linked_sibling(), Some(&tp_address));
+        assert_eq!(
+            tp.validate_activated().unwrap_err(),
+            error!(CoreError::LinkedOrderNotActivated)
+        );
+
+        parent.resolve_linked_orders().unwrap();
+        assert!(!parent.has_pending_linked_orders());
+    }
+
+    #[test]
+    fn counted_order_is_bound_to_position_creation() {
+        let mut position = Position::default();
+        position.created_at = 1;
+
+        let mut order = order_of_kind(OrderKind::LimitDecrease);
+        assert!(!order.is_counted_by(&position));
+
+        order.set_counted_by_position(&position);
+        assert!(order.is_counted_by_position());
+        assert!(order.is_counted_by(&position));
+
+        // A position re-initialized at the same address does not count the order.
+        let mut recreated = Position::default();
+        recreated.created_at = 2;
+        assert!(!order.is_counted_by(&recreated));
+    }
+
+    #[test]
+    fn orders_cannot_be_linked_twice_or_with_wrong_kinds() {
+        let parent_address = Pubkey::new_unique();
+        let sibling_address = Pubkey::new_unique();
+
+        let mut child = order_of_kind(OrderKind::StopLossDecrease);
+        child
+            .link_as_child(&parent_address, &sibling_address)
+            .unwrap();
+        assert_eq!(
+            child
+                .link_as_child(&parent_address, &sibling_address)
+                .unwrap_err(),
+            error!(CoreError::InvalidLinkedOrder)
+        );
+
+        let mut market_decrease = order_of_kind(OrderKind::MarketDecrease);
+        assert_eq!(
+            market_decrease
+                .link_as_child(&parent_address, &sibling_address)
+                .unwrap_err(),
+            error!(CoreError::InvalidLinkedOrder)
+        );
+
+        let mut swap = order_of_kind(OrderKind::LimitSwap);
+        assert_eq!(
+            swap.link_as_parent([parent_address, sibling_address])
+                .unwrap_err(),
+            error!(CoreError::InvalidLinkedOrder)
+        );
+    }
+
+    #[test]
+    fn twap_order_executes_in_slices() {
+        let mut order = order_of_kind(OrderKind::MarketIncrease);
+        order.params.initial_collateral_delta_amount = 1_000;
+        order.params.size_delta_value = 10_000;
+        order.header.max_execution_lamports = 3_000_000;
+        order
+            .params
+            .init_twap(&TwapParams {
+                slices: 3,
+                interval: 60,
+            })
+            .unwrap();
+
+        assert_eq!(order.execution_lamports_per_execution(5_000_000), 1_000_000);
+
+        let mut executed = (0, 0);
+        let mut now = 100;
+        loop {
+            executed.0 += order.params.slice_amount();
+            executed.1 += order.params.slice_size();
+            if !order.params.advance_twap(now).unwrap() {
+                break;
+            }
+            assert_eq!(order.params.valid_from_ts(), now + 60);
+            now += 60;
+        }
+        // The last slice takes the rest.
+        assert_eq!(executed, (1_000, 10_000));
+        assert_eq!(order.params.slice_amount(), 334);
+        assert_eq!(order.params.twap_remaining_slices(), 1);
+    }
+
+    #[test]
+    fn twap_is_only_for_market_position_orders() {
+        let twap = TwapParams {
+            slices: 2,
+            interval: 60,
+        };
+        let mut limit = order_of_kind(OrderKind::LimitIncrease);
+        assert_eq!(
+            limit.params.init_twap(&twap).unwrap_err(),
+            error!(CoreError::InvalidTwapParams)
+        );
+
+        let mut market = order_of_kind(OrderKind::MarketDecrease);
+        assert_eq!(
+            market
+                .params
+                .init_twap(&TwapParams { slices: 1, ..twap })
+                .unwrap_err(),
+            error!(CoreError::InvalidTwapParams)
+        );
+        assert!(!market.params.is_twap());
+        assert_eq!(market.params.twap_remaining_slices(), 1);
+        market.params.init_twap(&twap).unwrap();
+        assert!(market.params.is_twap());
+    }
+
+    #[test]
+    fn limit_orders_expire_after_valid_until_ts() {
+        let mut order = order_of_kind(OrderKind::LimitIncrease);
+        assert_eq!(order.params.valid_until_ts(), None);
+        assert!(!order.params.is_expired(i64::MAX));
+
+        assert_eq!(
+            order.params.init_valid_until_ts(100, 100).unwrap_err(),
+            error!(CoreError::InvalidValidUntilTs)
+        );
+        order.params.valid_from_ts = 200;
+        assert_eq!(
+            order.params.init_valid_until_ts(200, 100).unwrap_err(),
+            error!(CoreError::InvalidValidUntilTs)
+        );
+
+        order.params.init_valid_until_ts(300, 100).unwrap();
+        assert_eq!(order.params.valid_until_ts(), Some(300));
+        assert!(!order.params.is_expired(300));
+        assert!(order.params.is_expired(301));
+
+        let mut market = order_of_kind(OrderKind::MarketIncrease);
+        assert_eq!(
+            market.params.init_valid_until_ts(300, 100).unwrap_err(),
+            error!(CoreError::InvalidValidUntilTs)
+        );
+    }
+
+    #[test]
+    fn stop_increase_triggers_when_price_breaks_out() {
+        let price = |value: u128| Price {
+            min: value,
+            max: value,
+        };
+        let mut long: Order = bytemuck::Zeroable::zeroed();
+        long.params
+            .init_increase(
+                true,
+                OrderKind::StopIncrease,
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                0,
+                1_000,
+                Some(100),
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+        assert!(long.validate_trigger_price(&price(99)).is_err());
+        long.validate_trigger_price(&price(100)).unwrap();
+        long.validate_trigger_price(&price(101)).unwrap();
+
+        let mut short = long;
+        short.params.side = OrderSide::Short.into();
+        short.validate_trigger_price(&price(99)).unwrap();
+        assert!(short.validate_trigger_price(&price(101)).is_err());
+    }
 }
@@ -1,6 +1,7 @@
-use crate::{constants, CoreError};
+use crate::{constants, utils::pubkey::optional_address, CoreError};
 use anchor_lang::prelude::*;
 use borsh::{BorshDeserialize, BorshSerialize};
+use gmsol_model::PositionStateExt;
 use num_enum::TryFromPrimitive;
 
 use super::{Market, Seed};
@@ -32,10 +33,25 @@ pub struct Position {
     pub collateral_token: Pubkey,
     /// Position State.
     pub state: PositionState,
+    /// The pending next owner of this position.
+    next_owner: Pubkey,
+    /// The number of orders referencing this position that have not been closed.
+    num_orders: u32,
+    /// Whether `num_orders` covers all the orders referencing this position.
+    order_count_synced: u8,
+    /// Padding.
+    #[cfg_attr(feature = "debug", debug(skip))]
+    padding_1: [u8; 3],
+    /// The time the pending collateral switch was requested at.
+    collateral_switch_requested_at: i64,
+    /// The slot the pending collateral switch was requested at.
+    collateral_switch_requested_at_slot: u64,
+    /// The min collateral amount after the pending collateral switch.
+    collateral_switch_min_amount: u64,
     /// Reserved.
     #[cfg_attr(feature = "debug", debug(skip))]
     #[cfg_attr(feature = "serde", serde(with = "serde_bytes"))]
-    reserved: [u8; 256],
+    reserved: [u8; 192],
 }
 
 impl Default for Position {
@@ -106,6 +122,207 @@ impl Position {
         self.owner = *owner;
         self.market_token = *market_token;
         self.collateral_token = *collateral_token;
+        // All orders referencing a newly initialized position are counted.
+        self.order_count_synced = 1;
+        Ok(())
+    }
+
+    /// Get the pending next owner, if a transfer has been initiated.
+    pub fn next_owner(&self) -> Option<&Pubkey> {
+        optional_address(&self.next_owner)
+    }
+
+    /// Get the number of orders referencing this position that have not been closed.
+    ///
+    /// Only accurate if [`is_order_count_synced`](Self::is_order_count_synced) returns `true`.
+    pub fn num_orders(&self) -> u32 {
+        self.num_orders
+    }
+
+    /// Returns whether the order count covers all the orders referencing this position.
+    ///
+    /// It is `false` for positions initialized before the order count was introduced,
+    /// until their order count is synced.
+    pub fn is_order_count_synced(&self) -> bool {
+        self.order_count_synced != 0
+    }
+
+    /// Count the given number of orders that were created before the order count was
+    /// introduced, and mark the order count as synced.
+    pub(crate) fn sync_order_count(&mut self, uncounted_orders: u32) -> Result<()> {
+        self.num_orders = self
+            .num_orders
+            .checked_add(uncounted_orders)
+            .ok_or_else(|| error!(CoreError::ValueOverflow))?;
+        self.order_count_synced = 1;
+        Ok(())
+    }
+
+    /// Record that an order referencing this position has been created.
+    pub(crate) fn on_order_created(&mut self) -> Result<()> {
+        self.num_orders = self
+            .num_orders
+            .checked_add(1)
+            .ok_or_else(|| error!(CoreError::ValueOverflow))?;
+        Ok(())
+    }
+
+    /// Record that an order counted by this position has been closed.
+    pub(crate) fn on_order_closed(&mut self) {
+        // The position account may have been closed and re-initialized
+        // while the order was pending, in which case it is not counted.
+        self.num_orders = self.num_orders.saturating_sub(1);
+    }
+
+    /// Validate that no transfer of this position is in progress.
+    pub(crate) fn validate_not_transferring(&self) -> Result<()> {
+        require!(
+            self.next_owner().is_none(),
+            CoreError::PositionTransferInProgress
+        );
+        Ok(())
+    }
+
+    /// Validate that no orders are referencing this position.
+    pub(crate) fn validate_no_orders(&self) -> Result<()> {
+        require!(
+            self.is_order_count_synced(),
+            CoreError::PositionOrderCountNotSynced
+        );
+        require_eq!(self.num_orders, 0, CoreError::PositionHasPendingOrders);
+        Ok(())
+    }
+
+    /// Initiate a transfer of this position to `next_owner`.
+    pub(crate) fn set_next_owner(&mut self, next_owner: &Pubkey) -> Result<()> {
+        require_keys_neq!(self.owner, *next_owner, CoreError::InvalidArgument);
+        require_keys_neq!(*next_owner, Pubkey::default(), CoreError::InvalidArgument);
+        require!(
+            !self.has_collateral_switch_request(),
+            CoreError::PreconditionsAreNotMet
+        );
+        self.validate_no_orders()?;
+        self.next_owner = *next_owner;
+        Ok(())
+    }
+
+    /// Cancel the transfer of this position.
+    pub(crate) fn cancel_transfer(&mut self) -> Result<()> {
+        require!(
+            self.next_owner().is_some(),
+            CoreError::PreconditionsAreNotMet
+        );
+        self.next_owner = Pubkey::default();
+        Ok(())
+    }
+
+    /// Returns whether a collateral switch has been requested.
+    pub fn has_collateral_switch_request(&self) -> bool {
+        self.collateral_switch_requested_at_slot != 0
+    }
+
+    /// Get the time and slot the pending collateral switch was requested at.
+    pub fn collateral_switch_requested_at(&self) -> Option<(i64, u64)> {
+        self.has_collateral_switch_request().then_some((
+            self.collateral_switch_requested_at,
+            self.collateral_switch_requested_at_slot,
+        ))
+    }
+
+    /// Get the min collateral amount after the pending collateral switch.
+    pub fn collateral_switch_min_amount(&self) -> u64 {
+        self.collateral_switch_min_amount
+    }
+
+    /// Request to switch the collateral token of this position to the opposite token of the market.
+    pub(crate) fn request_collateral_switch(&mut self, min_collateral_amount: u64) -> Result<()> {
+        require!(
+            !self.has_collateral_switch_request(),
+            CoreError::PreconditionsAreNotMet
+        );
+        require!(!self.state.is_empty(), CoreError::InvalidPosition);
+        self.validate_not_transferring()?;
+        self.validate_no_orders()?;
+        let clock = Clock::get()?;
+        self.collateral_switch_requested_at = clock.unix_timestamp;
+        self.collateral_switch_requested_at_slot = clock.slot;
+        self.collateral_switch_min_amount = min_collateral_amount;
+        Ok(())
+    }
+
+    /// Cancel the pending collateral switch.
+    pub(crate) fn cancel_collateral_switch(&mut self) -> Result<()> {
+        require!(
+            self.has_collateral_switch_request(),
+            CoreError::PreconditionsAreNotMet
+        );
+        self.collateral_switch_requested_at = 0;
+        self.collateral_switch_requested_at_slot = 0;
+        self.collateral_switch_min_amount = 0;
+        Ok(())
+    }
+
+    /// Validate that `target` can take over the state of this position after switching its
+    /// collateral token to `collateral_token`.
+    pub(crate) fn validate_collateral_switch_target(
+        &self,
+        target: &Self,
+        collateral_token: &Pubkey,
+    ) -> Result<()> {
+        require_keys_neq!(
+            *collateral_token,
+            self.collateral_token,
+            CoreError::InvalidArgument
+        );
+        require_keys_eq!(
+            target.collateral_token,
+            *collateral_token,
+            CoreError::InvalidPosition
+        );
+        require_keys_eq!(target.owner, self.owner, CoreError::OwnerMismatched);
+        require_keys_eq!(target.store, self.store, CoreError::InvalidPosition);
+        require_keys_eq!(
+            target.market_token,
+            self.market_token,
+            CoreError::InvalidPosition
+        );
+        require_eq!(target.kind()?, self.kind()?, CoreError::InvalidPosition);
+        require!(target.state.is_empty(), CoreError::InvalidPosition);
+        target.validate_no_orders()?;
+        target.validate_not_transferring()?;
+        Ok(())
+    }
+
+    /// Move the state of this position to `target`, which must be an empty position of the
+    /// pending next owner with the same store, market, collateral token and kind.
+    ///
+    /// The state of this position is cleared afterwards.
+    pub(crate) fn move_to(&mut self, target: &mut Self) -> Result<()> {
+        let next_owner = *self
+            .next_owner()
+            .ok_or_else(|| error!(CoreError::PreconditionsAreNotMet))?;
+        self.validate_no_orders()?;
+        require_keys_eq!(target.owner, next_owner, CoreError::OwnerMismatched);
+        require_keys_eq!(target.store, self.store, CoreError::InvalidPosition);
+        require_keys_eq!(
+            target.market_token,
+            self.market_token,
+            CoreError::InvalidPosition
+        );
+        require_keys_eq!(
+            target.collateral_token,
+            self.collateral_token,
+            CoreError::InvalidPosition
+        );
+        require_eq!(target.kind()?, self.kind()?, CoreError::InvalidPosition);
+        require!(target.state.is_empty(), CoreError::InvalidPosition);
+        target.validate_no_orders()?;
+        target.validate_not_transferring()?;
+
+        target.created_at = self.created_at;
+        target.state = self.state;
+        self.state = bytemuck::Zeroable::zeroed();
+        self.next_owner = Pubkey::default();
         Ok(())
     }
 
@@ -361,3 +578,117 @@ impl gmsol_model::Position<{ constants::MARKET_DECIMALS }> for AsPosition<'_> {
         self.position.validate_for_market(self.market, false)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(owner: &Pubkey, store: &Pubkey, market_token: &Pubkey) -> Position {
+        let mut position = Position::default();
+        position.kind = PositionKind::Long as u8;
+        position.store = *store;
+        position.owner = *owner;
+        position.market_token = *market_token;
+        position.collateral_token = *market_token;
+        position.order_count_synced = 1;
+        position
+    }
+
+    #[test]
+    fn position_transfer() {
+        let store = Pubkey::new_unique();
+        let market_token = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let next_owner = Pubkey::new_unique();
+
+        let mut source = position(&owner, &store, &market_token);
+        source.created_at = 1;
+        source.state.size_in_usd = 1_000;
+        source.state.long_token_claimable_funding_amount_per_size = 10;
+        let mut target = position(&next_owner, &store, &market_token);
+
+        assert!(source.move_to(&mut target).is_err());
+
+        source.on_order_created().unwrap();
+        assert!(source.set_next_owner(&next_owner).is_err());
+        source.on_order_closed();
+        assert!(source.set_next_owner(&owner).is_err());
+        source.set_next_owner(&next_owner).unwrap();
+        assert!(source.validate_not_transferring().is_err());
+
+        let mut other = position(&Pubkey::new_unique(), &store, &market_token);
+        assert!(source.move_to(&mut other).is_err());
+
+        source.move_to(&mut target).unwrap();
+        assert_eq!(target.created_at, 1);
+        assert_eq!(target.state.size_in_usd, 1_000);
+        assert_eq!(
+            target.state.long_token_claimable_funding_amount_per_size,
+            10
+        );
+        assert!(source.state.is_empty());
+        assert!(source.next_owner().is_none());
+    }
+
+    #[test]
+    fn legacy_position_requires_order_count_sync() {
+        let store = Pubkey::new_unique();
+        let market_token = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let next_owner = Pubkey::new_unique();
+
+        let mut legacy = position(&owner, &store, &market_token);
+        legacy.order_count_synced = 0;
+        assert!(!legacy.is_order_count_synced());
+        assert!(legacy.set_next_owner(&next_owner).is_err());
+
+        legacy.sync_order_count(1).unwrap();
+        assert!(legacy.is_order_count_synced());
+        assert_eq!(legacy.num_orders(), 1);
+        assert!(legacy.set_next_owner(&next_owner).is_err());
+
+        legacy.on_order_closed();
+        legacy.set_next_owner(&next_owner).unwrap();
+    }
+
+    #[test]
+    fn collateral_switch_target() {
+        let store = Pubkey::new_unique();
+        let market_token = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let collateral_token = Pubkey::new_unique();
+
+        let mut source = position(&owner, &store, &market_token);
+        source.state.size_in_usd = 1_000;
+        let mut target = position(&owner, &store, &market_token);
+        target.collateral_token = collateral_token;
+
+        assert!(source
+            .validate_collateral_switch_target(&source, &market_token)
+            .is_err());
+        assert!(source
+            .validate_collateral_switch_target(&target, &Pubkey::new_unique())
+            .is_err());
+
+        let mut other = position(&Pubkey::new_unique(), &store, &market_token);
+        other.collateral_token = collateral_token;
+        assert!(source
+            .validate_collateral_switch_target(&other, &collateral_token)
+            .is_err());
+
+        target.on_order_created().unwrap();
+        assert!(source
+            .validate_collateral_switch_target(&target, &collateral_token)
+            .is_err());
+        target.on_order_closed();
+        source
+            .validate_collateral_switch_target(&target, &collateral_token)
+            .unwrap();
+
+        assert!(source.cancel_collateral_switch().is_err());
+        source.collateral_switch_requested_at_slot = 1;
+        assert!(source.set_next_owner(&Pubkey::new_unique()).is_err());
+        source.cancel_collateral_switch().unwrap();
+        assert!(!source.has_collateral_switch_request());
+    }
+}
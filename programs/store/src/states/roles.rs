@@ -185,6 +185,25 @@ impl RoleStore {
         Ok(bitmap.get(index as usize))
     }
 
+    /// Check if the given role is granted to the pubkey.
+    ///
+    /// Unlike [`has_role`](Self::has_role), returns `false` instead of an error
+    /// if the pubkey is not a member or the role is not enabled.
+    pub fn is_granted(&self, authority: &Pubkey, role: &str) -> Result<bool> {
+        let Some(value) = self.members.get(authority) else {
+            return Ok(false);
+        };
+        let Some(metadata) = self.roles.get(role) else {
+            return Ok(false);
+        };
+        require_eq!(metadata.name()?, role, CoreError::InvalidArgument);
+        if !metadata.is_enabled() {
+            return Ok(false);
+        }
+        let bitmap = RoleBitmap::from_value(*value);
+        Ok(bitmap.get(metadata.index as usize))
+    }
+
     /// Grant a role to the pubkey.
     ///
     /// # Errors
@@ -324,4 +343,30 @@ mod tests {
         store.enable_role(RoleKey::GT_CONTROLLER).unwrap();
         assert_eq!(store.has_role(&authority, RoleKey::GT_CONTROLLER), Ok(true));
     }
+
+    #[test]
+    fn is_granted_without_errors() {
+        let mut store = RoleStore::zeroed();
+        let authority = Pubkey::new_unique();
+
+        assert_eq!(
+            store.is_granted(&authority, RoleKey::MARKET_MAKER),
+            Ok(false)
+        );
+        store.enable_role(RoleKey::MARKET_MAKER).unwrap();
+        assert_eq!(
+            store.is_granted(&authority, RoleKey::MARKET_MAKER),
+            Ok(false)
+        );
+        store.grant(&authority, RoleKey::MARKET_MAKER).unwrap();
+        assert_eq!(
+            store.is_granted(&authority, RoleKey::MARKET_MAKER),
+            Ok(true)
+        );
+        store.disable_role(RoleKey::MARKET_MAKER).unwrap();
+        assert_eq!(
+            store.is_granted(&authority, RoleKey::MARKET_MAKER),
+            Ok(false)
+        );
+    }
 }
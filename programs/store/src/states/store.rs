@@ -53,8 +53,10 @@ pub struct Store {
     /// GT State.
     gt: GtState,
     pub(crate) market_config_permissions: MarketConfigPermissions,
+    /// Order fee discount tiers by trading volume.
+    volume_tiers: VolumeTiers,
     #[cfg_attr(feature = "debug", debug(skip))]
-    reserved: [u8; 992],
+    reserved: [u8; 816],
 }
 
 static_assertions::const_assert!(Store::INIT_SPACE + 8 <= 10240);
@@ -161,6 +163,12 @@ impl Store {
         }
     }
 
+    /// Returns whether the given account is exempt from the per-account max position size caps,
+    /// i.e., it has been granted the [`MARKET_MAKER`](RoleKey::MARKET_MAKER) role.
+    pub fn is_exempt_from_position_size_cap(&self, account: &Pubkey) -> Result<bool> {
+        self.role.is_granted(account, RoleKey::MARKET_MAKER)
+    }
+
     /// Grant a role.
     pub fn grant(&mut self, authority: &Pubkey, role: &str) -> Result<()> {
         self.role.grant(authority, role)
@@ -320,6 +328,65 @@ impl Store {
         Ok(index.to_le_bytes())
     }
 
+    /// Get the time after which the claimable account of a user for the given timestamp
+    /// can be unlocked.
+    ///
+    /// Returns `None` if the claimable collateral delay is not set.
+    pub fn claimable_account_unlocked_at(&self, timestamp: i64) -> Result<Option<i64>> {
+        if self.amount.claimable_collateral_delay == 0 {
+            return Ok(None);
+        }
+        let window: i64 = self
+            .claimable_time_window()?
+            .get()
+            .try_into()
+            .map_err(|_| error!(CoreError::InvalidArgument))?;
+        let delay: i64 = self
+            .amount
+            .claimable_collateral_delay
+            .try_into()
+            .map_err(|_| error!(CoreError::InvalidArgument))?;
+        self.claimable_time_window_index(timestamp)?
+            .checked_add(1)
+            .and_then(|index| index.checked_mul(window))
+            .and_then(|end| end.checked_add(delay))
+            .map(Some)
+            .ok_or_else(|| error!(CoreError::InvalidArgument))
+    }
+
+    /// Validate that an action pending since `since` can be executed by anyone,
+    /// claiming the given execution fee.
+    ///
+    /// # Errors
+    /// - Self-execution must be enabled, and the grace period must have passed since `since`.
+    /// - The `execution_fee` must not exceed the max execution fee for self-execution.
+    pub fn validate_self_execution(&self, since: i64, execution_fee: u64) -> Result<()> {
+        let grace_period = self.amount.self_execution_grace_period;
+        require_neq!(grace_period, 0, CoreError::SelfExecutionNotAllowed);
+        let allowed_at = since
+            .checked_add_unsigned(grace_period)
+            .ok_or_else(|| error!(CoreError::ValueOverflow))?;
+        require_gte!(
+            Clock::get()?.unix_timestamp,
+            allowed_at,
+            CoreError::SelfExecutionNotAllowed
+        );
+        require_gte!(
+            self.amount.self_execution_max_execution_fee,
+            execution_fee,
+            CoreError::SelfExecutionFeeCapExceeded
+        );
+        Ok(())
+    }
+
+    /// Validate that the self-execution grace period, if enabled, is less than the
+    /// request expiration.
+    ///
+    /// Otherwise, actions would have expired by the time they can be self-executed.
+    pub(crate) fn validate_self_execution_grace_period(&self) -> Result<()> {
+        self.amount.validate_self_execution_grace_period()
+    }
+
     /// Get holding address.
     pub fn holding(&self) -> &Pubkey {
         &self.address.holding
@@ -365,6 +432,16 @@ impl Store {
         &mut self.gt
     }
 
+    /// Get order fee discount tiers by trading volume.
+    pub fn volume_tiers(&self) -> &VolumeTiers {
+        &self.volume_tiers
+    }
+
+    /// Set order fee discount tiers by trading volume.
+    pub(crate) fn set_volume_tiers(&mut self, thresholds: &[u128], factors: &[u128]) -> Result<()> {
+        self.volume_tiers.set(thresholds, factors)
+    }
+
     /// Get feature disabled.
     pub fn get_feature_disabled(
         &self,
@@ -443,35 +520,50 @@ impl Store {
     }
 
     /// Get order fee discount factor.
-    pub fn order_fee_discount_factor(&self, rank: u8, is_referred: bool) -> Result<u128> {
-        use gmsol_model::utils::apply_factor;
-
-        let discount_factor_for_rank = self.gt().order_fee_discount_factor(rank)?;
+    ///
+    /// The discounts for the GT rank, the referral and the trading `volume`
+    /// (in USD) are composed as `1 - (1 - A) * (1 - B) * (1 - C)`.
+    pub fn order_fee_discount_factor(
+        &self,
+        rank: u8,
+        is_referred: bool,
+        volume: u128,
+    ) -> Result<u128> {
+        let mut discount_factor = self.gt().order_fee_discount_factor(rank)?;
         if is_referred {
             let discount_factor_for_referred = self
                 .get_factor_by_key(FactorKey::OrderFeeDiscountForReferredUser)
                 .ok_or_else(|| error!(CoreError::Unimplemented))?;
-            let complement_discount_factor_for_referred = constants::MARKET_USD_UNIT
-                .checked_sub(*discount_factor_for_referred)
-                .ok_or_else(|| error!(CoreError::Internal))?;
-
-            // 1 - (1 - A) * (1 - B) == A + B * (1 - A)
-            let discount_factor = apply_factor::<_, { constants::MARKET_DECIMALS }>(
-                &discount_factor_for_rank,
-                &complement_discount_factor_for_referred,
-            )
-            .and_then(|factor| discount_factor_for_referred.checked_add(factor))
-            .ok_or_else(|| error!(CoreError::ValueOverflow))?;
-
-            debug_assert!(discount_factor <= constants::MARKET_USD_UNIT);
-
-            Ok(discount_factor)
-        } else {
-            Ok(discount_factor_for_rank)
+            discount_factor =
+                compose_discount_factors(discount_factor, *discount_factor_for_referred)?;
+        }
+        let discount_factor_for_volume = self.volume_tiers.discount_factor(volume);
+        if discount_factor_for_volume != 0 {
+            discount_factor =
+                compose_discount_factors(discount_factor, discount_factor_for_volume)?;
         }
+        Ok(discount_factor)
     }
 }
 
+/// Compose two discount factors.
+fn compose_discount_factors(a: u128, b: u128) -> Result<u128> {
+    use gmsol_model::utils::apply_factor;
+
+    let complement_b = constants::MARKET_USD_UNIT
+        .checked_sub(b)
+        .ok_or_else(|| error!(CoreError::Internal))?;
+
+    // 1 - (1 - A) * (1 - B) == A + B * (1 - A)
+    let discount_factor = apply_factor::<_, { constants::MARKET_DECIMALS }>(&a, &complement_b)
+        .and_then(|factor| b.checked_add(factor))
+        .ok_or_else(|| error!(CoreError::ValueOverflow))?;
+
+    debug_assert!(discount_factor <= constants::MARKET_USD_UNIT);
+
+    Ok(discount_factor)
+}
+
 /// Store Wallet Signer.
 pub(crate) struct StoreWalletSigner {
     store: Pubkey,
@@ -554,8 +646,11 @@ pub struct Amounts {
     pub(crate) adl_prices_max_staleness: Amount,
     pub(crate) min_position_age_for_manual_close: Amount,
     pub(crate) market_closed_prices_max_staleness: Amount,
+    pub(crate) claimable_collateral_delay: Amount,
+    pub(crate) self_execution_grace_period: Amount,
+    pub(crate) self_execution_max_execution_fee: Amount,
     #[cfg_attr(feature = "debug", debug(skip))]
-    reserved: [Amount; 124],
+    reserved: [Amount; 121],
 }
 
 impl Amounts {
@@ -574,6 +669,18 @@ impl Amounts {
             constants::DEFAULT_MARKET_CLOSED_PRICES_MAX_STALENESS;
     }
 
+    fn validate_self_execution_grace_period(&self) -> Result<()> {
+        let grace_period = self.self_execution_grace_period;
+        if grace_period != 0 {
+            require_gt!(
+                self.request_expiration,
+                grace_period,
+                CoreError::InvalidArgument
+            );
+        }
+        Ok(())
+    }
+
     /// Get.
     fn get(&self, key: &AmountKey) -> Option<&Amount> {
         let value = match key {
@@ -586,6 +693,9 @@ impl Amounts {
             AmountKey::AdlPricesMaxStaleness => &self.adl_prices_max_staleness,
             AmountKey::MinPositionAgeForManualClose => &self.min_position_age_for_manual_close,
             AmountKey::MarketClosedPricesMaxStaleness => &self.market_closed_prices_max_staleness,
+            AmountKey::ClaimableCollateralDelay => &self.claimable_collateral_delay,
+            AmountKey::SelfExecutionGracePeriod => &self.self_execution_grace_period,
+            AmountKey::SelfExecutionMaxExecutionFee => &self.self_execution_max_execution_fee,
             _ => return None,
         };
         Some(value)
@@ -607,6 +717,9 @@ impl Amounts {
             AmountKey::MarketClosedPricesMaxStaleness => {
                 &mut self.market_closed_prices_max_staleness
             }
+            AmountKey::ClaimableCollateralDelay => &mut self.claimable_collateral_delay,
+            AmountKey::SelfExecutionGracePeriod => &mut self.self_execution_grace_period,
+            AmountKey::SelfExecutionMaxExecutionFee => &mut self.self_execution_max_execution_fee,
             _ => return None,
         };
         Some(value)
@@ -692,6 +805,75 @@ impl Addresses {
     }
 }
 
+/// Max number of order fee discount tiers by trading volume.
+pub const MAX_VOLUME_TIERS: usize = 5;
+
+/// Order fee discount tiers by trading volume.
+#[zero_copy]
+#[cfg_attr(feature = "debug", derive(derive_more::Debug))]
+pub struct VolumeTiers {
+    num_tiers: u8,
+    #[cfg_attr(feature = "debug", debug(skip))]
+    padding_0: [u8; 15],
+    /// The min trading volume (in USD) of each tier.
+    thresholds: [u128; MAX_VOLUME_TIERS],
+    /// The order fee discount factor of each tier.
+    factors: [u128; MAX_VOLUME_TIERS],
+}
+
+impl VolumeTiers {
+    /// Get the tiers as `(threshold, factor)` pairs.
+    pub fn tiers(&self) -> impl Iterator<Item = (u128, u128)> + '_ {
+        let len = usize::from(self.num_tiers);
+        self.thresholds[..len]
+            .iter()
+            .copied()
+            .zip(self.factors[..len].iter().copied())
+    }
+
+    /// Get the order fee discount factor for the given trading volume (in USD).
+    ///
+    /// Returns `0` if the volume does not reach the first tier.
+    pub fn discount_factor(&self, volume: u128) -> u128 {
+        self.tiers()
+            .take_while(|(threshold, _)| *threshold <= volume)
+            .last()
+            .map(|(_, factor)| factor)
+            .unwrap_or(0)
+    }
+
+    fn set(&mut self, thresholds: &[u128], factors: &[u128]) -> Result<()> {
+        require_eq!(thresholds.len(), factors.len(), CoreError::InvalidArgument);
+        require_gte!(
+            MAX_VOLUME_TIERS,
+            thresholds.len(),
+            CoreError::ExceedMaxLengthLimit
+        );
+
+        // Thresholds must be strictly increasing.
+        require!(
+            thresholds.windows(2).all(|ab| ab[0] < ab[1]),
+            CoreError::InvalidArgument
+        );
+
+        // Factors must be sorted and not greater than 100%.
+        require!(
+            factors.windows(2).all(|ab| ab[0] <= ab[1])
+                && factors
+                    .iter()
+                    .all(|factor| *factor <= constants::MARKET_USD_UNIT),
+            CoreError::InvalidArgument
+        );
+
+        *self = Zeroable::zeroed();
+        self.thresholds[..thresholds.len()].copy_from_slice(thresholds);
+        self.factors[..factors.len()].copy_from_slice(factors);
+        self.num_tiers = thresholds.len() as u8;
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -729,4 +911,59 @@ mod tests {
         let factors: Factors = bytemuck::Zeroable::zeroed();
         assert_eq!(factors.get(&FactorKey::MaxBuilderFeeFactor), Some(&0u128));
     }
+
+    #[test]
+    fn self_execution_grace_period_is_less_than_request_expiration() {
+        let mut amounts: Amounts = bytemuck::Zeroable::zeroed();
+        amounts.init();
+        amounts.validate_self_execution_grace_period().unwrap();
+
+        amounts.self_execution_grace_period = constants::DEFAULT_REQUEST_EXPIRATION - 1;
+        amounts.validate_self_execution_grace_period().unwrap();
+
+        amounts.self_execution_grace_period = constants::DEFAULT_REQUEST_EXPIRATION;
+        assert!(amounts.validate_self_execution_grace_period().is_err());
+    }
+
+    #[test]
+    fn volume_tier_discounts() {
+        const UNIT: u128 = constants::MARKET_USD_UNIT;
+
+        let mut store: Store = Zeroable::zeroed();
+        assert_eq!(std::mem::offset_of!(Store, volume_tiers) % 16, 0);
+        assert_eq!(
+            store
+                .order_fee_discount_factor(0, false, 10 * UNIT)
+                .unwrap(),
+            0
+        );
+
+        let thresholds = [1_000_000 * UNIT, 10_000_000 * UNIT];
+        let factors = [UNIT / 10, UNIT / 5];
+        store.set_volume_tiers(&thresholds, &factors).unwrap();
+        assert_eq!(store.volume_tiers().discount_factor(999_999 * UNIT), 0);
+        assert_eq!(
+            store.volume_tiers().discount_factor(1_000_000 * UNIT),
+            UNIT / 10
+        );
+        assert_eq!(store.volume_tiers().discount_factor(u128::MAX), UNIT / 5);
+
+        // 1 - (1 - 10%) * (1 - 20%) == 28%
+        store.factor.order_fee_discount_for_referred_user = UNIT / 10;
+        assert_eq!(
+            store
+                .order_fee_discount_factor(0, true, 20_000_000 * UNIT)
+                .unwrap(),
+            UNIT * 28 / 100
+        );
+
+        assert!(store
+            .set_volume_tiers(&[10 * UNIT, UNIT], &[UNIT / 10, UNIT / 5])
+            .is_err());
+        assert!(store
+            .set_volume_tiers(&[UNIT, 10 * UNIT], &[UNIT / 5, UNIT / 10])
+            .is_err());
+        store.set_volume_tiers(&[], &[]).unwrap();
+        assert_eq!(store.volume_tiers().discount_factor(u128::MAX), 0);
+    }
 }
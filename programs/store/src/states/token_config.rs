@@ -113,6 +113,7 @@ impl TokenConfigExt for TokenConfig {
         }
         self.expected_provider = expected_provider.unwrap_or(PriceProviderKind::default() as u8);
         self.heartbeat_duration = heartbeat_duration;
+        self.validate_shared_feed_slot().map_err(CoreError::from)?;
         Ok(())
     }
 }
@@ -261,6 +262,39 @@ impl<'info> TokenMapLoader<'info> for AccountLoader<'info, TokenMapHeader> {
     }
 }
 
+impl<'a> TokenMapRef<'a> {
+    /// Load token map from the given account info.
+    ///
+    /// This is useful when the token map is not provided as an [`AccountLoader`],
+    /// e.g., in CPI contexts.
+    pub fn try_from_account_info(info: &'a AccountInfo<'_>) -> Result<Self> {
+        require_keys_eq!(
+            *info.owner,
+            crate::ID,
+            ErrorCode::AccountOwnedByWrongProgram
+        );
+        let data = info.try_borrow_data()?;
+        let disc = TokenMapHeader::DISCRIMINATOR;
+        require_gte!(
+            data.len(),
+            disc.len() + size_of::<TokenMapHeader>(),
+            ErrorCode::AccountDidNotDeserialize
+        );
+        require!(
+            data.starts_with(disc),
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+
+        let (_disc, data) = Ref::map_split(data, |d| d.split_at(disc.len()));
+        let (header, configs) = Ref::map_split(data, |d| d.split_at(size_of::<TokenMapHeader>()));
+
+        Ok(Self {
+            header: Ref::map(header, bytemuck::from_bytes),
+            configs,
+        })
+    }
+}
+
 impl TokenMapAccess for TokenMapRef<'_> {
     fn get(&self, token: &Pubkey) -> Option<&TokenConfig> {
         self.header.get_token_config_unchecked(token, &self.configs)
@@ -5,6 +5,7 @@ use gmsol_utils::{
 };
 
 use crate::{
+    events::{TradeData, TradeFees},
     utils::pubkey::{optional_address, DEFAULT_PUBKEY},
     CoreError,
 };
@@ -34,9 +35,15 @@ pub struct UserHeader {
     /// This user's builder fee factor, as a builder. `0` means the user
     /// has not advertised a rate.
     pub(crate) builder_fee_factor: u128,
+    /// The nonce expected by the next order intent signed by the owner.
+    pub(crate) intent_nonce: u64,
+    #[cfg_attr(feature = "debug", debug(skip))]
+    padding_1: [u8; 8],
+    /// Rolling trading volume.
+    pub(crate) volume: UserTradingVolume,
     #[cfg_attr(feature = "debug", debug(skip))]
     #[cfg_attr(feature = "serde", serde(with = "serde_bytes"))]
-    reserved: [u8; 112],
+    reserved: [u8; 16],
 }
 
 gmsol_utils::flags!(UserFlag, MAX_USER_FLAGS, u8);
@@ -62,6 +69,19 @@ impl UserHeader {
         Ok(())
     }
 
+    /// Return whether the owner has a user stats account.
+    ///
+    /// The user stats account must be provided to every execution of
+    /// the owner's trades once it is prepared.
+    pub fn has_stats(&self) -> bool {
+        self.flags.get_flag(UserFlag::HasStats)
+    }
+
+    /// Mark that the owner has a user stats account.
+    pub(crate) fn set_has_stats(&mut self) {
+        self.flags.set_flag(UserFlag::HasStats, true);
+    }
+
     /// Get User Account space.
     pub fn space(_version: u8) -> usize {
         std::mem::size_of::<Self>()
@@ -165,6 +185,25 @@ impl UserHeader {
     pub(crate) fn set_builder_fee_factor(&mut self, factor: u128) -> u128 {
         std::mem::replace(&mut self.builder_fee_factor, factor)
     }
+
+    /// Get the nonce expected by the next order intent.
+    pub fn intent_nonce(&self) -> u64 {
+        self.intent_nonce
+    }
+
+    /// Get the rolling trading volume.
+    pub fn volume(&self) -> &UserTradingVolume {
+        &self.volume
+    }
+
+    /// Consume the given intent nonce, which must be the expected one.
+    pub(crate) fn use_intent_nonce(&mut self, nonce: u64) -> Result<()> {
+        require_eq!(self.intent_nonce, nonce, CoreError::InvalidOrderIntent);
+        self.intent_nonce = nonce
+            .checked_add(1)
+            .ok_or_else(|| error!(CoreError::ValueOverflow))?;
+        Ok(())
+    }
 }
 
 impl Seed for UserHeader {
@@ -368,6 +407,231 @@ impl UserGtState {
     }
 }
 
+/// Length of a trading volume bucket in seconds.
+pub const VOLUME_BUCKET_SECONDS: i64 = 10 * 86_400;
+
+/// Number of trading volume buckets.
+pub const NUM_VOLUME_BUCKETS: usize = 4;
+
+/// Rolling 30-day trading volume.
+///
+/// The volume is recorded in buckets of [`VOLUME_BUCKET_SECONDS`], where `buckets[0]`
+/// is the bucket with index `last_bucket`. The oldest bucket only partially overlaps
+/// the window, so it is weighted by the overlapping fraction.
+#[zero_copy]
+#[cfg_attr(feature = "debug", derive(derive_more::Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UserTradingVolume {
+    last_bucket: i64,
+    #[cfg_attr(feature = "debug", debug(skip))]
+    padding_0: [u8; 8],
+    buckets: [u128; NUM_VOLUME_BUCKETS],
+}
+
+impl UserTradingVolume {
+    fn bucket_of(ts: i64) -> i64 {
+        ts.div_euclid(VOLUME_BUCKET_SECONDS)
+    }
+
+    /// Returns the number of buckets elapsed since the last update.
+    fn elapsed_buckets(&self, bucket: i64) -> usize {
+        usize::try_from(bucket.saturating_sub(self.last_bucket))
+            .unwrap_or(0)
+            .min(NUM_VOLUME_BUCKETS)
+    }
+
+    /// Record the given volume (in USD) at `ts`.
+    pub(crate) fn record(&mut self, ts: i64, volume: u128) {
+        let bucket = Self::bucket_of(ts);
+        let elapsed = self.elapsed_buckets(bucket);
+        if elapsed != 0 {
+            self.buckets.rotate_right(elapsed % NUM_VOLUME_BUCKETS);
+            self.buckets[..elapsed].fill(0);
+            self.last_bucket = bucket;
+        }
+        self.buckets[0] = self.buckets[0].saturating_add(volume);
+    }
+
+    /// Get the trading volume (in USD) of the 30 days before `ts`.
+    pub fn rolling_volume(&self, ts: i64) -> u128 {
+        let elapsed = self.elapsed_buckets(Self::bucket_of(ts));
+        if elapsed == NUM_VOLUME_BUCKETS {
+            return 0;
+        }
+        let oldest = NUM_VOLUME_BUCKETS - 1 - elapsed;
+        let length = VOLUME_BUCKET_SECONDS as u128;
+        let overlap = length - ts.rem_euclid(VOLUME_BUCKET_SECONDS) as u128;
+        self.buckets[..=oldest]
+            .iter()
+            .enumerate()
+            .map(|(idx, volume)| {
+                if idx == oldest {
+                    volume
+                        .checked_mul(overlap)
+                        .map(|weighted| weighted / length)
+                        .unwrap_or_else(|| (volume / length).saturating_mul(overlap))
+                } else {
+                    *volume
+                }
+            })
+            .fold(0, u128::saturating_add)
+    }
+}
+
+/// Trading statistics of a user.
+#[account(zero_copy)]
+#[cfg_attr(feature = "debug", derive(derive_more::Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UserStats {
+    version: u8,
+    /// The bump seed.
+    pub(crate) bump: u8,
+    #[cfg_attr(feature = "debug", debug(skip))]
+    padding_0: [u8; 14],
+    /// The store.
+    pub(crate) store: Pubkey,
+    /// The owner.
+    pub(crate) owner: Pubkey,
+    /// Number of trades.
+    num_trades: u64,
+    /// Number of liquidations.
+    num_liquidations: u64,
+    /// The timestamp of the last trade.
+    last_trade_at: i64,
+    #[cfg_attr(feature = "debug", debug(skip))]
+    padding_1: [u8; 8],
+    /// Total trading volume (in USD).
+    volume: u128,
+    /// Total realized PnL (in USD).
+    realized_pnl: i128,
+    /// Fees paid.
+    fees: UserFeeStats,
+    #[cfg_attr(feature = "debug", debug(skip))]
+    #[cfg_attr(feature = "serde", serde(with = "serde_bytes"))]
+    reserved: [u8; 256],
+}
+
+impl InitSpace for UserStats {
+    const INIT_SPACE: usize = std::mem::size_of::<Self>();
+}
+
+impl Seed for UserStats {
+    const SEED: &'static [u8] = b"user_stats";
+}
+
+impl UserStats {
+    pub(crate) fn init(&mut self, bump: u8, store: &Pubkey, owner: &Pubkey) {
+        self.bump = bump;
+        self.store = *store;
+        self.owner = *owner;
+    }
+
+    /// Returns whether the account is initialized.
+    pub fn is_initialized(&self) -> bool {
+        self.owner != DEFAULT_PUBKEY
+    }
+
+    /// Get the store.
+    pub fn store(&self) -> &Pubkey {
+        &self.store
+    }
+
+    /// Get the owner.
+    pub fn owner(&self) -> &Pubkey {
+        &self.owner
+    }
+
+    /// Get the number of trades.
+    pub fn num_trades(&self) -> u64 {
+        self.num_trades
+    }
+
+    /// Get the number of liquidations.
+    pub fn num_liquidations(&self) -> u64 {
+        self.num_liquidations
+    }
+
+    /// Get the timestamp of the last trade.
+    pub fn last_trade_at(&self) -> i64 {
+        self.last_trade_at
+    }
+
+    /// Get the total trading volume (in USD).
+    pub fn volume(&self) -> u128 {
+        self.volume
+    }
+
+    /// Get the total realized PnL (in USD).
+    pub fn realized_pnl(&self) -> i128 {
+        self.realized_pnl
+    }
+
+    /// Get the fees paid.
+    pub fn fees(&self) -> &UserFeeStats {
+        &self.fees
+    }
+
+    /// Update the statistics with the given trade.
+    ///
+    /// Statistics must never prevent a trade from being executed,
+    /// so all the accumulations are saturating.
+    pub(crate) fn update_with_trade(&mut self, trade: &TradeData, is_liquidation: bool) {
+        self.num_trades = self.num_trades.saturating_add(1);
+        if is_liquidation {
+            self.num_liquidations = self.num_liquidations.saturating_add(1);
+        }
+        self.last_trade_at = trade.ts;
+        self.volume = self
+            .volume
+            .saturating_add(trade.after.size_in_usd.abs_diff(trade.before.size_in_usd));
+        self.realized_pnl = self.realized_pnl.saturating_add(trade.pnl.pnl);
+
+        // Fees are paid in collateral tokens.
+        let collateral_price = if trade.is_collateral_long() {
+            trade.prices.long.min
+        } else {
+            trade.prices.short.min
+        };
+        self.fees
+            .update_with_trade_fees(&trade.fees, collateral_price);
+    }
+}
+
+/// Fees paid by a user, in USD.
+#[zero_copy]
+#[cfg_attr(feature = "debug", derive(derive_more::Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UserFeeStats {
+    /// Order fee value.
+    pub order_fee_value: u128,
+    /// Borrowing fee value.
+    pub borrowing_fee_value: u128,
+    /// Funding fee value.
+    pub funding_fee_value: u128,
+    /// Liquidation fee value.
+    pub liquidation_fee_value: u128,
+}
+
+impl UserFeeStats {
+    fn update_with_trade_fees(&mut self, fees: &TradeFees, collateral_price: u128) {
+        let value = |amount: u128| amount.saturating_mul(collateral_price);
+
+        self.order_fee_value = self.order_fee_value.saturating_add(value(
+            fees.order_fee_for_receiver_amount
+                .saturating_add(fees.order_fee_for_pool_amount),
+        ));
+        self.borrowing_fee_value = self
+            .borrowing_fee_value
+            .saturating_add(value(fees.total_borrowing_fee_amount));
+        self.funding_fee_value = self
+            .funding_fee_value
+            .saturating_add(value(fees.funding_fee_amount));
+        self.liquidation_fee_value = self
+            .liquidation_fee_value
+            .saturating_add(value(fees.liquidation_fee_amount));
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -376,6 +640,7 @@ mod tests {
     const EXPECTED_USER_HEADER_ACCOUNT_SIZE: usize = 512;
     const GT_OFFSET: usize = 224;
     const BUILDER_FEE_FACTOR_OFFSET: usize = 384;
+    const VOLUME_OFFSET: usize = 416;
 
     #[test]
     fn user_header_account_layout() {
@@ -388,8 +653,10 @@ mod tests {
             std::mem::offset_of!(UserHeader, builder_fee_factor),
             BUILDER_FEE_FACTOR_OFFSET
         );
+        assert_eq!(std::mem::offset_of!(UserHeader, volume), VOLUME_OFFSET);
         // u128 requires 16-byte alignment.
         assert_eq!(BUILDER_FEE_FACTOR_OFFSET % 16, 0);
+        assert_eq!(VOLUME_OFFSET % 16, 0);
     }
 
     #[test]
@@ -398,5 +665,80 @@ mod tests {
         // throughout what used to be its `reserved` region.
         let user: UserHeader = bytemuck::Zeroable::zeroed();
         assert_eq!(user.builder_fee_factor(), 0);
+        assert_eq!(user.volume().rolling_volume(1_704_067_200), 0);
+    }
+
+    #[test]
+    fn user_stats_flag_does_not_affect_initialization() {
+        let mut user: UserHeader = bytemuck::Zeroable::zeroed();
+        assert!(!user.has_stats());
+        user.set_has_stats();
+        assert!(user.has_stats());
+        assert!(!user.is_initialized());
+    }
+
+    #[test]
+    fn user_stats_update_with_trade() {
+        const UNIT: u128 = crate::constants::MARKET_USD_UNIT;
+        // The price of a token with 6 decimals at $1.
+        const PRICE: u128 = UNIT / 1_000_000;
+
+        let mut stats: UserStats = bytemuck::Zeroable::zeroed();
+        let mut trade: TradeData = bytemuck::Zeroable::zeroed();
+        trade.ts = 1_704_067_200;
+        trade.before.size_in_usd = 1_000 * UNIT;
+        trade.after.size_in_usd = 400 * UNIT;
+        trade.pnl.pnl = -50 * UNIT as i128;
+        trade.prices.short.min = PRICE;
+        trade.fees.order_fee_for_receiver_amount = 300_000;
+        trade.fees.order_fee_for_pool_amount = 300_000;
+        trade.fees.liquidation_fee_amount = 2_000_000;
+
+        stats.update_with_trade(&trade, true);
+        stats.update_with_trade(&trade, false);
+
+        assert_eq!(stats.num_trades(), 2);
+        assert_eq!(stats.num_liquidations(), 1);
+        assert_eq!(stats.last_trade_at(), trade.ts);
+        assert_eq!(stats.volume(), 1_200 * UNIT);
+        assert_eq!(stats.realized_pnl(), -100 * UNIT as i128);
+        assert_eq!(stats.fees().order_fee_value, UNIT * 12 / 10);
+        assert_eq!(stats.fees().liquidation_fee_value, 4 * UNIT);
+        assert_eq!(stats.fees().borrowing_fee_value, 0);
+    }
+
+    #[test]
+    fn rolling_volume() {
+        const UNIT: u128 = crate::constants::MARKET_USD_UNIT;
+        let start = 100 * VOLUME_BUCKET_SECONDS;
+        let mut volume: UserTradingVolume = bytemuck::Zeroable::zeroed();
+
+        volume.record(start, 10 * UNIT);
+        volume.record(start + 1, 5 * UNIT);
+        assert_eq!(volume.rolling_volume(start + 1), 15 * UNIT);
+
+        volume.record(start + VOLUME_BUCKET_SECONDS, 20 * UNIT);
+        assert_eq!(
+            volume.rolling_volume(start + VOLUME_BUCKET_SECONDS),
+            35 * UNIT
+        );
+
+        // The first bucket becomes the oldest one, and is half outside the window.
+        let ts = start + 3 * VOLUME_BUCKET_SECONDS + VOLUME_BUCKET_SECONDS / 2;
+        assert_eq!(volume.rolling_volume(ts), 15 * UNIT / 2 + 20 * UNIT);
+
+        volume.record(ts, UNIT);
+        assert_eq!(volume.rolling_volume(ts), 15 * UNIT / 2 + 21 * UNIT);
+
+        // Only the latest bucket remains in the window.
+        let ts = ts + 3 * VOLUME_BUCKET_SECONDS;
+        assert_eq!(volume.rolling_volume(ts), UNIT / 2);
+        assert_eq!(volume.rolling_volume(ts + VOLUME_BUCKET_SECONDS), 0);
+
+        volume.record(ts + 10 * VOLUME_BUCKET_SECONDS, 2 * UNIT);
+        assert_eq!(
+            volume.rolling_volume(ts + 10 * VOLUME_BUCKET_SECONDS),
+            2 * UNIT
+        );
     }
 }
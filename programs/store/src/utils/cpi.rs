@@ -2,7 +2,8 @@ use anchor_lang::{prelude::*, Bumps};
 
 use crate::{
     cpi::accounts::{CheckRole, ClearAllPrices, SetPricesFromPriceFeed},
-    states::RoleKey,
+    states::{RoleKey, TokenMapAccess, TokenMapRef},
+    CoreError,
 };
 
 /// With Store.
@@ -128,13 +129,20 @@ pub trait WithOracleExt<'info>: WithOracle<'info> {
         signer_seeds: &[&[u8]],
         f: impl FnOnce(&mut Self, &'info [AccountInfo<'info>]) -> Result<T>,
     ) -> Result<T> {
+        let num_feeds = {
+            let token_map = self.token_map();
+            let num_feeds = TokenMapRef::try_from_account_info(&token_map)?
+                .num_feed_accounts(&tokens)
+                .ok_or_else(|| error!(CoreError::NotFound))?;
+            num_feeds
+        };
         require_gte!(
             remaining_accounts.len(),
-            tokens.len(),
+            num_feeds,
             ErrorCode::AccountNotEnoughKeys
         );
-        let feeds = remaining_accounts[..tokens.len()].to_vec();
-        let remaining_accounts = &remaining_accounts[tokens.len()..];
+        let feeds = remaining_accounts[..num_feeds].to_vec();
+        let remaining_accounts = &remaining_accounts[num_feeds..];
         crate::cpi::set_prices_from_price_feed(
             self.set_prices_from_price_feed_ctx(feeds)
                 .with_signer(&[signer_seeds]),
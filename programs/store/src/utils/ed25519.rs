@@ -0,0 +1,211 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        ed25519_program,
+        sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+    },
+};
+
+use crate::CoreError;
+
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_SERIALIZED_SIZE: usize = 14;
+const PUBKEY_SERIALIZED_SIZE: usize = 32;
+const SIGNATURE_SERIALIZED_SIZE: usize = 64;
+
+/// Verify that the instruction right before the current one is an ed25519 program
+/// instruction verifying a single signature of `signer` over `message`.
+///
+/// The public key, signature and message must all be contained in the ed25519
+/// instruction itself.
+pub(crate) fn verify_ed25519_signature_ix(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let data = load_previous_ed25519_ix_data(instructions_sysvar)?;
+    let (pubkey, signed_message) = parse_single_signature(&data)?;
+    require!(
+        pubkey == signer.as_ref() && signed_message == message,
+        CoreError::InvalidSignature
+    );
+    Ok(())
+}
+
+/// Load the signers and the messages of all the signatures verified by the ed25519
+/// program instruction right before the current one.
+///
+/// The public keys, signatures and messages must all be contained in the ed25519
+/// instruction itself.
+pub(crate) fn load_ed25519_signatures_ix(
+    instructions_sysvar: &AccountInfo,
+) -> Result<Vec<(Pubkey, Vec<u8>)>> {
+    let data = load_previous_ed25519_ix_data(instructions_sysvar)?;
+    parse_signatures(&data)?
+        .into_iter()
+        .map(|(pubkey, message)| {
+            let pubkey =
+                Pubkey::try_from(pubkey).map_err(|_| error!(CoreError::InvalidSignature))?;
+            Ok((pubkey, message.to_vec()))
+        })
+        .collect()
+}
+
+fn load_previous_ed25519_ix_data(instructions_sysvar: &AccountInfo) -> Result<Vec<u8>> {
+    let current = load_current_index_checked(instructions_sysvar)?;
+    let index = current
+        .checked_sub(1)
+        .ok_or_else(|| error!(CoreError::InvalidSignature))?;
+    let ix = load_instruction_at_checked(usize::from(index), instructions_sysvar)?;
+    require_keys_eq!(
+        ix.program_id,
+        ed25519_program::ID,
+        CoreError::InvalidSignature
+    );
+    require!(ix.accounts.is_empty(), CoreError::InvalidSignature);
+    Ok(ix.data)
+}
+
+/// Parse the public key and the message of an ed25519 program instruction data
+/// that verifies exactly one signature with all data in the same instruction.
+fn parse_single_signature(data: &[u8]) -> Result<(&[u8], &[u8])> {
+    require!(data.first() == Some(&1), CoreError::InvalidSignature);
+    parse_signatures(data)?
+        .pop()
+        .ok_or_else(|| error!(CoreError::InvalidSignature))
+}
+
+/// Parse the public keys and the messages of an ed25519 program instruction data
+/// with all data in the same instruction.
+fn parse_signatures(data: &[u8]) -> Result<Vec<(&[u8], &[u8])>> {
+    let count = usize::from(
+        *data
+            .first()
+            .ok_or_else(|| error!(CoreError::InvalidSignature))?,
+    );
+    require!(
+        count != 0
+            && data.len() >= SIGNATURE_OFFSETS_START + count * SIGNATURE_OFFSETS_SERIALIZED_SIZE,
+        CoreError::InvalidSignature
+    );
+    (0..count)
+        .map(|idx| {
+            parse_signature_at(
+                data,
+                SIGNATURE_OFFSETS_START + idx * SIGNATURE_OFFSETS_SERIALIZED_SIZE,
+            )
+        })
+        .collect()
+}
+
+fn parse_signature_at(data: &[u8], start: usize) -> Result<(&[u8], &[u8])> {
+    let offsets = &data[start..start + SIGNATURE_OFFSETS_SERIALIZED_SIZE];
+    let read_u16 = |at: usize| u16::from_le_bytes([offsets[at], offsets[at + 1]]);
+    let signature_offset = usize::from(read_u16(0));
+    let signature_ix_index = read_u16(2);
+    let pubkey_offset = usize::from(read_u16(4));
+    let pubkey_ix_index = read_u16(6);
+    let message_offset = usize::from(read_u16(8));
+    let message_size = usize::from(read_u16(10));
+    let message_ix_index = read_u16(12);
+
+    // All data must be in the ed25519 instruction itself.
+    require!(
+        signature_ix_index == u16::MAX
+            && pubkey_ix_index == u16::MAX
+            && message_ix_index == u16::MAX,
+        CoreError::InvalidSignature
+    );
+
+    let slice = |offset: usize, size: usize| {
+        data.get(offset..offset.saturating_add(size))
+            .ok_or_else(|| error!(CoreError::InvalidSignature))
+    };
+    slice(signature_offset, SIGNATURE_SERIALIZED_SIZE)?;
+    let pubkey = slice(pubkey_offset, PUBKEY_SERIALIZED_SIZE)?;
+    let message = slice(message_offset, message_size)?;
+    Ok((pubkey, message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build ed25519 instruction data with the same layout as the one produced by
+    /// the ed25519 program helpers.
+    fn build_data(pubkey: &[u8; 32], signature: &[u8; 64], message: &[u8]) -> Vec<u8> {
+        build_multi_data(&[(pubkey, signature, message)])
+    }
+
+    fn build_multi_data(signatures: &[(&[u8; 32], &[u8; 64], &[u8])]) -> Vec<u8> {
+        let mut offset =
+            SIGNATURE_OFFSETS_START + signatures.len() * SIGNATURE_OFFSETS_SERIALIZED_SIZE;
+        let mut data = vec![signatures.len() as u8, 0];
+        let mut payload = vec![];
+        for (pubkey, signature, message) in signatures {
+            let pubkey_offset = offset;
+            let signature_offset = pubkey_offset + PUBKEY_SERIALIZED_SIZE;
+            let message_offset = signature_offset + SIGNATURE_SERIALIZED_SIZE;
+            offset = message_offset + message.len();
+            for value in [
+                signature_offset as u16,
+                u16::MAX,
+                pubkey_offset as u16,
+                u16::MAX,
+                message_offset as u16,
+                message.len() as u16,
+                u16::MAX,
+            ] {
+                data.extend_from_slice(&value.to_le_bytes());
+            }
+            payload.extend_from_slice(*pubkey);
+            payload.extend_from_slice(*signature);
+            payload.extend_from_slice(message);
+        }
+        data.extend_from_slice(&payload);
+        data
+    }
+
+    #[test]
+    fn parse_ed25519_instruction_data() {
+        let pubkey = [7; 32];
+        let message = b"order intent";
+        let data = build_data(&pubkey, &[9; 64], message);
+
+        let (parsed_pubkey, parsed_message) = parse_single_signature(&data).unwrap();
+        assert_eq!(parsed_pubkey, pubkey);
+        assert_eq!(parsed_message, message);
+
+        // Multiple signatures are not supported.
+        let mut multiple = data.clone();
+        multiple[0] = 2;
+        assert!(parse_single_signature(&multiple).is_err());
+
+        // Data in other instructions is not supported.
+        let mut external = data.clone();
+        external[SIGNATURE_OFFSETS_START + 6] = 0;
+        external[SIGNATURE_OFFSETS_START + 7] = 0;
+        assert!(parse_single_signature(&external).is_err());
+
+        // Out of bounds.
+        assert!(parse_single_signature(&data[..data.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn parse_multiple_signatures() {
+        let data = build_multi_data(&[
+            (&[1; 32], &[9; 64], b"price 1"),
+            (&[2; 32], &[9; 64], b"price 22"),
+        ]);
+
+        let signatures = parse_signatures(&data).unwrap();
+        assert_eq!(signatures.len(), 2);
+        assert_eq!(signatures[0], (&[1; 32][..], &b"price 1"[..]));
+        assert_eq!(signatures[1], (&[2; 32][..], &b"price 22"[..]));
+        assert!(parse_single_signature(&data).is_err());
+
+        // Offsets out of bounds.
+        assert!(parse_signatures(&data[..SIGNATURE_OFFSETS_START + 20]).is_err());
+        assert!(parse_signatures(&[0, 0]).is_err());
+    }
+}
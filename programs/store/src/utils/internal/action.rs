@@ -119,8 +119,18 @@ where
         event_emitter: &EventEmitter<'_, 'info>,
     ) -> Result<Success>;
 
+    /// Process the remaining accounts after the action has been processed
+    /// successfully and before it is closed.
+    fn process_remaining_accounts(
+        &self,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        let _ = remaining_accounts;
+        Ok(())
+    }
+
     /// Close Action.
-    fn close(ctx: &Context<'_, '_, '_, 'info, Self>, reason: &str) -> Result<()> {
+    fn close(ctx: &Context<'_, '_, 'info, 'info, Self>, reason: &str) -> Result<()> {
         let accounts = &ctx.accounts;
         accounts.validate()?;
         let is_caller_owner = accounts.preprocess()?;
@@ -134,6 +144,7 @@ where
         let event_emitter = EventEmitter::new(&authority, bump);
 
         if accounts.process(is_caller_owner, &store_wallet_signer, &event_emitter)? {
+            accounts.process_remaining_accounts(ctx.remaining_accounts)?;
             {
                 let action_address = accounts.action().key();
                 let action = accounts.action().load()?;
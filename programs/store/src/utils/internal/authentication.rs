@@ -44,6 +44,34 @@ pub(crate) trait Authentication<'info> {
     }
 }
 
+/// Accounts for executing an action that can be executed by anyone once it has
+/// been pending for longer than the self-execution grace period.
+pub(crate) trait SelfExecution<'info>: Authentication<'info> {
+    /// Get the time since which the action has been pending.
+    ///
+    /// Returns `None` if the action cannot be self-executed.
+    fn pending_since(&self) -> Result<Option<i64>>;
+
+    /// Check that the `authority` is an ORDER_KEEPER, or that the action
+    /// can be self-executed with the given execution fee.
+    fn ensure_can_execute(&self, execution_fee: u64) -> Result<()> {
+        let store = self.store().load()?;
+        if matches!(
+            store.has_role(self.authority().key, RoleKey::ORDER_KEEPER),
+            Ok(true)
+        ) {
+            return Ok(());
+        }
+        store.validate_not_restarted()?;
+        let Some(since) = self.pending_since()? else {
+            return err!(CoreError::PermissionDenied);
+        };
+        store.validate_self_execution(since, execution_fee)?;
+        msg!("[Execution] self-executed by {}", self.authority().key);
+        Ok(())
+    }
+}
+
 /// Provides access control utils for [`Authentication`]s.
 pub(crate) trait Authenticate<'info>: Authentication<'info> + Bumps + Sized {
     /// Check that the `authority` has the given `role`.
@@ -111,6 +139,15 @@ pub(crate) trait Authenticate<'info>: Authentication<'info> + Bumps + Sized {
     fn only_migration_keeper(ctx: &Context<Self>) -> Result<()> {
         Self::only(ctx, RoleKey::MIGRATION_KEEPER)
     }
+
+    /// Check that the `authority` has the [`ORDER_KEEPER`](`RoleKey::ORDER_KEEPER`) role,
+    /// or that the action can be self-executed with the given execution fee.
+    fn only_order_keeper_or_self_executor(ctx: &Context<Self>, execution_fee: u64) -> Result<()>
+    where
+        Self: SelfExecution<'info>,
+    {
+        ctx.accounts.ensure_can_execute(execution_fee)
+    }
 }
 
 impl<'info, T> Authenticate<'info> for T where T: Authentication<'info> + Bumps + Sized {}
@@ -4,6 +4,6 @@ mod transfer;
 
 pub(crate) use self::{
     action::{Close, Create, Success},
-    authentication::{Authenticate, Authentication},
+    authentication::{Authenticate, Authentication, SelfExecution},
     transfer::TransferUtils,
 };
@@ -24,6 +24,8 @@ pub mod dynamic_access;
 /// Logging utils.
 pub mod logging;
 
+pub(crate) mod ed25519;
+
 /// Utils for deserializing "zero-copy" account.
 #[cfg(feature = "utils")]
 pub mod de;
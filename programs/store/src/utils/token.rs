@@ -98,6 +98,26 @@ pub fn validate_associated_token_account<'info>(
     Ok(())
 }
 
+/// Validate that the escrow account is an associated token account that is not frozen
+/// and holds at least `min_amount` tokens, so that transfers from or to it cannot fail.
+pub fn validate_transferable_escrow<'info>(
+    account: &impl AsRef<AccountInfo<'info>>,
+    expected_owner: &Pubkey,
+    expected_mint: &Pubkey,
+    token_program_id: &Pubkey,
+    min_amount: u64,
+) -> Result<()> {
+    validate_associated_token_account(account, expected_owner, expected_mint, token_program_id)?;
+    require!(account.as_ref().is_writable, ErrorCode::ConstraintMut);
+
+    let mut data: &[u8] = &account.as_ref().try_borrow_data()?;
+    let escrow = anchor_spl::token_interface::TokenAccount::try_deserialize(&mut data)?;
+    require!(!escrow.is_frozen(), CoreError::InvalidArgument);
+    require_gte!(escrow.amount, min_amount, CoreError::NotEnoughTokenAmount);
+
+    Ok(())
+}
+
 #[derive(TypedBuilder)]
 pub struct TransferAllFromEscrowToATA<'a, 'info> {
     /// Store wallet account, must be mutable.
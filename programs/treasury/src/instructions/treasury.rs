@@ -12,7 +12,7 @@ use gmsol_store::{
         clear_all_prices, confirm_gt_exchange_vault_v2, set_prices_from_price_feed,
     },
     program::GmsolStore,
-    states::{gt::GtExchangeVault, Chainlink, Oracle, Seed, Store},
+    states::{gt::GtExchangeVault, Chainlink, Oracle, Seed, Store, TokenMapAccess, TokenMapRef},
     utils::{token::is_associated_token_account_with_program_id, CpiAuthentication, WithStore},
     CoreError,
 };
@@ -659,10 +659,10 @@ impl<'info> ConfirmGtBuyback<'info> {
     fn validate_and_split_remaining_accounts(
         &self,
         remaining_accounts: &'info [AccountInfo<'info>],
-        num_tokens: usize,
+        num_feeds: usize,
     ) -> Result<(&'info [AccountInfo<'info>], &'info [AccountInfo<'info>])> {
         let num_treasury_tokens = self.treasury_vault_config.load()?.num_tokens();
-        let treasury_tokens_end = num_tokens
+        let treasury_tokens_end = num_feeds
             .checked_add(num_treasury_tokens)
             .ok_or_else(|| error!(CoreError::Internal))?;
         let end = treasury_tokens_end
@@ -674,8 +674,8 @@ impl<'info> ConfirmGtBuyback<'info> {
             ErrorCode::AccountNotEnoughKeys
         );
 
-        let feeds = &remaining_accounts[0..num_tokens];
-        let mints = &remaining_accounts[num_tokens..treasury_tokens_end];
+        let feeds = &remaining_accounts[0..num_feeds];
+        let mints = &remaining_accounts[num_feeds..treasury_tokens_end];
         let vaults = &remaining_accounts[treasury_tokens_end..end];
 
         let treasury_vault_config_key = self.treasury_vault_config.key();
@@ -723,17 +723,22 @@ impl<'info> ConfirmGtBuyback<'info> {
             .load()?
             .tokens()
             .chain(self.treasury_vault_config.load()?.tokens())
-            .collect::<BTreeSet<_>>();
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
 
+        let num_feeds = TokenMapRef::try_from_account_info(&self.token_map)?
+            .num_feed_accounts(&tokens)
+            .ok_or_else(|| error!(CoreError::NotFound))?;
         let (feeds, vaults) =
-            self.validate_and_split_remaining_accounts(remaining_accounts, tokens.len())?;
+            self.validate_and_split_remaining_accounts(remaining_accounts, num_feeds)?;
 
         // Set prices.
         let ctx = self.set_prices_from_price_feed_ctx();
         set_prices_from_price_feed(
             ctx.with_signer(&[&signer.as_seeds()])
                 .with_remaining_accounts(feeds.to_vec()),
-            tokens.iter().copied().collect(),
+            tokens,
         )?;
 
         let (buyback_value, buyback_price) = self.update_balances(vaults, total_gt_amount)?;
@@ -112,25 +112,18 @@ impl GtBank {
     ) -> Result<gmsol_store::states::common::TokensWithFeed> {
         use std::collections::BTreeSet;
 
-        use gmsol_store::states::common::{TokenRecord, TokensWithFeed};
+        use gmsol_store::states::common::TokensWithFeed;
 
         let tokens = self
             .tokens()
             .chain(treasury_vault_config.tokens())
             .collect::<BTreeSet<_>>();
-        let records = tokens
-            .iter()
-            .map(|token| {
-                let config = map
-                    .get(token)
-                    .ok_or_else(|| error!(CoreError::UnknownToken))?;
-                TokenRecord::from_config(*token, config)
-                    .map_err(CoreError::from)
-                    .map_err(|err| error!(err))
-            })
-            .collect::<Result<Vec<_>>>()?;
-
-        TokensWithFeed::try_from_records(records)
+        require!(
+            tokens.iter().all(|token| map.get(token).is_some()),
+            CoreError::UnknownToken
+        );
+
+        TokensWithFeed::try_from_token_map(map, &tokens)
             .map_err(CoreError::from)
             .map_err(|err| error!(err))
     }